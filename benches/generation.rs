@@ -0,0 +1,67 @@
+//! Measures the generation time of a large set of records.
+//!
+//! Run with `cargo bench --bench generation`, the number of records can be set with the
+//! `RECORDS` environment variable (600 by default).
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use rsgen_avro::{Generator, Schema, Source};
+
+/// Returns records referencing the previous ones, with defaults and nested anonymous types.
+fn raw_schemas(records: usize) -> Vec<String> {
+    (0..records)
+        .map(|i| {
+            let previous = match i {
+                0 => r#""string""#.to_string(),
+                _ => format!(r#""R{}""#, (i - 1) / 2),
+            };
+            format!(
+                r#"
+{{
+  "type": "record",
+  "name": "R{i}",
+  "namespace": "bench",
+  "fields": [
+    {{"name": "id", "type": "long", "default": {i}}},
+    {{"name": "label", "type": "string", "default": "record {i}"}},
+    {{"name": "previous", "type": ["null", {previous}], "default": null}},
+    {{"name": "scores", "type": {{"type": "array", "items": ["int", "double"]}}, "default": [1]}},
+    {{"name": "tags", "type": {{"type": "map", "values": {{"type": "array", "items": "string"}}}}, "default": {{"a": ["b"]}}}},
+    {{"name": "kind", "type": {{"type": "enum", "name": "Kind{i}", "symbols": ["A", "B", "C"]}}, "default": "B"}},
+    {{"name": "hash", "type": {{"type": "fixed", "name": "Hash{i}", "size": 16}}}}
+  ]
+}}"#
+            )
+        })
+        .collect()
+}
+
+fn generation(c: &mut Criterion) {
+    let records = std::env::var("RECORDS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(600);
+    let raw_schemas = raw_schemas(records);
+    let raw_schemas = raw_schemas.iter().map(String::as_str).collect::<Vec<_>>();
+    let schemas = Schema::parse_list(&raw_schemas).expect("Invalid schemas");
+    let g = Generator::new().expect("Invalid generator");
+
+    let mut group = c.benchmark_group("generation");
+    // Each generation of the default 600 records takes a fraction of a second
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(records as u64));
+    group.bench_function(format!("{records} records"), |b| {
+        b.iter(|| {
+            let mut buf = vec![];
+            g.gen(&Source::Schemas(&schemas), &mut buf)
+                .expect("Generation failed");
+            black_box(buf)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, generation);
+criterion_main!(benches);
@@ -0,0 +1,190 @@
+//! Procedural macros generating Rust types from Avro schemas at compile time, see
+//! [`include_avro!`](include_avro!).
+
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use rsgen_avro::{Generator, GeneratorBuilder, Source};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, Lit, LitStr, Token};
+
+/// Generates Rust types from Avro schema files at compile time.
+///
+/// The first argument is a glob pattern selecting schema files, relative to the directory of
+/// the crate manifest. It can be followed by generator options, named after the methods of
+/// [`GeneratorBuilder`](rsgen_avro::GeneratorBuilder): `precision`, `nullable`,
+/// `use_avro_rs_unions`, `use_chrono_dates`, `use_decimal_structs`, `string_enums`,
+/// `derive_builders`, `derive_schemas`, `avro_schema_impls`, `avro_message_impls`,
+/// `round_trip_tests`, `proptest_arbitrary`, `quickcheck_arbitrary`,
+/// `writer_schema_decoders`, `try_from_bytes`, `wrapper_conversions`, `alias_types`,
+/// `schema_constants`, `zeroize`, `debug_impls`, `hex_debug`, `validators`, `validate_derive`
+/// (`"validator"` or `"serde_valid"`), `default_impls`, `strict_defaults`, `empty_defaults`,
+/// `serde_impls`, `doc_examples`, `deserialization` (`"standard"`, `"strict"` or
+/// `"lenient"`), `optional_fields` (`"unions"` or `"defaults"`), `box_threshold`,
+/// `smallvec_arrays`, `union_names`, `union_json` (`"untagged"`, `"external"` or `"avro"`),
+/// `rfc3339_timestamps`, `wrap_module`, `allowed_lints` (comma-separated), `record_lints`,
+/// `enum_lints`, `fixed_lints`, `union_lints`, `decimal_lints`, `trait_lints`,
+/// `validation_lints` (see `item_lints`) and `avro_crate`.
+///
+/// ```ignore
+/// use rsgen_avro_macros::include_avro;
+///
+/// include_avro!("schemas/user.avsc");
+/// include_avro!("schemas/events/*.avsc", derive_builders = true, avro_crate = "avro");
+/// ```
+///
+/// The selected files are tracked, modifying them triggers a new generation.
+#[proc_macro]
+pub fn include_avro(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as IncludeAvro);
+    input
+        .expand()
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The arguments of [`include_avro!`](include_avro!).
+struct IncludeAvro {
+    pattern: LitStr,
+    options: Vec<(Ident, Lit)>,
+}
+
+impl Parse for IncludeAvro {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let pattern = input.parse()?;
+        let mut options = vec![];
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key = input.parse()?;
+            input.parse::<Token![=]>()?;
+            options.push((key, input.parse()?));
+        }
+        Ok(IncludeAvro { pattern, options })
+    }
+}
+
+impl IncludeAvro {
+    fn expand(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let error = |e: &dyn std::fmt::Display| syn::Error::new(self.pattern.span(), e);
+
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| error(&e))?;
+        let pattern = PathBuf::from(manifest_dir).join(self.pattern.value());
+        let pattern = pattern.to_string_lossy();
+
+        let mut files = vec![];
+        for entry in glob::glob(&pattern).map_err(|e| error(&e))? {
+            let path = entry.map_err(|e| error(&e))?;
+            if !path.is_dir() {
+                files.push(path.to_string_lossy().into_owned());
+            }
+        }
+        if files.is_empty() {
+            return Err(error(&format!("No Avro schema file matches '{pattern}'")));
+        }
+
+        let generator = self.builder()?.build().map_err(|e| error(&e))?;
+        let mut buf = vec![];
+        generator
+            .gen(&Source::GlobPattern(&pattern), &mut buf)
+            .map_err(|e| error(&e))?;
+        let code = String::from_utf8(buf)
+            .map_err(|e| error(&e))?
+            .parse::<proc_macro2::TokenStream>()
+            .map_err(|e| error(&e))?;
+
+        Ok(quote! {
+            #(const _: &[u8] = include_bytes!(#files);)*
+            #code
+        })
+    }
+
+    fn builder(&self) -> syn::Result<GeneratorBuilder> {
+        let mut builder = Generator::builder();
+        for (key, value) in &self.options {
+            builder = match (key.to_string().as_str(), value) {
+                ("precision", Lit::Int(i)) => builder.precision(i.base10_parse()?),
+                ("nullable", Lit::Bool(b)) => builder.nullable(b.value),
+                ("use_avro_rs_unions", Lit::Bool(b)) => builder.use_avro_rs_unions(b.value),
+                ("use_chrono_dates", Lit::Bool(b)) => builder.use_chrono_dates(b.value),
+                ("use_decimal_structs", Lit::Bool(b)) => builder.use_decimal_structs(b.value),
+                ("string_enums", Lit::Bool(b)) => builder.string_enums(b.value),
+                ("derive_builders", Lit::Bool(b)) => builder.derive_builders(b.value),
+                ("derive_schemas", Lit::Bool(b)) => builder.derive_schemas(b.value),
+                ("avro_schema_impls", Lit::Bool(b)) => builder.avro_schema_impls(b.value),
+                ("avro_message_impls", Lit::Bool(b)) => builder.avro_message_impls(b.value),
+                ("round_trip_tests", Lit::Bool(b)) => builder.round_trip_tests(b.value),
+                ("proptest_arbitrary", Lit::Bool(b)) => builder.proptest_arbitrary(b.value),
+                ("quickcheck_arbitrary", Lit::Bool(b)) => builder.quickcheck_arbitrary(b.value),
+                ("writer_schema_decoders", Lit::Bool(b)) => builder.writer_schema_decoders(b.value),
+                ("try_from_bytes", Lit::Bool(b)) => builder.try_from_bytes(b.value),
+                ("wrapper_conversions", Lit::Bool(b)) => builder.wrapper_conversions(b.value),
+                ("alias_types", Lit::Bool(b)) => builder.alias_types(b.value),
+                ("schema_constants", Lit::Bool(b)) => builder.schema_constants(b.value),
+                ("zeroize", Lit::Bool(b)) => builder.zeroize(b.value),
+                ("debug_impls", Lit::Bool(b)) => builder.debug_impls(b.value),
+                ("hex_debug", Lit::Bool(b)) => builder.hex_debug(b.value),
+                ("validators", Lit::Bool(b)) => builder.validators(b.value),
+                ("default_impls", Lit::Bool(b)) => builder.default_impls(b.value),
+                ("strict_defaults", Lit::Bool(b)) => builder.strict_defaults(b.value),
+                ("empty_defaults", Lit::Bool(b)) => builder.empty_defaults(b.value),
+                ("serde_impls", Lit::Bool(b)) => builder.serde_impls(b.value),
+                ("doc_examples", Lit::Str(s)) => builder.doc_examples(s.value()),
+                ("rfc3339_timestamps", Lit::Bool(b)) => builder.rfc3339_timestamps(b.value),
+                ("deserialization", Lit::Str(s)) => builder.deserialization(
+                    s.value()
+                        .parse()
+                        .map_err(|e: rsgen_avro::Error| syn::Error::new(s.span(), e.to_string()))?,
+                ),
+                ("optional_fields", Lit::Str(s)) => builder.optional_fields(
+                    s.value()
+                        .parse()
+                        .map_err(|e: rsgen_avro::Error| syn::Error::new(s.span(), e.to_string()))?,
+                ),
+                ("box_threshold", Lit::Int(i)) => builder.box_threshold(i.base10_parse()?),
+                ("smallvec_arrays", Lit::Int(i)) => builder.smallvec_arrays(i.base10_parse()?),
+                ("union_names", Lit::Str(s)) => builder.union_names(s.value()),
+                ("validate_derive", Lit::Str(s)) => builder.validate_derive(
+                    s.value()
+                        .parse()
+                        .map_err(|e: rsgen_avro::Error| syn::Error::new(s.span(), e.to_string()))?,
+                ),
+                ("union_json", Lit::Str(s)) => builder.union_json(
+                    s.value()
+                        .parse()
+                        .map_err(|e: rsgen_avro::Error| syn::Error::new(s.span(), e.to_string()))?,
+                ),
+                ("wrap_module", Lit::Str(s)) => builder.wrap_module(s.value()),
+                ("allowed_lints", Lit::Str(s)) => builder.allowed_lints(
+                    s.value()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|lint| !lint.is_empty()),
+                ),
+                (
+                    kind @ ("record_lints" | "enum_lints" | "fixed_lints" | "union_lints"
+                    | "decimal_lints" | "trait_lints" | "validation_lints"),
+                    Lit::Str(s),
+                ) => {
+                    builder.item_lints(
+                        kind.trim_end_matches("_lints").parse().map_err(
+                            |e: rsgen_avro::Error| syn::Error::new(key.span(), e.to_string()),
+                        )?,
+                        s.value(),
+                    )
+                }
+                ("avro_crate", Lit::Str(s)) => builder.avro_crate(s.value()),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("Unsupported option or value type for `{key}`"),
+                    ))
+                }
+            };
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,35 @@
+use rsgen_avro_macros::include_avro;
+
+mod record {
+    rsgen_avro_macros::include_avro!("../tests/schemas/record.avsc");
+}
+
+mod enums {
+    super::include_avro!("../tests/schemas/enums.avsc", avro_crate = "apache_avro");
+}
+
+#[test]
+fn include_record() {
+    let user = record::User {
+        r#as: "as".to_string(),
+        favorite_number: 7,
+        likes_pizza: false,
+        b: vec![],
+        union_b: None,
+        a_bool: vec![],
+        some_integer: vec![],
+        map_of_f64: Default::default(),
+    };
+    let value = apache_avro::to_value(&user).unwrap();
+    assert_eq!(
+        user,
+        apache_avro::from_value::<record::User>(&value).unwrap()
+    );
+}
+
+#[test]
+fn include_enums() {
+    let value = apache_avro::types::Value::Enum(0, "RED".to_string());
+    let color = apache_avro::from_value::<enums::Colors>(&value).unwrap();
+    assert_eq!(enums::Colors::Red, color);
+}
@@ -0,0 +1,217 @@
+//! Reading of the schemas packaged in archives, `.tar`, `.tar.gz`, `.tgz` or `.zip` files,
+//! with the `archives` feature.
+
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// The extensions of the archive files, matched against the end of their names.
+const ARCHIVE_EXTENSIONS: [&str; 4] = [".tar", ".tar.gz", ".tgz", ".zip"];
+
+/// Returns whether a path is read as an archive, by its extension.
+pub(crate) fn is_archive(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext)))
+}
+
+/// Returns the `.avsc` schemas and `.avpr` protocols of an archive, as `(entry name, JSON)`
+/// pairs sorted by name.
+///
+/// Other files are ignored.
+#[cfg(feature = "archives")]
+pub(crate) fn archive_documents(path: &Path) -> Result<Vec<(String, String)>> {
+    use std::io::Read;
+
+    let invalid =
+        |e: std::io::Error| Error::Schema(format!("Invalid archive {}: {e}", path.display()));
+    let file = std::fs::File::open(path)?;
+    let mut entries = vec![];
+    if path.to_string_lossy().ends_with(".zip") {
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| invalid(e.into()))?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i).map_err(|e| invalid(e.into()))?;
+            if entry.is_file() {
+                let mut content = vec![];
+                entry.read_to_end(&mut content).map_err(invalid)?;
+                entries.push((entry.name().to_string(), content));
+            }
+        }
+    } else {
+        let reader: Box<dyn Read> = match path.to_string_lossy().ends_with(".tar") {
+            true => Box::new(file),
+            false => Box::new(flate2::read::GzDecoder::new(file)),
+        };
+        let mut tar = tar::Archive::new(reader);
+        for entry in tar.entries().map_err(invalid)? {
+            let mut entry = entry.map_err(invalid)?;
+            if entry.header().entry_type().is_file() {
+                let name = entry
+                    .path()
+                    .map_err(invalid)?
+                    .to_string_lossy()
+                    .into_owned();
+                let mut content = vec![];
+                entry.read_to_end(&mut content).map_err(invalid)?;
+                entries.push((name.trim_start_matches("./").to_string(), content));
+            }
+        }
+    }
+
+    let mut documents = entries
+        .into_iter()
+        .filter(|(name, _)| {
+            (name.ends_with(".avsc") || name.ends_with(".avpr")) && !name.starts_with("__MACOSX/")
+        })
+        .map(|(name, content)| match String::from_utf8(content) {
+            Ok(document) => Ok((name, document)),
+            Err(e) => Err(Error::Schema(format!(
+                "Invalid schema {name} in {}: {e}",
+                path.display()
+            ))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    documents.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(documents)
+}
+
+/// Fails without the `archives` feature, which reads the documents of archives.
+#[cfg(not(feature = "archives"))]
+pub(crate) fn archive_documents(path: &Path) -> Result<Vec<(String, String)>> {
+    Err(Error::Config(format!(
+        "Reading the archive {} requires the `archives` feature",
+        path.display()
+    )))
+}
+
+#[cfg(all(test, feature = "archives"))]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"{"type": "fixed", "name": "Id", "size": 16}"#;
+
+    /// Returns a tar archive of `files`.
+    fn tar(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(vec![]);
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            builder
+                .append_data(&mut header, name, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    /// Returns a zip archive of deflated `files`.
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(vec![]));
+        for (name, content) in files {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            std::io::Write::write_all(&mut writer, content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn archives() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let long_name = format!("{}/c.avsc", "nested".repeat(20));
+        let files = [
+            ("schemas/b.avsc", SCHEMA),
+            ("schemas/README.md", "# Schemas"),
+            ("./schemas/a.avsc", SCHEMA),
+            (&long_name, SCHEMA),
+            ("service.avpr", "{}"),
+        ];
+        let expected = vec![
+            (long_name.clone(), SCHEMA.to_string()),
+            ("schemas/a.avsc".to_string(), SCHEMA.to_string()),
+            ("schemas/b.avsc".to_string(), SCHEMA.to_string()),
+            ("service.avpr".to_string(), "{}".to_string()),
+        ];
+
+        let path = dir.path().join("schemas.tar");
+        fs::write(&path, tar(&files))?;
+        assert!(is_archive(&path));
+        assert_eq!(archive_documents(&path)?, expected);
+
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        std::io::Write::write_all(&mut encoder, &tar(&files))?;
+        let path = dir.path().join("schemas.tgz");
+        fs::write(&path, encoder.finish()?)?;
+        assert!(is_archive(&path));
+        assert_eq!(archive_documents(&path)?, expected);
+
+        let files = [
+            ("schemas/b.avsc", SCHEMA),
+            ("schemas/a.avsc", SCHEMA),
+            ("service.avpr", "{}"),
+        ];
+        let expected = vec![
+            ("schemas/a.avsc".to_string(), SCHEMA.to_string()),
+            ("schemas/b.avsc".to_string(), SCHEMA.to_string()),
+            ("service.avpr".to_string(), "{}".to_string()),
+        ];
+        let path = dir.path().join("schemas.zip");
+        fs::write(&path, zip(&files))?;
+        assert!(is_archive(&path));
+        assert_eq!(archive_documents(&path)?, expected);
+
+        fs::write(&path, "not a zip")?;
+        let err = archive_documents(&path).unwrap_err().to_string();
+        assert!(err.starts_with("Schema error: Invalid archive"), "{err}");
+        assert!(err.contains("schemas.zip: "), "{err}");
+        assert!(!is_archive(&dir.path().join("schema.avsc")));
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn generation() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let user =
+            r#"{"type": "record", "name": "User", "fields": [{"name": "id", "type": "Id"}]}"#;
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("schemas.tar");
+        fs::write(&path, tar(&[("user.avsc", user), ("id.avsc", SCHEMA)]))?;
+
+        let g = crate::Generator::new()?;
+        let mut expected = vec![];
+        let documents = [("id.avsc", SCHEMA), ("user.avsc", user)];
+        g.gen(&crate::Source::Documents(&documents), &mut expected)?;
+        let mut code = vec![];
+        let pattern = format!("{}/*.tar", dir.path().display());
+        g.gen(&crate::Source::GlobPattern(&pattern), &mut code)?;
+        assert_eq!(String::from_utf8(expected)?, String::from_utf8(code)?);
+
+        let items = g.items(&crate::Source::GlobPattern(&pattern))?;
+        let sources: Vec<_> = items.map(|item| item.unwrap().source.unwrap()).collect();
+        assert_eq!(sources, [path.join("user.avsc"), path.join("id.avsc")]);
+
+        let protocol = r#"{
+  "protocol": "Users",
+  "types": [{"type": "error", "name": "NotFound", "fields": [{"name": "id", "type": "Id"}]}],
+  "messages": {"get": {"request": [], "response": "User", "errors": ["NotFound"]}}
+}"#;
+        let files = [
+            ("user.avsc", user),
+            ("id.avsc", SCHEMA),
+            ("users.avpr", protocol),
+        ];
+        fs::write(&path, tar(&files))?;
+        let items = g.items(&crate::Source::GlobPattern(&pattern))?;
+        let items = items.collect::<crate::Result<Vec<_>>>()?;
+        let names: Vec<_> = items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, ["NotFound", "User", "Id", "GetError"]);
+        assert_eq!(items[0].source, Some(path.join("users.avpr")));
+
+        dir.close()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+//! Encoding of the generated types in the Avro JSON encoding.
+
+/// The code of the module, where `{avro}` is the path of the Avro crate.
+const AVRO_JSON_MODULE: &str = r#"//! Encoding and decoding of the generated types in the Avro JSON encoding, as written by
+//! the `JsonEncoder` of the Java implementation.
+//!
+//! Unlike serde_json, union values other than `null` are wrapped in an object keyed by the
+//! name of their type (e.g. `{"long": 12}`), and `bytes` and `fixed` values are strings of
+//! the code points of their bytes (e.g. `"ÿ"`).
+
+use std::collections::HashMap;
+
+use {avro}::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, Name, RecordSchema,
+    ResolvedSchema,
+};
+use {avro}::types::Value;
+use {avro}::{Error, Schema};
+use serde_json::Value as Json;
+
+type Names<'s> = HashMap<Name, &'s Schema>;
+
+/// Encodes a value of a generated type in the Avro JSON encoding of its `schema`.
+pub fn to_json<T: serde::Serialize>(value: &T, schema: &Schema) -> Result<Json, Error> {
+    let resolved = ResolvedSchema::try_from(schema)?;
+    let value = {avro}::to_value(value)?.resolve(schema)?;
+    encode(&value, schema, resolved.get_names())
+}
+
+/// Encodes a value of a generated type as a string, like [`to_json`].
+pub fn to_string<T: serde::Serialize>(value: &T, schema: &Schema) -> Result<String, Error> {
+    to_json(value, schema).map(|json| json.to_string())
+}
+
+/// Decodes a value of a generated type from the Avro JSON encoding of its `schema`.
+pub fn from_json<T: serde::de::DeserializeOwned>(json: &Json, schema: &Schema) -> Result<T, Error> {
+    let resolved = ResolvedSchema::try_from(schema)?;
+    let value = decode(json, schema, resolved.get_names())?;
+    {avro}::from_value(&value)
+}
+
+/// Decodes a value of a generated type from a string, like [`from_json`].
+pub fn from_str<T: serde::de::DeserializeOwned>(json: &str, schema: &Schema) -> Result<T, Error> {
+    let json = serde_json::from_str(json).map_err(|e| Error::DeserializeValue(e.to_string()))?;
+    from_json(&json, schema)
+}
+
+/// Returns the schema named by a reference, the schema itself otherwise.
+fn named<'s>(schema: &'s Schema, names: &Names<'s>) -> Result<&'s Schema, Error> {
+    match schema {
+        Schema::Ref { name } => names
+            .get(name)
+            .copied()
+            .ok_or_else(|| Error::SchemaResolutionError(name.clone())),
+        _ => Ok(schema),
+    }
+}
+
+/// Returns the name of a type keying the union values of this type.
+fn type_name(schema: &Schema) -> String {
+    match schema {
+        Schema::Record(RecordSchema { name, .. })
+        | Schema::Enum(EnumSchema { name, .. })
+        | Schema::Fixed(FixedSchema { name, .. })
+        | Schema::Ref { name } => return name.fullname(None),
+        Schema::Decimal(DecimalSchema { inner, .. }) => return type_name(inner),
+        Schema::Null => "null",
+        Schema::Boolean => "boolean",
+        Schema::Int | Schema::Date | Schema::TimeMillis => "int",
+        Schema::Long
+        | Schema::TimeMicros
+        | Schema::TimestampMillis
+        | Schema::TimestampMicros
+        | Schema::TimestampNanos
+        | Schema::LocalTimestampMillis
+        | Schema::LocalTimestampMicros
+        | Schema::LocalTimestampNanos => "long",
+        Schema::Float => "float",
+        Schema::Double => "double",
+        Schema::Bytes | Schema::BigDecimal => "bytes",
+        Schema::String | Schema::Uuid => "string",
+        Schema::Duration => "fixed",
+        Schema::Array(_) => "array",
+        Schema::Map(_) => "map",
+        Schema::Union(_) => "union",
+    }
+    .to_string()
+}
+
+/// Returns the string of the code points of `bytes`.
+fn bytes_to_json(bytes: &[u8]) -> Json {
+    Json::String(bytes.iter().map(|b| char::from(*b)).collect())
+}
+
+/// Returns the bytes of the code points of `s`, `None` if some are not bytes.
+fn bytes_from_json(s: &str) -> Option<Vec<u8>> {
+    s.chars().map(|c| u8::try_from(c).ok()).collect()
+}
+
+fn encode<'s>(value: &Value, schema: &'s Schema, names: &Names<'s>) -> Result<Json, Error> {
+    let schema = named(schema, names)?;
+    let float = |v: f64| {
+        serde_json::Number::from_f64(v)
+            .map(Json::Number)
+            .ok_or_else(|| Error::SerializeValue(format!("{v} is not a JSON number")))
+    };
+    Ok(match (value, schema) {
+        (Value::Union(_, value), Schema::Union(_)) if matches!(**value, Value::Null) => Json::Null,
+        (Value::Union(index, value), Schema::Union(union)) => {
+            let Some(schema) = union.variants().get(*index as usize) else {
+                return Err(Error::SerializeValue(format!("No union branch {index}")));
+            };
+            let mut entry = serde_json::Map::new();
+            entry.insert(type_name(schema), encode(value, schema, names)?);
+            Json::Object(entry)
+        }
+        (Value::Record(values), Schema::Record(RecordSchema { fields, .. })) => Json::Object(
+            values
+                .iter()
+                .zip(fields)
+                .map(|((name, value), field)| {
+                    Ok((name.clone(), encode(value, &field.schema, names)?))
+                })
+                .collect::<Result<_, Error>>()?,
+        ),
+        (Value::Array(values), Schema::Array(ArraySchema { items, .. })) => Json::Array(
+            values
+                .iter()
+                .map(|value| encode(value, items, names))
+                .collect::<Result<_, _>>()?,
+        ),
+        (Value::Map(values), Schema::Map(MapSchema { types, .. })) => Json::Object(
+            values
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), encode(value, types, names)?)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        (Value::Null, _) => Json::Null,
+        (Value::Boolean(b), _) => Json::Bool(*b),
+        (Value::Int(v) | Value::Date(v) | Value::TimeMillis(v), _) => Json::from(*v),
+        (
+            Value::Long(v)
+            | Value::TimeMicros(v)
+            | Value::TimestampMillis(v)
+            | Value::TimestampMicros(v)
+            | Value::TimestampNanos(v)
+            | Value::LocalTimestampMillis(v)
+            | Value::LocalTimestampMicros(v)
+            | Value::LocalTimestampNanos(v),
+            _,
+        ) => Json::from(*v),
+        (Value::Float(v), _) => float(f64::from(*v))?,
+        (Value::Double(v), _) => float(*v)?,
+        (Value::Bytes(bytes) | Value::Fixed(_, bytes), _) => bytes_to_json(bytes),
+        (Value::Decimal(decimal), _) => bytes_to_json(&Vec::<u8>::try_from(decimal)?),
+        (Value::Duration(duration), _) => bytes_to_json(&<[u8; 12]>::from(*duration)),
+        (Value::String(s) | Value::Enum(_, s), _) => Json::String(s.clone()),
+        (Value::Uuid(uuid), _) => Json::String(uuid.to_string()),
+        (value, _) => {
+            return Err(Error::SerializeValue(format!(
+                "Unsupported value {value:?} of type `{}`",
+                type_name(schema)
+            )))
+        }
+    })
+}
+
+fn decode<'s>(json: &Json, schema: &'s Schema, names: &Names<'s>) -> Result<Value, Error> {
+    let schema = named(schema, names)?;
+    let invalid = || {
+        Error::DeserializeValue(format!(
+            "Invalid value {json} of type `{}`",
+            type_name(schema)
+        ))
+    };
+    let bytes = |s: &str, size: Option<usize>| {
+        bytes_from_json(s)
+            .filter(|bytes| size.map_or(true, |size| bytes.len() == size))
+            .ok_or_else(invalid)
+    };
+    Ok(match (schema, json) {
+        (Schema::Union(union), _) => {
+            let (name, json) = match json {
+                Json::Null => ("null", json),
+                Json::Object(entry) if entry.len() == 1 => {
+                    let (name, json) = entry.iter().next().ok_or_else(invalid)?;
+                    (name.as_str(), json)
+                }
+                _ => return Err(invalid()),
+            };
+            let (index, schema) = union
+                .variants()
+                .iter()
+                .enumerate()
+                .find(|(_, schema)| type_name(schema) == name)
+                .ok_or_else(invalid)?;
+            Value::Union(index as u32, Box::new(decode(json, schema, names)?))
+        }
+        (Schema::Record(RecordSchema { fields, .. }), Json::Object(values)) => Value::Record(
+            fields
+                .iter()
+                .map(|field| {
+                    let json = values.get(&field.name).ok_or_else(|| {
+                        Error::DeserializeValue(format!("Missing field `{}` in {json}", field.name))
+                    })?;
+                    Ok((field.name.clone(), decode(json, &field.schema, names)?))
+                })
+                .collect::<Result<_, Error>>()?,
+        ),
+        (Schema::Array(ArraySchema { items, .. }), Json::Array(values)) => Value::Array(
+            values
+                .iter()
+                .map(|json| decode(json, items, names))
+                .collect::<Result<_, _>>()?,
+        ),
+        (Schema::Map(MapSchema { types, .. }), Json::Object(values)) => Value::Map(
+            values
+                .iter()
+                .map(|(key, json)| Ok((key.clone(), decode(json, types, names)?)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        (Schema::Enum(EnumSchema { symbols, .. }), Json::String(s)) => {
+            let index = symbols
+                .iter()
+                .position(|symbol| symbol == s)
+                .ok_or_else(invalid)?;
+            Value::Enum(index as u32, s.clone())
+        }
+        (Schema::Decimal(DecimalSchema { inner, .. }), _) => decode(json, inner, names)?,
+        (Schema::Null, Json::Null) => Value::Null,
+        (Schema::Boolean, Json::Bool(b)) => Value::Boolean(*b),
+        (Schema::Int | Schema::Date | Schema::TimeMillis, Json::Number(n)) => Value::Int(
+            n.as_i64()
+                .and_then(|n| i32::try_from(n).ok())
+                .ok_or_else(invalid)?,
+        ),
+        (
+            Schema::Long
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos,
+            Json::Number(n),
+        ) => Value::Long(n.as_i64().ok_or_else(invalid)?),
+        (Schema::Float, Json::Number(n)) => Value::Float(n.as_f64().ok_or_else(invalid)? as f32),
+        (Schema::Double, Json::Number(n)) => Value::Double(n.as_f64().ok_or_else(invalid)?),
+        (Schema::Bytes | Schema::BigDecimal, Json::String(s)) => Value::Bytes(bytes(s, None)?),
+        (Schema::Fixed(FixedSchema { size, .. }), Json::String(s)) => {
+            Value::Fixed(*size, bytes(s, Some(*size))?)
+        }
+        (Schema::Duration, Json::String(s)) => {
+            let bytes: [u8; 12] = bytes(s, Some(12))?.try_into().map_err(|_| invalid())?;
+            Value::Duration(bytes.into())
+        }
+        (Schema::String, Json::String(s)) => Value::String(s.clone()),
+        (Schema::Uuid, Json::String(s)) => {
+            Value::Uuid({avro}::Uuid::parse_str(s).map_err(|_| invalid())?)
+        }
+        _ => return Err(invalid()),
+    })
+}
+"#;
+
+/// Returns the code of a module encoding and decoding the generated types in the Avro JSON
+/// encoding of their schema, referencing the Avro crate at `avro_crate`.
+pub(crate) fn avro_json_module(avro_crate: &str) -> String {
+    AVRO_JSON_MODULE.replace("{avro}", avro_crate)
+}
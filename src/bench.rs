@@ -0,0 +1,144 @@
+//! Criterion benchmark harnesses of generated types.
+
+use std::collections::HashSet;
+
+use apache_avro::schema::{ArraySchema, MapSchema, Name, RecordSchema};
+use apache_avro::types::Value;
+use apache_avro::Schema;
+use heck::ToSnakeCase;
+
+use crate::examples::example;
+use crate::templates::{inlined_schema, GenState, Templater};
+
+/// Returns the code of a criterion benchmark harness measuring the Avro encoding and
+/// decoding of the records among `schemas`, whose types are imported from `types_path`.
+///
+/// Each record is benchmarked with its [example](crate::Generator::gen_examples) value.
+/// Records whose example cannot be resolved against their schema (e.g. decimals), or
+/// which cannot be deserialized into their types (see [`decodable`](decodable)), are listed
+/// in a comment instead.
+pub(crate) fn bench_harness(
+    schemas: &[&Schema],
+    gen_state: &GenState,
+    types_path: &str,
+    templater: &Templater,
+) -> String {
+    let avro = |item: &str| gen_state.avro_path(item);
+    let mut functions = vec![];
+    let mut skipped = vec![];
+    let mut code = String::new();
+
+    for schema in schemas {
+        let Schema::Record(RecordSchema { name, .. }) = schema else {
+            continue;
+        };
+        let type_name = gen_state.name_mapper().type_name(&name.name);
+        let function = type_name
+            .to_snake_case()
+            .trim_start_matches("r#")
+            .to_string();
+        let inlined = inlined_schema(schema, gen_state, &mut Default::default());
+        let example = example(schema, gen_state);
+        let resolves = Schema::parse(&inlined)
+            .map(|s| Value::from(example.clone()).resolve(&s).is_ok())
+            .unwrap_or(false);
+        if !resolves || !decodable(schema, templater, gen_state, &mut HashSet::new()) {
+            skipped.push(format!("`{type_name}`"));
+            continue;
+        }
+        let schema_json = inlined.to_string();
+        let example_json = example.to_string();
+        code.push_str(&format!(
+            r#"
+fn {function}(c: &mut Criterion) {{
+    let schema = {parse_str}({schema_json:?}).unwrap();
+    let example: serde_json::Value = serde_json::from_str({example_json:?}).unwrap();
+    let example = {value}::from(example).resolve(&schema).unwrap();
+    let value: {type_name} = {from_value}(&example).unwrap();
+    let datum = {to_avro_datum}(&schema, example).unwrap();
+
+    let mut group = c.benchmark_group("{type_name}");
+    group.throughput(Throughput::Bytes(datum.len() as u64));
+    group.bench_function("encode", |b| {{
+        b.iter(|| {{
+            let value = {to_value}(&value).unwrap().resolve(&schema).unwrap();
+            {to_avro_datum}(&schema, value).unwrap()
+        }})
+    }});
+    group.bench_function("decode", |b| {{
+        b.iter(|| {{
+            let value = {from_avro_datum}(&schema, &mut datum.as_slice(), None).unwrap();
+            {from_value}::<{type_name}>(&value).unwrap()
+        }})
+    }});
+    group.finish();
+}}
+"#,
+            parse_str = avro("Schema::parse_str"),
+            value = avro("types::Value"),
+            from_value = avro("from_value"),
+            to_value = avro("to_value"),
+            to_avro_datum = avro("to_avro_datum"),
+            from_avro_datum = avro("from_avro_datum"),
+        ));
+        functions.push(function);
+    }
+
+    if !skipped.is_empty() {
+        code.push_str(&format!("\n// Not benchmarked: {}.\n", skipped.join(", ")));
+    }
+    if functions.is_empty() {
+        // `criterion_group!` requires at least one benchmark
+        return format!(
+            "//! Criterion benchmarks of the Avro encoding and decoding of generated types.\n\
+             {code}\n\
+             fn main() {{}}\n"
+        );
+    }
+    format!(
+        "//! Criterion benchmarks of the Avro encoding and decoding of generated types.\n\
+         \n\
+         use criterion::{{criterion_group, criterion_main, Criterion, Throughput}};\n\
+         use {types_path}::*;\n\
+         {code}\n\
+         criterion_group!(benches, {});\n\
+         criterion_main!(benches);\n",
+        functions.join(", ")
+    )
+}
+
+/// Returns whether the values of a schema can be deserialized from Avro values into the
+/// generated types.
+///
+/// Multi-valued unions are only supported as avro-rs unions, and nullable fields expect
+/// Avro unions.
+fn decodable<'a>(
+    schema: &'a Schema,
+    templater: &Templater,
+    gen_state: &'a GenState,
+    visited: &mut HashSet<&'a Name>,
+) -> bool {
+    match schema {
+        Schema::Ref { name } if visited.insert(name) => gen_state
+            .get_schema(name)
+            .is_none_or(|s| decodable(s, templater, gen_state, visited)),
+        Schema::Record(RecordSchema { fields, .. }) => fields.iter().all(|f| {
+            let optional =
+                matches!(&f.schema, Schema::Union(u) if u.is_nullable() && u.variants().len() == 2);
+            (optional || !templater.nullable) && decodable(&f.schema, templater, gen_state, visited)
+        }),
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => {
+            decodable(inner, templater, gen_state, visited)
+        }
+        Schema::Union(union) => {
+            let variants = union.variants();
+            (templater.use_avro_rs_unions
+                || variants.iter().filter(|v| **v != Schema::Null).count() <= 1)
+                && variants
+                    .iter()
+                    .all(|v| decodable(v, templater, gen_state, visited))
+        }
+        _ => true,
+    }
+}
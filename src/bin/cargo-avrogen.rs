@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+use clap::Parser;
+use rsgen_avro::{Config, CONFIG_FILE};
+
+#[derive(Debug, Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    Avrogen(Args),
+}
+
+/// Generate Rust types from the Avro schemas configured in rsgen-avro.toml files
+///
+/// Configuration files are read from the workspace root and from the directory of every
+/// workspace member.
+#[derive(Debug, clap::Args)]
+#[command(version)]
+struct Args {
+    /// Path to the Cargo.toml of the workspace
+    #[clap(long, value_name = "PATH")]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Only generate the targets of the given workspace members
+    #[clap(long, short, value_name = "NAME")]
+    pub package: Vec<String>,
+}
+
+/// The parts of `cargo metadata` used to locate configuration files.
+#[derive(Debug, serde::Deserialize)]
+struct Metadata {
+    workspace_root: PathBuf,
+    packages: Vec<Package>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Package {
+    name: String,
+    manifest_path: PathBuf,
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let Cargo::Avrogen(args) = Cargo::parse();
+    let metadata = metadata(args.manifest_path.as_deref())?;
+
+    let mut dirs = vec![];
+    if args.package.is_empty() {
+        dirs.push(metadata.workspace_root.clone());
+    }
+    for package in &metadata.packages {
+        if args.package.is_empty() || args.package.contains(&package.name) {
+            dirs.extend(package.manifest_path.parent().map(Path::to_path_buf));
+        }
+    }
+    for name in &args.package {
+        if !metadata.packages.iter().any(|p| &p.name == name) {
+            return Err(format!("Package `{name}` is not a member of the workspace").into());
+        }
+    }
+    dirs.sort();
+    dirs.dedup();
+
+    let config_files = dirs
+        .iter()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .filter(|file| file.is_file())
+        .collect::<Vec<_>>();
+    if config_files.is_empty() {
+        return Err(format!(
+            "No {CONFIG_FILE} found in workspace {}",
+            metadata.workspace_root.display()
+        )
+        .into());
+    }
+
+    for config_file in config_files {
+        for target in Config::load(&config_file)?.targets {
+            let written = target.generate()?;
+            if written && target.fmt {
+                // rustfmt also formats the namespace modules declared by mod.rs
+                let file = match target.split_namespaces {
+                    true => target.output.join("mod.rs"),
+                    false => target.output.clone(),
+                };
+                Command::new("rustfmt").arg(file).status()?;
+            }
+            let status = if written { "Generated" } else { "Fresh" };
+            let output = target
+                .output
+                .strip_prefix(&metadata.workspace_root)
+                .unwrap_or(&target.output);
+            eprintln!("{status:>12} {}", output.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs `cargo metadata` for the workspace of the current directory or of `manifest_path`.
+fn metadata(manifest_path: Option<&Path>) -> Result<Metadata, Box<dyn Error>> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.args(["metadata", "--format-version", "1", "--no-deps"]);
+    if let Some(path) = manifest_path {
+        command.arg("--manifest-path").arg(path);
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr)
+            .trim()
+            .to_string()
+            .into());
+    }
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+fn main() {
+    run().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+}
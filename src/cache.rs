@@ -0,0 +1,130 @@
+//! Content-hash cache of rendered items, for incremental generations.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{DefaultHasher, Hasher};
+use std::path::Path;
+
+use apache_avro::schema::{ArraySchema, MapSchema, Name, RecordSchema};
+use apache_avro::Schema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::templates::GenState;
+
+/// Returns the hexadecimal 128-bit hash of the given parts.
+///
+/// The hashes are not stable across Rust releases, which then only invalidate the caches.
+pub(crate) fn hash<'a>(parts: impl IntoIterator<Item = &'a [u8]>) -> String {
+    let mut hashers = [DefaultHasher::new(), DefaultHasher::new()];
+    hashers[1].write_u8(1);
+    for part in parts {
+        for hasher in &mut hashers {
+            // Length prefixes avoid collisions between different splits of the same bytes
+            hasher.write_u64(part.len() as u64);
+            hasher.write(part);
+        }
+    }
+    hashers
+        .iter()
+        .map(|hasher| format!("{:016x}", hasher.finish()))
+        .collect()
+}
+
+/// Returns the cache key of the item rendered from a schema.
+///
+/// Besides the schema itself, the key covers the named schemas it references, as their
+/// names and types are part of the rendered code.
+pub(crate) fn item_key(schema: &Schema, gen_state: &GenState) -> String {
+    fn walk<'a>(
+        schema: &'a Schema,
+        gen_state: &'a GenState,
+        visited: &mut HashSet<&'a Name>,
+        parts: &mut Vec<String>,
+    ) {
+        match schema {
+            Schema::Ref { name } if visited.insert(name) => {
+                if let Some(s) = gen_state.get_schema(name) {
+                    parts.push(serde_json::to_string(s).unwrap_or_default());
+                    walk(s, gen_state, visited, parts);
+                }
+            }
+            Schema::Record(RecordSchema { fields, .. }) => {
+                for field in fields {
+                    walk(&field.schema, gen_state, visited, parts);
+                }
+            }
+            Schema::Array(ArraySchema { items: inner, .. })
+            | Schema::Map(MapSchema { types: inner, .. }) => walk(inner, gen_state, visited, parts),
+            Schema::Union(union) => {
+                for variant in union.variants() {
+                    walk(variant, gen_state, visited, parts);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut parts = vec![serde_json::to_string(schema).unwrap_or_default()];
+    walk(schema, gen_state, &mut HashSet::new(), &mut parts);
+    hash(parts.iter().map(|p| p.as_bytes()))
+}
+
+/// Rendered items from a previous generation, keyed by [`item_key`](item_key).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct RenderCache {
+    /// The hash of the generator configuration the items were rendered with.
+    config: String,
+    /// The hash of the whole generated code.
+    output: String,
+    items: HashMap<String, String>,
+    #[serde(skip)]
+    used: HashMap<String, String>,
+}
+
+impl RenderCache {
+    /// Loads a cache file, ignoring it if it is missing, invalid, or was written with
+    /// another configuration.
+    pub(crate) fn load(path: &Path, config: &str) -> RenderCache {
+        let cache = fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<RenderCache>(&s).ok())
+            .filter(|cache| cache.config == config);
+        cache.unwrap_or_else(|| RenderCache {
+            config: config.to_string(),
+            ..RenderCache::default()
+        })
+    }
+
+    /// Returns the cached code of an item, rendering it if it is not in the cache.
+    pub(crate) fn get_or_render(
+        &mut self,
+        key: String,
+        render: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        let code = match self.items.get(&key) {
+            Some(code) => code.clone(),
+            None => render()?,
+        };
+        self.used.insert(key, code.clone());
+        Ok(code)
+    }
+
+    /// Returns whether the generated code differs from the previous generation.
+    pub(crate) fn update_output(&mut self, code: &str) -> bool {
+        let output = hash([code.as_bytes()]);
+        let changed = output != self.output;
+        self.output = output;
+        changed
+    }
+
+    /// Saves the items used by the last generation, dropping stale ones.
+    pub(crate) fn save(mut self, path: &Path) -> Result<()> {
+        self.items = std::mem::take(&mut self.used);
+        fs::write(
+            path,
+            serde_json::to_string(&self).map_err(std::io::Error::from)?,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+//! The [Parsing Canonical Form](https://avro.apache.org/docs/1.11.1/specification/#parsing-canonical-form-for-schemas)
+//! of Avro schemas.
+
+use std::collections::HashSet;
+
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, Name, RecordSchema,
+};
+use apache_avro::Schema;
+
+/// Returns the Parsing Canonical Form of a schema, which only differs between schemas that
+/// parse data differently.
+///
+/// Unlike [`Schema::canonical_form`](Schema::canonical_form) of apache-avro, logical types
+/// are reduced to their underlying types and only the attributes listed by the specification
+/// are kept, so that the form (and the fingerprints computed from it) match the ones of
+/// other Avro implementations.
+pub fn canonical_form(schema: &Schema) -> String {
+    let mut form = String::new();
+    write(schema, &mut HashSet::new(), &mut form);
+    form
+}
+
+/// Writes the canonical form of a schema, named schemas being written in full only the first
+/// time they are found, as in the schema text.
+fn write<'a>(schema: &'a Schema, defined: &mut HashSet<&'a Name>, form: &mut String) {
+    let primitive = match schema {
+        Schema::Null => "null",
+        Schema::Boolean => "boolean",
+        Schema::Int | Schema::Date | Schema::TimeMillis => "int",
+        Schema::Long
+        | Schema::TimeMicros
+        | Schema::TimestampMillis
+        | Schema::TimestampMicros
+        | Schema::TimestampNanos
+        | Schema::LocalTimestampMillis
+        | Schema::LocalTimestampMicros
+        | Schema::LocalTimestampNanos => "long",
+        Schema::Float => "float",
+        Schema::Double => "double",
+        Schema::Bytes | Schema::BigDecimal => "bytes",
+        Schema::String | Schema::Uuid => "string",
+        // apache-avro does not keep the underlying fixed of durations, see its serializer
+        Schema::Duration => {
+            form.push_str(r#"{"name":"duration","type":"fixed","size":12}"#);
+            return;
+        }
+        Schema::Decimal(DecimalSchema { inner, .. }) => return write(inner, defined, form),
+
+        Schema::Ref { name } => {
+            form.push_str(&string(&name.fullname(None)));
+            return;
+        }
+        Schema::Record(RecordSchema { name, .. })
+        | Schema::Enum(EnumSchema { name, .. })
+        | Schema::Fixed(FixedSchema { name, .. })
+            if !defined.insert(name) =>
+        {
+            form.push_str(&string(&name.fullname(None)));
+            return;
+        }
+
+        Schema::Record(RecordSchema { name, fields, .. }) => {
+            form.push_str(&format!(
+                r#"{{"name":{},"type":"record","fields":["#,
+                string(&name.fullname(None))
+            ));
+            for (i, field) in fields.iter().enumerate() {
+                if i > 0 {
+                    form.push(',');
+                }
+                form.push_str(&format!(r#"{{"name":{},"type":"#, string(&field.name)));
+                write(&field.schema, defined, form);
+                form.push('}');
+            }
+            form.push_str("]}");
+            return;
+        }
+        Schema::Enum(EnumSchema { name, symbols, .. }) => {
+            let symbols = symbols.iter().map(|s| string(s)).collect::<Vec<_>>();
+            form.push_str(&format!(
+                r#"{{"name":{},"type":"enum","symbols":[{}]}}"#,
+                string(&name.fullname(None)),
+                symbols.join(",")
+            ));
+            return;
+        }
+        Schema::Fixed(FixedSchema { name, size, .. }) => {
+            form.push_str(&format!(
+                r#"{{"name":{},"type":"fixed","size":{size}}}"#,
+                string(&name.fullname(None))
+            ));
+            return;
+        }
+        Schema::Array(ArraySchema { items, .. }) => {
+            form.push_str(r#"{"type":"array","items":"#);
+            write(items, defined, form);
+            form.push('}');
+            return;
+        }
+        Schema::Map(MapSchema { types, .. }) => {
+            form.push_str(r#"{"type":"map","values":"#);
+            write(types, defined, form);
+            form.push('}');
+            return;
+        }
+        Schema::Union(union) => {
+            form.push('[');
+            for (i, variant) in union.variants().iter().enumerate() {
+                if i > 0 {
+                    form.push(',');
+                }
+                write(variant, defined, form);
+            }
+            form.push(']');
+            return;
+        }
+    };
+    form.push_str(&string(primitive));
+}
+
+/// Returns a JSON string literal, with only the characters JSON requires escaped.
+fn string(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_forms() {
+        let schema = Schema::parse_str(
+            r#"
+        {"type": "record", "name": "User", "namespace": "com.acme", "doc": "A user.", "fields": [
+          {"name": "id", "type": {"type": "string", "logicalType": "uuid"}},
+          {"name": "born", "type": {"type": "int", "logicalType": "date"}, "default": 0},
+          {"name": "kind", "aliases": ["type"], "type": {
+            "type": "enum", "name": "Kind", "namespace": "com.acme.kinds", "symbols": ["A", "B"]
+          }},
+          {"name": "previous", "type": ["null", "com.acme.kinds.Kind"], "order": "ignore"},
+          {"name": "balance", "type": {
+            "type": "fixed", "name": "Amount", "size": 4, "logicalType": "decimal", "precision": 8
+          }},
+          {"name": "tags", "type": {"type": "map", "values": {"type": "array", "items": "long"}}}
+        ]}
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            concat!(
+                r#"{"name":"com.acme.User","type":"record","fields":["#,
+                r#"{"name":"id","type":"string"},"#,
+                r#"{"name":"born","type":"int"},"#,
+                r#"{"name":"kind","type":{"name":"com.acme.kinds.Kind","type":"enum","symbols":["A","B"]}},"#,
+                r#"{"name":"previous","type":["null","com.acme.kinds.Kind"]},"#,
+                r#"{"name":"balance","type":{"name":"com.acme.Amount","type":"fixed","size":4}},"#,
+                r#"{"name":"tags","type":{"type":"map","values":{"type":"array","items":"long"}}}"#,
+                "]}"
+            ),
+            canonical_form(&schema)
+        );
+
+        // Identical to apache-avro without logical types and extra attributes
+        let schema = Schema::parse_str(
+            r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": ["int", "R"]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(schema.canonical_form(), canonical_form(&schema));
+    }
+}
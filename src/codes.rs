@@ -0,0 +1,141 @@
+//! Stable codes of the errors, see [`Error::code`](crate::Error::code), and their
+//! explanations for schema authors.
+
+use apache_avro::Error as AvroError;
+
+use crate::error::{Error, ErrorKind};
+
+/// The error codes and their explanations, in Markdown.
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("E0001", include_str!("explanations/E0001.md")),
+    ("E0002", include_str!("explanations/E0002.md")),
+    ("E0003", include_str!("explanations/E0003.md")),
+    ("E0004", include_str!("explanations/E0004.md")),
+    ("E0005", include_str!("explanations/E0005.md")),
+    ("E0006", include_str!("explanations/E0006.md")),
+    ("E0007", include_str!("explanations/E0007.md")),
+    ("E0008", include_str!("explanations/E0008.md")),
+    ("E0009", include_str!("explanations/E0009.md")),
+    ("E0010", include_str!("explanations/E0010.md")),
+    ("E0011", include_str!("explanations/E0011.md")),
+    ("E0012", include_str!("explanations/E0012.md")),
+    ("E0013", include_str!("explanations/E0013.md")),
+];
+
+/// Returns the explanation of an error code, e.g. `E0004`, in Markdown: what the error
+/// means and how to fix it in the schema.
+///
+/// ```
+/// assert!(rsgen_avro::explain_error("E0004").is_some());
+/// assert!(rsgen_avro::explain_error("E9999").is_none());
+/// ```
+pub fn explain_error(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code.trim()))
+        .map(|(_, explanation)| *explanation)
+}
+
+/// Returns the code of an error.
+pub(crate) fn code(error: &Error) -> &'static str {
+    match error {
+        Error::Located { error, .. } => code(error),
+        Error::Avro(e) => match e.as_ref() {
+            AvroError::ParseSchemaJson(_) => "E0002",
+            AvroError::ParsePrimitive(_) => "E0003",
+            AvroError::GetDefaultUnion(..)
+            | AvroError::GetDefaultRecordField(..)
+            | AvroError::GetEnumDefault { .. }
+            | AvroError::FixedDefaultLenSizeMismatch(..) => "E0004",
+            AvroError::EmptyUnion | AvroError::GetNestedUnion | AvroError::GetUnionDuplicate => {
+                "E0005"
+            }
+            AvroError::NameCollision(_) | AvroError::AmbiguousSchemaDefinition(_) => "E0006",
+            _ => "E0001",
+        },
+        Error::Schema(_) => "E0001",
+        Error::InvalidSchema { kind, .. } | Error::Generation { kind, .. } => kind_code(*kind),
+        Error::InvalidDefault { .. } | Error::InvalidDefaultSize { .. } => "E0004",
+        Error::Template(_) => "E0009",
+        Error::Config(_) => "E0007",
+        Error::InvalidCode { .. } => "E0010",
+        Error::Io(_) => "E0011",
+        Error::GlobPattern(_) => "E0012",
+        Error::Cancelled => "E0013",
+    }
+}
+
+/// Returns the code of the errors of a kind.
+fn kind_code(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Json => "E0002",
+        ErrorKind::Reference => "E0003",
+        ErrorKind::Default => "E0004",
+        ErrorKind::Union => "E0005",
+        ErrorKind::Conflict => "E0006",
+        ErrorKind::Unsupported => "E0007",
+        ErrorKind::Attribute => "E0008",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Generator, Source};
+
+    #[test]
+    fn codes() {
+        let code = |raw_schema: &str| {
+            let err = Generator::new()
+                .unwrap()
+                .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+                .unwrap_err();
+            err.code()
+        };
+        assert_eq!("E0002", code(r#"{"type": "record""#));
+        assert_eq!("E0001", code(r#"{"type": "record", "name": "R"}"#));
+        assert_eq!(
+            "E0003",
+            code(r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": "A"}]}"#)
+        );
+        assert_eq!(
+            "E0004",
+            code(
+                r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": "double", "default": 1}]}"#
+            )
+        );
+        assert_eq!(
+            "E0005",
+            code(r#"{"type": "array", "items": ["string", "string"]}"#)
+        );
+        assert_eq!(
+            "E0006",
+            code(r#"{"type": "enum", "name": "E", "symbols": ["red", "RED"]}"#)
+        );
+        assert_eq!(
+            "E0008",
+            code(r#"{"type": "fixed", "name": "F", "size": 1, "rust.template": 1}"#)
+        );
+        assert_eq!("E0009", Error::Template("Unknown filter".into()).code());
+        // Codes are given by the kind of the errors, not by their message
+        assert_eq!(
+            "E0007",
+            Error::Generation {
+                kind: ErrorKind::Unsupported,
+                message: "Unsupported".into(),
+            }
+            .code()
+        );
+        assert_eq!("E0009", Error::Template("Invalid default".into()).code());
+        assert_eq!("E0013", Error::Cancelled.code());
+    }
+
+    #[test]
+    fn explanations() {
+        for (code, explanation) in EXPLANATIONS {
+            assert_eq!(Some(*explanation), explain_error(code));
+            assert!(explanation.ends_with(".\n"), "{code}");
+        }
+        assert_eq!(EXPLANATIONS[3].1, explain_error("e0004").unwrap());
+    }
+}
@@ -0,0 +1,466 @@
+//! Compatibility checks between Avro schemas, following the
+//! [schema resolution](https://avro.apache.org/docs/1.11.1/specification/#schema-resolution)
+//! rules of the specification.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, Name, RecordSchema,
+};
+use apache_avro::Schema;
+
+/// Which versions of a schema must be able to read data written with the other one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompatibilityMode {
+    /// The new schema can read data written with the old schema.
+    Backward,
+    /// The old schema can read data written with the new schema.
+    Forward,
+    /// Both backward and forward.
+    Full,
+}
+
+impl fmt::Display for CompatibilityMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CompatibilityMode::Backward => "backward",
+            CompatibilityMode::Forward => "forward",
+            CompatibilityMode::Full => "full",
+        })
+    }
+}
+
+/// A breaking difference between two schemas, see [`check_compatibility`](check_compatibility).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Incompatibility {
+    /// [`Backward`](CompatibilityMode::Backward) if the new schema cannot read old data,
+    /// [`Forward`](CompatibilityMode::Forward) if the old schema cannot read new data.
+    pub mode: CompatibilityMode,
+    /// The location of the incompatibility in the reader schema, e.g. `User.tags[]`, where
+    /// `[]` denotes array items and `{}` map values.
+    pub path: String,
+    /// What prevents the reader schema from reading the writer data.
+    pub message: String,
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.path, self.mode, self.message)
+    }
+}
+
+/// Returns the incompatibilities between an `old` and a `new` version of a schema, which is
+/// empty if they are compatible in the given `mode`.
+///
+/// ```
+/// use apache_avro::Schema;
+/// use rsgen_avro::{check_compatibility, CompatibilityMode};
+///
+/// let old = Schema::parse_str(r#"
+/// {"type": "record", "name": "User", "fields": [{"name": "name", "type": "string"}]}
+/// "#).unwrap();
+/// let new = Schema::parse_str(r#"
+/// {"type": "record", "name": "User", "fields": [
+///   {"name": "name", "type": "string"},
+///   {"name": "age", "type": "int"}
+/// ]}
+/// "#).unwrap();
+///
+/// assert!(check_compatibility(&old, &new, CompatibilityMode::Forward).is_empty());
+/// let incompatibilities = check_compatibility(&old, &new, CompatibilityMode::Backward);
+/// assert_eq!(
+///     "User.age (backward): Field is missing from the writer schema and has no default",
+///     incompatibilities[0].to_string()
+/// );
+/// ```
+pub fn check_compatibility(
+    old: &Schema,
+    new: &Schema,
+    mode: CompatibilityMode,
+) -> Vec<Incompatibility> {
+    let mut incompatibilities = vec![];
+    if mode != CompatibilityMode::Forward {
+        let checker = Checker::new(old, new, CompatibilityMode::Backward);
+        incompatibilities.extend(checker.run(old, new));
+    }
+    if mode != CompatibilityMode::Backward {
+        let checker = Checker::new(new, old, CompatibilityMode::Forward);
+        incompatibilities.extend(checker.run(new, old));
+    }
+    incompatibilities
+}
+
+/// Checks whether data written with a writer schema can be read with a reader schema.
+struct Checker<'a> {
+    mode: CompatibilityMode,
+    writer_names: HashMap<&'a Name, &'a Schema>,
+    reader_names: HashMap<&'a Name, &'a Schema>,
+    /// Pairs of named types being checked, assumed compatible to stop on recursive types.
+    visited: HashSet<(&'a Name, &'a Name)>,
+    incompatibilities: Vec<Incompatibility>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(writer: &'a Schema, reader: &'a Schema, mode: CompatibilityMode) -> Checker<'a> {
+        let mut writer_names = HashMap::new();
+        let mut reader_names = HashMap::new();
+        named_schemas(writer, &mut writer_names);
+        named_schemas(reader, &mut reader_names);
+        Checker {
+            mode,
+            writer_names,
+            reader_names,
+            visited: HashSet::new(),
+            incompatibilities: vec![],
+        }
+    }
+
+    fn run(mut self, writer: &'a Schema, reader: &'a Schema) -> Vec<Incompatibility> {
+        let path = match self.resolve(reader, false) {
+            Schema::Record(RecordSchema { name, .. })
+            | Schema::Enum(EnumSchema { name, .. })
+            | Schema::Fixed(FixedSchema { name, .. }) => name.name.clone(),
+            _ => String::new(),
+        };
+        self.check(writer, reader, &path);
+        self.incompatibilities
+    }
+
+    fn report(&mut self, path: &str, message: String) {
+        self.incompatibilities.push(Incompatibility {
+            mode: self.mode,
+            path: if path.is_empty() { "." } else { path }.to_string(),
+            message,
+        });
+    }
+
+    /// Returns the definition of a named type reference, and the underlying type of a
+    /// logical type, which is what resolution is based on.
+    fn resolve(&self, schema: &'a Schema, writer: bool) -> &'a Schema {
+        static INT: Schema = Schema::Int;
+        static LONG: Schema = Schema::Long;
+        static BYTES: Schema = Schema::Bytes;
+        static STRING: Schema = Schema::String;
+
+        match schema {
+            Schema::Ref { name } => {
+                let names = if writer {
+                    &self.writer_names
+                } else {
+                    &self.reader_names
+                };
+                names.get(name).map_or(schema, |s| self.resolve(s, writer))
+            }
+            Schema::Decimal(DecimalSchema { inner, .. }) => self.resolve(inner, writer),
+            Schema::BigDecimal => &BYTES,
+            Schema::Uuid => &STRING,
+            Schema::Date | Schema::TimeMillis => &INT,
+            Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos => &LONG,
+            _ => schema,
+        }
+    }
+
+    /// Returns whether the writer schema can be read with the reader schema, without
+    /// reporting anything.
+    fn can_read(&self, writer: &'a Schema, reader: &'a Schema) -> bool {
+        let mut checker = Checker {
+            mode: self.mode,
+            writer_names: self.writer_names.clone(),
+            reader_names: self.reader_names.clone(),
+            visited: self.visited.clone(),
+            incompatibilities: vec![],
+        };
+        checker.check(writer, reader, "");
+        checker.incompatibilities.is_empty()
+    }
+
+    fn check(&mut self, writer: &'a Schema, reader: &'a Schema, path: &str) {
+        let writer = self.resolve(writer, true);
+        let reader = self.resolve(reader, false);
+
+        match (writer, reader) {
+            (Schema::Union(w), _) => {
+                for variant in w.variants() {
+                    self.check(variant, reader, path);
+                }
+            }
+            (_, Schema::Union(r)) => {
+                if !r.variants().iter().any(|v| self.can_read(writer, v)) {
+                    let message = format!(
+                        "Reader union has no branch matching writer type {}",
+                        describe(writer)
+                    );
+                    self.report(path, message);
+                }
+            }
+
+            (Schema::Record(w), Schema::Record(r)) => {
+                if !same_name(&w.name, &r.name, &r.aliases) {
+                    self.report_names(path, writer, reader);
+                    return;
+                }
+                if !self.visited.insert((&w.name, &r.name)) {
+                    return;
+                }
+                for field in &r.fields {
+                    let field_path = format!("{path}.{}", field.name);
+                    let aliases = field.aliases.iter().flatten();
+                    // Only the reader aliases are used, unlike in `RecordSchema::lookup`
+                    let writer_field = w.fields.iter().find(|f| {
+                        f.name == field.name || aliases.clone().any(|alias| *alias == f.name)
+                    });
+                    match writer_field {
+                        Some(writer_field) => {
+                            self.check(&writer_field.schema, &field.schema, &field_path)
+                        }
+                        None if field.default.is_none() => self.report(
+                            &field_path,
+                            "Field is missing from the writer schema and has no default"
+                                .to_string(),
+                        ),
+                        None => (),
+                    }
+                }
+            }
+
+            (Schema::Enum(w), Schema::Enum(r)) => {
+                if !same_name(&w.name, &r.name, &r.aliases) {
+                    self.report_names(path, writer, reader);
+                    return;
+                }
+                let missing = w
+                    .symbols
+                    .iter()
+                    .filter(|s| !r.symbols.contains(s))
+                    .map(|s| format!("`{s}`"))
+                    .collect::<Vec<_>>();
+                if !missing.is_empty() && r.default.is_none() {
+                    let message = format!(
+                        "Reader enum has no default and lacks writer symbols {}",
+                        missing.join(", ")
+                    );
+                    self.report(path, message);
+                }
+            }
+
+            (Schema::Fixed(w), Schema::Fixed(r)) => {
+                if !same_name(&w.name, &r.name, &r.aliases) {
+                    self.report_names(path, writer, reader);
+                } else if w.size != r.size {
+                    let message = format!(
+                        "Reader fixed size {} differs from writer fixed size {}",
+                        r.size, w.size
+                    );
+                    self.report(path, message);
+                }
+            }
+
+            (
+                Schema::Array(ArraySchema { items: w, .. }),
+                Schema::Array(ArraySchema { items: r, .. }),
+            ) => self.check(w, r, &format!("{path}[]")),
+            (Schema::Map(MapSchema { types: w, .. }), Schema::Map(MapSchema { types: r, .. })) => {
+                self.check(w, r, &format!("{path}{{}}"))
+            }
+
+            (w, r) if promotes(w, r) => (),
+            (w, r) => {
+                let message = format!(
+                    "Reader type {} cannot read writer type {}",
+                    describe(r),
+                    describe(w)
+                );
+                self.report(path, message);
+            }
+        }
+    }
+
+    fn report_names(&mut self, path: &str, writer: &Schema, reader: &Schema) {
+        let message = format!(
+            "Reader type {} does not match writer type {}",
+            describe(reader),
+            describe(writer)
+        );
+        self.report(path, message);
+    }
+}
+
+/// Collects the named types defined in a schema.
+fn named_schemas<'a>(schema: &'a Schema, names: &mut HashMap<&'a Name, &'a Schema>) {
+    match schema {
+        Schema::Record(RecordSchema { name, fields, .. }) => {
+            names.insert(name, schema);
+            for field in fields {
+                named_schemas(&field.schema, names);
+            }
+        }
+        Schema::Enum(EnumSchema { name, .. }) | Schema::Fixed(FixedSchema { name, .. }) => {
+            names.insert(name, schema);
+        }
+        Schema::Decimal(DecimalSchema { inner, .. })
+        | Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => named_schemas(inner, names),
+        Schema::Union(union) => {
+            for variant in union.variants() {
+                named_schemas(variant, names);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Returns whether the unqualified writer name matches the reader name or one of its aliases.
+fn same_name(writer: &Name, reader: &Name, aliases: &apache_avro::schema::Aliases) -> bool {
+    writer.name == reader.name
+        || aliases
+            .iter()
+            .flatten()
+            .any(|alias| alias.name() == writer.name)
+}
+
+/// Returns whether values of the writer type can be read as values of the reader type,
+/// either because both types are the same or through a promotion.
+fn promotes(writer: &Schema, reader: &Schema) -> bool {
+    use Schema::*;
+    matches!(
+        (writer, reader),
+        (Null, Null)
+            | (Boolean, Boolean)
+            | (Int, Int | Long | Float | Double)
+            | (Long, Long | Float | Double)
+            | (Float, Float | Double)
+            | (Double, Double)
+            | (Bytes | String, Bytes | String)
+            | (Duration, Duration)
+    )
+}
+
+/// Returns a short description of a schema type for diagnostics.
+fn describe(schema: &Schema) -> String {
+    match schema {
+        Schema::Record(RecordSchema { name, .. }) => format!("record `{}`", name.fullname(None)),
+        Schema::Enum(EnumSchema { name, .. }) => format!("enum `{}`", name.fullname(None)),
+        Schema::Fixed(FixedSchema { name, .. }) => format!("fixed `{}`", name.fullname(None)),
+        Schema::Ref { name } => format!("`{}`", name.fullname(None)),
+        Schema::Array(_) => "array".to_string(),
+        Schema::Map(_) => "map".to_string(),
+        Schema::Union(_) => "union".to_string(),
+        Schema::Duration => "duration".to_string(),
+        other => serde_json::to_string(other)
+            .unwrap_or_default()
+            .replace('"', "`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(old: &str, new: &str, mode: CompatibilityMode) -> Vec<String> {
+        let old = Schema::parse_str(old).unwrap();
+        let new = Schema::parse_str(new).unwrap();
+        check_compatibility(&old, &new, mode)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn records() {
+        let old = r#"
+        {"type": "record", "name": "User", "namespace": "com.acme", "fields": [
+          {"name": "name", "type": "string"},
+          {"name": "age", "type": "int"},
+          {"name": "tags", "type": {"type": "array", "items": "string"}}
+        ]}
+        "#;
+        let new = r#"
+        {"type": "record", "name": "User", "namespace": "com.acme.v2", "fields": [
+          {"name": "full_name", "aliases": ["name"], "type": "string"},
+          {"name": "age", "type": "long"},
+          {"name": "email", "type": ["null", "string"], "default": null},
+          {"name": "tags", "type": {"type": "array", "items": "bytes"}}
+        ]}
+        "#;
+        assert!(check(old, new, CompatibilityMode::Backward).is_empty());
+        assert_eq!(
+            vec![
+                "User.name (forward): Field is missing from the writer schema and has no default",
+                "User.age (forward): Reader type `int` cannot read writer type `long`",
+            ],
+            check(old, new, CompatibilityMode::Full)
+        );
+    }
+
+    #[test]
+    fn unions_and_enums() {
+        let old = r#"
+        {"type": "record", "name": "Event", "fields": [
+          {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}},
+          {"name": "payload", "type": ["int", "string"]},
+          {"name": "labels", "type": {"type": "map", "values": "int"}}
+        ]}
+        "#;
+        let new = r#"
+        {"type": "record", "name": "Event", "fields": [
+          {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A"]}},
+          {"name": "payload", "type": ["null", "string"]},
+          {"name": "labels", "type": {"type": "map", "values": ["null", "double"]}}
+        ]}
+        "#;
+        assert_eq!(
+            vec![
+                "Event.kind (backward): Reader enum has no default and lacks writer symbols `B`",
+                "Event.payload (backward): Reader union has no branch matching writer type `int`",
+            ],
+            check(old, new, CompatibilityMode::Backward)
+        );
+        assert_eq!(
+            vec![
+                "Event.payload (forward): Reader union has no branch matching writer type `null`",
+                "Event.labels{} (forward): Reader type `int` cannot read writer type `null`",
+                "Event.labels{} (forward): Reader type `int` cannot read writer type `double`",
+            ],
+            check(old, new, CompatibilityMode::Forward)
+        );
+    }
+
+    #[test]
+    fn named_types() {
+        let old = r#"
+        {"type": "record", "name": "Node", "fields": [
+          {"name": "hash", "type": {"type": "fixed", "name": "Hash", "size": 16}},
+          {"name": "created", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+          {"name": "children", "type": {"type": "array", "items": "Node"}}
+        ]}
+        "#;
+        let new = r#"
+        {"type": "record", "name": "Node", "fields": [
+          {"name": "hash", "type": {"type": "fixed", "name": "Hash", "size": 32}},
+          {"name": "created", "type": "long"},
+          {"name": "children", "type": {"type": "array", "items": "Node"}}
+        ]}
+        "#;
+        assert_eq!(
+            vec![
+                "Node.hash (backward): Reader fixed size 32 differs from writer fixed size 16",
+                "Node.hash (forward): Reader fixed size 16 differs from writer fixed size 32",
+            ],
+            check(old, new, CompatibilityMode::Full)
+        );
+
+        let renamed = new.replace(r#""Node""#, r#""Tree""#);
+        assert_eq!(
+            vec!["Tree (backward): Reader type record `Tree` does not match writer type record `Node`"],
+            check(old, &renamed, CompatibilityMode::Backward)
+        );
+    }
+}
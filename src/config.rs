@@ -0,0 +1,999 @@
+//! Generation targets configured in `rsgen-avro.toml` files.
+//!
+//! A configuration file lists `[[generate]]` tables, each selecting schema files and the
+//! file the generated types are written to. Generator options set before the first table
+//! apply to all of them:
+//!
+//! ```toml
+//! derive_builders = true
+//!
+//! [[generate]]
+//! schemas = "schemas/*.avsc"
+//! output = "src/schemas.rs"
+//!
+//! [[generate]]
+//! schemas = "events/**/*.avsc"
+//! output = "src/events.rs"
+//! use_chrono_dates = true
+//! ```
+//!
+//! Only the subset of TOML needed by these files is supported: comments, `[[generate]]`
+//! headers and `key = value` pairs whose values are strings, integers or booleans.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::gen::{Generator, GeneratorBuilder, ItemKind, Source};
+use crate::templates::{
+    DeserializationMode, OptionalFields, UnionJson, ValidateDerive, DEFAULT_AVRO_CRATE,
+};
+
+/// The name of the configuration file read by `cargo avrogen`.
+pub const CONFIG_FILE: &str = "rsgen-avro.toml";
+
+/// The generation targets of a configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Config {
+    /// The `[[generate]]` tables, in file order.
+    pub targets: Vec<ConfigTarget>,
+}
+
+/// A single `[[generate]]` table of a [`Config`](Config).
+///
+/// Paths are resolved against the directory of the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConfigTarget {
+    /// Glob pattern selecting Avro schema files (`schemas`).
+    pub schemas: String,
+    /// The file where Rust types are written (`output`).
+    pub output: PathBuf,
+    /// Cache file enabling incremental generations (`cache`).
+    pub cache: Option<PathBuf>,
+    /// Directory where example JSON documents are written (`examples`), see
+    /// [`Generator::gen_examples`](Generator::gen_examples).
+    pub examples: Option<PathBuf>,
+    /// File where a benchmark harness is written (`bench`), importing the generated types
+    /// from `bench_types`, see [`Generator::gen_bench`](Generator::gen_bench).
+    pub bench: Option<(PathBuf, String)>,
+    /// File where a registry module is written (`registry`), importing the generated types
+    /// from `registry_types`, see [`Generator::gen_registry`](Generator::gen_registry).
+    pub registry: Option<(PathBuf, String)>,
+    /// Schema registry ids -> fullnames of the records whose payloads are decoded by the
+    /// registry module (`registry_ids`, e.g. `"7 = ns.User, 8 = ns.Order"`), see
+    /// [`Generator::gen_registry_with_ids`](Generator::gen_registry_with_ids).
+    pub registry_ids: Vec<(u32, String)>,
+    /// File where an Avro JSON module is written (`avro_json`), see
+    /// [`Generator::gen_avro_json`](Generator::gen_avro_json).
+    pub avro_json: Option<PathBuf>,
+    /// File where a manifest of the generated types is written (`manifest`), their paths
+    /// being in the `manifest_module` module (`crate` by default), see
+    /// [`Report::manifest`](crate::Report::manifest).
+    pub manifest: Option<(PathBuf, String)>,
+    /// Whether to run rustfmt on the output file (`fmt`).
+    pub fmt: bool,
+    /// Whether to write into the managed region of the output file (`in_place`), see
+    /// [`Generator::gen_in_place`](Generator::gen_in_place).
+    pub in_place: bool,
+    /// Whether to write one file per namespace into the `output` directory
+    /// (`split_namespaces`), see [`Generator::gen_namespaces`](Generator::gen_namespaces).
+    pub split_namespaces: bool,
+    /// See [`GeneratorBuilder::precision`](GeneratorBuilder::precision).
+    pub precision: usize,
+    /// See [`GeneratorBuilder::nullable`](GeneratorBuilder::nullable).
+    pub nullable: bool,
+    /// See [`GeneratorBuilder::use_avro_rs_unions`](GeneratorBuilder::use_avro_rs_unions).
+    pub use_avro_rs_unions: bool,
+    /// See [`GeneratorBuilder::use_chrono_dates`](GeneratorBuilder::use_chrono_dates).
+    pub use_chrono_dates: bool,
+    /// See [`GeneratorBuilder::use_decimal_structs`](GeneratorBuilder::use_decimal_structs).
+    pub use_decimal_structs: bool,
+    /// See [`GeneratorBuilder::string_enums`](GeneratorBuilder::string_enums).
+    pub string_enums: bool,
+    /// See [`GeneratorBuilder::derive_builders`](GeneratorBuilder::derive_builders).
+    pub derive_builders: bool,
+    /// See [`GeneratorBuilder::derive_schemas`](GeneratorBuilder::derive_schemas).
+    pub derive_schemas: bool,
+    /// See [`GeneratorBuilder::avro_schema_impls`](GeneratorBuilder::avro_schema_impls).
+    pub avro_schema_impls: bool,
+    /// See [`GeneratorBuilder::avro_message_impls`](GeneratorBuilder::avro_message_impls).
+    pub avro_message_impls: bool,
+    /// See [`GeneratorBuilder::round_trip_tests`](GeneratorBuilder::round_trip_tests).
+    pub round_trip_tests: bool,
+    /// See [`GeneratorBuilder::proptest_arbitrary`](GeneratorBuilder::proptest_arbitrary).
+    pub proptest_arbitrary: bool,
+    /// See [`GeneratorBuilder::quickcheck_arbitrary`](GeneratorBuilder::quickcheck_arbitrary).
+    pub quickcheck_arbitrary: bool,
+    /// See [`GeneratorBuilder::writer_schema_decoders`](GeneratorBuilder::writer_schema_decoders).
+    pub writer_schema_decoders: bool,
+    /// See [`GeneratorBuilder::try_from_bytes`](GeneratorBuilder::try_from_bytes).
+    pub try_from_bytes: bool,
+    /// See [`GeneratorBuilder::wrapper_conversions`](GeneratorBuilder::wrapper_conversions).
+    pub wrapper_conversions: bool,
+    /// See [`GeneratorBuilder::alias_types`](GeneratorBuilder::alias_types).
+    pub alias_types: bool,
+    /// See [`GeneratorBuilder::schema_constants`](GeneratorBuilder::schema_constants).
+    pub schema_constants: bool,
+    /// See [`GeneratorBuilder::zeroize`](GeneratorBuilder::zeroize).
+    pub zeroize: bool,
+    /// See [`GeneratorBuilder::debug_impls`](GeneratorBuilder::debug_impls).
+    pub debug_impls: bool,
+    /// See [`GeneratorBuilder::hex_debug`](GeneratorBuilder::hex_debug).
+    pub hex_debug: bool,
+    /// See [`GeneratorBuilder::validators`](GeneratorBuilder::validators).
+    pub validators: bool,
+    /// See [`GeneratorBuilder::validate_derive`](GeneratorBuilder::validate_derive).
+    pub validate_derive: Option<ValidateDerive>,
+    /// See [`GeneratorBuilder::default_impls`](GeneratorBuilder::default_impls).
+    pub default_impls: bool,
+    /// See [`GeneratorBuilder::strict_defaults`](GeneratorBuilder::strict_defaults).
+    pub strict_defaults: bool,
+    /// See [`GeneratorBuilder::empty_defaults`](GeneratorBuilder::empty_defaults).
+    pub empty_defaults: bool,
+    /// See [`GeneratorBuilder::serde_impls`](GeneratorBuilder::serde_impls).
+    pub serde_impls: bool,
+    /// See [`GeneratorBuilder::rfc3339_timestamps`](GeneratorBuilder::rfc3339_timestamps).
+    pub rfc3339_timestamps: bool,
+    /// See [`GeneratorBuilder::deserialization`](GeneratorBuilder::deserialization).
+    pub deserialization: DeserializationMode,
+    /// See [`GeneratorBuilder::optional_fields`](GeneratorBuilder::optional_fields).
+    pub optional_fields: OptionalFields,
+    /// See [`GeneratorBuilder::box_threshold`](GeneratorBuilder::box_threshold).
+    pub box_threshold: Option<usize>,
+    /// See [`GeneratorBuilder::smallvec_arrays`](GeneratorBuilder::smallvec_arrays).
+    pub smallvec_arrays: Option<usize>,
+    /// See [`GeneratorBuilder::union_names`](GeneratorBuilder::union_names).
+    pub union_names: Option<String>,
+    /// See [`GeneratorBuilder::union_json`](GeneratorBuilder::union_json).
+    pub union_json: Option<UnionJson>,
+    /// See [`GeneratorBuilder::wrap_module`](GeneratorBuilder::wrap_module).
+    pub wrap_module: Option<String>,
+    /// See [`GeneratorBuilder::allowed_lints`](GeneratorBuilder::allowed_lints), comma-separated
+    /// (`allowed_lints`).
+    pub allowed_lints: Option<Vec<String>>,
+    /// See [`GeneratorBuilder::item_lints`](GeneratorBuilder::item_lints), by item kind
+    /// (`record_lints`, `enum_lints`, `fixed_lints`, `union_lints`, `decimal_lints`,
+    /// `trait_lints` and `validation_lints`).
+    pub item_lints: Vec<(ItemKind, String)>,
+    /// See [`GeneratorBuilder::doc_examples`](GeneratorBuilder::doc_examples).
+    pub doc_examples: Option<String>,
+    /// See [`GeneratorBuilder::verify`](GeneratorBuilder::verify).
+    pub verify: bool,
+    /// See [`GeneratorBuilder::avro_crate`](GeneratorBuilder::avro_crate).
+    pub avro_crate: String,
+    /// See [`GeneratorBuilder::templates_dir`](GeneratorBuilder::templates_dir).
+    pub templates_dir: Option<PathBuf>,
+}
+
+/// The value of a `key = value` pair.
+#[derive(Debug)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+/// The `key = value` pairs of a table, along with their line.
+type Entries = Vec<(usize, String, Value)>;
+
+impl Config {
+    /// Reads a configuration file, resolving its paths against the file directory.
+    pub fn load(path: impl AsRef<Path>) -> Result<Config> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        Config::parse(&content, base_dir).map_err(|e| match e {
+            Error::Config(msg) => Error::Config(format!("{}: {}", path.display(), msg)),
+            e => e,
+        })
+    }
+
+    /// Parses the content of a configuration file, resolving its paths against `base_dir`.
+    pub fn parse(content: &str, base_dir: impl AsRef<Path>) -> Result<Config> {
+        let base_dir = base_dir.as_ref();
+        let mut defaults = Entries::new();
+        let mut tables = Vec::<(usize, Entries)>::new();
+
+        for (idx, line) in content.lines().enumerate() {
+            let line_nb = idx + 1;
+            let err = |msg: &str| Error::Config(format!("line {line_nb}: {msg}"));
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                match header.trim() {
+                    "[generate]]" => tables.push((line_nb, vec![])),
+                    _ => return Err(err(&format!("Unsupported table `{line}`"))),
+                }
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("Expected `key = value`"))?;
+            let key = key.trim();
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(err(&format!("Invalid key `{key}`")));
+            }
+            let value = parse_value(value.trim()).map_err(|msg| err(&msg))?;
+            let entries = match tables.last_mut() {
+                Some((_, entries)) => entries,
+                None => &mut defaults,
+            };
+            if entries.iter().any(|(_, k, _)| k == key) {
+                return Err(err(&format!("Duplicate key `{key}`")));
+            }
+            entries.push((line_nb, key.to_string(), value));
+        }
+
+        let targets = tables
+            .iter()
+            .map(|(line_nb, entries)| {
+                ConfigTarget::from_entries(*line_nb, &defaults, entries, base_dir)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Config { targets })
+    }
+}
+
+impl ConfigTarget {
+    fn from_entries(
+        line_nb: usize,
+        defaults: &Entries,
+        entries: &Entries,
+        base_dir: &Path,
+    ) -> Result<ConfigTarget> {
+        let mut schemas = None;
+        let mut output = None;
+        let mut bench = None;
+        let mut bench_types = None;
+        let mut registry = None;
+        let mut registry_types = None;
+        let mut manifest = None;
+        let mut manifest_module = None;
+        let mut target = ConfigTarget {
+            schemas: String::new(),
+            output: PathBuf::new(),
+            cache: None,
+            examples: None,
+            bench: None,
+            registry: None,
+            registry_ids: vec![],
+            avro_json: None,
+            manifest: None,
+            fmt: false,
+            in_place: false,
+            split_namespaces: false,
+            precision: 3,
+            nullable: false,
+            use_avro_rs_unions: false,
+            use_chrono_dates: false,
+            use_decimal_structs: false,
+            string_enums: false,
+            derive_builders: false,
+            derive_schemas: false,
+            avro_schema_impls: false,
+            avro_message_impls: false,
+            round_trip_tests: false,
+            proptest_arbitrary: false,
+            quickcheck_arbitrary: false,
+            writer_schema_decoders: false,
+            try_from_bytes: false,
+            wrapper_conversions: false,
+            alias_types: false,
+            schema_constants: false,
+            zeroize: false,
+            debug_impls: true,
+            hex_debug: false,
+            validators: false,
+            validate_derive: None,
+            default_impls: true,
+            strict_defaults: false,
+            empty_defaults: false,
+            serde_impls: true,
+            doc_examples: None,
+            deserialization: DeserializationMode::Standard,
+            optional_fields: OptionalFields::Unions,
+            box_threshold: None,
+            smallvec_arrays: None,
+            union_names: None,
+            union_json: None,
+            wrap_module: None,
+            allowed_lints: None,
+            item_lints: vec![],
+            rfc3339_timestamps: false,
+            verify: false,
+            avro_crate: DEFAULT_AVRO_CRATE.to_string(),
+            templates_dir: None,
+        };
+
+        for (line_nb, key, value) in defaults.iter().chain(entries) {
+            let err = |expected: &str| {
+                Error::Config(format!(
+                    "line {line_nb}: Expected {expected} for `{key}`, found {value:?}"
+                ))
+            };
+            let path = |value: &Value| match value {
+                Value::String(s) => Ok(base_dir.join(s)),
+                _ => Err(err("a string")),
+            };
+            let flag = |value: &Value| match value {
+                Value::Boolean(b) => Ok(*b),
+                _ => Err(err("a boolean")),
+            };
+            match key.as_str() {
+                "schemas" => schemas = Some(path(value)?.to_string_lossy().into_owned()),
+                "output" => output = Some(path(value)?),
+                "cache" => target.cache = Some(path(value)?),
+                "examples" => target.examples = Some(path(value)?),
+                "bench" => bench = Some(path(value)?),
+                "registry" => registry = Some(path(value)?),
+                "avro_json" => target.avro_json = Some(path(value)?),
+                "manifest" => manifest = Some(path(value)?),
+                "templates_dir" => target.templates_dir = Some(path(value)?),
+                "fmt" => target.fmt = flag(value)?,
+                "in_place" => target.in_place = flag(value)?,
+                "split_namespaces" => target.split_namespaces = flag(value)?,
+                "nullable" => target.nullable = flag(value)?,
+                "use_avro_rs_unions" => target.use_avro_rs_unions = flag(value)?,
+                "use_chrono_dates" => target.use_chrono_dates = flag(value)?,
+                "use_decimal_structs" => target.use_decimal_structs = flag(value)?,
+                "string_enums" => target.string_enums = flag(value)?,
+                "derive_builders" => target.derive_builders = flag(value)?,
+                "derive_schemas" => target.derive_schemas = flag(value)?,
+                "avro_schema_impls" => target.avro_schema_impls = flag(value)?,
+                "avro_message_impls" => target.avro_message_impls = flag(value)?,
+                "round_trip_tests" => target.round_trip_tests = flag(value)?,
+                "proptest_arbitrary" => target.proptest_arbitrary = flag(value)?,
+                "quickcheck_arbitrary" => target.quickcheck_arbitrary = flag(value)?,
+                "writer_schema_decoders" => target.writer_schema_decoders = flag(value)?,
+                "try_from_bytes" => target.try_from_bytes = flag(value)?,
+                "wrapper_conversions" => target.wrapper_conversions = flag(value)?,
+                "alias_types" => target.alias_types = flag(value)?,
+                "schema_constants" => target.schema_constants = flag(value)?,
+                "zeroize" => target.zeroize = flag(value)?,
+                "debug_impls" => target.debug_impls = flag(value)?,
+                "hex_debug" => target.hex_debug = flag(value)?,
+                "validators" => target.validators = flag(value)?,
+                "default_impls" => target.default_impls = flag(value)?,
+                "strict_defaults" => target.strict_defaults = flag(value)?,
+                "empty_defaults" => target.empty_defaults = flag(value)?,
+                "serde_impls" => target.serde_impls = flag(value)?,
+                "rfc3339_timestamps" => target.rfc3339_timestamps = flag(value)?,
+                "verify" => target.verify = flag(value)?,
+                "precision" => match value {
+                    Value::Integer(i) => {
+                        target.precision = usize::try_from(*i).map_err(|_| err("a precision"))?
+                    }
+                    _ => return Err(err("an integer")),
+                },
+                "box_threshold" => match value {
+                    Value::Integer(i) => {
+                        target.box_threshold =
+                            Some(usize::try_from(*i).map_err(|_| err("a size in bytes"))?)
+                    }
+                    _ => return Err(err("an integer")),
+                },
+                "smallvec_arrays" => match value {
+                    Value::Integer(i) => {
+                        target.smallvec_arrays =
+                            Some(usize::try_from(*i).map_err(|_| err("a capacity"))?)
+                    }
+                    _ => return Err(err("an integer")),
+                },
+                "avro_crate" => match value {
+                    Value::String(s) => target.avro_crate = s.clone(),
+                    _ => return Err(err("a string")),
+                },
+                "deserialization" => match value {
+                    Value::String(s) => {
+                        target.deserialization = s
+                            .parse()
+                            .map_err(|_| err("`standard`, `strict` or `lenient`"))?
+                    }
+                    _ => return Err(err("a string")),
+                },
+                "optional_fields" => match value {
+                    Value::String(s) => {
+                        target.optional_fields =
+                            s.parse().map_err(|_| err("`unions` or `defaults`"))?
+                    }
+                    _ => return Err(err("a string")),
+                },
+                "validate_derive" => match value {
+                    Value::String(s) => {
+                        target.validate_derive =
+                            Some(s.parse().map_err(|_| err("`validator` or `serde_valid`"))?)
+                    }
+                    _ => return Err(err("a string")),
+                },
+                "union_json" => match value {
+                    Value::String(s) => {
+                        target.union_json = Some(
+                            s.parse()
+                                .map_err(|_| err("`untagged`, `external` or `avro`"))?,
+                        )
+                    }
+                    _ => return Err(err("a string")),
+                },
+                "union_names" => match value {
+                    Value::String(s) => target.union_names = Some(s.clone()),
+                    _ => return Err(err("a string")),
+                },
+                "wrap_module" => match value {
+                    Value::String(s) => target.wrap_module = Some(s.clone()),
+                    _ => return Err(err("a string")),
+                },
+                "allowed_lints" => match value {
+                    Value::String(s) => {
+                        target.allowed_lints = Some(
+                            s.split(',')
+                                .map(str::trim)
+                                .filter(|lint| !lint.is_empty())
+                                .map(str::to_string)
+                                .collect(),
+                        )
+                    }
+                    _ => return Err(err("a string")),
+                },
+                "record_lints" | "enum_lints" | "fixed_lints" | "union_lints" | "decimal_lints"
+                | "trait_lints" | "validation_lints" => match value {
+                    Value::String(s) => {
+                        let kind = key.trim_end_matches("_lints").parse()?;
+                        target.item_lints.retain(|(k, _)| *k != kind);
+                        target.item_lints.push((kind, s.clone()));
+                    }
+                    _ => return Err(err("a string")),
+                },
+                "doc_examples" => match value {
+                    Value::String(s) => target.doc_examples = Some(s.clone()),
+                    _ => return Err(err("a string")),
+                },
+                "bench_types" => match value {
+                    Value::String(s) => bench_types = Some(s.clone()),
+                    _ => return Err(err("a string")),
+                },
+                "registry_ids" => match value {
+                    Value::String(s) => {
+                        target.registry_ids =
+                            schema_ids(s).ok_or_else(|| err("`<id> = <fullname>` pairs"))?
+                    }
+                    _ => return Err(err("a string")),
+                },
+                "registry_types" => match value {
+                    Value::String(s) => registry_types = Some(s.clone()),
+                    _ => return Err(err("a string")),
+                },
+                "manifest_module" => match value {
+                    Value::String(s) => manifest_module = Some(s.clone()),
+                    _ => return Err(err("a string")),
+                },
+                _ => {
+                    return Err(Error::Config(format!(
+                        "line {line_nb}: Unknown option `{key}`"
+                    )))
+                }
+            }
+        }
+
+        let missing =
+            |key: &str| Error::Config(format!("line {line_nb}: Missing `{key}` in [[generate]]"));
+        target.schemas = schemas.ok_or_else(|| missing("schemas"))?;
+        target.output = output.ok_or_else(|| missing("output"))?;
+        target.bench = match (bench, bench_types) {
+            (Some(file), Some(types_path)) => Some((file, types_path)),
+            (None, None) => None,
+            (Some(_), None) => return Err(missing("bench_types")),
+            (None, Some(_)) => return Err(missing("bench")),
+        };
+        target.registry = match (registry, registry_types) {
+            (Some(file), Some(types_path)) => Some((file, types_path)),
+            (None, None) => None,
+            (Some(_), None) => return Err(missing("registry_types")),
+            (None, Some(_)) => return Err(missing("registry")),
+        };
+        if target.registry.is_none() && !target.registry_ids.is_empty() {
+            return Err(missing("registry"));
+        }
+        target.manifest = match (manifest, manifest_module) {
+            (Some(file), module) => Some((file, module.unwrap_or_else(|| "crate".to_string()))),
+            (None, None) => None,
+            (None, Some(_)) => return Err(missing("manifest")),
+        };
+        if target.in_place && target.cache.is_some() {
+            return Err(Error::Config(format!(
+                "line {line_nb}: `in_place` cannot be combined with `cache`"
+            )));
+        }
+        if target.manifest.is_some() && (target.in_place || target.cache.is_some()) {
+            return Err(Error::Config(format!(
+                "line {line_nb}: `manifest` cannot be combined with `in_place` or `cache`"
+            )));
+        }
+        if target.split_namespaces
+            && (target.in_place || target.cache.is_some() || target.manifest.is_some())
+        {
+            return Err(Error::Config(format!(
+                "line {line_nb}: `split_namespaces` cannot be combined with `in_place`, `cache` \
+                 or `manifest`"
+            )));
+        }
+        Ok(target)
+    }
+
+    /// Returns a [`GeneratorBuilder`](GeneratorBuilder) set with the options of this target.
+    pub fn builder(&self) -> GeneratorBuilder {
+        let mut builder = Generator::builder()
+            .precision(self.precision)
+            .nullable(self.nullable)
+            .use_avro_rs_unions(self.use_avro_rs_unions)
+            .use_chrono_dates(self.use_chrono_dates)
+            .use_decimal_structs(self.use_decimal_structs)
+            .string_enums(self.string_enums)
+            .derive_builders(self.derive_builders)
+            .derive_schemas(self.derive_schemas)
+            .avro_schema_impls(self.avro_schema_impls)
+            .avro_message_impls(self.avro_message_impls)
+            .round_trip_tests(self.round_trip_tests)
+            .proptest_arbitrary(self.proptest_arbitrary)
+            .quickcheck_arbitrary(self.quickcheck_arbitrary)
+            .writer_schema_decoders(self.writer_schema_decoders)
+            .try_from_bytes(self.try_from_bytes)
+            .wrapper_conversions(self.wrapper_conversions)
+            .alias_types(self.alias_types)
+            .schema_constants(self.schema_constants)
+            .zeroize(self.zeroize)
+            .debug_impls(self.debug_impls)
+            .hex_debug(self.hex_debug)
+            .validators(self.validators)
+            .default_impls(self.default_impls)
+            .strict_defaults(self.strict_defaults)
+            .empty_defaults(self.empty_defaults)
+            .serde_impls(self.serde_impls)
+            .deserialization(self.deserialization)
+            .optional_fields(self.optional_fields)
+            .rfc3339_timestamps(self.rfc3339_timestamps)
+            .verify(self.verify)
+            .avro_crate(&self.avro_crate);
+        if let Some(types_path) = &self.doc_examples {
+            builder = builder.doc_examples(types_path);
+        }
+        if let Some(bytes) = self.box_threshold {
+            builder = builder.box_threshold(bytes);
+        }
+        if let Some(capacity) = self.smallvec_arrays {
+            builder = builder.smallvec_arrays(capacity);
+        }
+        if let Some(template) = &self.union_names {
+            builder = builder.union_names(template);
+        }
+        if let Some(union_json) = self.union_json {
+            builder = builder.union_json(union_json);
+        }
+        if let Some(derive) = self.validate_derive {
+            builder = builder.validate_derive(derive);
+        }
+        if let Some(name) = &self.wrap_module {
+            builder = builder.wrap_module(name);
+        }
+        if let Some(lints) = &self.allowed_lints {
+            builder = builder.allowed_lints(lints);
+        }
+        for (kind, attributes) in &self.item_lints {
+            builder = builder.item_lints(*kind, attributes);
+        }
+        match &self.templates_dir {
+            Some(dir) => builder.templates_dir(dir),
+            None => builder,
+        }
+    }
+
+    /// Generates the Rust types of this target into its output file, creating the parent
+    /// directories of the output and cache files if needed.
+    ///
+    /// Returns whether the output file was written, which is always the case unless a
+    /// [`cache`](ConfigTarget::cache) file is configured or the generation is done
+    /// [in place](ConfigTarget::in_place).
+    pub fn generate(&self) -> Result<bool> {
+        let generator = self.builder().build()?;
+        let source = Source::GlobPattern(&self.schemas);
+        if let Some(dir) = &self.examples {
+            generator.gen_examples(&source, dir)?;
+        }
+        if let Some((file, types_path)) = &self.bench {
+            if let Some(dir) = file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            generator.gen_bench(&source, types_path, &mut fs::File::create(file)?)?;
+        }
+        if let Some((file, types_path)) = &self.registry {
+            if let Some(dir) = file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let schema_ids: Vec<_> = self
+                .registry_ids
+                .iter()
+                .map(|(id, fullname)| (*id, fullname.as_str()))
+                .collect();
+            let mut output = fs::File::create(file)?;
+            generator.gen_registry_with_ids(&source, types_path, &schema_ids, &mut output)?;
+        }
+        if let Some(file) = &self.avro_json {
+            if let Some(dir) = file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            generator.gen_avro_json(&mut fs::File::create(file)?)?;
+        }
+        if self.in_place {
+            return generator.gen_in_place(&source, &self.output);
+        }
+        if self.split_namespaces {
+            generator.gen_namespaces(&source, &self.output)?;
+            return Ok(true);
+        }
+        let manifest_file = self.manifest.as_ref().map(|(file, _)| file);
+        for file in [Some(&self.output), self.cache.as_ref(), manifest_file]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(dir) = file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+        }
+        match &self.cache {
+            Some(cache) => generator.gen_incremental(&source, &self.output, cache),
+            None => {
+                let mut output = std::io::BufWriter::new(fs::File::create(&self.output)?);
+                match &self.manifest {
+                    Some((file, module)) => {
+                        let mut report = generator.gen_with_report(&source, &mut output)?;
+                        report.set_output(&self.output);
+                        report.manifest(module).write_json(file)?;
+                    }
+                    None => generator.gen(&source, &mut output)?,
+                }
+                std::io::Write::flush(&mut output)?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// Parses comma-separated `<id> = <fullname>` pairs, `None` if they are invalid.
+fn schema_ids(value: &str) -> Option<Vec<(u32, String)>> {
+    value
+        .split(',')
+        .map(|pair| {
+            let (id, fullname) = pair.split_once('=')?;
+            let fullname = fullname.trim();
+            match fullname.is_empty() {
+                true => None,
+                false => Some((id.trim().parse().ok()?, fullname.to_string())),
+            }
+        })
+        .collect()
+}
+
+/// Removes the comment at the end of a line, if any.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..idx],
+            _ => (),
+        }
+        escaped = false;
+    }
+    line
+}
+
+fn parse_value(value: &str) -> std::result::Result<Value, String> {
+    if let Some(literal) = value.strip_prefix('\'') {
+        return match literal.strip_suffix('\'') {
+            Some(s) if !s.contains('\'') => Ok(Value::String(s.to_string())),
+            _ => Err(format!("Invalid string {value}")),
+        };
+    }
+    if let Some(basic) = value.strip_prefix('"') {
+        let basic = basic
+            .strip_suffix('"')
+            .ok_or_else(|| format!("Unterminated string {value}"))?;
+        let mut s = String::new();
+        let mut chars = basic.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('\\') => s.push('\\'),
+                    Some('"') => s.push('"'),
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    _ => return Err(format!("Unsupported escape sequence in {value}")),
+                },
+                '"' => return Err(format!("Invalid string {value}")),
+                c => s.push(c),
+            }
+        }
+        return Ok(Value::String(s));
+    }
+    match value {
+        "true" => Ok(Value::Boolean(true)),
+        "false" => Ok(Value::Boolean(false)),
+        _ => value
+            .replace('_', "")
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| format!("Unsupported value `{value}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let config = Config::parse(
+            r##"
+            # Applies to all targets
+            derive_builders = true
+            schema_constants = true
+            precision = 2
+            enum_lints = "warn(missing_docs)"
+
+            [[generate]]
+            schemas = "schemas/*.avsc"  # comment
+            output = 'src/schemas.rs'
+            manifest = "target/schemas.json"
+            validate_derive = "serde_valid"
+
+            [[generate]]
+            schemas = "events/\"quoted\"#.avsc"
+            output = "src/events.rs"
+            cache = "target/events.json"
+            registry = "src/registry.rs"
+            registry_types = "crate::events"
+            registry_ids = "7 = ns.Event, 12 = ns.Source"
+            avro_json = "src/avro_json.rs"
+            derive_builders = false
+            default_impls = false
+            strict_defaults = true
+            empty_defaults = true
+            doc_examples = "my_crate::events"
+            deserialization = "lenient"
+            optional_fields = "defaults"
+            box_threshold = 512
+            smallvec_arrays = 4
+            union_names = "{Record}{Field}Union"
+            union_json = "avro"
+            wrap_module = "avro_gen"
+            allowed_lints = "clippy::all, non_camel_case_types"
+            record_lints = "allow(clippy::large_enum_variant), deny(missing_docs)"
+            use_chrono_dates = true
+            use_decimal_structs = true
+            string_enums = true
+            rfc3339_timestamps = true
+            hex_debug = true
+            validators = true
+            try_from_bytes = true
+            wrapper_conversions = true
+            alias_types = true
+            avro_schema_impls = true
+            avro_message_impls = true
+            avro_crate = "avro"
+
+            [[generate]]
+            schemas = "common/*.avsc"
+            output = "src/common"
+            split_namespaces = true
+            "##,
+            "crate",
+        )?;
+
+        assert_eq!(3, config.targets.len());
+        let (schemas, events) = (&config.targets[0], &config.targets[1]);
+        assert!(!events.split_namespaces && config.targets[2].split_namespaces);
+        assert_eq!("crate/schemas/*.avsc", schemas.schemas);
+        assert_eq!(Path::new("crate/src/schemas.rs"), schemas.output);
+        assert!(schemas.derive_builders);
+        assert!(schemas.schema_constants && events.schema_constants);
+        assert_eq!(2, schemas.precision);
+        assert_eq!("apache_avro", schemas.avro_crate);
+        assert_eq!(None, schemas.cache);
+        assert_eq!(
+            Some((
+                PathBuf::from("crate/target/schemas.json"),
+                "crate".to_string()
+            )),
+            schemas.manifest
+        );
+
+        assert_eq!("crate/events/\"quoted\"#.avsc", events.schemas);
+        assert!(!events.derive_builders);
+        assert!(schemas.default_impls && !events.default_impls);
+        assert!(!schemas.strict_defaults && events.strict_defaults);
+        assert!(!schemas.empty_defaults && events.empty_defaults);
+        assert_eq!(None, schemas.doc_examples);
+        assert_eq!(Some("my_crate::events"), events.doc_examples.as_deref());
+        assert_eq!(DeserializationMode::Standard, schemas.deserialization);
+        assert_eq!(DeserializationMode::Lenient, events.deserialization);
+        assert_eq!(OptionalFields::Unions, schemas.optional_fields);
+        assert_eq!(OptionalFields::Defaults, events.optional_fields);
+        assert_eq!(
+            (None, Some(512)),
+            (schemas.box_threshold, events.box_threshold)
+        );
+        assert_eq!(
+            (None, Some(4)),
+            (schemas.smallvec_arrays, events.smallvec_arrays)
+        );
+        assert_eq!(None, schemas.union_names);
+        assert_eq!(Some("{Record}{Field}Union"), events.union_names.as_deref());
+        assert_eq!(
+            (None, Some(UnionJson::Avro)),
+            (schemas.union_json, events.union_json)
+        );
+        assert_eq!(
+            (None, Some("avro_gen")),
+            (
+                schemas.wrap_module.as_deref(),
+                events.wrap_module.as_deref()
+            )
+        );
+        assert_eq!(
+            Some(vec![
+                "clippy::all".to_string(),
+                "non_camel_case_types".to_string()
+            ]),
+            events.allowed_lints
+        );
+        assert_eq!(
+            (
+                &vec![(ItemKind::Enum, "warn(missing_docs)".to_string())],
+                &vec![
+                    (ItemKind::Enum, "warn(missing_docs)".to_string()),
+                    (
+                        ItemKind::Record,
+                        "allow(clippy::large_enum_variant), deny(missing_docs)".to_string()
+                    ),
+                ]
+            ),
+            (&schemas.item_lints, &events.item_lints)
+        );
+        assert!(!schemas.rfc3339_timestamps && events.rfc3339_timestamps);
+        assert!(!schemas.hex_debug && events.hex_debug);
+        assert!(!schemas.validators && events.validators);
+        assert_eq!(
+            (Some(ValidateDerive::SerdeValid), None),
+            (schemas.validate_derive, events.validate_derive)
+        );
+        assert!(!schemas.try_from_bytes && events.try_from_bytes);
+        assert!(!schemas.wrapper_conversions && events.wrapper_conversions);
+        assert!(!schemas.alias_types && events.alias_types);
+        assert!(!schemas.avro_schema_impls && events.avro_schema_impls);
+        assert!(!schemas.avro_message_impls && events.avro_message_impls);
+        assert!(!schemas.use_decimal_structs && events.use_decimal_structs);
+        assert!(!schemas.string_enums && events.string_enums);
+        assert_eq!(2, events.precision);
+        assert_eq!("avro", events.avro_crate);
+        assert_eq!(
+            Some(Path::new("crate/target/events.json")),
+            events.cache.as_deref()
+        );
+        assert_eq!(
+            Some((
+                PathBuf::from("crate/src/registry.rs"),
+                "crate::events".to_string()
+            )),
+            events.registry
+        );
+        assert_eq!(
+            vec![(7, "ns.Event".to_string()), (12, "ns.Source".to_string())],
+            events.registry_ids
+        );
+        assert_eq!(None, schemas.avro_json);
+        assert_eq!(
+            Some(Path::new("crate/src/avro_json.rs")),
+            events.avro_json.as_deref()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |content: &str| Config::parse(content, "").unwrap_err().to_string();
+
+        assert_eq!(
+            "Configuration error: line 1: Missing `output` in [[generate]]",
+            err("[[generate]]\nschemas = \"*.avsc\"")
+        );
+        assert_eq!(
+            "Configuration error: line 1: Unknown option `unknown`",
+            err("unknown = 1\n[[generate]]\nschemas = \"*.avsc\"\noutput = \"a.rs\"")
+        );
+        assert_eq!(
+            "Configuration error: line 2: Expected a boolean for `fmt`, found String(\"yes\")",
+            err("[[generate]]\nfmt = \"yes\"")
+        );
+        assert_eq!(
+            "Configuration error: line 2: Expected `standard`, `strict` or `lenient` for `deserialization`, found String(\"loose\")",
+            err("[[generate]]\ndeserialization = \"loose\"")
+        );
+        assert_eq!(
+            "Configuration error: line 1: Unsupported table `[targets]`",
+            err("[targets]")
+        );
+        assert_eq!(
+            "Configuration error: line 1: Unterminated string \"a.rs",
+            err("output = \"a.rs")
+        );
+        assert_eq!(
+            "Configuration error: line 2: `in_place` cannot be combined with `cache`",
+            err("cache = \"a.json\"\n[[generate]]\nschemas = \"*.avsc\"\noutput = \"a.rs\"\nin_place = true")
+        );
+        assert_eq!(
+            "Configuration error: line 1: Missing `bench_types` in [[generate]]",
+            err("[[generate]]\nschemas = \"*.avsc\"\noutput = \"a.rs\"\nbench = \"benches/avro.rs\"")
+        );
+        assert_eq!(
+            "Configuration error: line 1: Missing `registry` in [[generate]]",
+            err(
+                "[[generate]]\nschemas = \"*.avsc\"\noutput = \"a.rs\"\nregistry_types = \"crate\""
+            )
+        );
+        assert_eq!(
+            "Configuration error: line 4: Expected `<id> = <fullname>` pairs for `registry_ids`, found String(\"7\")",
+            err("[[generate]]\nschemas = \"*.avsc\"\noutput = \"a.rs\"\nregistry_ids = \"7\"")
+        );
+        assert_eq!(
+            "Configuration error: line 1: `split_namespaces` cannot be combined with `in_place`, \
+             `cache` or `manifest`",
+            err("[[generate]]\nschemas = \"*.avsc\"\noutput = \"a\"\nsplit_namespaces = true\ncache = \"b.json\"")
+        );
+        assert_eq!(
+            "Configuration error: line 1: `manifest` cannot be combined with `in_place` or `cache`",
+            err("[[generate]]\nschemas = \"*.avsc\"\noutput = \"a.rs\"\nmanifest = \"a.json\"\ncache = \"b.json\"")
+        );
+        assert_eq!(
+            "Configuration error: line 2: Duplicate key `fmt`",
+            err("fmt = true\nfmt = false")
+        );
+    }
+
+    #[test]
+    fn generate() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let schema = r#"{"type": "fixed", "name": "Id", "size": 4}"#;
+        fs::write(dir.path().join("id.avsc"), schema)?;
+        let config_file = dir.path().join(CONFIG_FILE);
+        fs::write(
+            &config_file,
+            "[[generate]]\nschemas = \"*.avsc\"\noutput = \"src/gen/ids.rs\"\n",
+        )?;
+
+        let config = Config::load(&config_file)?;
+        assert!(config.targets[0].generate()?);
+
+        let mut expected = vec![];
+        Generator::new()?.gen(&Source::SchemaStr(schema), &mut expected)?;
+        assert_eq!(
+            String::from_utf8(expected)?,
+            fs::read_to_string(dir.path().join("src/gen/ids.rs"))?
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! Typed contexts passed to templates.
+//!
+//! These structs define the variables available to the built-in and custom templates
+//! (see [`GeneratorBuilder::templates_dir`](crate::GeneratorBuilder::templates_dir)).
+//! Each struct field is exposed as a template variable of the same name.
+//!
+//! The custom attributes of schemas and record fields are exposed as they are, e.g.
+//! `{{ attributes["acme.table"] }}`, for templates to honour organization-specific
+//! annotations. They are also kept in the [`GeneratedItem`](crate::GeneratedItem)s.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::templates::{DeserializationMode, UnionJson, ValidateDerive};
+
+/// The context used to render `record.tera`, for Avro records.
+#[derive(Debug, Clone, Default, Serialize)]
+#[non_exhaustive]
+pub struct RecordContext {
+    /// The name of the generated struct.
+    pub name: String,
+    /// The template selected by the `rust.template` schema attribute, if any.
+    pub template: Option<String>,
+    /// The lint attributes of the struct, e.g. `allow(dead_code)`, rendered as `#[...]`.
+    pub lints: Vec<String>,
+    /// The record documentation, empty if there is none.
+    pub doc: String,
+    /// The code of a rustdoc example building and encoding the struct, if enabled.
+    pub example: Option<String>,
+    /// The field names of the generated struct, in schema order.
+    pub fields: Vec<String>,
+    /// Field name -> field Rust type.
+    pub types: BTreeMap<String, String>,
+    /// Field name -> original Avro field name.
+    pub originals: BTreeMap<String, String>,
+    /// Field name -> Rust expression of the default value, for fields having one.
+    pub defaults: BTreeMap<String, String>,
+    /// Field name -> field documentation, for fields having one.
+    pub docs: BTreeMap<String, String>,
+    /// The custom attributes of the record schema, e.g. `rust.sensitive` or
+    /// organization-specific annotations.
+    pub attributes: BTreeMap<String, serde_json::Value>,
+    /// Field name -> custom attributes of the field, for fields having some.
+    pub field_attributes: BTreeMap<String, BTreeMap<String, serde_json::Value>>,
+    /// Field name -> path of the module to use with `#[serde(with = ...)]`.
+    pub serde_with: BTreeMap<String, String>,
+    /// The fields marked by the `rust.sensitive` attribute, whose values are masked in the
+    /// `Debug` implementation.
+    pub sensitive: Vec<String>,
+    /// The `bytes` and `fixed` fields printed as truncated hexadecimal in the `Debug`
+    /// implementation.
+    pub hex: Vec<String>,
+    /// The fields optional because of their default value, omitted when `None` for their
+    /// default value to be written instead.
+    pub omitted: Vec<String>,
+    /// The fields without default value set to `Default::default()` by the `Default`
+    /// implementation, with `empty_defaults`: optional fields, arrays and maps.
+    pub empty: Vec<String>,
+    /// Whether `zeroize::Zeroize` and `Drop` are implemented to zeroize sensitive fields.
+    pub zeroize: bool,
+    /// Whether `From` is implemented both ways between the struct and the type of its field,
+    /// when it has a single one.
+    pub wrapper_conversions: bool,
+    /// Whether `Debug` is derived or implemented.
+    pub debug_impls: bool,
+    /// Whether `Default` is implemented when all fields have a default value.
+    pub default_impls: bool,
+    /// Whether `serde::Deserialize` and `serde::Serialize` are derived.
+    pub serde_impls: bool,
+    /// How strictly the struct is deserialized, `standard`, `strict` or `lenient`.
+    pub deserialization: DeserializationMode,
+    /// Whether `Eq` can be derived for the struct.
+    pub is_eq_derivable: bool,
+    /// Whether `null` values are deserialized as the field default value.
+    pub nullable: bool,
+    /// Whether `derive_builder::Builder` is derived.
+    pub derive_builders: bool,
+    /// Whether `AvroSchema` is derived.
+    pub derive_schemas: bool,
+    /// The crate deriving `Validate`, if any, `validator` or `serde_valid`.
+    pub validate_derive: Option<ValidateDerive>,
+    /// Field name -> `validate` attributes of the field, rendered as `#[...]`, for fields
+    /// having constraints with `validate_derive`.
+    pub validations: BTreeMap<String, Vec<String>>,
+    /// The path of the Avro crate referenced by the generated code.
+    pub avro_crate: String,
+}
+
+/// The context used to render `enum.tera`, for Avro enums.
+#[derive(Debug, Clone, Default, Serialize)]
+#[non_exhaustive]
+pub struct EnumContext {
+    /// The name of the generated enum.
+    pub name: String,
+    /// The template selected by the `rust.template` schema attribute, if any.
+    pub template: Option<String>,
+    /// The lint attributes of the enum, e.g. `allow(dead_code)`, rendered as `#[...]`.
+    pub lints: Vec<String>,
+    /// The enum documentation, empty if there is none.
+    pub doc: String,
+    /// The variant names of the generated enum, in schema order.
+    pub symbols: Vec<String>,
+    /// Variant name -> original Avro symbol.
+    pub originals: BTreeMap<String, String>,
+    /// Variant name -> name of the associated constant of the symbol, with `string_enums`.
+    pub constants: BTreeMap<String, String>,
+    /// Variant name -> variant documentation, for symbols documented by the `symbolDocs`
+    /// attribute.
+    pub docs: BTreeMap<String, String>,
+    /// Variant name -> former symbols, deserialized as the variant, for symbols renamed
+    /// according to the `symbolAliases` attribute.
+    pub aliases: BTreeMap<String, Vec<String>>,
+    /// The custom attributes of the enum schema, e.g. `symbolDocs`.
+    pub attributes: BTreeMap<String, serde_json::Value>,
+    /// Whether `Debug` is derived.
+    pub debug_impls: bool,
+    /// Whether `serde::Deserialize` and `serde::Serialize` are derived.
+    pub serde_impls: bool,
+    /// How strictly the enum is deserialized, `standard`, `strict` or `lenient`.
+    pub deserialization: DeserializationMode,
+    /// Whether the enum is generated as a `String` newtype with a constant per symbol.
+    pub string_enums: bool,
+    /// The variant unknown symbols are deserialized as in `lenient` mode.
+    pub fallback: String,
+}
+
+/// The context used to render `fixed.tera`, for Avro fixed.
+#[derive(Debug, Clone, Default, Serialize)]
+#[non_exhaustive]
+pub struct FixedContext {
+    /// The name of the generated type.
+    pub name: String,
+    /// The template selected by the `rust.template` schema attribute, if any.
+    pub template: Option<String>,
+    /// The lint attributes of the type, e.g. `allow(dead_code)`, rendered as `#[...]`.
+    pub lints: Vec<String>,
+    /// The size of the fixed, in bytes.
+    pub size: usize,
+    /// The custom attributes of the fixed schema.
+    pub attributes: BTreeMap<String, serde_json::Value>,
+}
+
+/// The context used to render `union.tera`, for anonymous Avro unions.
+#[derive(Debug, Clone, Default, Serialize)]
+#[non_exhaustive]
+pub struct UnionContext {
+    /// The name of the generated enum.
+    pub name: String,
+    /// The lint attributes of the enum, e.g. `allow(dead_code)`, rendered as `#[...]`.
+    pub lints: Vec<String>,
+    /// The variant names of the generated enum, in schema order.
+    pub variants: Vec<String>,
+    /// The variants of the generated enum, as Rust code (e.g. `Long(i64)`).
+    pub symbols: Vec<String>,
+    /// The variants that can be converted from/into their inner type.
+    pub visitors: Vec<UnionVisitorContext>,
+    /// Whether a custom deserializer for apache-avro union values is generated.
+    pub use_avro_rs_unions: bool,
+    /// How the enum is represented by human-readable formats like JSON, if set:
+    /// `untagged`, `external` or `avro`.
+    pub json: Option<UnionJson>,
+    /// Variant name -> name of the Avro type of the variant in the Avro JSON encoding, e.g.
+    /// `long` or `com.acme.User`.
+    pub json_names: BTreeMap<String, String>,
+    /// Whether `Eq` can be derived for the enum.
+    pub is_eq_derivable: bool,
+    /// Whether `Debug` is derived.
+    pub debug_impls: bool,
+    /// Whether `serde::Deserialize` and `serde::Serialize` are implemented.
+    pub serde_impls: bool,
+}
+
+/// An union variant wrapping a single Rust type, see [`UnionContext`](UnionContext).
+#[derive(Debug, Clone, Default, Serialize)]
+#[non_exhaustive]
+pub struct UnionVisitorContext {
+    /// The name of the variant.
+    pub variant: String,
+    /// The Rust type wrapped by the variant.
+    pub rust_type: String,
+    /// The type visited by serde when deserializing apache-avro union values, if supported.
+    pub serde_visitor: Option<String>,
+}
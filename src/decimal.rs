@@ -0,0 +1,391 @@
+//! The `Decimal` struct of the decimal logical types, generated instead of the apache-avro
+//! type with [`use_decimal_structs`](crate::GeneratorBuilder::use_decimal_structs).
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use apache_avro::schema::{ArraySchema, MapSchema, RecordSchema};
+use apache_avro::Schema;
+
+use crate::error::{Error, Result};
+use crate::gen::ItemKind;
+use crate::templates::{GenState, Templater};
+
+/// The name of the generated struct.
+pub(crate) const DECIMAL_STRUCT: &str = "Decimal";
+
+/// The name of the generated error of [`Decimal::try_new`], for values invalid for their schema.
+pub(crate) const DECIMAL_ERROR: &str = "DecimalError";
+
+/// Returns a decimal typed with the generated struct among the record fields of `schemas`,
+/// if any, failing if the struct or error name is taken by a named schema, or with a configuration
+/// error if decimals are nested in arrays or maps, whose items cannot be given the scale of their
+/// schema when deserialized.
+pub(crate) fn decimal_struct_schema<'a>(
+    schemas: &'a [Schema],
+    gen_state: &GenState,
+) -> Result<Option<&'a Schema>> {
+    let mut found = None;
+    for schema in schemas {
+        if let Some(name) = schema.name() {
+            let type_name = gen_state.name_mapper().type_name(&name.name);
+            if type_name == DECIMAL_STRUCT {
+                return Err(Error::Schema(format!(
+                    "{} conflicts with the generated {DECIMAL_STRUCT} struct",
+                    name.fullname(None)
+                )));
+            }
+            if type_name == DECIMAL_ERROR {
+                return Err(Error::Schema(format!(
+                    "{} conflicts with the generated {DECIMAL_ERROR} enum",
+                    name.fullname(None)
+                )));
+            }
+        }
+        if let Schema::Record(RecordSchema { name, fields, .. }) = schema {
+            if let Some(field) = fields.iter().find(|f| in_collection(&f.schema)) {
+                return Err(Error::Config(format!(
+                    "`use_decimal_structs` does not support decimals in arrays and maps, found in {}.{}",
+                    name.fullname(None),
+                    field.name
+                )));
+            }
+            found = found.or_else(|| fields.iter().find_map(|f| nested_decimal(&f.schema)));
+        }
+    }
+    Ok(found)
+}
+
+/// Returns the decimal of a schema typed with the generated struct: a decimal, possibly
+/// nested in arrays, maps and optional unions.
+fn nested_decimal(schema: &Schema) -> Option<&Schema> {
+    match schema {
+        Schema::Decimal(_) => Some(schema),
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => nested_decimal(inner),
+        Schema::Union(union) if union.is_nullable() && union.variants().len() == 2 => {
+            nested_decimal(&union.variants()[1])
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether a decimal is nested in an array or a map of a schema, possibly optional.
+fn in_collection(schema: &Schema) -> bool {
+    match schema {
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => nested_decimal(inner).is_some(),
+        Schema::Union(union) if union.is_nullable() && union.variants().len() == 2 => {
+            in_collection(&union.variants()[1])
+        }
+        _ => false,
+    }
+}
+
+/// Decodes the mantissa of a decimal from its Avro encoding, big-endian two's complement
+/// bytes, `None` if it does not fit in an `i128`.
+pub(crate) fn decimal_mantissa(bytes: &[u8]) -> Option<i128> {
+    let sign = if bytes.first().is_some_and(|b| *b >= 0x80) {
+        0xff
+    } else {
+        0
+    };
+    let mut bytes = bytes;
+    if bytes.len() > 16 {
+        let (extension, rest) = bytes.split_at(bytes.len() - 16);
+        if extension.iter().any(|b| *b != sign) || (rest[0] ^ sign) >= 0x80 {
+            return None;
+        }
+        bytes = rest;
+    }
+    let mut buf = [sign; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(buf))
+}
+
+/// Returns the code of the generated struct, with its checked constructor and its error,
+/// its conversions from and to the Avro encoding, and the serde functions used by the
+/// modules of [`scale_modules`].
+pub(crate) fn decimal_struct(templater: &Templater) -> String {
+    let mut derives = vec![];
+    if templater.debug_impls {
+        derives.push("Debug");
+    }
+    derives.extend(["Clone", "Copy", "PartialEq", "Eq", "Hash"]);
+    if templater.default_impls {
+        derives.push("Default");
+    }
+    let lints: String = templater
+        .lints(ItemKind::Decimal)
+        .iter()
+        .map(|lint| format!("#[{lint}]\n"))
+        .collect();
+    let mut code = format!(
+        r#"
+/// A decimal number of the Avro `decimal` logical type, `mantissa * 10^-scale`.
+{lints}#[derive({derives})]
+pub struct Decimal {{
+    /// The unscaled value.
+    pub mantissa: i128,
+    /// The number of digits of the fractional part.
+    pub scale: u32,
+}}
+
+impl Decimal {{
+    /// Returns the decimal `mantissa * 10^-scale` of a schema of the given `precision` and
+    /// `scale`, and `size` for `fixed` decimals (`None` for `bytes` ones), or the error of
+    /// a value invalid for the schema, which would fail when encoded.
+    pub fn try_new(
+        mantissa: i128,
+        scale: u32,
+        precision: u32,
+        size: Option<usize>,
+    ) -> Result<Decimal, DecimalError> {{
+        if scale > precision {{
+            return Err(DecimalError::Scale {{ scale, precision }});
+        }}
+        let digits = mantissa.unsigned_abs().checked_ilog10().map_or(1, |log| log + 1);
+        if digits > precision {{
+            return Err(DecimalError::Precision {{ digits, precision }});
+        }}
+        // The length of the shortest big-endian two's complement bytes of the mantissa
+        let bits = 129 - if mantissa < 0 {{ !mantissa }} else {{ mantissa }}.leading_zeros();
+        let len = bits.div_ceil(8) as usize;
+        if let Some(size) = size.filter(|size| len > *size) {{
+            return Err(DecimalError::Size {{ len, size }});
+        }}
+        Ok(Decimal {{ mantissa, scale }})
+    }}
+
+    /// Decodes the Avro encoding of a decimal of the given `scale`, the big-endian two's
+    /// complement bytes of its mantissa, `None` if the mantissa does not fit in an `i128`.
+    pub fn from_avro_bytes(bytes: &[u8], scale: u32) -> Option<Decimal> {{
+        let sign = if bytes.first().is_some_and(|b| *b >= 0x80) {{ 0xff }} else {{ 0 }};
+        let mut bytes = bytes;
+        if bytes.len() > 16 {{
+            let (extension, rest) = bytes.split_at(bytes.len() - 16);
+            if extension.iter().any(|b| *b != sign) || (rest[0] ^ sign) >= 0x80 {{
+                return None;
+            }}
+            bytes = rest;
+        }}
+        let mut buf = [sign; 16];
+        buf[16 - bytes.len()..].copy_from_slice(bytes);
+        Some(Decimal {{ mantissa: i128::from_be_bytes(buf), scale }})
+    }}
+
+    /// Returns the Avro encoding of the decimal, the big-endian two's complement bytes of
+    /// its mantissa.
+    pub fn to_avro_bytes(&self) -> [u8; 16] {{
+        self.mantissa.to_be_bytes()
+    }}
+
+    /// Returns the same number with the given `scale`, `None` if digits would be lost or if
+    /// the mantissa would overflow.
+    pub fn rescale(&self, scale: u32) -> Option<Decimal> {{
+        let mantissa = if scale >= self.scale {{
+            self.mantissa.checked_mul(10i128.checked_pow(scale - self.scale)?)?
+        }} else {{
+            let factor = 10i128.checked_pow(self.scale - scale)?;
+            if self.mantissa % factor != 0 {{
+                return None;
+            }}
+            self.mantissa / factor
+        }};
+        Some(Decimal {{ mantissa, scale }})
+    }}
+"#,
+        derives = derives.join(", ")
+    );
+    if templater.serde_impls {
+        code.push_str(
+            r#"
+    /// Serializes the decimal as the Avro bytes of its mantissa in the given `scale`.
+    pub fn serialize_with_scale<S>(&self, scale: u32, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let decimal = self.rescale(scale).ok_or_else(|| {
+            serde::ser::Error::custom(format!("{self} cannot be written with scale {scale}"))
+        })?;
+        serializer.serialize_bytes(&decimal.to_avro_bytes())
+    }
+
+    /// Deserializes a decimal of the given `scale` from the Avro bytes of its mantissa.
+    pub fn deserialize_with_scale<'de, D>(deserializer: D, scale: u32) -> Result<Decimal, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor(u32);
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Decimal;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("the bytes of a decimal")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Decimal, E> {
+                Decimal::from_avro_bytes(v, self.0)
+                    .ok_or_else(|| E::custom("decimal mantissa overflowing i128"))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Decimal, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut bytes = vec![];
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+        }
+
+        deserializer.deserialize_bytes(Visitor(scale))
+    }
+
+    /// Serializes an optional decimal like [`serialize_with_scale`](Decimal::serialize_with_scale).
+    pub fn serialize_option_with_scale<S>(
+        value: &Option<Decimal>,
+        scale: u32,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct Scaled<'a>(&'a Decimal, u32);
+
+        impl serde::Serialize for Scaled<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.serialize_with_scale(self.1, serializer)
+            }
+        }
+
+        match value {
+            Some(decimal) => serializer.serialize_some(&Scaled(decimal, scale)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes an optional decimal like
+    /// [`deserialize_with_scale`](Decimal::deserialize_with_scale).
+    pub fn deserialize_option_with_scale<'de, D>(
+        deserializer: D,
+        scale: u32,
+    ) -> Result<Option<Decimal>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Scaled(Decimal);
+
+        impl<'de> serde::Deserialize<'de> for Scaled {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                // The scale is set once deserialized
+                Decimal::deserialize_with_scale(deserializer, 0).map(Scaled)
+            }
+        }
+
+        let value = <Option<Scaled> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(value.map(|Scaled(decimal)| Decimal { scale, ..decimal }))
+    }
+"#,
+        );
+    }
+    code.push_str(
+        r#"}
+
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{sign}{integer}.{fraction}")
+    }
+}
+"#,
+    );
+    let _ = write!(
+        code,
+        r#"
+/// The error of a decimal invalid for its schema, see [`Decimal::try_new`].
+{lints}#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalError {{
+    /// The mantissa has more digits than the precision of the schema.
+    Precision {{ digits: u32, precision: u32 }},
+    /// The scale of the schema is greater than its precision.
+    Scale {{ scale: u32, precision: u32 }},
+    /// The mantissa is encoded in more bytes than the size of the fixed of the schema.
+    Size {{ len: usize, size: usize }},
+}}
+
+impl std::fmt::Display for DecimalError {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        match self {{
+            DecimalError::Precision {{ digits, precision }} => {{
+                write!(f, "{{digits}} digits exceed the decimal precision {{precision}}")
+            }}
+            DecimalError::Scale {{ scale, precision }} => {{
+                write!(f, "The decimal scale {{scale}} exceeds its precision {{precision}}")
+            }}
+            DecimalError::Size {{ len, size }} => {{
+                write!(f, "{{len}} bytes exceed the decimal fixed size {{size}}")
+            }}
+        }}
+    }}
+}}
+
+impl std::error::Error for DecimalError {{}}
+"#
+    );
+    code
+}
+
+/// Returns the code of a `module` of serde `with` modules named after the `scales` of the
+/// decimal fields of a record (e.g. `scale_2`, or `scale_2_option` for optional fields),
+/// giving their scale to the generated struct when deserializing.
+pub(crate) fn scale_modules(module: &str, scales: &BTreeSet<(usize, bool)>) -> String {
+    let mut code = format!(
+        "\n/// Serde functions of the decimal fields, with the scale of their schema.\n\
+         mod {module} {{\n    use super::Decimal;\n"
+    );
+    for (scale, optional) in scales {
+        let (suffix, rust_type, function) = if *optional {
+            ("_option", "Option<super::Decimal>", "option_with_scale")
+        } else {
+            ("", "super::Decimal", "with_scale")
+        };
+        let serialize = if *optional {
+            format!("super::Decimal::serialize_{function}(value, {scale}, serializer)")
+        } else {
+            format!("value.serialize_{function}({scale}, serializer)")
+        };
+        let _ = write!(
+            code,
+            r#"
+    pub mod scale_{scale}{suffix} {{
+        pub fn serialize<S>(value: &{rust_type}, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {{
+            {serialize}
+        }}
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<{rust_type}, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {{
+            super::Decimal::deserialize_{function}(deserializer, {scale})
+        }}
+    }}
+"#
+        );
+    }
+    code.push_str("}\n");
+    code
+}
@@ -0,0 +1,306 @@
+//! Validation of record field default values, run before any code is generated, and zero
+//! values of fields without default value.
+
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, RecordSchema,
+};
+use apache_avro::Schema;
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::templates::GenState;
+
+/// Checks that the default values of all the record fields defined in a schema can be
+/// generated, which is stricter than the parsing done by apache-avro (e.g. floating-point
+/// defaults need a fractional part, and nullable union defaults must be null, while the
+/// union items of array and map defaults can be of any variant).
+///
+/// Failures are reported as [`Error::InvalidDefault`](Error::InvalidDefault), or
+/// [`Error::InvalidDefaultSize`](Error::InvalidDefaultSize) for strings of the wrong length,
+/// with a JSON pointer into the document of the schema.
+pub(crate) fn validate_defaults(schema: &Schema, gen_state: &GenState) -> Result<()> {
+    walk(schema, gen_state, "")
+}
+
+/// Validates the defaults of the records defined in a schema, found at `pointer`.
+fn walk(schema: &Schema, gen_state: &GenState, pointer: &str) -> Result<()> {
+    match schema {
+        Schema::Record(RecordSchema { name, fields, .. }) => {
+            for (i, field) in fields.iter().enumerate() {
+                if let Some(default) = &field.default {
+                    let default_pointer = format!("{pointer}/fields/{i}/default");
+                    check(&field.schema, gen_state, default, &default_pointer).map_err(
+                        |(pointer, reason)| match reason {
+                            Reason::Invalid(reason) => Error::InvalidDefault {
+                                record: name.fullname(None),
+                                field: field.name.clone(),
+                                pointer,
+                                reason,
+                            },
+                            Reason::Size { size, len, literal } => Error::InvalidDefaultSize {
+                                record: name.fullname(None),
+                                field: field.name.clone(),
+                                pointer,
+                                size,
+                                len,
+                                literal,
+                            },
+                        },
+                    )?;
+                }
+                walk(
+                    &field.schema,
+                    gen_state,
+                    &format!("{pointer}/fields/{i}/type"),
+                )?;
+            }
+        }
+        Schema::Array(ArraySchema { items, .. }) => {
+            walk(items, gen_state, &format!("{pointer}/items"))?
+        }
+        Schema::Map(MapSchema { types, .. }) => {
+            walk(types, gen_state, &format!("{pointer}/values"))?
+        }
+        Schema::Union(union) => {
+            for (i, variant) in union.variants().iter().enumerate() {
+                walk(variant, gen_state, &format!("{pointer}/{i}"))?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Why a default value is invalid.
+enum Reason {
+    /// The value does not match its schema.
+    Invalid(String),
+    /// The string of a `fixed` or `duration` value is not of the expected length.
+    Size {
+        size: usize,
+        len: usize,
+        literal: String,
+    },
+}
+
+/// Checks a default value found at `pointer`, returning the pointer of the invalid part of
+/// the value and why it is invalid.
+fn check(
+    schema: &Schema,
+    gen_state: &GenState,
+    default: &Value,
+    pointer: &str,
+) -> std::result::Result<(), (String, Reason)> {
+    let fail = |expected: &str| {
+        Err((
+            pointer.to_string(),
+            Reason::Invalid(format!("Expected {expected}, found {default}")),
+        ))
+    };
+    let string_of_len = |size: usize| match default {
+        Value::String(s) if s.len() == size => Ok(()),
+        Value::String(s) => Err((
+            pointer.to_string(),
+            Reason::Size {
+                size,
+                len: s.len(),
+                literal: default.to_string(),
+            },
+        )),
+        _ => fail("a string"),
+    };
+
+    match schema {
+        Schema::Ref { name } => match gen_state.get_schema(name) {
+            Some(schema) => check(schema, gen_state, default, pointer),
+            None => Err((
+                pointer.to_string(),
+                Reason::Invalid(format!(
+                    "Schema reference `{}` cannot be resolved",
+                    name.fullname(None)
+                )),
+            )),
+        },
+
+        Schema::Null => match default {
+            Value::Null => Ok(()),
+            _ => fail("null"),
+        },
+        Schema::Boolean => match default {
+            Value::Bool(_) => Ok(()),
+            _ => fail("a boolean"),
+        },
+        Schema::Int
+        | Schema::Long
+        | Schema::Date
+        | Schema::TimeMillis
+        | Schema::TimeMicros
+        | Schema::TimestampMillis
+        | Schema::TimestampMicros
+        | Schema::TimestampNanos
+        | Schema::LocalTimestampMillis
+        | Schema::LocalTimestampMicros
+        | Schema::LocalTimestampNanos => match default {
+            Value::Number(n) if n.is_i64() => Ok(()),
+            _ => fail("an integer"),
+        },
+        Schema::Float | Schema::Double => match default {
+            Value::Number(n) if n.is_f64() => Ok(()),
+            _ => fail("a number with a fractional part"),
+        },
+        Schema::Bytes | Schema::String | Schema::BigDecimal => match default {
+            Value::String(_) => Ok(()),
+            _ => fail("a string"),
+        },
+        Schema::Uuid => match default {
+            Value::String(s) if apache_avro::Uuid::parse_str(s).is_ok() => Ok(()),
+            _ => fail("a UUID string"),
+        },
+        Schema::Duration => string_of_len(12),
+        Schema::Fixed(FixedSchema { size, .. }) => string_of_len(*size),
+        Schema::Decimal(DecimalSchema { inner, .. }) => match inner.as_ref() {
+            Schema::Fixed(FixedSchema { size, .. }) => string_of_len(*size),
+            _ => check(&Schema::Bytes, gen_state, default, pointer),
+        },
+
+        Schema::Enum(EnumSchema { symbols, .. }) => match default {
+            Value::String(s) if symbols.contains(s) => Ok(()),
+            _ => fail(&format!(
+                "one of the symbols {}",
+                symbols
+                    .iter()
+                    .map(|s| format!("\"{s}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        },
+
+        Schema::Array(ArraySchema { items, .. }) => match default {
+            Value::Array(values) => values.iter().enumerate().try_for_each(|(i, value)| {
+                check_item(items, gen_state, value, &format!("{pointer}/{i}"))
+            }),
+            _ => fail("an array"),
+        },
+        Schema::Map(MapSchema { types, .. }) => match default {
+            Value::Object(values) => values.iter().try_for_each(|(key, value)| {
+                check_item(
+                    types,
+                    gen_state,
+                    value,
+                    &format!("{pointer}/{}", escape(key)),
+                )
+            }),
+            _ => fail("an object"),
+        },
+        Schema::Record(RecordSchema { fields, .. }) => match default {
+            Value::Object(values) => fields.iter().try_for_each(|field| {
+                let field_pointer = format!("{pointer}/{}", escape(&field.name));
+                match values.get(&field.name) {
+                    Some(value) => check(&field.schema, gen_state, value, &field_pointer),
+                    None if field.default.is_none() => Err((
+                        pointer.to_string(),
+                        Reason::Invalid(format!("Missing field `{}` without default", field.name)),
+                    )),
+                    None => Ok(()),
+                }
+            }),
+            _ => fail("an object"),
+        },
+
+        // Only the first variant of a union can be the type of its default value, which is
+        // always null for optional fields
+        Schema::Union(union) if union.is_nullable() => {
+            check(&Schema::Null, gen_state, default, pointer)
+        }
+        Schema::Union(union) => match union.variants().first() {
+            Some(variant) => check(variant, gen_state, default, pointer),
+            None => fail("no value for an empty union"),
+        },
+    }
+}
+
+/// Checks an item of an array or map default value, which can be of any variant of an union
+/// like in Avro implementations, the first one accepting it being its type.
+fn check_item(
+    schema: &Schema,
+    gen_state: &GenState,
+    default: &Value,
+    pointer: &str,
+) -> std::result::Result<(), (String, Reason)> {
+    match schema {
+        Schema::Union(union) => match item_variant(union.variants(), gen_state, default) {
+            Some(_) => Ok(()),
+            None => Err((
+                pointer.to_string(),
+                Reason::Invalid(format!(
+                    "Expected a value of a variant of the union, found {default}"
+                )),
+            )),
+        },
+        _ => check(schema, gen_state, default, pointer),
+    }
+}
+
+/// Returns the first of the union `variants` accepting an array or map item default value.
+pub(crate) fn item_variant<'a>(
+    variants: &'a [Schema],
+    gen_state: &GenState,
+    default: &Value,
+) -> Option<&'a Schema> {
+    variants
+        .iter()
+        .find(|variant| check_item(variant, gen_state, default, "").is_ok())
+}
+
+/// Returns the zero value of a schema, in the JSON form of Avro default values: `false`, `0`,
+/// empty strings, arrays and maps, the default (or first) symbol of enums, zeroed fixed, and
+/// the zero value of the first variant of unions.
+///
+/// Records are empty objects, i.e. they are made of the default values of their fields,
+/// which all have one in lenient types.
+pub(crate) fn zero_default(schema: &Schema, gen_state: &GenState) -> Value {
+    let zeros = |size: usize| Value::String("\0".repeat(size));
+    match schema {
+        Schema::Ref { name } => match gen_state.get_schema(name) {
+            Some(schema) => zero_default(schema, gen_state),
+            None => Value::Null,
+        },
+        Schema::Null => Value::Null,
+        Schema::Boolean => Value::Bool(false),
+        Schema::Int
+        | Schema::Long
+        | Schema::Date
+        | Schema::TimeMillis
+        | Schema::TimeMicros
+        | Schema::TimestampMillis
+        | Schema::TimestampMicros
+        | Schema::TimestampNanos
+        | Schema::LocalTimestampMillis
+        | Schema::LocalTimestampMicros
+        | Schema::LocalTimestampNanos => Value::from(0),
+        Schema::Float | Schema::Double => Value::from(0.0),
+        Schema::Bytes | Schema::String => Value::String(String::new()),
+        Schema::BigDecimal => Value::String("0".to_string()),
+        Schema::Uuid => Value::String(apache_avro::Uuid::nil().to_string()),
+        Schema::Duration => zeros(12),
+        Schema::Fixed(FixedSchema { size, .. }) => zeros(*size),
+        Schema::Decimal(DecimalSchema { inner, .. }) => match inner.as_ref() {
+            Schema::Fixed(FixedSchema { size, .. }) => zeros(*size),
+            _ => zeros(1),
+        },
+        Schema::Enum(EnumSchema {
+            symbols, default, ..
+        }) => Value::from(default.as_ref().or(symbols.first()).cloned()),
+        Schema::Array(_) => Value::Array(vec![]),
+        Schema::Map(_) | Schema::Record(_) => Value::Object(Default::default()),
+        Schema::Union(union) => match union.variants().first() {
+            Some(variant) => zero_default(variant, gen_state),
+            None => Value::Null,
+        },
+    }
+}
+
+/// Escapes a JSON pointer reference token, see RFC 6901.
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
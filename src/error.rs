@@ -7,12 +7,119 @@ pub enum Error {
     Schema(String),
     #[error("Templating error: {}", .0)]
     Template(String),
+    #[error("Schema error: {message}")]
+    InvalidSchema {
+        /// What is invalid in the schema, which determines the [`code`](Error::code).
+        kind: ErrorKind,
+        /// The description of the error.
+        message: String,
+    },
+    #[error("Templating error: {message}")]
+    Generation {
+        /// Why the code of the schema cannot be generated, which determines the
+        /// [`code`](Error::code).
+        kind: ErrorKind,
+        /// The description of the error.
+        message: String,
+    },
     #[error("Unexpected io error: {}", .0)]
     Io(#[from] std::io::Error),
     #[error("Avro error: {}", .0)]
-    Avro(#[from] apache_avro::Error),
+    Avro(#[from] Box<apache_avro::Error>),
     #[error("Invalid glob pattern: {}", .0)]
     GlobPattern(#[from] glob::PatternError),
+    #[error(
+        "Invalid default value of field `{field}` in record `{record}` at {pointer}: {reason}"
+    )]
+    InvalidDefault {
+        /// The fullname of the record.
+        record: String,
+        /// The name of the field.
+        field: String,
+        /// A JSON pointer to the invalid value, in the document of the schema.
+        pointer: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+    #[error(
+        "Invalid default value of field `{field}` in record `{record}` at {pointer}: \
+         Expected a string of {size} bytes, found {len} bytes in {literal}"
+    )]
+    InvalidDefaultSize {
+        /// The fullname of the record.
+        record: String,
+        /// The name of the field.
+        field: String,
+        /// A JSON pointer to the invalid value, in the document of the schema.
+        pointer: String,
+        /// The size of the `fixed` (or `duration`) schema, in bytes.
+        size: usize,
+        /// The length of the string, in bytes.
+        len: usize,
+        /// The JSON string of the invalid value.
+        literal: String,
+    },
+    #[error("{error}\n --> {}:{line}:{column}\n{snippet}", path.display())]
+    Located {
+        /// The error, e.g. [`InvalidDefault`](Error::InvalidDefault).
+        error: Box<Error>,
+        /// The path of the schema file.
+        path: std::path::PathBuf,
+        /// The line of the offending JSON value in the file, starting at 1.
+        line: usize,
+        /// The column of the offending JSON value in its line, in characters starting at 1.
+        column: usize,
+        /// The line of the offending JSON value, which is underlined.
+        snippet: String,
+    },
+    #[error("Invalid Rust code generated for `{item}`: {reason}\n{snippet}")]
+    InvalidCode {
+        /// The name of the generated item.
+        item: String,
+        /// The parsing error.
+        reason: String,
+        /// The lines of generated code around the error.
+        snippet: String,
+    },
+    #[error("Configuration error: {}", .0)]
+    Config(String),
+    #[error("Generation cancelled")]
+    Cancelled,
+}
+
+/// The kind of an [`InvalidSchema`](Error::InvalidSchema) or
+/// [`Generation`](Error::Generation) error, each one having its own [`code`](Error::code).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The schema is not valid JSON (`E0002`).
+    Json,
+    /// A schema reference cannot be resolved (`E0003`).
+    Reference,
+    /// A default value does not match the type of its field (`E0004`).
+    Default,
+    /// The shape of an union, or the use of `null`, is not supported (`E0005`).
+    Union,
+    /// Two Avro names are mapped to the same Rust name (`E0006`).
+    Conflict,
+    /// An option of the generator is not supported by the schema (`E0007`).
+    Unsupported,
+    /// A `rust.*` or constraint attribute has an invalid value (`E0008`).
+    Attribute,
+}
+
+impl Error {
+    /// Returns the stable code of the error, e.g. `E0004` for invalid default values, whose
+    /// explanation is returned by [`explain_error`](crate::explain_error).
+    pub fn code(&self) -> &'static str {
+        crate::codes::code(self)
+    }
+}
+
+impl From<apache_avro::Error> for Error {
+    fn from(source: apache_avro::Error) -> Self {
+        Error::Avro(Box::new(source))
+    }
 }
 
 impl From<tera::Error> for Error {
@@ -31,5 +138,28 @@ mod tests {
             "Schema error: Some message",
             Error::Schema("Some message".into()).to_string()
         );
+        assert_eq!(
+            "Invalid default value of field `b` in record `a.A` at /fields/0/default: Expected null, found 1",
+            Error::InvalidDefault {
+                record: "a.A".into(),
+                field: "b".into(),
+                pointer: "/fields/0/default".into(),
+                reason: "Expected null, found 1".into(),
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "Invalid default value of field `id` in record `a.A` at /fields/0/default/1: \
+             Expected a string of 4 bytes, found 2 bytes in \"ab\"",
+            Error::InvalidDefaultSize {
+                record: "a.A".into(),
+                field: "id".into(),
+                pointer: "/fields/0/default/1".into(),
+                size: 4,
+                len: 2,
+                literal: "\"ab\"".into(),
+            }
+            .to_string()
+        );
     }
 }
@@ -0,0 +1,130 @@
+//! Synthesis of example JSON documents from Avro schemas.
+
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, Name, RecordSchema,
+};
+use apache_avro::Schema;
+use serde_json::{json, Value};
+
+use crate::templates::{reaches, GenState};
+
+/// Returns an example JSON document valid against a schema.
+///
+/// Documents have the JSON form of Avro default values, i.e. unions are not wrapped in an
+/// object naming their branch. Schema default values are used whenever they exist, other
+/// values are synthesized: strings are named after their field, arrays and maps have a
+/// single element, unions take their first non-null branch. Recursive schemas end with
+/// empty arrays and maps, or null unions.
+pub(crate) fn example(schema: &Schema, gen_state: &GenState) -> Value {
+    synthesize(schema, gen_state, None, &mut vec![], false)
+}
+
+/// Returns an example JSON document valid against a schema, which is also a valid Avro
+/// default value: unions take their first branch unless it is recursive, e.g. nullable
+/// unions are null. Record and map default values are not used, since record default
+/// values can omit fields.
+pub(crate) fn default_example(schema: &Schema, gen_state: &GenState) -> Value {
+    synthesize(schema, gen_state, None, &mut vec![], true)
+}
+
+fn synthesize<'a>(
+    schema: &'a Schema,
+    gen_state: &'a GenState,
+    field: Option<&str>,
+    records: &mut Vec<&'a Name>,
+    first_branches: bool,
+) -> Value {
+    let recursive = |s: &Schema, records: &Vec<&Name>| {
+        records
+            .iter()
+            .any(|r| reaches(s, r, gen_state, &mut Default::default()))
+    };
+    match schema {
+        Schema::Ref { name } => match gen_state.get_schema(name) {
+            Some(s) => synthesize(s, gen_state, field, records, first_branches),
+            None => Value::Null,
+        },
+        Schema::Null => Value::Null,
+        Schema::Boolean => json!(true),
+        Schema::Int | Schema::Long => json!(1),
+        Schema::Float | Schema::Double => json!(1.5),
+        Schema::String => json!(field.unwrap_or("string")),
+        Schema::Bytes | Schema::BigDecimal => json!("bytes"),
+        Schema::Uuid => json!("00000000-0000-0000-0000-000000000000"),
+        Schema::Date
+        | Schema::TimeMillis
+        | Schema::TimeMicros
+        | Schema::TimestampMillis
+        | Schema::TimestampMicros
+        | Schema::TimestampNanos
+        | Schema::LocalTimestampMillis
+        | Schema::LocalTimestampMicros
+        | Schema::LocalTimestampNanos => json!(0),
+        Schema::Decimal(DecimalSchema { inner, .. }) => match inner.as_ref() {
+            Schema::Fixed(FixedSchema { size, .. }) => json!("\0".repeat(*size)),
+            _ => json!("\0"),
+        },
+        Schema::Duration => json!("\0".repeat(12)),
+        Schema::Fixed(FixedSchema { size, .. }) => json!("0".repeat(*size)),
+        Schema::Enum(EnumSchema {
+            symbols, default, ..
+        }) => json!(default.as_ref().or(symbols.first())),
+
+        Schema::Record(RecordSchema { name, fields, .. }) => {
+            if records.contains(&name) {
+                return Value::Null;
+            }
+            records.push(name);
+            let mut fields_by_pos = fields.iter().collect::<Vec<_>>();
+            fields_by_pos.sort_by_key(|f| f.position);
+            let object = fields_by_pos
+                .into_iter()
+                .map(|f| {
+                    let value = match &f.default {
+                        Some(default) if !(first_branches && default.is_object()) => {
+                            default.clone()
+                        }
+                        _ => {
+                            synthesize(&f.schema, gen_state, Some(&f.name), records, first_branches)
+                        }
+                    };
+                    (f.name.clone(), value)
+                })
+                .collect();
+            records.pop();
+            Value::Object(object)
+        }
+
+        Schema::Array(ArraySchema { items, .. }) => {
+            if recursive(items, records) {
+                json!([])
+            } else {
+                json!([synthesize(items, gen_state, field, records, first_branches)])
+            }
+        }
+        Schema::Map(MapSchema { types, .. }) => {
+            if recursive(types, records) {
+                json!({})
+            } else {
+                json!({ "key": synthesize(types, gen_state, field, records, first_branches) })
+            }
+        }
+        Schema::Union(union) => {
+            let variants = union.variants();
+            let branch = variants
+                .first()
+                .filter(|v| first_branches && !recursive(v, records))
+                .or_else(|| {
+                    variants
+                        .iter()
+                        .find(|v| **v != Schema::Null && !recursive(v, records))
+                })
+                .or_else(|| variants.iter().find(|v| **v == Schema::Null))
+                .or(variants.first());
+            match branch {
+                Some(branch) => synthesize(branch, gen_state, field, records, first_branches),
+                None => Value::Null,
+            }
+        }
+    }
+}
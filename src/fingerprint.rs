@@ -0,0 +1,72 @@
+//! [Fingerprints](https://avro.apache.org/docs/1.11.1/specification/#schema-fingerprints) of
+//! Avro schemas, computed from their [canonical form](crate::canonical_form).
+
+use apache_avro::rabin::Rabin;
+use apache_avro::Schema;
+use digest::Digest;
+#[cfg(feature = "fingerprints")]
+use md5::Md5;
+#[cfg(feature = "fingerprints")]
+use sha2::Sha256;
+
+use crate::canonical::canonical_form;
+
+/// Returns the 64-bit Rabin fingerprint of a schema, e.g. for the header of
+/// [single-object encoded](https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding)
+/// datums, where it is written in little-endian.
+pub fn rabin_fingerprint(schema: &Schema) -> u64 {
+    let bytes = Rabin::digest(canonical_form(schema));
+    u64::from_le_bytes(bytes.into())
+}
+
+/// Returns the MD5 fingerprint of a schema, with the `fingerprints` feature.
+#[cfg(feature = "fingerprints")]
+pub fn md5_fingerprint(schema: &Schema) -> [u8; 16] {
+    Md5::digest(canonical_form(schema)).into()
+}
+
+/// Returns the SHA-256 fingerprint of a schema, with the `fingerprints` feature.
+#[cfg(feature = "fingerprints")]
+pub fn sha256_fingerprint(schema: &Schema) -> [u8; 32] {
+    Sha256::digest(canonical_form(schema)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints() {
+        // Value of the test suite of the Avro specification
+        let schema = Schema::parse_str(r#""int""#).unwrap();
+        assert_eq!(0x7275d51a3f395c8f, rabin_fingerprint(&schema));
+
+        let schema = Schema::parse_str(
+            r#"{"type": "fixed", "name": "Id", "namespace": "com.acme", "size": 4, "doc": "Ids"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rabin_fingerprint(
+                &Schema::parse_str(r#"{"name":"com.acme.Id","type":"fixed","size":4}"#).unwrap()
+            ),
+            rabin_fingerprint(&schema)
+        );
+    }
+
+    #[cfg(feature = "fingerprints")]
+    #[test]
+    fn md5_and_sha256_fingerprints() {
+        let hex = |bytes: &[u8]| -> String { bytes.iter().map(|b| format!("{b:02x}")).collect() };
+
+        // Values of the test suite of the Avro specification
+        let schema = Schema::parse_str(r#""int""#).unwrap();
+        assert_eq!(
+            "ef524ea1b91e73173d938ade36c1db32",
+            hex(&md5_fingerprint(&schema))
+        );
+        assert_eq!(
+            "3f2b87a9fe7cc9b13835598c3981cd45e3e355309e5090aa0933d7becb6fba45",
+            hex(&sha256_fingerprint(&schema))
+        );
+    }
+}
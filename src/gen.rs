@@ -1,11 +1,29 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
 use std::fs;
 use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
 
-use apache_avro::schema::{ArraySchema, DecimalSchema, MapSchema, RecordField, RecordSchema};
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, Name, RecordField, RecordSchema,
+};
+use heck::ToSnakeCase;
 
-use crate::error::{Error, Result};
+use crate::cache::{hash, item_key, RenderCache};
+use crate::decimal::{decimal_struct, decimal_struct_schema, DECIMAL_STRUCT};
+use crate::error::{Error, ErrorKind, Result};
+use crate::fingerprint::rabin_fingerprint;
+use crate::message::{message_trait, MESSAGE_TRAIT};
+use crate::naming::{sanitize, DefaultNameMapper, NameMapper};
+use crate::progress::{CancellationToken, Progress};
+use crate::render::{Renderer, TeraRenderer};
+use crate::report::{timed, Report, ReportItem, Stats};
 use crate::templates::*;
+use crate::types::{DefaultTypeMapper, TypeMapper};
+use crate::validate::{validation_errors, validator_trait, VALIDATION_ERRORS, VALIDATOR_TRAIT};
+use crate::versions::Version;
 use crate::Schema;
 
 /// An input source for generating Rust types.
@@ -17,15 +35,63 @@ pub enum Source<'a> {
     /// An Avro schema string in json format.
     SchemaStr(&'a str),
     /// Pattern for selecting files containing Avro schemas in json format.
+    ///
+    /// Files with an `.avro` extension are read as Avro object container files instead,
+    /// generating the types of the schema found in their header, and files with a `.tar`,
+    /// `.tar.gz`, `.tgz` or `.zip` extension as archives, e.g. built by schema packaging
+    /// pipelines, generating the types of all the `.avsc` files they contain.
     GlobPattern(&'a str),
+    /// Named Avro schema strings in json format, as `(name, schema)` pairs, e.g. the open
+    /// buffers of an editor.
+    ///
+    /// Schemas can reference each other as the files of a [`GlobPattern`](Source::GlobPattern),
+    /// without accessing the file system, and names are used as the source of generated items.
+    Documents(&'a [(&'a str, &'a str)]),
 }
 
+/// The name of the index file written to `OUT_DIR` by
+/// [`Generator::gen_out_dir`](Generator::gen_out_dir).
+pub const OUT_DIR_INDEX: &str = "rsgen_avro.rs";
+
+/// The comment marking the beginning of the region written by
+/// [`Generator::gen_in_place`](Generator::gen_in_place).
+pub const REGION_BEGIN: &str = "// <rsgen-avro:begin>";
+
+/// The comment marking the end of the region written by
+/// [`Generator::gen_in_place`](Generator::gen_in_place).
+pub const REGION_END: &str = "// <rsgen-avro:end>";
+
+/// The lints allowed in the [`wrap_module`](GeneratorBuilder::wrap_module) module, unless
+/// configured otherwise.
+pub const DEFAULT_ALLOWED_LINTS: &[&str] = &["dead_code", "clippy::all"];
+
 /// The main component for generating Rust types from a [`Source`](Source).
 ///
-/// It is stateless and can be reused many times.
-#[derive(Debug)]
+/// It is stateless and can be reused many times. As it is `Send` and `Sync`, a single
+/// instance can also be shared across threads to run generations in parallel.
 pub struct Generator {
     templater: Templater,
+    progress: Option<Arc<dyn Progress>>,
+    cancellation_token: Option<CancellationToken>,
+    verify: bool,
+    module: Option<String>,
+    config_hash: String,
+}
+
+// Generators are shared by parallel generations, rendering must not require `&mut self`.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Generator>();
+};
+
+impl std::fmt::Debug for Generator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Generator")
+            .field("templater", &self.templater)
+            .field("cancellation_token", &self.cancellation_token)
+            .field("verify", &self.verify)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Generator {
@@ -42,103 +108,1410 @@ impl Generator {
     /// Generates Rust code from an Avro schema [`Source`](Source).
     /// Writes all generated types to the output.
     pub fn gen(&self, source: &Source, output: &mut impl Write) -> Result<()> {
-        match source {
-            Source::Schema(schema) => {
-                let mut deps = deps_stack(schema, vec![]);
-                self.gen_in_order(&mut deps, output)?;
+        let items = self.items(source)?;
+        output.write_all(self.module_start().as_bytes())?;
+        for item in items {
+            output.write_all(item?.code.as_bytes())?;
+        }
+        output.write_all(self.module_end().as_bytes())?;
+        Ok(())
+    }
+
+    /// Returns the code opening the [`wrap_module`](GeneratorBuilder::wrap_module) module
+    /// of the generated items, with its lint allowances, empty without module.
+    fn module_start(&self) -> &str {
+        self.module.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the code closing the [`wrap_module`](GeneratorBuilder::wrap_module) module
+    /// of the generated items, empty without module.
+    fn module_end(&self) -> &str {
+        if self.module.is_some() {
+            "}\n"
+        } else {
+            ""
+        }
+    }
+
+    /// Generates Rust code from an Avro schema read from `reader`, like [`gen`](Generator::gen).
+    ///
+    /// The schema JSON is deserialized from the reader, without first reading it into a
+    /// string, and every item is written as soon as it is rendered. The generation is not
+    /// incremental though: the whole JSON document is parsed into a schema before generating,
+    /// and the schema is held in memory until the last item. The generation of large schema
+    /// files can be followed with a [`Progress`](GeneratorBuilder::progress).
+    pub fn gen_reader(&self, reader: &mut impl Read, output: &mut impl Write) -> Result<()> {
+        let json: serde_json::Value = serde_json::from_reader(std::io::BufReader::new(reader))
+            .map_err(|e| Error::InvalidSchema {
+                kind: ErrorKind::Json,
+                message: format!("Invalid JSON: {e}"),
+            })?;
+        let schema = Schema::parse(&json)?;
+        drop(json);
+        self.gen(&Source::Schema(&schema), output)
+    }
+
+    /// Generates Rust code from an Avro schema [`Source`](Source), like [`gen`](Generator::gen),
+    /// and returns a [`Report`](Report) of the generated items.
+    pub fn gen_with_report(&self, source: &Source, output: &mut impl Write) -> Result<Report> {
+        let mut report = Report::default();
+        let items = self.items(source)?;
+        output.write_all(self.module_start().as_bytes())?;
+        for item in items {
+            let item = item?;
+            output.write_all(item.code.as_bytes())?;
+            report.items.push(ReportItem::from(&item));
+        }
+        output.write_all(self.module_end().as_bytes())?;
+        Ok(report)
+    }
+
+    /// Generates Rust code from an Avro schema [`Source`](Source), like [`gen`](Generator::gen),
+    /// and returns [`Stats`](Stats) of the generation: the number of processed schemas,
+    /// generated items and warnings, and the time spent in each phase.
+    ///
+    /// The time spent on each schema is measured by [`SchemaTimings`](crate::SchemaTimings).
+    pub fn gen_with_stats(&self, source: &Source, output: &mut impl Write) -> Result<Stats> {
+        let mut stats = Stats::default();
+        let mut items = timed(&mut stats.parsing, || self.items(source))?;
+        stats.resolving = items.resolving;
+        stats.parsing = stats.parsing.saturating_sub(items.resolving);
+        stats.schemas = items.total;
+        output.write_all(self.module_start().as_bytes())?;
+        while let Some(item) = timed(&mut stats.rendering, || items.next()) {
+            let item = item?;
+            timed(&mut stats.writing, || {
+                output.write_all(item.code.as_bytes())
+            })?;
+            *stats.items.entry(item.kind).or_default() += 1;
+            stats.warnings += item.warnings.len();
+        }
+        output.write_all(self.module_end().as_bytes())?;
+        Ok(stats)
+    }
+
+    /// Generates Rust code from an Avro schema [`Source`](Source) into `output_file`,
+    /// reusing the items rendered by previous generations whenever possible.
+    ///
+    /// Rendered items are cached in `cache_file`, keyed by a hash of their schema and of the
+    /// generator configuration, so that only new or modified schemas are rendered again.
+    /// The output file is only rewritten if the generated code changed, in which case `true`
+    /// is returned.
+    ///
+    /// Note that custom [renderers](GeneratorBuilder::renderer), mappers, filters and
+    /// functions are not part of the hashed configuration: the cache file must be removed
+    /// when they change.
+    pub fn gen_incremental(
+        &self,
+        source: &Source,
+        output_file: impl AsRef<Path>,
+        cache_file: impl AsRef<Path>,
+    ) -> Result<bool> {
+        let (output_file, cache_file) = (output_file.as_ref(), cache_file.as_ref());
+
+        let mut items = self.items(source)?;
+        items.cache = Some(RenderCache::load(cache_file, &self.config_hash));
+        let mut code = self.module_start().to_string();
+        for item in items.by_ref() {
+            code.push_str(&item?.code);
+        }
+        code.push_str(self.module_end());
+        let mut cache = items.cache.take().unwrap_or_default();
+
+        let changed = cache.update_output(&code) || !output_file.exists();
+        if changed {
+            fs::write(output_file, code)?;
+        }
+        cache.save(cache_file)?;
+        Ok(changed)
+    }
+
+    /// Generates Rust code from an Avro schema [`Source`](Source) into an existing source
+    /// file, replacing the lines between its [`REGION_BEGIN`](REGION_BEGIN) and
+    /// [`REGION_END`](REGION_END) comments.
+    ///
+    /// The code around the region, including the marker comments, is left untouched. The
+    /// file is only rewritten if the generated code changed, in which case `true` is
+    /// returned.
+    pub fn gen_in_place(&self, source: &Source, file: impl AsRef<Path>) -> Result<bool> {
+        let file = file.as_ref();
+        let content = fs::read_to_string(file)?;
+        let mut code = vec![];
+        self.gen(source, &mut code)?;
+        let code = String::from_utf8(code)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        let updated = replace_region(&content, &code).map_err(|msg| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", file.display(), msg),
+            )
+        })?;
+        let changed = updated != content;
+        if changed {
+            fs::write(file, updated)?;
+        }
+        Ok(changed)
+    }
+
+    /// Generates Rust code from an Avro schema [`Source`](Source) into `dir`, with one file
+    /// per Avro namespace, and returns the paths of the written files.
+    ///
+    /// The types of each namespace are written to a module named after it, e.g.
+    /// `com_acme_users.rs` for `com.acme.users`, which is declared and re-exported by the
+    /// `mod.rs` file of `dir`. This file also holds the items without namespace, e.g. union
+    /// enums and the [`Decimal`](GeneratorBuilder::use_decimal_structs) struct. Modules import
+    /// the items of `mod.rs`, so that types can reference the ones of other namespaces.
+    ///
+    /// Types of different namespaces can have the same name, the modules referencing one of
+    /// them from another namespace importing it explicitly. Such types are not re-exported by
+    /// `mod.rs`, and fail the generation if used together in the same module.
+    ///
+    /// Modules are documented with a summary of their types, linked with the first line of
+    /// their schema documentation, and `mod.rs` with the list of the namespace modules.
+    ///
+    /// The [`wrap_module`](GeneratorBuilder::wrap_module) option is not supported, `dir`
+    /// being the module of the generated types.
+    pub fn gen_namespaces(&self, source: &Source, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        if self.module.is_some() {
+            return Err(Error::Config(
+                "`wrap_module` is not supported when splitting namespaces".to_string(),
+            ));
+        }
+        let dir = dir.as_ref();
+        let items = self.source_items(source, true)?;
+        let imports = namespace_imports(&items.deps, self.templater.name_mapper.as_ref())?;
+        // Ambiguous type names are not re-exported by `mod.rs`
+        let reexport = match imports {
+            Some(_) => "#[allow(ambiguous_glob_reexports, unused_imports)]\npub use",
+            None => "pub use",
+        };
+        let mut imports = imports.unwrap_or_default();
+        // Fullname -> first line of the schema documentation
+        let docs = items
+            .deps
+            .iter()
+            .filter_map(|schema| {
+                let (Schema::Record(RecordSchema { name, doc, .. })
+                | Schema::Enum(EnumSchema { name, doc, .. })
+                | Schema::Fixed(FixedSchema { name, doc, .. })) = schema
+                else {
+                    return None;
+                };
+                let line = doc
+                    .as_deref()?
+                    .lines()
+                    .map(str::trim)
+                    .find(|l| !l.is_empty())?;
+                Some((name.fullname(None), line.to_string()))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut root = String::new();
+        // Module name -> namespace, summary, code
+        let mut modules = BTreeMap::<String, (String, String, String)>::new();
+        for item in items {
+            let item = item?;
+            let namespace = item
+                .fullname
+                .as_deref()
+                .and_then(|name| name.rsplit_once('.'));
+            let Some((namespace, _)) = namespace else {
+                root.push_str(&item.code);
+                continue;
+            };
+            let module = namespace_module(namespace);
+            let (other, summary, code) = modules
+                .entry(module.clone())
+                .or_insert_with(|| (namespace.to_string(), String::new(), String::new()));
+            if other != namespace {
+                return Err(Error::Schema(format!(
+                    "Namespaces `{other}` and `{namespace}` are both written to the `{module}` \
+                     module"
+                )));
             }
+            let doc = item
+                .fullname
+                .as_ref()
+                .and_then(|fullname| docs.get(fullname));
+            match doc {
+                Some(doc) => summary.push_str(&format!("//! - [`{}`]: {doc}\n", item.name)),
+                None => summary.push_str(&format!("//! - [`{}`]\n", item.name)),
+            }
+            code.push_str(&item.code);
+        }
 
-            Source::Schemas(schemas) => {
-                let mut deps = schemas
-                    .iter()
-                    .fold(vec![], |deps, schema| deps_stack(schema, deps));
+        fs::create_dir_all(dir)?;
+        let mut index =
+            String::from("//! The generated Avro types, with a module per namespace:\n//!\n");
+        let mut declarations = String::new();
+        let mut paths = vec![dir.join("mod.rs")];
+        for (module, (namespace, summary, code)) in &modules {
+            index.push_str(&format!("//! - [`{module}`]: `{namespace}`\n"));
+            declarations.push_str(&format!("pub mod {module};\n{reexport} {module}::*;\n"));
+            let path = dir.join(format!("{}.rs", module.trim_start_matches("r#")));
+            let imports = imports
+                .remove(&Some(namespace.clone()))
+                .unwrap_or_default()
+                .concat();
+            fs::write(
+                &path,
+                format!(
+                    "//! The types of the `{namespace}` namespace:\n//!\n{summary}\n\
+                     #[allow(unused_imports)]\nuse super::*;\n{imports}{code}"
+                ),
+            )?;
+            paths.push(path);
+        }
+        let imports = imports.remove(&None).unwrap_or_default().concat();
+        fs::write(&paths[0], format!("{index}\n{declarations}{imports}{root}"))?;
+        Ok(paths)
+    }
+
+    /// Generates Rust code from an Avro schema [`Source`](Source) into `file_name` in the
+    /// `OUT_DIR` directory of a build script, and returns the path of the written file.
+    ///
+    /// An index file ([`OUT_DIR_INDEX`](OUT_DIR_INDEX)) including all the files generated
+    /// this way is also maintained, so that they can all be included in the crate with
+    /// [`include_generated!`](crate::include_generated!). When generating from schema
+    /// files, `cargo:rerun-if-changed` instructions are printed for them.
+    pub fn gen_out_dir(&self, source: &Source, file_name: &str) -> Result<PathBuf> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "OUT_DIR is not set, gen_out_dir must be called from a build script",
+            )
+        })?;
+        self.gen_into_dir(source, Path::new(&out_dir), file_name)
+    }
+
+    fn gen_into_dir(&self, source: &Source, dir: &Path, file_name: &str) -> Result<PathBuf> {
+        let path = dir.join(file_name);
+        let mut output = std::io::BufWriter::new(fs::File::create(&path)?);
+        let report = self.gen_with_report(source, &mut output)?;
+        output.flush()?;
+
+        // Rebuild when schema files are modified, added or removed
+        let mut watched = report
+            .items
+            .iter()
+            .filter_map(|item| item.source.as_deref())
+            .flat_map(|source| [Some(source), source.parent()])
+            .flatten()
+            .collect::<Vec<_>>();
+        watched.sort();
+        watched.dedup();
+        for path in watched {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        let index_path = dir.join(OUT_DIR_INDEX);
+        let include = format!(
+            "include!(concat!(env!(\"OUT_DIR\"), \"/\", {:?}));",
+            file_name
+        );
+        let mut index = fs::read_to_string(&index_path).unwrap_or_default();
+        if !index.lines().any(|line| line == include) {
+            index.push_str(&include);
+            index.push('\n');
+            fs::write(index_path, index)?;
+        }
+
+        Ok(path)
+    }
 
-                self.gen_in_order(&mut deps, output)?;
+    /// Asserts that the items generated from an Avro schema [`Source`](Source) match the
+    /// golden files of `dir`, one `{name}.rs` file per item.
+    ///
+    /// On mismatch, panics with a diff of each changed item, along with the items without
+    /// snapshot and the snapshots of items which are no longer generated. Setting the
+    /// `RSGEN_AVRO_UPDATE_SNAPSHOTS` environment variable to `1` writes the snapshots
+    /// instead, e.g. to create them or after an intended change.
+    ///
+    /// Requires the `snapshots` feature.
+    ///
+    /// ```no_run
+    /// # use rsgen_avro::{Generator, Source};
+    /// #[test]
+    /// fn codegen() {
+    ///     Generator::new()
+    ///         .unwrap()
+    ///         .assert_matches_snapshot(&Source::GlobPattern("schemas/*.avsc"), "tests/snapshots");
+    /// }
+    /// ```
+    #[cfg(feature = "snapshots")]
+    #[track_caller]
+    pub fn assert_matches_snapshot(&self, source: &Source, dir: impl AsRef<Path>) {
+        let items = self
+            .items(source)
+            .and_then(|items| {
+                items
+                    .map(|item| item.map(|item| (item.name, item.code)))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .unwrap_or_else(|e| panic!("Generation failed: {e}"));
+        let update = std::env::var(crate::snapshot::UPDATE_SNAPSHOTS_ENV).is_ok_and(|v| v == "1");
+        match crate::snapshot::compare(&items, dir.as_ref(), update) {
+            Ok(None) => (),
+            Ok(Some(report)) => panic!("{report}"),
+            Err(e) => panic!("Snapshot comparison failed: {e}"),
+        }
+    }
+
+    /// Writes an example JSON document for each record of an Avro schema [`Source`](Source)
+    /// into `dir`, and returns the paths of the written files.
+    ///
+    /// Files are named after the fullname of their record, e.g. `com.acme.User.json`. Their
+    /// documents use schema default values, or simple synthesized values otherwise, and can
+    /// be used as fixtures for contract tests or as documentation.
+    pub fn gen_examples(&self, source: &Source, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let items = self.items(source)?;
+        let mut paths = vec![];
+        for schema in items.deps.iter().rev() {
+            if let Schema::Record(RecordSchema { name, .. }) = schema {
+                let example = crate::examples::example(schema, &items.gen_state);
+                let json = serde_json::to_string_pretty(&example).map_err(std::io::Error::from)?;
+                let path = dir.join(format!("{}.json", name.fullname(None)));
+                fs::write(&path, json + "\n")?;
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Writes a [criterion](https://docs.rs/criterion) benchmark harness measuring the
+    /// Avro encoding and decoding throughput of each record of an Avro schema
+    /// [`Source`](Source).
+    ///
+    /// The harness is meant to be written into the `benches/` directory of the crate where
+    /// the types are generated, and imports them from `types_path` (e.g. `my_crate::avro`).
+    /// Records are benchmarked with their [example](Generator::gen_examples) values. The
+    /// crate then needs `criterion` and `serde_json` dev-dependencies, and a `[[bench]]`
+    /// target with `harness = false`.
+    pub fn gen_bench(
+        &self,
+        source: &Source,
+        types_path: &str,
+        output: &mut impl Write,
+    ) -> Result<()> {
+        self.requires_serde("benchmarks")?;
+        let items = self.items(source)?;
+        let schemas = items.deps.iter().rev().collect::<Vec<_>>();
+        let code =
+            crate::bench::bench_harness(&schemas, &items.gen_state, types_path, &self.templater);
+        output.write_all(code.as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes a registry module of the records of an Avro schema [`Source`](Source), looking
+    /// up their schema and decoding their Avro datums by fullname at runtime.
+    ///
+    /// The module is meant to be written next to the generated types, which it imports from
+    /// `types_path` (e.g. `crate::avro`). It contains a `Record` enum with a variant per
+    /// record, the `schemas` and `schema` functions returning the parsed schemas by fullname,
+    /// and `decode(fullname, bytes)` decoding an Avro datum into a `Record`.
+    pub fn gen_registry(
+        &self,
+        source: &Source,
+        types_path: &str,
+        output: &mut impl Write,
+    ) -> Result<()> {
+        self.gen_registry_with_ids(source, types_path, &[], output)
+    }
+
+    /// Writes a registry module like [`gen_registry`](Generator::gen_registry), which also
+    /// dispatches the decoding of Kafka payloads by the schema registry id they start with.
+    ///
+    /// `schema_ids` maps the schema registry ids of the versions of the schemas generated
+    /// from to the fullnames of their records. The module then also contains
+    /// `fullname(id)`, and `decode_payload(payload)` reading the schema id of a payload in
+    /// the Confluent wire format and decoding it into a `Record`.
+    pub fn gen_registry_with_ids(
+        &self,
+        source: &Source,
+        types_path: &str,
+        schema_ids: &[(u32, &str)],
+        output: &mut impl Write,
+    ) -> Result<()> {
+        self.requires_serde("a registry")?;
+        let items = self.items(source)?;
+        let schemas = items.deps.iter().rev().collect::<Vec<_>>();
+        let code = crate::registry::registry_module(
+            &schemas,
+            &items.gen_state,
+            types_path,
+            schema_ids,
+            &self.templater,
+        )?;
+        output.write_all(code.as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes a module encoding and decoding the generated types in the Avro JSON encoding,
+    /// as written by the `JsonEncoder` of the Java implementation.
+    ///
+    /// Unlike serde_json, the encoding wraps union values other than `null` in an object
+    /// keyed by the name of their type, and writes `bytes` and `fixed` values as strings of
+    /// the code points of their bytes. The module contains the `to_json`, `to_string`,
+    /// `from_json` and `from_str` functions, taking the schema of the encoded type, and
+    /// requires the `serde_json` crate.
+    pub fn gen_avro_json(&self, output: &mut impl Write) -> Result<()> {
+        self.requires_serde("an Avro JSON module")?;
+        let code = crate::avro_json::avro_json_module(&self.templater.avro_crate);
+        output.write_all(code.as_bytes())?;
+        Ok(())
+    }
+
+    /// Generates Rust code from successive versions of Avro schemas, e.g. fetched from a
+    /// schema registry, with the items of each version in its own module (`v1`, `v2`, ...).
+    ///
+    /// Each module but the first also contains `From` impls converting the records and enums
+    /// of the previous version into the ones with the same fullname (or alias), following
+    /// the Avro schema resolution rules: fields are matched by name or alias, missing fields
+    /// take their default value, numbers are promoted, and unknown enum symbols take the
+    /// default symbol. Types which cannot be converted this way are reported as errors.
+    ///
+    /// Bytes read as strings are converted lossily, as documented on the impls doing so:
+    /// invalid UTF-8 sequences are replaced with `U+FFFD`.
+    pub fn gen_versions(&self, versions: &[Source], output: &mut impl Write) -> Result<()> {
+        let mut previous: Option<(String, Vec<Schema>, GenState)> = None;
+        output.write_all(self.module_start().as_bytes())?;
+        for (i, source) in versions.iter().enumerate() {
+            let mut items = self.items(source)?;
+            let schemas = items.deps.clone();
+            let mut code = String::new();
+            for item in items.by_ref() {
+                code.push_str(&item?.code);
             }
 
+            let module = format!("v{}", i + 1);
+            if let Some((old_module, old_schemas, old_gen_state)) = &previous {
+                code.push_str(&crate::versions::conversions(
+                    &self.templater,
+                    &Version {
+                        module: old_module,
+                        schemas: old_schemas,
+                        gen_state: old_gen_state,
+                    },
+                    &Version {
+                        module: &module,
+                        schemas: &schemas,
+                        gen_state: &items.gen_state,
+                    },
+                )?);
+            }
+            writeln!(output, "pub mod {module} {{\n{code}}}")?;
+            previous = Some((module, schemas, items.gen_state));
+        }
+        output.write_all(self.module_end().as_bytes())?;
+        Ok(())
+    }
+
+    /// Returns an iterator over the Rust items generated from an Avro schema
+    /// [`Source`](Source).
+    ///
+    /// Items are rendered lazily, one at a time, in the order they would be written by
+    /// [`gen`](Generator::gen). This allows writing them incrementally without holding
+    /// the whole generated code in memory.
+    ///
+    /// With the `parallel` feature, items are instead rendered on the available threads in
+    /// batches of a few items per thread, a batch being rendered when the items of the
+    /// previous one have all been returned, except by
+    /// [`gen_incremental`](Generator::gen_incremental).
+    pub fn items(&self, source: &Source) -> Result<GeneratedItems<'_>> {
+        self.source_items(source, false)
+    }
+
+    /// Returns the items generated from an Avro schema [`Source`](Source), the types of
+    /// different namespaces only conflicting if in the same module when `namespaced`, see
+    /// [`check_type_names`].
+    fn source_items(&self, source: &Source, namespaced: bool) -> Result<GeneratedItems<'_>> {
+        let parsed;
+        let schemas = match source {
+            Source::Schema(schema) => std::slice::from_ref(*schema),
+
+            Source::Schemas(schemas) => *schemas,
+
             Source::SchemaStr(raw_schema) => {
-                let schema = Schema::parse_str(raw_schema)?;
-                let mut deps = deps_stack(&schema, vec![]);
-                self.gen_in_order(&mut deps, output)?;
+                parsed = Schema::parse_str(raw_schema)?;
+                std::slice::from_ref(&parsed)
             }
 
             Source::GlobPattern(pattern) => {
+                let mut paths = vec![];
                 let mut raw_schemas = vec![];
+                let mut messages = vec![];
                 for entry in glob::glob(pattern)? {
                     let path = entry.map_err(|e| e.into_error())?;
-                    if !path.is_dir() {
-                        raw_schemas.push(fs::read_to_string(path)?);
+                    if path.is_dir() {
+                        continue;
+                    }
+                    if crate::archive::is_archive(&path) {
+                        for (entry, raw) in crate::archive::archive_documents(&path)? {
+                            let path = path.join(entry);
+                            if path.extension().is_some_and(|ext| ext == "avpr") {
+                                let protocol = parse_protocol(&raw, &path)?;
+                                paths.extend(protocol.types.iter().map(|_| path.clone()));
+                                raw_schemas.extend(protocol.types);
+                                messages.extend(protocol.messages);
+                            } else {
+                                raw_schemas.push(raw);
+                                paths.push(path);
+                            }
+                        }
+                        continue;
+                    } else if path.extension().is_some_and(|ext| ext == "avpr") {
+                        let protocol = parse_protocol(&fs::read_to_string(&path)?, &path)?;
+                        paths.extend(protocol.types.iter().map(|_| path.clone()));
+                        raw_schemas.extend(protocol.types);
+                        messages.extend(protocol.messages);
+                        continue;
+                    } else if path.extension().is_some_and(|ext| ext == "avro") {
+                        raw_schemas.push(container_schema(&path)?);
+                    } else {
+                        raw_schemas.push(fs::read_to_string(&path)?);
                     }
+                    paths.push(path);
                 }
+                let mut items =
+                    self.documents(raw_schemas.iter().map(String::as_str), paths, namespaced)?;
+                items.put_message_errors(&messages)?;
+                return Ok(items);
+            }
+
+            Source::Documents(documents) => {
+                let paths = documents.iter().map(|(name, _)| PathBuf::from(name));
+                return self.documents(
+                    documents.iter().map(|(_, raw)| *raw),
+                    paths.collect(),
+                    namespaced,
+                );
+            }
+        };
+        self.parsed_items(schemas, &[], namespaced)
+    }
+
+    /// Returns the items generated from parsed schemas, the invalid default values of the ones
+    /// parsed from `documents` (their JSON and path) being located in them.
+    fn parsed_items(
+        &self,
+        schemas: &[Schema],
+        documents: &[Option<(&str, &Path)>],
+        namespaced: bool,
+    ) -> Result<GeneratedItems<'_>> {
+        let mut resolving = std::time::Duration::ZERO;
+        let mut items = timed(&mut resolving, || {
+            let mut definitions = HashMap::new();
+            for schema in schemas {
+                check_definitions(schema, &mut definitions)?;
+            }
+            let deps = deps_stack(schemas);
+            check_type_names(&deps, self.templater.name_mapper.as_ref(), namespaced)?;
+            let items = GeneratedItems::new(self, deps)?;
 
-                let schemas = &raw_schemas.iter().map(|s| s.as_str()).collect::<Vec<_>>();
-                let schemas = Schema::parse_list(schemas)?;
-                self.gen(&Source::Schemas(&schemas), output)?;
+            for (i, schema) in schemas.iter().enumerate() {
+                crate::defaults::validate_defaults(schema, &items.gen_state).map_err(|e| {
+                    match documents.get(i) {
+                        Some(Some((raw_schema, path))) => crate::span::locate(e, raw_schema, path),
+                        _ => e,
+                    }
+                })?;
+            }
+            Ok::<_, Error>(items)
+        })?;
+        items.resolving = resolving;
+        Ok(items)
+    }
+
+    /// Returns the items generated from schema strings which can reference each other, the
+    /// ones of each schema being attributed to its path.
+    fn documents<'s>(
+        &self,
+        raw_schemas: impl Iterator<Item = &'s str>,
+        paths: Vec<PathBuf>,
+        namespaced: bool,
+    ) -> Result<GeneratedItems<'_>> {
+        let raw_schemas: Vec<_> = raw_schemas.collect();
+        // Errors are not located in .avro files, whose schema is embedded in binary data, nor
+        // in .avpr files, whose types are parsed from their protocol
+        let documents: Vec<_> = raw_schemas
+            .iter()
+            .zip(&paths)
+            .map(|(raw_schema, path)| {
+                let embedded = path
+                    .extension()
+                    .is_some_and(|ext| ext == "avro" || ext == "avpr");
+                (!embedded).then_some((*raw_schema, path.as_path()))
+            })
+            .collect();
+        let schemas = Schema::parse_list(&raw_schemas).map_err(|e| {
+            documents
+                .iter()
+                .flatten()
+                .find_map(|(raw_schema, path)| crate::span::json_error(raw_schema, path))
+                .unwrap_or_else(|| e.into())
+        })?;
+
+        // Sub-schemas are attributed to the first file they are found in
+        let mut sources = HashMap::new();
+        for (schema, path) in schemas.iter().zip(&paths) {
+            for dep in deps_stack([schema]) {
+                sources
+                    .entry(schema_key(&dep))
+                    .or_insert_with(|| path.clone());
             }
         }
 
-        Ok(())
+        let mut items = self.parsed_items(&schemas, &documents, namespaced)?;
+        items.sources = sources;
+        Ok(items)
+    }
+
+    /// Fails if the serde impls, required by the generated `what`, are disabled.
+    fn requires_serde(&self, what: &str) -> Result<()> {
+        if self.templater.serde_impls {
+            Ok(())
+        } else {
+            Err(Error::Config(format!(
+                "Generating {what} requires the serde impls"
+            )))
+        }
+    }
+}
+
+/// The kind of a [`GeneratedItem`](GeneratedItem).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    /// A struct generated from an Avro record.
+    Record,
+    /// An enum generated from an Avro enum.
+    Enum,
+    /// A type alias generated from an Avro fixed.
+    Fixed,
+    /// An enum generated from an anonymous Avro union.
+    Union,
+    /// The struct of the Avro decimals, see
+    /// [`use_decimal_structs`](GeneratorBuilder::use_decimal_structs).
+    Decimal,
+    /// The `AvroMessage` trait of the record structs and enums, see
+    /// [`avro_message_impls`](GeneratorBuilder::avro_message_impls).
+    Trait,
+    /// The `ValidationErrors` of the `validate` methods of the record structs, see
+    /// [`validators`](GeneratorBuilder::validators).
+    Validation,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ItemKind::Record => "record",
+            ItemKind::Enum => "enum",
+            ItemKind::Fixed => "fixed",
+            ItemKind::Union => "union",
+            ItemKind::Decimal => "decimal",
+            ItemKind::Trait => "trait",
+            ItemKind::Validation => "validation",
+        })
+    }
+}
+
+impl FromStr for ItemKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ItemKind> {
+        match s {
+            "record" => Ok(ItemKind::Record),
+            "enum" => Ok(ItemKind::Enum),
+            "fixed" => Ok(ItemKind::Fixed),
+            "union" => Ok(ItemKind::Union),
+            "decimal" => Ok(ItemKind::Decimal),
+            "trait" => Ok(ItemKind::Trait),
+            "validation" => Ok(ItemKind::Validation),
+            _ => Err(Error::Config(format!(
+                "Unknown item kind `{s}`, expected `record`, `enum`, `fixed`, `union`, `decimal`, \
+                 `trait` or `validation`"
+            ))),
+        }
+    }
+}
+
+/// A single Rust item generated from an Avro schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedItem {
+    /// The kind of schema this item was generated from.
+    pub kind: ItemKind,
+    /// The name of the generated Rust type.
+    pub name: String,
+    /// The Avro fullname of the schema, if it is a named schema.
+    pub fullname: Option<String>,
+    /// The generated Rust code.
+    pub code: String,
+    /// The file of the source schema, if it was read from a file.
+    pub source: Option<PathBuf>,
+    /// The Rabin fingerprint of the schema [canonical form](crate::canonical_form), in hexadecimal.
+    pub fingerprint: String,
+    /// Lossy generation choices made for this item, e.g. coerced default values.
+    pub warnings: Vec<String>,
+    /// The custom attributes of the schema, e.g. organization-specific annotations, empty
+    /// for unions.
+    pub attributes: BTreeMap<String, serde_json::Value>,
+}
+
+/// The number of sub-schemas rendered per thread in a batch with the `parallel` feature,
+/// bounding the rendered items held before being returned.
+#[cfg(feature = "parallel")]
+const PARALLEL_BATCH: usize = 16;
+
+/// An iterator over the Rust items generated from an Avro schema [`Source`](Source).
+///
+/// Created by [`Generator::items`](Generator::items).
+pub struct GeneratedItems<'a> {
+    generator: &'a Generator,
+    deps: Vec<Schema>,
+    gen_state: GenState,
+    sources: HashMap<String, PathBuf>,
+    cache: Option<RenderCache>,
+    total: usize,
+    /// The time spent resolving the dependencies of the schemas and validating them.
+    resolving: std::time::Duration,
+    decimal_item: Option<GeneratedItem>,
+    message_item: Option<GeneratedItem>,
+    validation_item: Option<GeneratedItem>,
+    /// The error enums of the protocol messages, written after the types they reference.
+    message_errors: VecDeque<GeneratedItem>,
+    #[cfg(feature = "parallel")]
+    rendered: VecDeque<Result<Option<GeneratedItem>>>,
+}
+
+impl<'a> GeneratedItems<'a> {
+    fn new(generator: &'a Generator, deps: Vec<Schema>) -> Result<GeneratedItems<'a>> {
+        let mut gen_state = GenState::new(&deps)?
+            .with_chrono_dates(generator.templater.use_chrono_dates)
+            .with_decimal_structs(generator.templater.use_decimal_structs)
+            .with_avro_crate(&generator.templater.avro_crate)
+            .with_name_mapper(generator.templater.name_mapper.clone())
+            .with_type_mapper(generator.templater.type_mapper.clone());
+        if let Some(template) = &generator.templater.union_names {
+            gen_state.put_union_names(&deps, template)?;
+        }
+        gen_state.put_nested_types(&deps)?;
+        let decimal_item = if generator.templater.use_decimal_structs {
+            decimal_struct_schema(&deps, &gen_state)?
+                .map(|schema| {
+                    let code = decimal_struct(&generator.templater);
+                    support_item(generator, ItemKind::Decimal, DECIMAL_STRUCT, code, schema)
+                })
+                .transpose()?
+        } else {
+            None
+        };
+        let message_item = if generator.templater.avro_message_impls {
+            deps.iter()
+                .find(|s| matches!(s, Schema::Record(_) | Schema::Enum(_)))
+                .map(|schema| {
+                    let code = message_trait(&generator.templater);
+                    support_item(generator, ItemKind::Trait, MESSAGE_TRAIT, code, schema)
+                })
+                .transpose()?
+        } else {
+            None
+        };
+        let validation_item = if generator.templater.validators {
+            deps.iter()
+                .find(|s| matches!(s, Schema::Record(_)))
+                .map(|schema| {
+                    let code = validation_errors(&generator.templater);
+                    support_item(
+                        generator,
+                        ItemKind::Validation,
+                        VALIDATION_ERRORS,
+                        code,
+                        schema,
+                    )
+                })
+                .transpose()?
+        } else if generator.templater.validate_derive == Some(ValidateDerive::Validator) {
+            deps.iter()
+                .find(|s| matches!(s, Schema::Record(_)))
+                .map(|schema| {
+                    let code = validator_trait();
+                    support_item(
+                        generator,
+                        ItemKind::Validation,
+                        VALIDATOR_TRAIT,
+                        code,
+                        schema,
+                    )
+                })
+                .transpose()?
+        } else {
+            None
+        };
+        if let Some(progress) = &generator.progress {
+            progress.on_generation_start(deps.len());
+        }
+        Ok(GeneratedItems {
+            generator,
+            total: deps.len(),
+            resolving: std::time::Duration::ZERO,
+            decimal_item,
+            message_item,
+            validation_item,
+            message_errors: VecDeque::new(),
+            deps,
+            gen_state,
+            sources: HashMap::new(),
+            cache: None,
+            #[cfg(feature = "parallel")]
+            rendered: VecDeque::new(),
+        })
     }
 
-    /// Given an Avro `schema`:
-    /// * Find its ordered, nested dependencies with `deps_stack(schema)`
+    /// Given the ordered dependencies of the source schemas:
     /// * Pops sub-schemas and generate appropriate Rust types
     /// * Keeps tracks of nested schema->name with `GenState` mapping
-    /// * Appends generated Rust types to the output
-    fn gen_in_order(&self, deps: &mut Vec<Schema>, output: &mut impl Write) -> Result<()> {
-        let mut gs = GenState::new(deps)?.with_chrono_dates(self.templater.use_chrono_dates);
-
-        while let Some(s) = deps.pop() {
-            match s {
-                // Simply generate code
-                Schema::Fixed { .. } => {
-                    let code = &self.templater.str_fixed(&s)?;
-                    output.write_all(code.as_bytes())?
-                }
-                Schema::Enum { .. } => {
-                    let code = &self.templater.str_enum(&s)?;
-                    output.write_all(code.as_bytes())?
-                }
+    /// * Returns the next generated Rust item, if any
+    fn next_item(&mut self) -> Result<Option<GeneratedItem>> {
+        // Written first, as the items reference it
+        if let Some(item) = self
+            .decimal_item
+            .take()
+            .or_else(|| self.message_item.take())
+            .or_else(|| self.validation_item.take())
+        {
+            return Ok(Some(item));
+        }
 
-                // Generate code with potentially nested types
-                Schema::Record { .. } => {
-                    let code = &self.templater.str_record(&s, &gs)?;
-                    output.write_all(code.as_bytes())?
-                }
+        // Render caches are only used sequentially
+        #[cfg(feature = "parallel")]
+        if self.cache.is_none() {
+            return match self.next_rendered()? {
+                Some(item) => Ok(Some(item)),
+                None => Ok(self.message_errors.pop_front()),
+            };
+        }
 
-                // Register inner type for it to be used as a nested type later
-                Schema::Array(ArraySchema {
-                    items: ref inner, ..
-                }) => {
-                    let type_str = array_type(inner, &gs)?;
-                    gs.put_type(&s, type_str)
-                }
-                Schema::Map(MapSchema {
-                    types: ref inner, ..
-                }) => {
-                    let type_str = map_type(inner, &gs)?;
-                    gs.put_type(&s, type_str)
+        while let Some(s) = self.deps.pop() {
+            let index = self.total - self.deps.len() - 1;
+            let mut cache = self.cache.take();
+            let item = self.process(&s, index, &mut cache);
+            self.cache = cache;
+
+            if let Some(item) = item? {
+                return Ok(Some(item));
+            }
+        }
+
+        Ok(self.message_errors.pop_front())
+    }
+
+    /// Puts the error enums of protocol messages, written after the generated types.
+    fn put_message_errors(&mut self, messages: &[crate::protocol::Message]) -> Result<()> {
+        let templater = &self.generator.templater;
+        let enums =
+            crate::protocol::message_errors(messages, &self.deps, templater, &self.gen_state)?;
+        for (name, code, schema) in enums {
+            let item = support_item(self.generator, ItemKind::Enum, &name, code, &schema)?;
+            self.message_errors.push_back(item);
+        }
+        Ok(())
+    }
+
+    /// Returns the next item rendered in parallel, rendering the next batch of items first
+    /// if needed.
+    #[cfg(feature = "parallel")]
+    fn next_rendered(&mut self) -> Result<Option<GeneratedItem>> {
+        loop {
+            while let Some(item) = self.rendered.pop_front() {
+                if let Some(item) = item? {
+                    return Ok(Some(item));
                 }
+            }
+            if self.deps.is_empty() {
+                return Ok(None);
+            }
+            let rendered = self.render_parallel();
+            self.rendered.extend(rendered);
+        }
+    }
 
-                Schema::Union(ref union) => {
-                    // Generate custom enum with potentially nested types
-                    if (union.is_nullable() && union.variants().len() > 2)
-                        || (!union.is_nullable() && !union.variants().is_empty())
-                    {
-                        let code = &self.templater.str_union_enum(&s, &gs)?;
-                        output.write_all(code.as_bytes())?
-                    }
+    /// Processes the next [`PARALLEL_BATCH`] sub-schemas per available thread, each thread
+    /// picking the next unprocessed one, and returns the results in dependency order.
+    #[cfg(feature = "parallel")]
+    fn render_parallel(&mut self) -> Vec<Result<Option<GeneratedItem>>> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let batch = self.deps.len().min(threads * PARALLEL_BATCH);
+        let offset = self.total - self.deps.len();
+        let schemas = self
+            .deps
+            .drain(self.deps.len() - batch..)
+            .rev()
+            .collect::<Vec<_>>();
+        let threads = threads.min(schemas.len());
+        let next = AtomicUsize::new(0);
+
+        let this = &*self;
+        let mut results = std::thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = vec![];
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(s) = schemas.get(i) else { break };
+                            results.push((i, this.process(s, offset + i, &mut None)));
+                        }
+                        results
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect::<Vec<_>>()
+        });
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Processes the sub-schema at `index`, reporting progress and verifying its item.
+    fn process(
+        &self,
+        s: &Schema,
+        index: usize,
+        cache: &mut Option<RenderCache>,
+    ) -> Result<Option<GeneratedItem>> {
+        if let Some(token) = &self.generator.cancellation_token {
+            if token.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+        }
+
+        if let Some(progress) = &self.generator.progress {
+            progress.on_schema_start(s, index, self.total);
+        }
+        let item = self.gen_item(s, cache)?;
+        if let Some(item) = item.as_ref().filter(|_| self.generator.verify) {
+            crate::verify::verify(item)?;
+        }
+        if let Some(progress) = &self.generator.progress {
+            progress.on_schema_done(s, index, self.total);
+        }
+        Ok(item)
+    }
 
-                    // Register inner union for it to be used as a nested type later
-                    let type_str = union_type(union, &gs, true)?;
-                    gs.put_type(&s, type_str)
+    /// Generates the Rust item of a sub-schema, if it is not only registered as a nested type.
+    fn gen_item(
+        &self,
+        s: &Schema,
+        cache: &mut Option<RenderCache>,
+    ) -> Result<Option<GeneratedItem>> {
+        let templater = &self.generator.templater;
+        let gs = &self.gen_state;
+
+        let (kind, code) = match s {
+            // Simply generate code
+            Schema::Fixed { .. } => (
+                ItemKind::Fixed,
+                render_cached(cache, s, gs, || {
+                    Ok(templater.str_fixed(s, gs)? + &templater.str_alias_types(s, gs)?)
+                })?,
+            ),
+            Schema::Enum { .. } => (
+                ItemKind::Enum,
+                render_cached(cache, s, gs, || {
+                    Ok(templater.str_enum(s, gs)?
+                        + &templater.str_alias_types(s, gs)?
+                        + &templater.str_avro_schema_impl(s, gs)?
+                        + &templater.str_avro_message_impl(s, gs)?
+                        + &templater.str_proptest_arbitrary(s, gs)?
+                        + &templater.str_quickcheck_arbitrary(s, gs)?)
+                })?,
+            ),
+
+            // Generate code with potentially nested types
+            Schema::Record { .. } => (
+                ItemKind::Record,
+                render_cached(cache, s, gs, || {
+                    Ok(templater.str_record(s, gs)?
+                        + &templater.str_alias_types(s, gs)?
+                        + &templater.str_writer_schema_decoder(s, gs)?
+                        + &templater.str_try_from_bytes(s, gs)?
+                        + &templater.str_avro_schema_impl(s, gs)?
+                        + &templater.str_avro_message_impl(s, gs)?
+                        + &templater.str_schema_constants(s, gs)?
+                        + &templater.str_validate_impl(s, gs)?
+                        + &templater.str_round_trip_tests(s, gs)?
+                        + &templater.str_proptest_arbitrary(s, gs)?
+                        + &templater.str_quickcheck_arbitrary(s, gs)?)
+                })?,
+            ),
+
+            // Nested types, already registered in the state
+            Schema::Array(..) | Schema::Map(..) => return Ok(None),
+
+            Schema::Union(union) => {
+                // Generate custom enum with potentially nested types
+                let code = if (union.is_nullable() && union.variants().len() > 2)
+                    || (!union.is_nullable() && !union.variants().is_empty())
+                {
+                    Some(render_cached(cache, s, gs, || {
+                        Ok(templater.str_union_enum(s, gs)?
+                            + &templater.str_proptest_arbitrary(s, gs)?
+                            + &templater.str_quickcheck_arbitrary(s, gs)?)
+                    })?)
+                } else {
+                    None
+                };
+
+                match code {
+                    Some(code) => (ItemKind::Union, code),
+                    None => return Ok(None),
                 }
+            }
+
+            _ => return Err(Error::Schema(format!("Not a valid root schema: {:?}", s))),
+        };
+
+        Ok(Some(GeneratedItem {
+            kind,
+            name: templater.type_name(s, gs)?,
+            fullname: s.name().map(|n| n.fullname(None)),
+            code,
+            source: self.sources.get(&schema_key(s)).cloned(),
+            fingerprint: hex_fingerprint(s),
+            warnings: templater.warnings(s, gs),
+            attributes: s.custom_attributes().cloned().unwrap_or_default(),
+        }))
+    }
+}
+
+/// Parses the protocol of an `.avpr` file, its JSON errors being located in it.
+fn parse_protocol(raw_protocol: &str, path: &Path) -> Result<crate::protocol::Protocol> {
+    crate::protocol::parse_protocol(raw_protocol)
+        .map_err(|e| crate::span::json_error(raw_protocol, path).unwrap_or(e))
+}
+
+/// Returns a support item of the generated types, e.g. the struct of the decimal logical types
+/// or the error enum of a protocol message, fingerprinted like `schema`, the first schema of
+/// the types using it.
+fn support_item(
+    generator: &Generator,
+    kind: ItemKind,
+    name: &str,
+    code: String,
+    schema: &Schema,
+) -> Result<GeneratedItem> {
+    let item = GeneratedItem {
+        kind,
+        name: name.to_string(),
+        fullname: None,
+        code,
+        source: None,
+        fingerprint: hex_fingerprint(schema),
+        warnings: vec![],
+        attributes: BTreeMap::new(),
+    };
+    if generator.verify {
+        crate::verify::verify(&item)?;
+    }
+    Ok(item)
+}
+
+/// Returns the Rabin fingerprint of a schema, as the hex of its little-endian bytes.
+fn hex_fingerprint(schema: &Schema) -> String {
+    rabin_fingerprint(schema)
+        .to_le_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Renders the item of a schema with `render`, unless it is found in the cache.
+fn render_cached(
+    cache: &mut Option<RenderCache>,
+    schema: &Schema,
+    gen_state: &GenState,
+    render: impl FnOnce() -> Result<String>,
+) -> Result<String> {
+    match cache {
+        Some(cache) => cache.get_or_render(item_key(schema, gen_state), render),
+        None => render(),
+    }
+}
 
-                _ => return Err(Error::Schema(format!("Not a valid root schema: {:?}", s))),
+impl Iterator for GeneratedItems<'_> {
+    type Item = Result<GeneratedItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_item() {
+            Ok(item) => item.map(Ok),
+            Err(e) => {
+                // Stop iterating once an error has been returned
+                self.deps.clear();
+                #[cfg(feature = "parallel")]
+                self.rendered.clear();
+                Some(Err(e))
             }
         }
+    }
+}
 
-        Ok(())
+/// Returns the raw schema found in the header of an Avro object container file, without
+/// reading its data blocks.
+fn container_schema(path: &Path) -> Result<String> {
+    let mut reader = std::io::BufReader::new(fs::File::open(path)?);
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != *b"Obj\x01" {
+        return Err(Error::Schema(format!(
+            "{} is not an Avro object container file",
+            path.display()
+        )));
+    }
+
+    let metadata = apache_avro::from_avro_datum(&Schema::map(Schema::Bytes), &mut reader, None)?;
+    let schema = match metadata {
+        apache_avro::types::Value::Map(mut metadata) => metadata.remove("avro.schema"),
+        _ => None,
+    };
+    match schema {
+        Some(apache_avro::types::Value::Bytes(schema)) => String::from_utf8(schema)
+            .map_err(|e| Error::Schema(format!("Invalid schema in {}: {e}", path.display()))),
+        _ => Err(Error::Schema(format!(
+            "No schema found in the header of {}",
+            path.display()
+        ))),
+    }
+}
+
+/// Checks that named schemas sharing a fullname, e.g. defined in several files, have the same
+/// definition, as a single Rust type is generated for each fullname.
+///
+/// Definitions are compared by their fields, symbols or size, ignoring documentation and
+/// custom attributes.
+fn check_definitions(schema: &Schema, definitions: &mut HashMap<String, String>) -> Result<()> {
+    let definition = match schema {
+        Schema::Record(RecordSchema { fields, .. }) => {
+            for field in fields {
+                check_definitions(&field.schema, definitions)?;
+            }
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by_key(|f| f.position);
+            let fields = fields
+                .iter()
+                .map(|f| {
+                    let default = f.default.as_ref().map(|d| d.to_string());
+                    format!("{}: {} = {default:?}", f.name, schema_key(&f.schema))
+                })
+                .collect::<Vec<_>>();
+            format!("record {fields:?}")
+        }
+        Schema::Enum(EnumSchema {
+            symbols, default, ..
+        }) => format!("enum {symbols:?} = {default:?}"),
+        Schema::Fixed(FixedSchema { size, .. }) => format!("fixed {size}"),
+        Schema::Decimal(DecimalSchema { inner, .. }) => {
+            return check_definitions(inner, definitions)
+        }
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => {
+            return check_definitions(inner, definitions)
+        }
+        Schema::Union(union) => {
+            for variant in union.variants() {
+                check_definitions(variant, definitions)?;
+            }
+            return Ok(());
+        }
+        _ => return Ok(()),
+    };
+
+    let fullname = schema_key(schema);
+    match definitions.get(&fullname) {
+        Some(other) if *other != definition => Err(Error::InvalidSchema {
+            kind: ErrorKind::Conflict,
+            message: format!("Conflicting definitions of the named schema '{fullname}'"),
+        }),
+        Some(_) => Ok(()),
+        None => {
+            definitions.insert(fullname, definition);
+            Ok(())
+        }
+    }
+}
+
+/// Checks that named schemas with different fullnames, e.g. `com.acme.Address` and
+/// `com.other.Address`, are not generated as Rust types of the same name in the same module.
+/// When `namespaced`, the types of each namespace are written to their own module, see
+/// [`gen_namespaces`](Generator::gen_namespaces).
+///
+/// Names are compared once resolved, relative references inheriting the namespace of the
+/// enclosing schema, so that only distinct schemas conflict.
+fn check_type_names(deps: &[Schema], name_mapper: &dyn NameMapper, namespaced: bool) -> Result<()> {
+    // Namespace of the module and Rust type name -> fullname
+    let mut types = HashMap::new();
+    for schema in deps {
+        let (Schema::Record(RecordSchema { name, .. })
+        | Schema::Enum(EnumSchema { name, .. })
+        | Schema::Fixed(FixedSchema { name, .. })) = schema
+        else {
+            continue;
+        };
+        let type_name = name_mapper.type_name(&name.name);
+        let fullname = name.fullname(None);
+        let namespace = name.namespace.as_ref().filter(|_| namespaced);
+        match types.get(&(namespace, type_name.clone())) {
+            Some(other) if *other != fullname => {
+                return Err(Error::InvalidSchema {
+                    kind: ErrorKind::Conflict,
+                    message: format!(
+                        "Conflicting Rust types of the named schemas '{other}' and '{fullname}', \
+                     both named '{type_name}'"
+                    ),
+                })
+            }
+            _ => {
+                types.insert((namespace, type_name), fullname);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the module of the types of an Avro namespace, e.g. `com_acme_users` for
+/// `com.acme.users`, see [`gen_namespaces`](Generator::gen_namespaces).
+fn namespace_module(namespace: &str) -> String {
+    sanitize(namespace.replace('.', "_").to_snake_case())
+}
+
+/// Returns the `use` declarations of the namespace modules (`None` for `mod.rs`), written by
+/// [`gen_namespaces`](Generator::gen_namespaces), for the types of other modules they
+/// reference whose name is taken by types of several modules, making it ambiguous among the
+/// re-exports of `mod.rs`. Union enums, written to `mod.rs`, reference their variants from
+/// it.
+///
+/// Returns `None` if no type name is ambiguous, and fails if a module references several types
+/// of the same name, or one named like a type of its own.
+fn namespace_imports(
+    deps: &[Schema],
+    name_mapper: &dyn NameMapper,
+) -> Result<Option<HashMap<Option<String>, Vec<String>>>> {
+    // Rust type name -> namespaces defining it
+    let mut namespaces = HashMap::<String, Vec<Option<&str>>>::new();
+    for schema in deps {
+        if let Some(name) = schema.name() {
+            let defining = namespaces
+                .entry(name_mapper.type_name(&name.name))
+                .or_default();
+            if !defining.contains(&name.namespace.as_deref()) {
+                defining.push(name.namespace.as_deref());
+            }
+        }
+    }
+    if namespaces.values().all(|defining| defining.len() < 2) {
+        return Ok(None);
+    }
+
+    // Namespace -> Rust type name -> imported fullname
+    let mut imported = BTreeMap::<Option<&str>, BTreeMap<String, String>>::new();
+    for schema in deps {
+        let Schema::Record(RecordSchema { name, fields, .. }) = schema else {
+            continue;
+        };
+        let mut names = vec![];
+        for field in fields {
+            referenced_names(&field.schema, true, &mut names);
+        }
+        for (referenced, by_record) in names {
+            let type_name = name_mapper.type_name(&referenced.name);
+            let defining = &namespaces[&type_name];
+            let module = if by_record {
+                name.namespace.as_deref()
+            } else {
+                None
+            };
+            if defining.len() < 2 || referenced.namespace.as_deref() == module {
+                continue;
+            }
+            let fullname = referenced.fullname(None);
+            let conflicting = if defining.contains(&module) {
+                Some(match module {
+                    Some(namespace) => format!("{namespace}.{}", referenced.name),
+                    None => referenced.name.clone(),
+                })
+            } else {
+                let imports = imported.entry(module).or_default();
+                let import = imports.entry(type_name.clone()).or_insert(fullname.clone());
+                (*import != fullname).then(|| import.clone())
+            };
+            if let Some(other) = conflicting {
+                return Err(Error::InvalidSchema {
+                    kind: ErrorKind::Conflict,
+                    message: format!(
+                        "Conflicting Rust types of the named schemas '{other}' and '{fullname}', \
+                     both named '{type_name}' and used in the same module"
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(Some(
+        imported
+            .into_iter()
+            .map(|(module, imports)| {
+                let prefix = if module.is_some() { "super::" } else { "" };
+                let imports = imports
+                    .into_iter()
+                    .map(|(type_name, fullname)| match fullname.rsplit_once('.') {
+                        Some((namespace, _)) => format!(
+                            "use {prefix}{}::{type_name};\n",
+                            namespace_module(namespace)
+                        ),
+                        None => format!("use {prefix}{type_name};\n"),
+                    })
+                    .map(|import| match module {
+                        // Shadowing the ambiguous re-exports
+                        None => format!("#[allow(hidden_glob_reexports)]\n{import}"),
+                        Some(_) => import,
+                    })
+                    .collect();
+                (module.map(str::to_string), imports)
+            })
+            .collect(),
+    ))
+}
+
+/// Pushes the names of the named schemas referenced by a field schema, with whether they are
+/// referenced by the type of its record, as opposed to by a union enum.
+fn referenced_names<'s>(schema: &'s Schema, by_record: bool, names: &mut Vec<(&'s Name, bool)>) {
+    match schema {
+        Schema::Ref { name }
+        | Schema::Record(RecordSchema { name, .. })
+        | Schema::Enum(EnumSchema { name, .. })
+        | Schema::Fixed(FixedSchema { name, .. }) => names.push((name, by_record)),
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => referenced_names(inner, by_record, names),
+        Schema::Union(union) => match union.variants() {
+            // Generated as an `Option` of the inner type
+            [Schema::Null, inner] => referenced_names(inner, by_record, names),
+            variants => {
+                for variant in variants {
+                    referenced_names(variant, false, names);
+                }
+            }
+        },
+        _ => {}
     }
 }
 
@@ -146,14 +1519,36 @@ impl Generator {
 /// Explores nested `schema`s in a breadth-first fashion, pushing them on a stack at the
 /// same time in order to have them ordered.  It is similar to traversing the `schema`
 /// tree in a post-order fashion.
-fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
-    fn push_unique(deps: &mut Vec<Schema>, s: Schema) {
-        if let Some(i) = deps.iter().position(|d| d == &s) {
-            deps.remove(i);
+///
+/// Schemas generating the same Rust type (see `schema_key`) are only kept once, at the
+/// position they were last pushed at.
+fn deps_stack<'s>(schemas: impl IntoIterator<Item = &'s Schema>) -> Vec<Schema> {
+    let mut deps = DepsStack::default();
+    for schema in schemas {
+        push_deps(schema, &mut deps);
+    }
+    deps.schemas.into_iter().flatten().collect()
+}
+
+/// The ordered dependencies of schemas, see [`deps_stack`](deps_stack).
+#[derive(Default)]
+struct DepsStack {
+    /// Schemas pushed again are replaced by `None` at their previous position
+    schemas: Vec<Option<Schema>>,
+    positions: HashMap<String, usize>,
+}
+
+impl DepsStack {
+    fn push_unique(&mut self, s: Schema) {
+        if let Some(i) = self.positions.insert(schema_key(&s), self.schemas.len()) {
+            self.schemas[i] = None;
         }
-        deps.push(s);
+        self.schemas.push(Some(s));
     }
+}
 
+/// Pushes the dependencies of `schema`, then `schema` itself if it generates a Rust type.
+fn push_deps(schema: &Schema, deps: &mut DepsStack) {
     let mut q = VecDeque::new();
 
     q.push_back(schema);
@@ -162,17 +1557,17 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
 
         match s {
             // No nested schemas, add them to the result stack
-            Schema::Enum { .. } => push_unique(&mut deps, s.clone()),
-            Schema::Fixed { .. } => push_unique(&mut deps, s.clone()),
+            Schema::Enum { .. } => deps.push_unique(s.clone()),
+            Schema::Fixed { .. } => deps.push_unique(s.clone()),
             Schema::Decimal(DecimalSchema { inner, .. })
                 if matches!(inner.as_ref(), Schema::Fixed { .. }) =>
             {
-                push_unique(&mut deps, s.clone())
+                deps.push_unique(s.clone())
             }
 
             // Explore the record fields for potentially nested schemas
             Schema::Record(RecordSchema { fields, .. }) => {
-                push_unique(&mut deps, s.clone());
+                deps.push_unique(s.clone());
 
                 let by_pos = fields
                     .iter()
@@ -182,8 +1577,8 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
                 while let Some(RecordField { schema: sr, .. }) = by_pos.get(&i) {
                     match sr {
                         // No nested schemas, add them to the result stack
-                        Schema::Fixed { .. } => push_unique(&mut deps, sr.clone()),
-                        Schema::Enum { .. } => push_unique(&mut deps, sr.clone()),
+                        Schema::Fixed { .. } => deps.push_unique(sr.clone()),
+                        Schema::Enum { .. } => deps.push_unique(sr.clone()),
 
                         // Push to the exploration queue for further checks
                         Schema::Record { .. } => q.push_back(sr),
@@ -198,7 +1593,7 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
                             | Schema::Array(..)
                             | Schema::Union(..) => {
                                 q.push_back(sc);
-                                push_unique(&mut deps, s.clone());
+                                deps.push_unique(s.clone());
                             }
                             _ => (),
                         },
@@ -206,7 +1601,7 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
                             if (union.is_nullable() && union.variants().len() > 2)
                                 || (!union.is_nullable() && !union.variants().is_empty())
                             {
-                                push_unique(&mut deps, sr.clone());
+                                deps.push_unique(sr.clone());
                             }
 
                             union.variants().iter().for_each(|sc| match sc {
@@ -217,7 +1612,7 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
                                 | Schema::Array(..)
                                 | Schema::Union(..) => {
                                     q.push_back(sc);
-                                    push_unique(&mut deps, sc.clone());
+                                    deps.push_unique(sc.clone());
                                 }
 
                                 _ => (),
@@ -240,17 +1635,17 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
                 | Schema::Array(..)
                 | Schema::Union(..) => {
                     q.push_back(sc.as_ref());
-                    push_unique(&mut deps, s.clone());
+                    deps.push_unique(s.clone());
                 }
                 // ... Not nested, can be pushed to the result stack
-                _ => push_unique(&mut deps, s.clone()),
+                _ => deps.push_unique(s.clone()),
             },
 
             Schema::Union(union) => {
                 if (union.is_nullable() && union.variants().len() > 2)
                     || (!union.is_nullable() && union.variants().len() > 1)
                 {
-                    push_unique(&mut deps, s.clone());
+                    deps.push_unique(s.clone());
                 }
 
                 union.variants().iter().for_each(|sc| match sc {
@@ -262,10 +1657,10 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
                     | Schema::Array(..)
                     | Schema::Union(..) => {
                         q.push_back(sc);
-                        push_unique(&mut deps, s.clone());
+                        deps.push_unique(s.clone());
                     }
                     // ... Not nested, can be pushed to the result stack
-                    _ => push_unique(&mut deps, s.clone()),
+                    _ => deps.push_unique(s.clone()),
                 });
             }
 
@@ -273,29 +1668,183 @@ fn deps_stack(schema: &Schema, mut deps: Vec<Schema>) -> Vec<Schema> {
             _ => (),
         }
     }
+}
 
-    deps
+/// Returns whether `lint` is the path of a lint, e.g. `dead_code` or `clippy::all`.
+fn is_lint(lint: &str) -> bool {
+    lint.split("::").all(|segment| {
+        !segment.is_empty() && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
 }
 
-/// A builder class to customize `Generator`.
-pub struct GeneratorBuilder {
-    precision: usize,
+/// Parses lint attributes separated by commas, e.g. `allow(dead_code, clippy::all), deny(unsafe_code)`,
+/// into their normalized code without `#[...]`, `None` if any is not a lint attribute.
+fn lint_attributes(attributes: &str) -> Option<Vec<String>> {
+    let mut parsed = vec![];
+    let mut rest = attributes.trim();
+    while !rest.is_empty() {
+        let (level, tail) = rest.split_once('(')?;
+        let (lints, tail) = tail.split_once(')')?;
+        let level = level.trim();
+        if !["allow", "expect", "warn", "deny", "forbid"].contains(&level) {
+            return None;
+        }
+        let lints = lints.split(',').map(str::trim).collect::<Vec<_>>();
+        if !lints.iter().all(|lint| is_lint(lint)) {
+            return None;
+        }
+        parsed.push(format!("{level}({})", lints.join(", ")));
+        rest = tail.trim_start();
+        if let Some(tail) = rest.strip_prefix(',') {
+            rest = tail.trim_start();
+        } else if !rest.is_empty() {
+            return None;
+        }
+    }
+    Some(parsed)
+}
+
+/// Replaces the lines between the region markers of `content` by `code`.
+fn replace_region(content: &str, code: &str) -> std::result::Result<String, String> {
+    let marker_line = |marker: &str| {
+        let mut offset = 0;
+        let mut found = vec![];
+        for line in content.split_inclusive('\n') {
+            if line.trim() == marker {
+                found.push((offset, offset + line.len()));
+            }
+            offset += line.len();
+        }
+        match found[..] {
+            [line] => Ok(line),
+            [] => Err(format!("Missing `{marker}` comment")),
+            _ => Err(format!("Multiple `{marker}` comments")),
+        }
+    };
+    let (_, begin) = marker_line(REGION_BEGIN)?;
+    let (end, _) = marker_line(REGION_END)?;
+    if end < begin {
+        return Err(format!("`{REGION_END}` comment before `{REGION_BEGIN}`"));
+    }
+
+    let mut updated = content[..begin].to_string();
+    if !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(code);
+    if !code.is_empty() && !code.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(&content[end..]);
+    Ok(updated)
+}
+
+/// A builder class to customize `Generator`.
+pub struct GeneratorBuilder {
+    progress: Option<Arc<dyn Progress>>,
+    cancellation_token: Option<CancellationToken>,
+    precision: usize,
     nullable: bool,
     use_avro_rs_unions: bool,
     use_chrono_dates: bool,
+    use_decimal_structs: bool,
+    string_enums: bool,
     derive_builders: bool,
     derive_schemas: bool,
+    avro_schema_impls: bool,
+    avro_message_impls: bool,
+    round_trip_tests: bool,
+    proptest_arbitrary: bool,
+    quickcheck_arbitrary: bool,
+    writer_schema_decoders: bool,
+    try_from_bytes: bool,
+    wrapper_conversions: bool,
+    alias_types: bool,
+    schema_constants: bool,
+    zeroize: bool,
+    debug_impls: bool,
+    hex_debug: bool,
+    validators: bool,
+    validate_derive: Option<ValidateDerive>,
+    default_impls: bool,
+    strict_defaults: bool,
+    empty_defaults: bool,
+    serde_impls: bool,
+    doc_examples: Option<String>,
+    deserialization: DeserializationMode,
+    optional_fields: OptionalFields,
+    box_threshold: Option<usize>,
+    smallvec_arrays: Option<usize>,
+    union_names: Option<String>,
+    union_json: Option<UnionJson>,
+    rfc3339_timestamps: bool,
+    verify: bool,
+    wrap_module: Option<String>,
+    allowed_lints: Vec<String>,
+    item_lints: Vec<(ItemKind, String)>,
+    avro_crate: String,
+    templates_dir: Option<PathBuf>,
+    filters: Vec<(String, Box<dyn tera::Filter>)>,
+    functions: Vec<(String, Box<dyn tera::Function>)>,
+    renderer: Option<Box<dyn Renderer>>,
+    name_mapper: Arc<dyn NameMapper>,
+    type_mapper: Arc<dyn TypeMapper>,
 }
 
 impl Default for GeneratorBuilder {
     fn default() -> Self {
         Self {
+            progress: None,
+            cancellation_token: None,
             precision: 3,
             nullable: false,
             use_avro_rs_unions: false,
             use_chrono_dates: false,
+            use_decimal_structs: false,
+            string_enums: false,
             derive_builders: false,
             derive_schemas: false,
+            avro_schema_impls: false,
+            avro_message_impls: false,
+            round_trip_tests: false,
+            proptest_arbitrary: false,
+            quickcheck_arbitrary: false,
+            writer_schema_decoders: false,
+            try_from_bytes: false,
+            wrapper_conversions: false,
+            alias_types: false,
+            schema_constants: false,
+            zeroize: false,
+            debug_impls: true,
+            hex_debug: false,
+            validators: false,
+            validate_derive: None,
+            default_impls: true,
+            strict_defaults: false,
+            empty_defaults: false,
+            serde_impls: true,
+            doc_examples: None,
+            deserialization: DeserializationMode::Standard,
+            optional_fields: OptionalFields::Unions,
+            box_threshold: None,
+            smallvec_arrays: None,
+            union_names: None,
+            union_json: None,
+            rfc3339_timestamps: false,
+            verify: false,
+            wrap_module: None,
+            allowed_lints: DEFAULT_ALLOWED_LINTS
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+            item_lints: vec![],
+            avro_crate: DEFAULT_AVRO_CRATE.to_string(),
+            templates_dir: None,
+            filters: vec![],
+            functions: vec![],
+            renderer: None,
+            name_mapper: Arc::new(DefaultNameMapper),
+            type_mapper: Arc::new(DefaultTypeMapper),
         }
     }
 }
@@ -335,6 +1884,30 @@ impl GeneratorBuilder {
         self
     }
 
+    /// Maps the `decimal` logical type to a generated `Decimal` struct, holding an `i128`
+    /// mantissa and a `u32` scale, instead of `apache_avro::Decimal`.
+    ///
+    /// The struct converts from and to the Avro encoding of decimals, and record fields are
+    /// serialized with the scale of their schema. Its `try_new` constructor checks values
+    /// against the precision, scale and fixed size of their schema, returning a generated
+    /// `DecimalError` instead of failing when encoded. Decimals nested in arrays or maps, as well
+    /// as [`derive_schemas`](GeneratorBuilder::derive_schemas), are not supported.
+    pub fn use_decimal_structs(mut self, use_decimal_structs: bool) -> GeneratorBuilder {
+        self.use_decimal_structs = use_decimal_structs;
+        self
+    }
+
+    /// Generates enums as `String` newtypes, e.g. `pub struct Color(pub String)`, with an
+    /// associated `&str` constant per symbol, e.g. `Color::GREEN`, for applications to
+    /// tolerate symbols unknown to their schema.
+    ///
+    /// The newtypes also have the `SYMBOLS` of their schema, and `is_known()` checks whether
+    /// a value is one of them.
+    pub fn string_enums(mut self, string_enums: bool) -> GeneratorBuilder {
+        self.string_enums = string_enums;
+        self
+    }
+
     /// Adds support to derive builders using the `rust-derive-builder` crate.
     ///
     /// Applies to record structs.
@@ -351,16 +1924,684 @@ impl GeneratorBuilder {
         self
     }
 
+    /// Implements the `AvroSchema` trait of apache-avro for the record structs and enums,
+    /// returning their schema embedded in the generated code, so that they can be used with
+    /// e.g. `SpecificSingleObjectWriter` without the derive macro of apache-avro.
+    ///
+    /// Unlike [`derive_schemas`](GeneratorBuilder::derive_schemas), the implemented schema is
+    /// the source schema, whatever the other options.
+    pub fn avro_schema_impls(mut self, avro_schema_impls: bool) -> GeneratorBuilder {
+        self.avro_schema_impls = avro_schema_impls;
+        self
+    }
+
+    /// Generates an object-safe `AvroMessage` trait, implemented by the record structs and
+    /// enums, for applications to hold heterogeneous messages, e.g. in a
+    /// `Vec<Box<dyn AvroMessage>>`.
+    ///
+    /// The trait returns the Avro `fullname()` and the `schema_json()` of a message, embedded
+    /// in the generated code, and `encode()`s it as an Avro datum of its schema.
+    pub fn avro_message_impls(mut self, avro_message_impls: bool) -> GeneratorBuilder {
+        self.avro_message_impls = avro_message_impls;
+        self
+    }
+
+    /// Generates a `#[cfg(test)]` module of serde round-trip tests after each record
+    /// implementing `Default`.
+    ///
+    /// The tests encode and decode `Default::default()` through Avro datums, and check that
+    /// schema default values decode to it, catching template and serde mismatches early.
+    pub fn round_trip_tests(mut self, round_trip_tests: bool) -> GeneratorBuilder {
+        self.round_trip_tests = round_trip_tests;
+        self
+    }
+
+    /// Implements `proptest::arbitrary::Arbitrary` for generated types.
+    ///
+    /// Applies to record structs, enums and union enums. The generated code then requires
+    /// the `proptest` crate.
+    pub fn proptest_arbitrary(mut self, proptest_arbitrary: bool) -> GeneratorBuilder {
+        self.proptest_arbitrary = proptest_arbitrary;
+        self
+    }
+
+    /// Implements `quickcheck::Arbitrary` for generated types.
+    ///
+    /// Applies to record structs, enums and union enums. The generated code then requires
+    /// the `quickcheck` crate.
+    pub fn quickcheck_arbitrary(mut self, quickcheck_arbitrary: bool) -> GeneratorBuilder {
+        self.quickcheck_arbitrary = quickcheck_arbitrary;
+        self
+    }
+
+    /// Generates a `decode_with_writer_schema` function for each record struct, decoding
+    /// Avro datums written with another, compatible, schema.
+    ///
+    /// Datums are resolved against the schema of the record following the Avro schema
+    /// resolution rules: fields are matched by name whatever their order, missing fields
+    /// take their default value and numbers are promoted. Field aliases are not supported
+    /// by apache-avro.
+    pub fn writer_schema_decoders(mut self, writer_schema_decoders: bool) -> GeneratorBuilder {
+        self.writer_schema_decoders = writer_schema_decoders;
+        self
+    }
+
+    /// Implements `TryFrom<&[u8]>` for each record struct, decoding a single Avro datum
+    /// written with the schema of the record, embedded in the generated code, so that callers
+    /// do not need the apache-avro APIs, e.g. `User::try_from(payload)?`.
+    ///
+    /// Bytes left after the datum are an error.
+    pub fn try_from_bytes(mut self, try_from_bytes: bool) -> GeneratorBuilder {
+        self.try_from_bytes = try_from_bytes;
+        self
+    }
+
+    /// Implements `From` conversions both ways between the struct of each record having a
+    /// single field and the type of this field, e.g. `UserId::from(42)` and `i64::from(id)`,
+    /// for wrapper records.
+    ///
+    /// Structs implementing `Drop` because of [`zeroize`](GeneratorBuilder::zeroize) are
+    /// only converted from their field type.
+    pub fn wrapper_conversions(mut self, wrapper_conversions: bool) -> GeneratorBuilder {
+        self.wrapper_conversions = wrapper_conversions;
+        self
+    }
+
+    /// Generates type aliases of records, enums and fixed named after their Avro `aliases`,
+    /// e.g. `pub type Customer = Client;`, for code written against former generated types
+    /// to keep compiling once they are renamed.
+    pub fn alias_types(mut self, alias_types: bool) -> GeneratorBuilder {
+        self.alias_types = alias_types;
+        self
+    }
+
+    /// Generates, for each record struct, the `SCHEMA` and `SCHEMA_PRETTY` constants holding
+    /// its Avro schema as compact and pretty-printed JSON, and a `schema_json` function
+    /// returning it as a `serde_json::Value`, which requires a dependency on `serde_json`.
+    pub fn schema_constants(mut self, schema_constants: bool) -> GeneratorBuilder {
+        self.schema_constants = schema_constants;
+        self
+    }
+
+    /// Implements `zeroize::Zeroize` for the record structs having sensitive fields, zeroizing
+    /// these fields, as well as `Drop` to zeroize them when the struct is dropped.
+    ///
+    /// Fields are sensitive when they, or their record, have the `"rust.sensitive": true`
+    /// attribute, and their types must implement `Zeroize` (e.g. strings, bytes, numbers, and
+    /// options or vectors of them). As they implement `Drop`, fields cannot be moved out of
+    /// these structs.
+    pub fn zeroize(mut self, zeroize: bool) -> GeneratorBuilder {
+        self.zeroize = zeroize;
+        self
+    }
+
+    /// Derives or implements `Debug` for the generated types, `true` by default.
+    ///
+    /// Required by [`round_trip_tests`](GeneratorBuilder::round_trip_tests) and
+    /// [`proptest_arbitrary`](GeneratorBuilder::proptest_arbitrary).
+    pub fn debug_impls(mut self, debug_impls: bool) -> GeneratorBuilder {
+        self.debug_impls = debug_impls;
+        self
+    }
+
+    /// Implements `Debug` for the record structs having `bytes` or `fixed` fields, possibly
+    /// optional, printing them as truncated hexadecimal with their length (e.g.
+    /// `0xDEADBEEF00C0FFEE… (16 bytes)`) instead of lists of numbers.
+    pub fn hex_debug(mut self, hex_debug: bool) -> GeneratorBuilder {
+        self.hex_debug = hex_debug;
+        self
+    }
+
+    /// Generates a `validate(&self) -> Result<(), ValidationErrors>` method per record struct,
+    /// checking the structural invariants of its fields and the constraints set by their
+    /// custom attributes, and validating the records it holds, e.g. before encoding values
+    /// built from untyped sources.
+    ///
+    /// Decimals typed with [`use_decimal_structs`](GeneratorBuilder::use_decimal_structs)
+    /// must fit the precision and scale of their schema, and the size of their `fixed`, and
+    /// enums typed with [`string_enums`](GeneratorBuilder::string_enums) be one of their
+    /// symbols, directly or as items of arrays and maps.
+    ///
+    /// The `minimum` and `maximum` attributes bound `int`, `long`, `float` and `double`
+    /// fields, and the `pattern` attribute is a regular expression matched by `string`
+    /// fields, the crate then needing a `regex` dependency, invalid patterns failing the
+    /// generation with the `pattern-checks` feature. Optional fields are only checked when set.
+    /// The generated `ValidationErrors` lists the paths of the invalid fields, e.g.
+    /// `address.zip`, with their violated constraints.
+    pub fn validators(mut self, validators: bool) -> GeneratorBuilder {
+        self.validators = validators;
+        self
+    }
+
+    /// Derives the `Validate` trait of the `validator` or `serde_valid` crate for the record
+    /// structs, mapping the `minimum`, `maximum` and `pattern` attributes of their fields to
+    /// `#[validate(...)]` attributes, and validating the records they hold, for codebases
+    /// already standardized on these crates.
+    ///
+    /// The attributes are checked like with [`validators`](GeneratorBuilder::validators),
+    /// which is not supported alongside. The patterns of the `validator` crate are
+    /// `regex::Regex` statics, the crate then needing a `regex` dependency.
+    pub fn validate_derive(mut self, validate_derive: ValidateDerive) -> GeneratorBuilder {
+        self.validate_derive = Some(validate_derive);
+        self
+    }
+
+    /// Implements `Default` for the record structs whose fields all have a default value,
+    /// `true` by default.
+    ///
+    /// Default values of fields are still used when deserializing records without these
+    /// impls. Required by [`round_trip_tests`](GeneratorBuilder::round_trip_tests).
+    pub fn default_impls(mut self, default_impls: bool) -> GeneratorBuilder {
+        self.default_impls = default_impls;
+        self
+    }
+
+    /// Omits the `Default` impls of the record structs having fields without a schema default
+    /// value, for which `Default` would invent values, e.g. the zero values of
+    /// [`lenient`](DeserializationMode::Lenient) deserialization.
+    ///
+    /// These zero values are still used when deserializing. Without this option, the invented
+    /// values are reported as [`warnings`](GeneratedItem::warnings).
+    pub fn strict_defaults(mut self, strict_defaults: bool) -> GeneratorBuilder {
+        self.strict_defaults = strict_defaults;
+        self
+    }
+
+    /// Also implements `Default` for the record structs whose fields without a default value
+    /// all have an empty value: optional fields are `None`, arrays and maps are empty.
+    ///
+    /// Records with other fields without a default value, e.g. mandatory ids, still have no
+    /// `Default` impl, and deserialization still requires the fields without a default value.
+    pub fn empty_defaults(mut self, empty_defaults: bool) -> GeneratorBuilder {
+        self.empty_defaults = empty_defaults;
+        self
+    }
+
+    /// Derives or implements `serde::Serialize` and `serde::Deserialize` for the generated
+    /// types, `true` by default.
+    ///
+    /// Required by [`nullable`](GeneratorBuilder::nullable),
+    /// [`use_avro_rs_unions`](GeneratorBuilder::use_avro_rs_unions),
+    /// [`round_trip_tests`](GeneratorBuilder::round_trip_tests) and
+    /// [`writer_schema_decoders`](GeneratorBuilder::writer_schema_decoders), as well as by
+    /// [`Generator::gen_bench`](Generator::gen_bench) and
+    /// [`Generator::gen_registry`](Generator::gen_registry).
+    pub fn serde_impls(mut self, serde_impls: bool) -> GeneratorBuilder {
+        self.serde_impls = serde_impls;
+        self
+    }
+
+    /// Documents each record struct with a rustdoc example building its
+    /// [example](Generator::gen_examples) value and encoding it.
+    ///
+    /// The examples are doctests of the crate where the types are generated, which import
+    /// them from `types_path` (e.g. `my_crate::avro`). Records whose example cannot be
+    /// encoded (e.g. with decimals) are left without example. Requires the
+    /// [`serde_impls`](GeneratorBuilder::serde_impls).
+    pub fn doc_examples(mut self, types_path: impl Into<String>) -> GeneratorBuilder {
+        self.doc_examples = Some(types_path.into());
+        self
+    }
+
+    /// Sets how strictly the generated types are deserialized, see
+    /// [`DeserializationMode`](DeserializationMode).
+    ///
+    /// Strict types suit consumers which must reject incomplete data, lenient ones suit
+    /// consumers which must accept data from newer or sloppier producers. Defaults to
+    /// [`Standard`](DeserializationMode::Standard).
+    pub fn deserialization(mut self, mode: DeserializationMode) -> GeneratorBuilder {
+        self.deserialization = mode;
+        self
+    }
+
+    /// Sets which record fields are generated as `Option`, see
+    /// [`OptionalFields`](OptionalFields).
+    ///
+    /// With [`Defaults`](OptionalFields::Defaults), the fields having a default value are
+    /// optional too, for producers to leave them unset: they are omitted when `None`, their
+    /// default value being written instead when the serialized value is resolved against the
+    /// schema. Defaults to [`Unions`](OptionalFields::Unions).
+    pub fn optional_fields(mut self, optional_fields: OptionalFields) -> GeneratorBuilder {
+        self.optional_fields = optional_fields;
+        self
+    }
+
+    /// Generates the nested record fields, possibly optional, whose struct is estimated
+    /// larger than `bytes` as a `Box` of this struct, keeping the outer structs small to move.
+    ///
+    /// The estimate sums the sizes of the struct fields, boxed ones counting as a pointer.
+    /// Fields can also be boxed one by one with the `rust.box` schema attribute.
+    pub fn box_threshold(mut self, bytes: usize) -> GeneratorBuilder {
+        self.box_threshold = Some(bytes);
+        self
+    }
+
+    /// Generates the array fields, possibly optional, as `smallvec::SmallVec<[T; capacity]>`,
+    /// storing up to `capacity` items without allocating, e.g. for the tiny arrays of
+    /// hot-path messages.
+    ///
+    /// Fields can also be mapped one by one, or given another capacity, with the
+    /// `rust.smallvec` schema attribute, e.g. `"rust.smallvec": 4`. The generated code
+    /// requires the `smallvec` crate, with its `serde` feature.
+    pub fn smallvec_arrays(mut self, capacity: usize) -> GeneratorBuilder {
+        self.smallvec_arrays = Some(capacity);
+        self
+    }
+
+    /// Names the enums generated for the unions of record fields after a `template`, instead
+    /// of concatenating the names of their variants (e.g. `UnionStringLong`), for names to
+    /// stay the same when variants are added.
+    ///
+    /// The `{Record}` and `{Field}` placeholders are replaced by the names of the record and
+    /// of the field, in `UpperCamelCase`, and `{Variants}` by the names of the variants, e.g.
+    /// `{Record}{Field}Union`. An union shared by several fields is named after the first one.
+    pub fn union_names(mut self, template: impl Into<String>) -> GeneratorBuilder {
+        self.union_names = Some(template.into());
+        self
+    }
+
+    /// Sets how the enums of unions are represented by human-readable formats like JSON,
+    /// which downstream consumers expect in different shapes: the value alone, keyed by the
+    /// variant name, or keyed by the Avro type name as in the Avro JSON encoding.
+    ///
+    /// By default, the value alone is serialized and a value keyed by the variant name is
+    /// deserialized. Since apache-avro serializers are human-readable by default,
+    /// `apache_avro::set_serde_human_readable(false)` must be called before using Avro.
+    pub fn union_json(mut self, union_json: UnionJson) -> GeneratorBuilder {
+        self.union_json = Some(union_json);
+        self
+    }
+
+    /// Serializes the chrono timestamps of
+    /// [`use_chrono_dates`](GeneratorBuilder::use_chrono_dates) as RFC 3339 strings with
+    /// human-readable formats like JSON, while keeping Avro epoch numbers with binary ones.
+    ///
+    /// Each record having timestamps gets its own `<record>_timestamps` module of serde `with`
+    /// modules. Since apache-avro serializers are human-readable by default,
+    /// `apache_avro::set_serde_human_readable(false)` must be called before using Avro.
+    pub fn rfc3339_timestamps(mut self, rfc3339_timestamps: bool) -> GeneratorBuilder {
+        self.rfc3339_timestamps = rfc3339_timestamps;
+        self
+    }
+
+    /// Parses each generated item with `syn`, failing the generation with an
+    /// [`Error::InvalidCode`](Error::InvalidCode) if it is not valid Rust.
+    ///
+    /// This catches broken code (e.g. from custom templates or unusual defaults) before
+    /// it reaches the compiler of the crate using it. Requires the `verify` feature, building
+    /// the generator failing otherwise.
+    pub fn verify(mut self, verify: bool) -> GeneratorBuilder {
+        self.verify = verify;
+        self
+    }
+
+    /// Wraps the generated items in a `pub mod` named `name`, e.g. `avro_gen`, allowing the
+    /// [`allowed_lints`](GeneratorBuilder::allowed_lints) in it so that generated code does
+    /// not trip the strict lint settings of the crate including it.
+    pub fn wrap_module(mut self, name: impl Into<String>) -> GeneratorBuilder {
+        self.wrap_module = Some(name.into());
+        self
+    }
+
+    /// Sets the lints allowed in the [`wrap_module`](GeneratorBuilder::wrap_module) module
+    /// by a `#![allow(...)]` attribute, e.g. `clippy::all` or `non_camel_case_types`.
+    /// Defaults to [`DEFAULT_ALLOWED_LINTS`](DEFAULT_ALLOWED_LINTS).
+    pub fn allowed_lints<I, S>(mut self, lints: I) -> GeneratorBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_lints = lints.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adds lint attributes to the generated items of the given kind, separated by commas,
+    /// e.g. `allow(clippy::large_enum_variant)` on records or `warn(missing_docs)` on enums.
+    ///
+    /// Can be called several times, including for the same kind.
+    pub fn item_lints(mut self, kind: ItemKind, attributes: impl Into<String>) -> GeneratorBuilder {
+        self.item_lints.push((kind, attributes.into()));
+        self
+    }
+
+    /// Sets the path of the Avro crate referenced by the generated code.
+    ///
+    /// Defaults to `apache_avro`. Useful when the generated code depends on a re-exported
+    /// or renamed crate, e.g. `rsgen_avro::apache_avro`. The generated code still uses the
+    /// API of apache-avro 0.17, so the path must lead to that crate: other Avro crates, e.g.
+    /// the superseded avro-rs, are not supported, neither for parsing nor as a target.
+    pub fn avro_crate(mut self, avro_crate: impl Into<String>) -> GeneratorBuilder {
+        self.avro_crate = avro_crate.into();
+        self
+    }
+
+    /// Loads custom templates from a directory of `.tera` files.
+    ///
+    /// Files named after a built-in template (`record.tera`, `enum.tera`, `fixed.tera` or
+    /// `union.tera`) override it. Their variables are described by [`RecordContext`],
+    /// [`EnumContext`], [`FixedContext`] and [`UnionContext`].
+    ///
+    /// Other templates can be selected for specific records, enums or fixed with a
+    /// `"rust.template": "<name>"` schema attribute, e.g. `event_record` for a
+    /// `event_record.tera` file.
+    ///
+    /// [`RecordContext`]: crate::RecordContext
+    /// [`EnumContext`]: crate::EnumContext
+    /// [`FixedContext`]: crate::FixedContext
+    /// [`UnionContext`]: crate::UnionContext
+    pub fn templates_dir(mut self, dir: impl Into<PathBuf>) -> GeneratorBuilder {
+        self.templates_dir = Some(dir.into());
+        self
+    }
+
+    /// Registers a [Tera filter](tera::Filter) usable by custom templates.
+    ///
+    /// See also [`templates_dir`](GeneratorBuilder::templates_dir).
+    pub fn register_filter(
+        mut self,
+        name: impl Into<String>,
+        filter: impl tera::Filter + 'static,
+    ) -> GeneratorBuilder {
+        self.filters.push((name.into(), Box::new(filter)));
+        self
+    }
+
+    /// Registers a [Tera function](tera::Function) usable by custom templates.
+    ///
+    /// See also [`templates_dir`](GeneratorBuilder::templates_dir).
+    pub fn register_function(
+        mut self,
+        name: impl Into<String>,
+        function: impl tera::Function + 'static,
+    ) -> GeneratorBuilder {
+        self.functions.push((name.into(), Box::new(function)));
+        self
+    }
+
+    /// Names generated types, fields and enum variants with a custom
+    /// [`NameMapper`](NameMapper).
+    pub fn name_mapper(mut self, name_mapper: impl NameMapper + 'static) -> GeneratorBuilder {
+        self.name_mapper = Arc::new(name_mapper);
+        self
+    }
+
+    /// Maps Avro schemas to Rust types with a custom [`TypeMapper`](TypeMapper).
+    pub fn type_mapper(mut self, type_mapper: impl TypeMapper + 'static) -> GeneratorBuilder {
+        self.type_mapper = Arc::new(type_mapper);
+        self
+    }
+
+    /// Renders Rust types with a custom [`Renderer`](Renderer) instead of the built-in Tera
+    /// templates.
+    ///
+    /// Custom templates, filters and functions only apply to the default renderer and are
+    /// ignored when a custom one is set.
+    pub fn renderer(mut self, renderer: impl Renderer + 'static) -> GeneratorBuilder {
+        self.renderer = Some(Box::new(renderer));
+        self
+    }
+
+    /// Reports the progress of generations to the given [`Progress`](Progress) implementor.
+    pub fn progress(mut self, progress: impl Progress + 'static) -> GeneratorBuilder {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Stops generations as soon as the given [`CancellationToken`](CancellationToken)
+    /// is cancelled.
+    ///
+    /// The token is checked between schemas.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> GeneratorBuilder {
+        self.cancellation_token = Some(token);
+        self
+    }
+
     /// Create a [`Generator`](Generator) with the builder parameters.
     pub fn build(self) -> Result<Generator> {
+        let requires = |option: &str, impls: &str| {
+            Err(Error::Config(format!(
+                "`{option}` requires the {impls} impls"
+            )))
+        };
+        if self.verify && !cfg!(feature = "verify") {
+            return Err(Error::Config(
+                "`verify` requires the `verify` feature".to_string(),
+            ));
+        }
+        if self.round_trip_tests && !(self.debug_impls && self.default_impls && self.serde_impls) {
+            return requires("round_trip_tests", "Debug, Default and serde");
+        }
+        if self.proptest_arbitrary && !self.debug_impls {
+            return requires("proptest_arbitrary", "Debug");
+        }
+        if self.hex_debug && !self.debug_impls {
+            return requires("hex_debug", "Debug");
+        }
+        if self.use_decimal_structs && self.derive_schemas {
+            return Err(Error::Config(
+                "`use_decimal_structs` does not support `derive_schemas`".to_string(),
+            ));
+        }
+        if self.optional_fields == OptionalFields::Defaults && self.derive_schemas {
+            return Err(Error::Config(
+                "`optional_fields = defaults` does not support `derive_schemas`".to_string(),
+            ));
+        }
+        if let Some(template) = &self.union_names {
+            let mut rest = template.as_str();
+            while let Some(start) = rest.find('{') {
+                let end = rest[start..]
+                    .find('}')
+                    .map_or(rest.len(), |end| start + end + 1);
+                let placeholder = &rest[start..end];
+                if !["{Record}", "{Field}", "{Variants}"].contains(&placeholder) {
+                    return Err(Error::Config(format!(
+                        "Unknown placeholder `{placeholder}` in `union_names`, expected \
+                         `{{Record}}`, `{{Field}}` or `{{Variants}}`"
+                    )));
+                }
+                rest = &rest[end..];
+            }
+        }
+        let module = match &self.wrap_module {
+            Some(name) if sanitize(name.clone()) != *name || name.starts_with("r#") => {
+                return Err(Error::Config(format!(
+                    "Invalid `wrap_module` name `{name}`, expected a Rust identifier"
+                )));
+            }
+            Some(name) => {
+                if let Some(lint) = self.allowed_lints.iter().find(|lint| !is_lint(lint)) {
+                    return Err(Error::Config(format!(
+                        "Invalid lint `{lint}` in `allowed_lints`"
+                    )));
+                }
+                let mut start = format!("pub mod {name} {{\n");
+                if !self.allowed_lints.is_empty() {
+                    start.push_str(&format!("#![allow({})]\n", self.allowed_lints.join(", ")));
+                }
+                Some(start)
+            }
+            None => None,
+        };
+        let mut item_lints = BTreeMap::<_, Vec<_>>::new();
+        for (kind, attributes) in &self.item_lints {
+            let parsed = lint_attributes(attributes).ok_or_else(|| {
+                Error::Config(format!(
+                    "Invalid lint attributes `{attributes}` for {kind} items, expected e.g. \
+                     `allow(dead_code)`"
+                ))
+            })?;
+            item_lints.entry(*kind).or_default().extend(parsed);
+        }
+        if self.smallvec_arrays == Some(0) {
+            return Err(Error::Config(
+                "`smallvec_arrays` requires a positive capacity".to_string(),
+            ));
+        }
+        if self.validators && self.validate_derive.is_some() {
+            return Err(Error::Config(
+                "`validate_derive` does not support `validators`".to_string(),
+            ));
+        }
+        if self.avro_schema_impls && self.derive_schemas {
+            return Err(Error::Config(
+                "`avro_schema_impls` does not support `derive_schemas`".to_string(),
+            ));
+        }
+        if self.smallvec_arrays.is_some() && self.derive_schemas {
+            return Err(Error::Config(
+                "`smallvec_arrays` does not support `derive_schemas`".to_string(),
+            ));
+        }
+        if self.rfc3339_timestamps && !self.use_chrono_dates {
+            return Err(Error::Config(
+                "`rfc3339_timestamps` requires `use_chrono_dates`".to_string(),
+            ));
+        }
+        for (option, enabled) in [
+            ("nullable", self.nullable),
+            ("use_avro_rs_unions", self.use_avro_rs_unions),
+            ("writer_schema_decoders", self.writer_schema_decoders),
+            ("try_from_bytes", self.try_from_bytes),
+            ("doc_examples", self.doc_examples.is_some()),
+            ("rfc3339_timestamps", self.rfc3339_timestamps),
+            ("union_json", self.union_json.is_some()),
+            ("avro_message_impls", self.avro_message_impls),
+        ] {
+            if enabled && !self.serde_impls {
+                return requires(option, "serde");
+            }
+        }
+
         let mut templater = Templater::new()?;
         templater.precision = self.precision;
         templater.nullable = self.nullable;
         templater.use_avro_rs_unions = self.use_avro_rs_unions;
         templater.use_chrono_dates = self.use_chrono_dates;
+        templater.use_decimal_structs = self.use_decimal_structs;
+        templater.string_enums = self.string_enums;
         templater.derive_builders = self.derive_builders;
         templater.derive_schemas = self.derive_schemas;
-        Ok(Generator { templater })
+        templater.avro_schema_impls = self.avro_schema_impls;
+        templater.avro_message_impls = self.avro_message_impls;
+        templater.round_trip_tests = self.round_trip_tests;
+        templater.proptest_arbitrary = self.proptest_arbitrary;
+        templater.quickcheck_arbitrary = self.quickcheck_arbitrary;
+        templater.writer_schema_decoders = self.writer_schema_decoders;
+        templater.try_from_bytes = self.try_from_bytes;
+        templater.wrapper_conversions = self.wrapper_conversions;
+        templater.alias_types = self.alias_types;
+        templater.schema_constants = self.schema_constants;
+        templater.zeroize = self.zeroize;
+        templater.debug_impls = self.debug_impls;
+        templater.hex_debug = self.hex_debug;
+        templater.validators = self.validators;
+        templater.validate_derive = self.validate_derive;
+        templater.default_impls = self.default_impls;
+        templater.strict_defaults = self.strict_defaults;
+        templater.empty_defaults = self.empty_defaults;
+        templater.serde_impls = self.serde_impls;
+        templater.doc_examples = self.doc_examples.clone();
+        templater.deserialization = self.deserialization;
+        templater.optional_fields = self.optional_fields;
+        templater.box_threshold = self.box_threshold;
+        templater.smallvec_arrays = self.smallvec_arrays;
+        templater.union_names = self.union_names.clone();
+        templater.union_json = self.union_json;
+        templater.rfc3339_timestamps = self.rfc3339_timestamps;
+        templater.item_lints = item_lints;
+        let mut config = vec![
+            env!("CARGO_PKG_VERSION").to_string(),
+            format!(
+                "{:?}",
+                (
+                    self.precision,
+                    self.nullable,
+                    self.use_avro_rs_unions,
+                    self.use_chrono_dates,
+                    self.derive_builders,
+                    self.derive_schemas,
+                    self.round_trip_tests,
+                    self.proptest_arbitrary,
+                    self.quickcheck_arbitrary,
+                    self.writer_schema_decoders,
+                    (
+                        self.zeroize,
+                        self.debug_impls,
+                        self.default_impls,
+                        self.strict_defaults,
+                        self.serde_impls,
+                        &self.doc_examples,
+                        self.deserialization,
+                        self.rfc3339_timestamps,
+                        self.schema_constants,
+                        self.use_decimal_structs,
+                        self.optional_fields,
+                        (
+                            self.box_threshold,
+                            self.smallvec_arrays,
+                            &self.union_names,
+                            &self.item_lints,
+                            self.hex_debug,
+                            self.try_from_bytes,
+                            self.avro_schema_impls,
+                            self.union_json,
+                            self.wrapper_conversions,
+                            self.alias_types,
+                            self.avro_message_impls,
+                            (
+                                self.string_enums,
+                                self.empty_defaults,
+                                self.validators,
+                                self.validate_derive,
+                            ),
+                        ),
+                    ),
+                    &self.avro_crate,
+                )
+            ),
+        ];
+        if let Some(dir) = &self.templates_dir {
+            let mut files = fs::read_dir(dir)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            files.sort();
+            for file in files.into_iter().filter(|f| f.is_file()) {
+                config.push(file.display().to_string());
+                config.push(fs::read_to_string(file)?);
+            }
+        }
+        let config_hash = hash(config.iter().map(|c| c.as_bytes()));
+
+        templater.avro_crate = self.avro_crate;
+        templater.name_mapper = self.name_mapper;
+        templater.type_mapper = self.type_mapper;
+        templater.renderer = match self.renderer {
+            Some(renderer) => renderer,
+            None => {
+                let mut renderer = TeraRenderer::new()?;
+                for (name, filter) in self.filters {
+                    renderer.register_filter(&name, move |value: &tera::Value, args: &_| {
+                        filter.filter(value, args)
+                    });
+                }
+                for (name, function) in self.functions {
+                    renderer.register_function(&name, move |args: &_| function.call(args));
+                }
+                if let Some(dir) = &self.templates_dir {
+                    renderer.load_templates_dir(dir)?;
+                }
+                Box::new(renderer)
+            }
+        };
+        Ok(Generator {
+            templater,
+            progress: self.progress,
+            cancellation_token: self.cancellation_token,
+            verify: self.verify,
+            module,
+            config_hash,
+        })
     }
 }
 
@@ -396,7 +2637,7 @@ mod tests {
 "#;
 
         let schema = Schema::parse_str(raw_schema).unwrap();
-        let mut deps = deps_stack(&schema, vec![]);
+        let mut deps = deps_stack([&schema]);
 
         let s = deps.pop().unwrap();
         assert!(
@@ -414,61 +2655,3019 @@ mod tests {
         );
 
         let s = deps.pop();
-        assert!(matches!(s, None));
+        assert!(s.is_none());
     }
 
     #[test]
-    fn cross_deps() -> std::result::Result<(), Box<dyn std::error::Error>> {
-        use std::fs::File;
-        use std::io::Write;
-        use tempfile::tempdir;
-
-        let dir = tempdir()?;
-
-        let mut schema_a_file = File::create(dir.path().join("schema_a.avsc"))?;
-        let schema_a_str = r#"
-{
-  "name": "A",
-  "type": "record",
-  "fields": [ {"name": "field_one", "type": "float"} ]
-}
-"#;
-        schema_a_file.write_all(schema_a_str.as_bytes())?;
-
-        let mut schema_b_file = File::create(dir.path().join("schema_b.avsc"))?;
-        let schema_b_str = r#"
+    fn items() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
 {
-  "name": "B",
   "type": "record",
-  "fields": [ {"name": "field_one", "type": "A"} ]
+  "name": "User",
+  "namespace": "test",
+  "fields": [
+    {"name": "id", "type": {"type": "fixed", "name": "Id", "size": 2}},
+    {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}},
+    {"name": "value", "type": ["int", "string"]}
+  ]
 }
 "#;
-        schema_b_file.write_all(schema_b_str.as_bytes())?;
 
-        let expected = r#"
-#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
-pub struct B {
-    pub field_one: A,
-}
+        let g = Generator::new()?;
+        let items = g
+            .items(&Source::SchemaStr(raw_schema))?
+            .collect::<Result<Vec<_>>>()?;
 
-#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
-pub struct A {
-    pub field_one: f32,
-}
-"#;
+        let summary = items
+            .iter()
+            .map(|i| (i.kind, i.name.as_str(), i.fullname.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (ItemKind::Union, "UnionIntString", None),
+                (ItemKind::Enum, "Kind", Some("test.Kind")),
+                (ItemKind::Fixed, "Id", Some("test.Id")),
+                (ItemKind::Record, "User", Some("test.User")),
+            ],
+            summary
+        );
 
-        let pattern = format!("{}/*.avsc", dir.path().display());
-        let source = Source::GlobPattern(pattern.as_str());
-        let g = Generator::new()?;
         let mut buf = vec![];
-        g.gen(&source, &mut buf)?;
-        let res = String::from_utf8(buf)?;
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = items.into_iter().map(|i| i.code).collect::<String>();
+        assert_eq!(String::from_utf8(buf)?, code);
 
-        assert_eq!(expected, res);
+        Ok(())
+    }
+
+    #[test]
+    fn progress_and_cancellation() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct Recorder {
+            events: Mutex<Vec<String>>,
+            token: CancellationToken,
+        }
+
+        impl Progress for Arc<Recorder> {
+            fn on_generation_start(&self, total: usize) {
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push(format!("generate {total}"));
+            }
+
+            fn on_schema_start(&self, schema: &Schema, index: usize, total: usize) {
+                let name = schema.name().map(|n| n.name.clone()).unwrap_or_default();
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push(format!("start {name} {index}/{total}"));
+            }
+
+            fn on_schema_done(&self, schema: &Schema, index: usize, total: usize) {
+                let name = schema.name().map(|n| n.name.clone()).unwrap_or_default();
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push(format!("done {name} {index}/{total}"));
+                self.token.cancel();
+            }
+        }
+
+        // More schemas than threads, for some not to be started before the cancellation
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let fields = (0..=threads)
+            .map(|i| {
+                format!(
+                    r#"{{"name": "e{i}", "type": {{"type": "enum", "name": "E{i}", "symbols": ["A"]}}}}"#
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let raw_schema = format!(r#"{{"type": "record", "name": "User", "fields": [{fields}]}}"#);
+
+        let recorder = Arc::new(Recorder::default());
+        let g = Generator::builder()
+            .progress(recorder.clone())
+            .cancellation_token(recorder.token.clone())
+            .build()?;
+
+        let mut buf = vec![];
+        let res = g.gen(&Source::SchemaStr(&raw_schema), &mut buf);
+        assert!(matches!(res, Err(Error::Cancelled)));
+        let events = recorder.events.lock().unwrap();
+        let code = String::from_utf8(buf)?;
+        let total = threads + 2;
+        if cfg!(feature = "parallel") {
+            // Schemas are rendered in any order by the threads, each one stopping once done
+            // with the schema it is rendering, and only the items before the first cancelled
+            // one are written
+            let done = events.iter().filter(|e| e.starts_with("done")).count();
+            assert!((1..=threads).contains(&done), "{events:?}");
+            assert!(code.matches("pub enum").count() <= done, "{code}");
+        } else {
+            assert_eq!(
+                vec![
+                    format!("generate {total}"),
+                    format!("start E{threads} 0/{total}"),
+                    format!("done E{threads} 0/{total}"),
+                ],
+                *events
+            );
+            assert!(code.contains(&format!("pub enum E{threads}")));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn templates_dir() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::Write;
+        use tempfile::tempdir;
+
+        let dir = tempdir()?;
+        let mut fixed_tera = File::create(dir.path().join("fixed.tera"))?;
+        fixed_tera.write_all(b"pub struct {{ name }}(pub [u8; {{ size }}]);\n")?;
+
+        let raw_schema = r#"{"type": "fixed", "name": "md5", "size": 16}"#;
+        let g = Generator::builder().templates_dir(dir.path()).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert_eq!("pub struct Md5(pub [u8; 16]);\n", String::from_utf8(buf)?);
+
+        drop(fixed_tera);
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn record_context() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("record.tera"),
+            "{{ name }}:{% for f in fields %} {{ f }}={{ originals[f] }}:{{ types[f] }}{% endfor %}\n",
+        )?;
+
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "test",
+  "fields": [
+    {"name": "camelCase", "type": "long"},
+    {"name": "type", "type": "string"}
+  ]
+}
+"#;
+        let g = Generator::builder().templates_dir(dir.path()).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert_eq!(
+            "Test: camel_case=camelCase:i64 r#type=type:String\n",
+            String::from_utf8(buf)?
+        );
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn custom_attributes() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("record.tera"),
+            "{{ name }} {{ attributes[\"acme.table\"] }}:{% for f in fields %}\
+             {% if field_attributes[f] %} {{ f }}={{ field_attributes[f][\"acme.column\"] }}{% endif %}\
+             {% endfor %}\n",
+        )?;
+        std::fs::write(
+            dir.path().join("enum.tera"),
+            "{{ name }} {{ attributes[\"acme.owner\"].team }}\n",
+        )?;
+
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "User",
+  "acme.table": "users",
+  "fields": [
+    {"name": "id", "type": "long", "acme.column": "user_id"},
+    {"name": "name", "type": "string"},
+    {"name": "role", "type": {"type": "enum", "name": "Role", "symbols": ["A"], "acme.owner": {"team": "auth"}}}
+  ]
+}
+"#;
+        let g = Generator::builder().templates_dir(dir.path()).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert_eq!(
+            "Role auth\nUser users: id=user_id\n",
+            String::from_utf8(buf)?
+        );
+
+        let items = g
+            .items(&Source::SchemaStr(raw_schema))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            Some(&serde_json::json!({"team": "auth"})),
+            items[0].attributes.get("acme.owner")
+        );
+        assert_eq!(
+            Some(&serde_json::json!("users")),
+            items[1].attributes.get("acme.table")
+        );
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn template_attribute() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("event_record.tera"),
+            "pub struct {{ name }}Event;\n",
+        )?;
+
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Click",
+  "rust.template": "event_record",
+  "fields": [
+    {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["Left"]}}
+  ]
+}
+"#;
+        let g = Generator::builder().templates_dir(dir.path()).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains("pub enum Kind {"));
+        assert!(generated.ends_with("pub struct ClickEvent;\n"));
+
+        let raw_schema =
+            r#"{"type": "fixed", "name": "Md5", "size": 16, "rust.template": "event_record.tera"}"#;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert_eq!("pub struct Md5Event;\n", String::from_utf8(buf)?);
+
+        let raw_schema = r#"{"type": "fixed", "name": "Md5", "size": 16, "rust.template": 1}"#;
+        let err = g
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Generation {
+                kind: ErrorKind::Attribute,
+                ..
+            }
+        ));
+
+        let raw_schema =
+            r#"{"type": "fixed", "name": "Md5", "size": 16, "rust.template": "missing"}"#;
+        assert!(g.gen(&Source::SchemaStr(raw_schema), &mut vec![]).is_err());
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn enum_symbol_collisions() {
+        let raw_schema =
+            r#"{"type": "enum", "name": "Kind", "symbols": ["A", "FOO_BAR", "FooBar"]}"#;
+        let err = Generator::new()
+            .unwrap()
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Symbols 'FOO_BAR' and 'FooBar' of enum 'Kind' are both mapped to the variant 'FooBar'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn string_enums() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        struct Verbatim;
+
+        impl NameMapper for Verbatim {
+            fn variant_name(&self, symbol: &str) -> String {
+                crate::naming::sanitize(symbol.to_string())
+            }
+        }
+
+        let raw_schema = r#"{"type": "enum", "name": "Kind", "symbols": ["fooBar", "FOO_BAR"]}"#;
+        let err = Generator::builder()
+            .string_enums(true)
+            .name_mapper(Verbatim)
+            .build()?
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Symbols 'fooBar' and 'FOO_BAR' of enum 'Kind' are both mapped to the constant 'FOO_BAR'",
+            err.to_string()
+        );
+        let g = Generator::builder().string_enums(true).build()?;
+        let raw_schema = r#"{"type": "enum", "name": "Kind", "symbols": ["A", "symbols"]}"#;
+        let err = g
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Symbol 'symbols' of enum 'Kind' is mapped to the constant 'SYMBOLS', which lists the symbols",
+            err.to_string()
+        );
+
+        let v1 = r#"{"type": "enum", "name": "Kind", "symbols": ["A", "B", "C"]}"#;
+        let v2 = r#"{"type": "enum", "name": "Kind", "symbols": ["A", "B"], "default": "A"}"#;
+        let mut buf = vec![];
+        g.gen_versions(&[Source::SchemaStr(v1), Source::SchemaStr(v2)], &mut buf)?;
+        assert!(String::from_utf8(buf)?.contains(
+            r#"
+impl From<super::v1::Kind> for Kind {
+    fn from(v: super::v1::Kind) -> Self {
+        match v.0.as_str() {
+            super::v1::Kind::C => Kind::from(Kind::A),
+            _ => Kind(v.0),
+        }
+    }
+}
+"#
+        ));
+        let mut buf = vec![];
+        g.gen_versions(&[Source::SchemaStr(v2), Source::SchemaStr(v1)], &mut buf)?;
+        assert!(String::from_utf8(buf)?.contains(
+            r#"
+impl From<super::v1::Kind> for Kind {
+    fn from(v: super::v1::Kind) -> Self {
+        Kind(v.0)
+    }
+}
+"#
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn field_name_collisions() {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "User",
+  "fields": [{"name": "fooBar", "type": "int"}, {"name": "foo_bar", "type": "int"}]
+}
+"#;
+        let err = Generator::new()
+            .unwrap()
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Fields 'fooBar' and 'foo_bar' of record 'User' are both mapped to the field 'foo_bar'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_sensitive_attribute() {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Customer",
+  "fields": [{"name": "email", "type": "string", "rust.sensitive": "yes"}]
+}
+"#;
+        let err = Generator::new()
+            .unwrap()
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Invalid 'rust.sensitive' attribute of field 'email', expected a boolean, found \"yes\"",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn wrapper_conversions() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Credentials",
+  "fields": [
+    {"name": "token", "type": {"type": "record", "name": "Token", "fields": [
+      {"name": "value", "type": "string", "rust.sensitive": true}
+    ]}},
+    {"name": "user", "type": "string"}
+  ]
+}
+"#;
+        let g = Generator::builder()
+            .wrapper_conversions(true)
+            .zeroize(true)
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+        // Fields cannot be moved out of structs implementing `Drop`
+        assert!(code.contains("impl From<String> for Token {"));
+        assert!(!code.contains("impl From<Token> for String {"));
+        assert!(!code.contains("for Credentials {\n    fn from("));
+
+        Ok(())
+    }
+
+    #[test]
+    fn alias_types_conflict() {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Client",
+  "aliases": ["Kind"],
+  "fields": [{"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A"]}}]
+}
+"#;
+        let err = Generator::builder()
+            .alias_types(true)
+            .build()
+            .unwrap()
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Alias 'Kind' of 'Client' conflicts with the type of 'Kind'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn symbol_aliases() {
+        let raw_schema = r#"{"type": "enum", "name": "Light", "symbols": ["STOP", "GO"], "symbolAliases": {"GO": ["GREEN"]}}"#;
+        let lenient = Generator::builder()
+            .deserialization(DeserializationMode::Lenient)
+            .build()
+            .unwrap();
+        let mut buf = vec![];
+        lenient
+            .gen(&Source::SchemaStr(raw_schema), &mut buf)
+            .unwrap();
+        let code = String::from_utf8(buf).unwrap();
+        assert!(code.contains(
+            "#[serde(rename = \"GO\")]\n            #[serde(alias = \"GREEN\")]\n            Go,\n            #[serde(other)]"
+        ));
+
+        let err = |symbol_aliases: &str| {
+            let raw_schema = format!(
+                r#"{{"type": "enum", "name": "Light", "symbols": ["STOP", "GO"], "symbolAliases": {symbol_aliases}}}"#
+            );
+            let e = Generator::new()
+                .unwrap()
+                .gen(&Source::SchemaStr(&raw_schema), &mut vec![])
+                .unwrap_err();
+            assert_eq!("E0008", e.code(), "{e}");
+            e.to_string()
+        };
+        assert_eq!(
+            "Templating error: Invalid 'symbolAliases' attribute of enum 'Light', expected arrays of former symbols, found \"GREEN\"",
+            err(r#"{"GO": "GREEN"}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'symbolAliases' attribute of enum 'Light', expected former symbols, found \"GREEN\\\"\"",
+            err(r#"{"GO": ["GREEN\""]}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'symbolAliases' attribute of enum 'Light', former symbol 'STOP' is already a symbol or a former symbol",
+            err(r#"{"GO": ["STOP"]}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'symbolAliases' attribute of enum 'Light', former symbol 'RED' is already a symbol or a former symbol",
+            err(r#"{"GO": ["RED"], "STOP": ["RED"]}"#)
+        );
+        assert_eq!(
+            "Templating error: Unknown symbol 'AMBER' in the 'symbolAliases' attribute of enum 'Light'",
+            err(r#"{"AMBER": []}"#)
+        );
+    }
+
+    #[test]
+    fn validators() {
+        let gen = |field: &str, validators: bool| {
+            let raw_schema = format!(r#"{{"type": "record", "name": "R", "fields": [{field}]}}"#);
+            let mut buf = vec![];
+            Generator::builder()
+                .validators(validators)
+                .build()
+                .unwrap()
+                .gen(&Source::SchemaStr(&raw_schema), &mut buf)
+                .map(|_| String::from_utf8(buf).unwrap())
+                .map_err(|e| e.to_string())
+        };
+        // The attributes are only honoured with the option
+        let field = r#"{"name": "a", "type": "string", "minimum": 1}"#;
+        assert!(!gen(field, false).unwrap().contains("fn validate"));
+        assert_eq!(
+            "Templating error: Invalid 'minimum' attribute of field 'a', expected an int, long, float or double field",
+            gen(field, true).unwrap_err()
+        );
+
+        let code = gen(r#"{"name": "a", "type": "int"}"#, true).unwrap();
+        assert!(code.contains("pub struct ValidationErrors {"));
+        assert!(code.contains(
+            "pub fn validate(&self) -> Result<(), ValidationErrors> {\n        Ok(())\n    }"
+        ));
+        assert!(gen(
+            r#"{"name": "a", "type": "int", "minimum": -2147483648}"#,
+            true
+        )
+        .unwrap()
+        .contains("Ok(())"));
+
+        let err = |field: &str| gen(field, true).unwrap_err();
+        assert_eq!(
+            "Templating error: Invalid 'maximum' attribute of field 'a', expected a number in the range of the field type, found 2147483648",
+            err(r#"{"name": "a", "type": "int", "maximum": 2147483648}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'minimum' attribute of field 'a', expected a number in the range of the field type, found 1.5",
+            err(r#"{"name": "a", "type": "long", "minimum": 1.5}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'minimum' attribute of field 'a', expected a number in the range of the field type, found -1",
+            err(r#"{"name": "a", "type": "int", "rust.unsigned": true, "minimum": -1}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'maximum' attribute of field 'a', expected a number in the range of the field type, found \"1\"",
+            err(r#"{"name": "a", "type": "double", "maximum": "1"}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'pattern' attribute of field 'a', expected a string field",
+            err(r#"{"name": "a", "type": "bytes", "pattern": "a"}"#)
+        );
+        #[cfg(feature = "pattern-checks")]
+        assert_eq!(
+            "Templating error: Invalid 'pattern' attribute of field 'a', expected a regular expression, found \"(\"",
+            err(r#"{"name": "a", "type": "string", "pattern": "("}"#)
+        );
+    }
+
+    #[test]
+    fn validate_derive() {
+        let err = Generator::builder()
+            .validators(true)
+            .validate_derive(ValidateDerive::Validator)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: `validate_derive` does not support `validators`",
+            err.to_string()
+        );
+
+        let raw_schema = r#"{"type": "record", "name": "R", "fields": [
+            {"name": "a", "type": "boolean", "maximum": 1}
+        ]}"#;
+        let err = Generator::builder()
+            .validate_derive(ValidateDerive::SerdeValid)
+            .build()
+            .unwrap()
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Invalid 'maximum' attribute of field 'a', expected an int, long, float or double field",
+            err.to_string()
+        );
+        assert_eq!(
+            ValidateDerive::SerdeValid,
+            "serde_valid".parse::<ValidateDerive>().unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_symbol_docs_attribute() {
+        let err = |symbol_docs: &str| {
+            let raw_schema = format!(
+                r#"{{"type": "enum", "name": "Light", "symbols": ["RED"], "symbolDocs": {symbol_docs}}}"#
+            );
+            let e = Generator::new()
+                .unwrap()
+                .gen(&Source::SchemaStr(&raw_schema), &mut vec![])
+                .unwrap_err();
+            assert_eq!("E0008", e.code(), "{e}");
+            e.to_string()
+        };
+        assert_eq!(
+            "Templating error: Invalid 'symbolDocs' attribute of enum 'Light', expected an object, found \"Stop.\"",
+            err(r#""Stop.""#)
+        );
+        assert_eq!(
+            "Templating error: Invalid 'symbolDocs' attribute of enum 'Light', expected string documentations, found 1",
+            err(r#"{"RED": 1}"#)
+        );
+        assert_eq!(
+            "Templating error: Unknown symbol 'GREEN' in the 'symbolDocs' attribute of enum 'Light'",
+            err(r#"{"GREEN": "Go."}"#)
+        );
+    }
+
+    #[test]
+    fn unsigned_fields() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Counters",
+  "fields": [
+    {"name": "id", "type": "long", "rust.unsigned": true},
+    {"name": "hits", "type": ["null", "int"], "default": null, "rust.unsigned": true}
+  ]
+}
+"#;
+        let g = Generator::builder()
+            .proptest_arbitrary(true)
+            .quickcheck_arbitrary(true)
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains("pub id: u64,"));
+        assert!(generated.contains("pub hits: Option<u32>,"));
+        assert!(generated.contains("#[serde(with = \"counters_unsigned::int_option\")]"));
+        assert!(generated.contains("(0..=i64::MAX as u64)"));
+        assert!(generated.contains("prop::option::of((0..=i32::MAX as u32))"));
+        assert!(generated.contains("id: <u64 as quickcheck::Arbitrary>::arbitrary(g) >> 1,"));
+
+        let invalid = |field: &str| {
+            let raw_schema =
+                format!(r#"{{"type": "record", "name": "Counters", "fields": [{field}]}}"#);
+            Generator::new()
+                .unwrap()
+                .gen(&Source::SchemaStr(&raw_schema), &mut vec![])
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            "Templating error: Invalid 'rust.unsigned' attribute of field 'name', expected an int or long field",
+            invalid(r#"{"name": "name", "type": "string", "rust.unsigned": true}"#)
+        );
+        assert_eq!(
+            "Templating error: Invalid default -1 of unsigned field 'hits'",
+            invalid(r#"{"name": "hits", "type": "int", "default": -1, "rust.unsigned": true}"#)
+        );
+        let err = Generator::builder()
+            .derive_schemas(true)
+            .build()?
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Unsigned field 'id' does not support `derive_schemas`",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn optional_fields() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Job",
+  "fields": [
+    {"name": "retries", "type": "int", "default": 3, "rust.unsigned": true},
+    {"name": "mode", "type": ["string", "long"], "default": "auto"},
+    {"name": "owner", "type": ["null", "string"], "default": null}
+  ]
+}
+"#;
+        let g = Generator::builder()
+            .optional_fields(OptionalFields::Defaults)
+            .round_trip_tests(true)
+            .quickcheck_arbitrary(true)
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains(
+            "#[serde(deserialize_with = \"present_job_retries\")]\n    \
+             #[serde(serialize_with = \"job_unsigned::int_option::serialize\")]\n    \
+             #[serde(skip_serializing_if = \"Option::is_none\")]\n    \
+             pub retries: Option<u32>,"
+        ));
+        assert!(generated.contains("job_unsigned::int::deserialize(deserializer).map(Some)"));
+        assert!(generated.contains("pub mode: Option<UnionStringLong>,"));
+        assert!(generated.contains("    pub mod int {"));
+        assert!(generated.contains("    pub owner: Option<String>,\n}"));
+        assert!(generated.contains(
+            "retries: if quickcheck::Arbitrary::arbitrary(g) { \
+             Some(<u32 as quickcheck::Arbitrary>::arbitrary(g) >> 1) } else { None },"
+        ));
+        // Unset fields are read back with their default value
+        assert!(!generated.contains("mod job_round_trip"));
+
+        let err = Generator::builder()
+            .optional_fields(OptionalFields::Defaults)
+            .derive_schemas(true)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: `optional_fields = defaults` does not support `derive_schemas`",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn box_threshold() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Outer",
+  "fields": [
+    {"name": "inner", "type": {
+      "type": "record",
+      "name": "Inner",
+      "fields": [
+        {"name": "blob", "type": {"type": "fixed", "name": "Blob", "size": 16}},
+        {"name": "small", "type": {"type": "record", "name": "Small", "fields": [
+          {"name": "flag", "type": "boolean"}
+        ]}, "rust.box": true}
+      ]
+    }, "default": {"blob": "aaaaaaaaaaaaaaaa", "small": {"flag": true}}},
+    {"name": "maybe", "type": ["null", "Inner"], "default": null}
+  ]
+}
+"#;
+        let g = Generator::builder()
+            .box_threshold(16)
+            .proptest_arbitrary(true)
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        // The boxed field of Inner counts as a pointer, Inner being 16 + 8 bytes
+        assert!(generated.contains("pub inner: Box<Inner>,"));
+        assert!(generated.contains("pub maybe: Option<Box<Inner>>,"));
+        assert!(generated.contains("pub small: Box<Small>,"));
+        assert!(generated.contains("small: Box::new(Small { flag: true, }), })"));
+        assert!(generated.contains(
+            "            any::<Inner>()\n                .prop_map(Box::new),\n            \
+             prop::option::of(any::<Inner>())\n                .prop_map(|v| v.map(Box::new)),"
+        ));
+
+        let g = Generator::builder().box_threshold(24).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains("pub inner: Inner,"));
+
+        let raw_schema = r#"
+{"type": "record", "name": "R", "fields": [{"name": "a", "type": "long", "rust.box": true}]}
+"#;
+        let err = Generator::new()?
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: Invalid 'rust.box' attribute of field 'a', expected a record field",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn smallvec_arrays() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Batch",
+  "fields": [
+    {"name": "ids", "type": {"type": "array", "items": "long"}, "rust.smallvec": 4},
+    {"name": "tags", "type": ["null", {"type": "array", "items": "string"}], "default": null}
+  ]
+}
+"#;
+        let g = Generator::builder().proptest_arbitrary(true).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains("pub ids: smallvec::SmallVec<[i64; 4]>,"));
+        assert!(generated.contains("pub tags: Option<Vec<String>>,"));
+        assert!(generated.contains(
+            "prop::collection::vec(any::<i64>(), 0..4)\n                \
+             .prop_map(smallvec::SmallVec::from_vec),"
+        ));
+
+        let invalid = [
+            (
+                r#"{"name": "a", "type": "long", "rust.smallvec": 4}"#,
+                "Invalid 'rust.smallvec' attribute of field 'a', expected an array field",
+            ),
+            (
+                r#"{"name": "a", "type": {"type": "array", "items": "long"}, "rust.smallvec": 0}"#,
+                "Invalid 'rust.smallvec' attribute of field 'a', expected a positive capacity, found 0",
+            ),
+        ];
+        for (field, expected) in invalid {
+            let raw_schema = format!(r#"{{"type": "record", "name": "R", "fields": [{field}]}}"#);
+            let err = Generator::new()?
+                .gen(&Source::SchemaStr(&raw_schema), &mut vec![])
+                .unwrap_err();
+            assert_eq!(format!("Templating error: {expected}"), err.to_string());
+        }
+
+        let err = Generator::builder()
+            .smallvec_arrays(2)
+            .derive_schemas(true)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: `smallvec_arrays` does not support `derive_schemas`",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn avro_schema_impls() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"{"type": "fixed", "name": "Md5", "size": 16}"#;
+        let g = Generator::builder()
+            .avro_schema_impls(true)
+            .avro_crate("avro")
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        // Fixed are type aliases of arrays, which cannot implement a foreign trait
+        assert!(!String::from_utf8(buf)?.contains("AvroSchema"));
+
+        let raw_schema = r#"{"type": "enum", "name": "Color", "symbols": ["RED"]}"#;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert!(String::from_utf8(buf)?
+            .contains("impl avro::AvroSchema for Color {\n    fn get_schema() -> avro::Schema {"));
+
+        let err = Generator::builder()
+            .avro_schema_impls(true)
+            .derive_schemas(true)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: `avro_schema_impls` does not support `derive_schemas`",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn avro_message_impls() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let g = Generator::builder()
+            .avro_message_impls(true)
+            .item_lints(ItemKind::Trait, "allow(dead_code)")
+            .build()?;
+        let kinds = |raw_schema: &str| -> Result<Vec<(ItemKind, String)>> {
+            g.items(&Source::SchemaStr(raw_schema))?
+                .map(|item| item.map(|item| (item.kind, item.name)))
+                .collect()
+        };
+        // Without record nor enum, the trait is not generated
+        assert_eq!(
+            vec![(ItemKind::Fixed, "Md5".to_string())],
+            kinds(r#"{"type": "fixed", "name": "Md5", "size": 16}"#)?
+        );
+        assert_eq!(
+            vec![
+                (ItemKind::Trait, "AvroMessage".to_string()),
+                (ItemKind::Enum, "Color".to_string())
+            ],
+            kinds(r#"{"type": "enum", "name": "Color", "symbols": ["RED"]}"#)?
+        );
+
+        let mut buf = vec![];
+        let raw_schema = r#"{"type": "enum", "name": "Color", "symbols": ["RED"]}"#;
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+        assert!(code.contains("#[allow(dead_code)]\npub trait AvroMessage {"));
+        assert!(code.contains("impl AvroMessage for Color {"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn union_json() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Reading",
+  "fields": [
+    {"name": "value", "type": ["int", "string"]}
+  ]
+}
+"#;
+        let g = Generator::builder()
+            .union_json(UnionJson::Untagged)
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+        assert!(code.contains("#[serde(untagged)]\n            enum Json {"));
+        assert!(!code.contains("is_human_readable() {\n            return Self::serialize"));
+
+        let g = Generator::builder()
+            .union_json(UnionJson::External)
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+        assert!(code.contains("is_human_readable() {\n            return Self::serialize"));
+        assert!(!code.contains("#[serde(untagged)]"));
+
+        let err = Generator::builder()
+            .union_json(UnionJson::Avro)
+            .serde_impls(false)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: `union_json` requires the serde impls",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn union_names() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Reading",
+  "fields": [
+    {"name": "value", "type": ["int", "double"]},
+    {"name": "unit", "type": ["string", "int"]}
+  ]
+}
+"#;
+        let g = Generator::builder()
+            .union_names("{Record}{Variants}")
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains("pub value: ReadingIntDouble,"));
+        assert!(generated.contains("pub unit: ReadingStringInt,"));
+
+        let err = Generator::builder()
+            .union_names("{Record}Union")
+            .build()?
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Templating error: The union of field 'unit' of record 'Reading' is named \
+             'ReadingUnion' like another union",
+            err.to_string()
+        );
+
+        let err = Generator::builder()
+            .union_names("{Record}{Name}")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: Unknown placeholder `{Name}` in `union_names`, expected \
+             `{Record}`, `{Field}` or `{Variants}`",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn item_lints() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Price",
+          "fields": [
+            {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 9, "scale": 2}}
+          ]
+        }
+        "#;
+        let g = Generator::builder()
+            .use_decimal_structs(true)
+            .item_lints(
+                ItemKind::Record,
+                " allow( dead_code,clippy::all ),deny(unsafe_code)",
+            )
+            .item_lints(ItemKind::Record, "expect(unused)")
+            .item_lints(
+                ItemKind::Decimal,
+                "allow(clippy::derived_hash_with_manual_eq)",
+            )
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains(
+            "#[allow(dead_code, clippy::all)]\n#[deny(unsafe_code)]\n#[expect(unused)]\n#[derive("
+        ));
+        assert!(generated.contains("#[allow(clippy::derived_hash_with_manual_eq)]\n#[derive("));
+
+        for attributes in [
+            "allow",
+            "allow()",
+            "cfg(test)",
+            "allow(dead_code) deny(unused)",
+        ] {
+            let err = Generator::builder()
+                .item_lints(ItemKind::Enum, attributes)
+                .build()
+                .unwrap_err();
+            assert_eq!(
+                format!(
+                    "Configuration error: Invalid lint attributes `{attributes}` for enum items, \
+                     expected e.g. `allow(dead_code)`"
+                ),
+                err.to_string()
+            );
+        }
+        assert_eq!(
+            "Configuration error: Unknown item kind `alias`, expected `record`, `enum`, `fixed`, \
+             `union`, `decimal`, `trait` or `validation`",
+            "alias".parse::<ItemKind>().unwrap_err().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn wrap_module() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"{"type": "enum", "name": "Color", "symbols": ["RED"]}"#;
+        let g = Generator::builder()
+            .wrap_module("avro_gen")
+            .allowed_lints(["non_camel_case_types"])
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.starts_with("pub mod avro_gen {\n#![allow(non_camel_case_types)]\n"));
+        assert!(generated.ends_with("}\n}\n"));
+
+        // Without lints, the module is left without attribute
+        let g = Generator::builder()
+            .wrap_module("avro_gen")
+            .allowed_lints(Vec::<String>::new())
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert!(String::from_utf8(buf)?.starts_with("pub mod avro_gen {\n\n"));
+
+        for (builder, expected) in [
+            (
+                Generator::builder().wrap_module("avro-gen"),
+                "Invalid `wrap_module` name `avro-gen`, expected a Rust identifier",
+            ),
+            (
+                Generator::builder()
+                    .wrap_module("avro_gen")
+                    .allowed_lints(["clippy::all)]"]),
+                "Invalid lint `clippy::all)]` in `allowed_lints`",
+            ),
+        ] {
+            let err = builder.build().unwrap_err();
+            assert_eq!(format!("Configuration error: {expected}"), err.to_string());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_name_mapper() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use crate::naming::sanitize;
+        use heck::ToUpperCamelCase;
+
+        struct Legacy;
+
+        impl NameMapper for Legacy {
+            fn type_name(&self, name: &str) -> String {
+                let name = name.strip_prefix("Avro").unwrap_or(name);
+                sanitize(name.to_upper_camel_case())
+            }
+            fn variant_name(&self, symbol: &str) -> String {
+                sanitize(symbol.to_string())
+            }
+        }
+
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "AvroUser",
+  "fields": [
+    {"name": "userId", "type": "long"},
+    {"name": "status", "type": {"type": "enum", "name": "AvroStatus", "symbols": ["IS_ACTIVE"]}, "default": "IS_ACTIVE"}
+  ]
+}
+"#;
+        let g = Generator::builder().name_mapper(Legacy).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains("pub enum Status {\n    IS_ACTIVE,\n}"));
+        assert!(generated.contains("pub struct User {"));
+        assert!(generated.contains("#[serde(rename = \"userId\")]\n    pub user_id: i64,"));
+        assert!(generated.contains("pub status: Status,"));
+        assert!(generated.contains("-> Status { Status::IS_ACTIVE }"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_type_mapper() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use apache_avro::schema::{RecordField, RecordSchema};
+
+        struct Unsigned;
+
+        impl TypeMapper for Unsigned {
+            fn map_type(&self, value_type: &str) -> String {
+                format!("::std::collections::BTreeMap<String, {value_type}>")
+            }
+            fn field_type(
+                &self,
+                _: &RecordSchema,
+                field: &RecordField,
+                rust_type: String,
+            ) -> String {
+                match field.custom_attributes.get("unsigned") {
+                    Some(serde_json::Value::Bool(true)) => rust_type.replace("i64", "u64"),
+                    _ => rust_type,
+                }
+            }
+        }
+
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Counters",
+  "fields": [
+    {"name": "total", "type": "long", "unsigned": true},
+    {"name": "delta", "type": "long"},
+    {"name": "by_key", "type": {"type": "map", "values": "long"}, "unsigned": true}
+  ]
+}
+"#;
+        let g = Generator::builder().type_mapper(Unsigned).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let generated = String::from_utf8(buf)?;
+        assert!(generated.contains("pub total: u64,"));
+        assert!(generated.contains("pub delta: i64,"));
+        assert!(generated.contains("pub by_key: ::std::collections::BTreeMap<String, u64>,"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn shared_across_threads() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let g = Generator::new()?;
+        let mut expected = vec![];
+        g.gen(
+            &Source::GlobPattern("tests/schemas/interop.avsc"),
+            &mut expected,
+        )?;
+
+        let outputs = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut buf = vec![];
+                        g.gen(&Source::GlobPattern("tests/schemas/interop.avsc"), &mut buf)
+                            .map(|_| buf)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .collect::<Result<Vec<_>>>()
+        })?;
+        for output in outputs {
+            assert_eq!(expected, output);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn protocol_errors() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let protocol = |errors: &str| {
+            format!(
+                r#"
+{{
+  "protocol": "Store",
+  "types": [
+    {{"type": "error", "name": "SaveError", "fields": [{{"name": "reason", "type": "string"}}]}},
+    {{"type": "error", "name": "Full", "fields": []}}
+  ],
+  "messages": {{"save": {{"request": [], "response": "null", "errors": {errors}}}}}
+}}
+"#
+            )
+        };
+        let pattern = dir.path().join("*.avpr");
+        let pattern = pattern.to_str().unwrap();
+        let g = Generator::new()?;
+
+        std::fs::write(
+            dir.path().join("store.avpr"),
+            protocol(r#"["Full", "string"]"#),
+        )?;
+        let e = g
+            .gen(&Source::GlobPattern(pattern), &mut vec![])
+            .unwrap_err();
+        assert_eq!("E0006", e.code());
+        assert!(e.to_string().contains("`SaveError`"), "{e}");
+
+        let raw = protocol(r#"["Missing"]"#).replace("SaveError", "Saved");
+        std::fs::write(dir.path().join("store.avpr"), raw)?;
+        let e = g
+            .gen(&Source::GlobPattern(pattern), &mut vec![])
+            .unwrap_err();
+        assert_eq!("E0003", e.code());
+
+        let raw = protocol(r#"["Full", "Full"]"#).replace("SaveError", "Saved");
+        std::fs::write(dir.path().join("store.avpr"), raw)?;
+        let e = g
+            .gen(&Source::GlobPattern(pattern), &mut vec![])
+            .unwrap_err();
+        assert_eq!("E0006", e.code());
+        assert!(e.to_string().contains("the `Full` variant"), "{e}");
+
+        let raw = protocol(r#"["Full", "other.Full"]"#)
+            .replace("SaveError", "Saved")
+            .replace(
+                r#"{"type": "error", "name": "Full", "fields": []}"#,
+                r#"{"type": "error", "name": "Full", "fields": []},
+    {"type": "error", "name": "Full", "namespace": "other", "fields": []}"#,
+            );
+        std::fs::write(dir.path().join("store.avpr"), raw)?;
+        let out = dir.path().join("out");
+        let e = g
+            .gen_namespaces(&Source::GlobPattern(pattern), &out)
+            .unwrap_err();
+        assert_eq!("E0006", e.code());
+        assert!(e.to_string().contains("the `Full` variant"), "{e}");
+
+        let raw = protocol(r#"["Full", "string"]"#).replace("SaveError", "Saved");
+        std::fs::write(dir.path().join("store.avpr"), raw)?;
+        let mut code = vec![];
+        g.gen(&Source::GlobPattern(pattern), &mut code)?;
+        let code = String::from_utf8(code)?;
+        assert!(code.contains("    Full(Full),\n"), "{code}");
+        assert!(code.contains("    String(String),\n"), "{code}");
+        Ok(())
+    }
+
+    #[test]
+    fn report() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let schema_file = dir.path().join("measure.avsc");
+        std::fs::write(
+            &schema_file,
+            r#"
+{
+  "type": "record",
+  "name": "Measure",
+  "fields": [
+    {"name": "value", "type": "double", "default": 1.23456},
+    {"name": "unit", "type": {"type": "enum", "name": "Unit", "symbols": ["M"]}}
+  ]
+}
+"#,
+        )?;
+
+        let pattern = dir.path().join("*.avsc");
+        let source = Source::GlobPattern(pattern.to_str().unwrap());
+        let mut buf = vec![];
+        let mut report = Generator::new()?.gen_with_report(&source, &mut buf)?;
+        report.set_output("measure.rs");
+
+        let names = report
+            .items
+            .iter()
+            .map(|i| i.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["Unit", "Measure"], names);
+        for item in &report.items {
+            assert_eq!(Some(&schema_file), item.source.as_ref());
+            assert_eq!(16, item.fingerprint.len());
+        }
+        assert_eq!(
+            vec!["Measure: Default value 1.23456 of field 'value' coerced to 1.235"],
+            report.warnings().collect::<Vec<_>>()
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()?)?;
+        assert_eq!("enum", json["items"][0]["kind"]);
+        assert_eq!("measure.rs", json["items"][1]["output"]);
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn stats() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Event",
+  "fields": [
+    {"name": "id", "type": {"type": "fixed", "name": "Id", "size": 4}},
+    {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A"]}},
+    {"name": "payload", "type": ["int", "string"]},
+    {"name": "ratio", "type": "float", "default": 1.23456}
+  ]
+}
+"#;
+        let mut buf = vec![];
+        let stats = Generator::new()?.gen_with_stats(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let mut code = vec![];
+        Generator::new()?.gen(&Source::SchemaStr(raw_schema), &mut code)?;
+        assert_eq!(code, buf);
+
+        assert_eq!(4, stats.schemas);
+        assert_eq!(
+            vec![
+                (ItemKind::Record, 1),
+                (ItemKind::Enum, 1),
+                (ItemKind::Fixed, 1),
+                (ItemKind::Union, 1)
+            ],
+            stats.items.clone().into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!((4, 1), (stats.total_items(), stats.warnings));
+        assert!(stats.to_string().starts_with(
+            "Generated 4 items from 4 schemas (1 record, 1 enum, 1 fixed, 1 union), 1 warning, in "
+        ));
+        assert!(stats.to_string().contains(" (parsing "));
+
+        let json = serde_json::to_value(&stats)?;
+        assert_eq!(
+            serde_json::json!({"record": 1, "enum": 1, "fixed": 1, "union": 1}),
+            json["items"]
+        );
+        assert!(json["parsing"].is_f64() && json["resolving"].is_f64());
+        Ok(())
+    }
+
+    #[test]
+    fn schema_timings() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Event",
+  "namespace": "com.acme",
+  "fields": [
+    {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A"]}},
+    {"name": "payload", "type": ["int", "string"]}
+  ]
+}
+"#;
+        let timings = crate::SchemaTimings::new();
+        let g = Generator::builder().progress(timings.clone()).build()?;
+        g.gen(&Source::SchemaStr(raw_schema), &mut vec![])?;
+
+        let mut names = timings
+            .slowest()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            vec![r#"["int","string"]"#, "com.acme.Event", "com.acme.Kind"],
+            names
+        );
+        let slowest = timings.slowest();
+        assert!(slowest.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        // Measures are reset by each generation
+        g.gen(
+            &Source::SchemaStr(r#"{"type": "fixed", "name": "F", "size": 1}"#),
+            &mut vec![],
+        )?;
+        assert_eq!(
+            vec!["F"],
+            timings
+                .slowest()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn manifest() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Event",
+  "namespace": "com.acme",
+  "fields": [
+    {"name": "id", "type": {"type": "fixed", "name": "Id", "size": 4}},
+    {"name": "payload", "type": ["int", "string"]}
+  ]
+}
+"#;
+        let mut report =
+            Generator::new()?.gen_with_report(&Source::SchemaStr(raw_schema), &mut vec![])?;
+        report.set_output("src/avro.rs");
+        let manifest = report.manifest("my_crate::avro");
+
+        assert_eq!(
+            vec!["com.acme.Event", "com.acme.Id"],
+            manifest.types.keys().collect::<Vec<_>>()
+        );
+        let json: serde_json::Value = serde_json::from_str(&manifest.to_json()?)?;
+        assert_eq!(
+            serde_json::json!({
+                "kind": "record",
+                "rust_path": "my_crate::avro::Event",
+                "output": "src/avro.rs",
+                "fingerprint": report.items.last().unwrap().fingerprint,
+            }),
+            json["types"]["com.acme.Event"]
+        );
+        assert_eq!(
+            "my_crate::avro::Id",
+            json["types"]["com.acme.Id"]["rust_path"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn incremental() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use crate::context::{EnumContext, FixedContext, RecordContext, UnionContext};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Counting(Arc<AtomicUsize>, TeraRenderer);
+
+        impl Renderer for Counting {
+            fn render_record(&self, ctx: &RecordContext) -> Result<String> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                self.1.render_record(ctx)
+            }
+            fn render_enum(&self, ctx: &EnumContext) -> Result<String> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                self.1.render_enum(ctx)
+            }
+            fn render_fixed(&self, ctx: &FixedContext) -> Result<String> {
+                self.1.render_fixed(ctx)
+            }
+            fn render_union(&self, ctx: &UnionContext) -> Result<String> {
+                self.1.render_union(ctx)
+            }
+        }
+
+        let dir = tempfile::tempdir()?;
+        let schema_a =
+            r#"{"type": "record", "name": "A", "fields": [{"name": "a", "type": "string"}]}"#;
+        std::fs::write(dir.path().join("a.avsc"), schema_a)?;
+        let schema_b = r#"{"type": "enum", "name": "B", "symbols": ["X"]}"#;
+        std::fs::write(dir.path().join("b.avsc"), schema_b)?;
+
+        let renders = Arc::new(AtomicUsize::new(0));
+        let g = Generator::builder()
+            .renderer(Counting(renders.clone(), TeraRenderer::new()?))
+            .build()?;
+        let pattern = dir.path().join("*.avsc");
+        let source = Source::GlobPattern(pattern.to_str().unwrap());
+        let (output, cache) = (dir.path().join("out.rs"), dir.path().join("cache.json"));
+
+        assert!(g.gen_incremental(&source, &output, &cache)?);
+        assert_eq!(2, renders.load(Ordering::SeqCst));
+        let mut expected = vec![];
+        g.gen(&source, &mut expected)?;
+        assert_eq!(
+            String::from_utf8(expected)?,
+            std::fs::read_to_string(&output)?
+        );
+        renders.store(0, Ordering::SeqCst);
+
+        assert!(!g.gen_incremental(&source, &output, &cache)?);
+        assert_eq!(0, renders.load(Ordering::SeqCst));
+
+        let schema_b = r#"{"type": "enum", "name": "B", "symbols": ["X", "Y"]}"#;
+        std::fs::write(dir.path().join("b.avsc"), schema_b)?;
+        assert!(g.gen_incremental(&source, &output, &cache)?);
+        assert_eq!(1, renders.load(Ordering::SeqCst));
+        assert!(std::fs::read_to_string(&output)?.contains("    Y,\n"));
+
+        let g = Generator::builder().derive_builders(true).build()?;
+        assert!(g.gen_incremental(&source, &output, &cache)?);
+        assert!(std::fs::read_to_string(&output)?.contains("derive_builder::Builder"));
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn out_dir() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let g = Generator::new()?;
+        let source = Source::SchemaStr(r#"{"type": "fixed", "name": "Id", "size": 4}"#);
+
+        let path = g.gen_into_dir(&source, dir.path(), "ids.rs")?;
+        assert_eq!(dir.path().join("ids.rs"), path);
+        let mut expected = vec![];
+        g.gen(&source, &mut expected)?;
+        assert_eq!(String::from_utf8(expected)?, std::fs::read_to_string(path)?);
+
+        g.gen_into_dir(&source, dir.path(), "other.rs")?;
+        g.gen_into_dir(&source, dir.path(), "ids.rs")?;
+        assert_eq!(
+            concat!(
+                "include!(concat!(env!(\"OUT_DIR\"), \"/\", \"ids.rs\"));\n",
+                "include!(concat!(env!(\"OUT_DIR\"), \"/\", \"other.rs\"));\n",
+            ),
+            std::fs::read_to_string(dir.path().join(OUT_DIR_INDEX))?
+        );
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn namespaces() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "User",
+          "namespace": "com.acme.Users",
+          "doc": "\nA user of the platform.\n\nCreated at sign-up.",
+          "fields": [
+            {"name": "address", "type": {"type": "record", "name": "Address", "namespace": "com.acme.common", "fields": [
+              {"name": "city", "type": "string"}
+            ]}},
+            {"name": "contact", "type": ["string", "long"]}
+          ]
+        }
+        "#;
+        let dir = tempfile::tempdir()?;
+        let out = dir.path().join("avro");
+        let paths = Generator::new()?.gen_namespaces(&Source::SchemaStr(raw_schema), &out)?;
+        assert_eq!(
+            vec![
+                out.join("mod.rs"),
+                out.join("com_acme_common.rs"),
+                out.join("com_acme_users.rs")
+            ],
+            paths
+        );
+        let index = fs::read_to_string(&paths[0])?;
+        assert!(index.starts_with(
+            "//! The generated Avro types, with a module per namespace:\n//!\n\
+             //! - [`com_acme_common`]: `com.acme.common`\n\
+             //! - [`com_acme_users`]: `com.acme.Users`\n\n\
+             pub mod com_acme_common;\npub use com_acme_common::*;\n\
+             pub mod com_acme_users;\npub use com_acme_users::*;\n"
+        ));
+        assert!(index.contains("pub enum UnionStringLong {"));
+        let users = fs::read_to_string(&paths[2])?;
+        assert!(users.starts_with(
+            "//! The types of the `com.acme.Users` namespace:\n//!\n\
+             //! - [`User`]: A user of the platform.\n\n\
+             #[allow(unused_imports)]\nuse super::*;\n"
+        ));
+        assert!(fs::read_to_string(&paths[1])?.starts_with(
+            "//! The types of the `com.acme.common` namespace:\n//!\n//! - [`Address`]\n\n"
+        ));
+        assert!(users.contains("pub struct User {"));
+        assert!(!users.contains("pub struct Address {"));
+
+        let raw_schema = r#"
+        {"type": "record", "name": "A", "namespace": "a.b_c", "fields": [
+          {"name": "b", "type": {"type": "fixed", "name": "B", "namespace": "a_b.c", "size": 2}}
+        ]}
+        "#;
+        let err = Generator::new()?
+            .gen_namespaces(&Source::SchemaStr(raw_schema), &out)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("are both written to the `a_b_c` module"));
+        let err = Generator::builder()
+            .wrap_module("avro")
+            .build()?
+            .gen_namespaces(&Source::SchemaStr(raw_schema), &out)
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: `wrap_module` is not supported when splitting namespaces",
+            err.to_string()
+        );
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn relative_references() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let user = r#"
+        {"type": "record", "name": "User", "namespace": "com.acme", "fields": [
+          {"name": "home", "type": "Address"},
+          {"name": "work", "type": {"type": "record", "name": "Office", "fields": [
+            {"name": "address", "type": "Address"}
+          ]}},
+          {"name": "billing", "type": ["null", "com.acme.Address"], "default": null}
+        ]}
+        "#;
+        let address = r#"
+        {"type": "record", "name": "Address", "namespace": "com.acme", "fields": [
+          {"name": "city", "type": "string"}
+        ]}
+        "#;
+        let documents = [("user.avsc", user), ("address.avsc", address)];
+        let dir = tempfile::tempdir()?;
+        let paths = Generator::new()?.gen_namespaces(&Source::Documents(&documents), dir.path())?;
+        assert_eq!(
+            vec![dir.path().join("mod.rs"), dir.path().join("com_acme.rs")],
+            paths
+        );
+        let acme = fs::read_to_string(&paths[1])?;
+        assert!(acme.contains("pub struct Office {\n    pub address: Address,\n}"));
+        assert!(acme.contains("pub home: Address,"));
+        assert!(acme.contains("pub billing: Option<Address>,"));
+        assert_eq!(1, acme.matches("pub struct Address {").count());
+
+        // The same name in another namespace is another schema
+        let other = r#"
+        {"type": "record", "name": "Address", "namespace": "com.other", "fields": [
+          {"name": "zip", "type": "int"}
+        ]}
+        "#;
+        let documents = [
+            ("user.avsc", user),
+            ("address.avsc", address),
+            ("other.avsc", other),
+        ];
+        let err = Generator::new()?
+            .gen(&Source::Documents(&documents), &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Schema error: Conflicting Rust types of the named schemas 'com.acme.Address' and \
+             'com.other.Address', both named 'Address'",
+            err.to_string()
+        );
+        assert_eq!("E0006", err.code());
+
+        // Types are written to the module of their namespace, the ambiguous ones being imported
+        // by the modules using them
+        let parcel = r#"
+        {"type": "record", "name": "Parcel", "namespace": "com.ship", "fields": [
+          {"name": "to", "type": ["null", "com.other.Address"], "default": null},
+          {"name": "label", "type": ["com.acme.Address", "string"]}
+        ]}
+        "#;
+        let documents = [
+            ("user.avsc", user),
+            ("address.avsc", address),
+            ("other.avsc", other),
+            ("parcel.avsc", parcel),
+        ];
+        let paths = Generator::new()?.gen_namespaces(&Source::Documents(&documents), dir.path())?;
+        let root = fs::read_to_string(&paths[0])?;
+        assert!(root.contains(
+            "pub mod com_other;\n#[allow(ambiguous_glob_reexports, unused_imports)]\n\
+             pub use com_other::*;\n"
+        ));
+        assert!(root.contains("#[allow(hidden_glob_reexports)]\nuse com_acme::Address;\n"));
+        assert!(root.contains("pub enum UnionAddressString {\n    Address(Address),"));
+        assert!(fs::read_to_string(dir.path().join("com_other.rs"))?
+            .contains("pub struct Address {\n    pub zip: i32,\n}"));
+        let ship = fs::read_to_string(dir.path().join("com_ship.rs"))?;
+        assert!(ship.contains("use super::*;\nuse super::com_other::Address;\n"));
+        assert!(ship.contains("pub to: Option<Address>,"));
+
+        let both = r#"
+        {"type": "record", "name": "Route", "namespace": "com.ship", "fields": [
+          {"name": "from", "type": "com.acme.Address"},
+          {"name": "to", "type": "com.other.Address"}
+        ]}
+        "#;
+        let documents = [
+            ("address.avsc", address),
+            ("other.avsc", other),
+            ("both.avsc", both),
+        ];
+        let err = Generator::new()?
+            .gen_namespaces(&Source::Documents(&documents), dir.path())
+            .unwrap_err();
+        assert_eq!(
+            "Schema error: Conflicting Rust types of the named schemas 'com.acme.Address' and \
+             'com.other.Address', both named 'Address' and used in the same module",
+            err.to_string()
+        );
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn in_place() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("ids.rs");
+        let g = Generator::new()?;
+        let source = Source::SchemaStr(r#"{"type": "fixed", "name": "Id", "size": 4}"#);
+        let mut code = vec![];
+        g.gen(&source, &mut code)?;
+        let code = String::from_utf8(code)?;
+
+        let before = "use std::fmt;\n\n    // <rsgen-avro:begin>\n";
+        let after = "    // <rsgen-avro:end>\n\nimpl fmt::Display for Id {}\n";
+        std::fs::write(&file, format!("{before}stale code\n{after}"))?;
+        assert!(g.gen_in_place(&source, &file)?);
+        assert_eq!(
+            format!("{before}{code}{after}"),
+            std::fs::read_to_string(&file)?
+        );
+        assert!(!g.gen_in_place(&source, &file)?);
+
+        std::fs::write(&file, "// <rsgen-avro:end>\n// <rsgen-avro:begin>\n")?;
+        let err = g.gen_in_place(&source, &file).unwrap_err().to_string();
+        assert!(err.ends_with("`// <rsgen-avro:end>` comment before `// <rsgen-avro:begin>`"));
+        std::fs::write(&file, "// <rsgen-avro:begin>\n")?;
+        let err = g.gen_in_place(&source, &file).unwrap_err().to_string();
+        assert!(err.ends_with("Missing `// <rsgen-avro:end>` comment"));
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn container_files() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "User",
+  "fields": [
+    {"name": "name", "type": "string"},
+    {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}}
+  ]
+}
+"#;
+        let dir = tempfile::tempdir()?;
+        let schema = Schema::parse_str(raw_schema)?;
+        let mut writer = apache_avro::Writer::new(&schema, vec![]);
+        let mut record = apache_avro::types::Record::new(&schema).unwrap();
+        record.put("name", "Jane");
+        record.put("kind", apache_avro::types::Value::Enum(1, "B".to_string()));
+        writer.append(record)?;
+        std::fs::write(dir.path().join("users.avro"), writer.into_inner()?)?;
+
+        let g = Generator::new()?;
+        let mut expected = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut expected)?;
+        let mut code = vec![];
+        let pattern = format!("{}/*.avro", dir.path().display());
+        g.gen(&Source::GlobPattern(&pattern), &mut code)?;
+        assert_eq!(String::from_utf8(expected)?, String::from_utf8(code)?);
+
+        std::fs::write(dir.path().join("users.avro"), raw_schema)?;
+        let err = g
+            .gen(&Source::GlobPattern(&pattern), &mut vec![])
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("users.avro is not an Avro object container file"));
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn gen_reader() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "User",
+  "fields": [
+    {"name": "tags", "type": {"type": "array", "items": ["int", "string"]}},
+    {"name": "country", "type": {"type": "enum", "name": "Country", "symbols": ["FR", "JP"]}}
+  ]
+}
+"#;
+        let g = Generator::new()?;
+        let mut expected = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut expected)?;
+
+        let mut buf = vec![];
+        g.gen_reader(&mut raw_schema.as_bytes(), &mut buf)?;
+        assert_eq!(String::from_utf8(expected)?, String::from_utf8(buf)?);
+
+        let err = g
+            .gen_reader(&mut r#"{"type": "record""#.as_bytes(), &mut vec![])
+            .unwrap_err();
+        assert_eq!("E0002", err.code(), "{err}");
+        let err = g
+            .gen_reader(&mut r#"{"type": "record"}"#.as_bytes(), &mut vec![])
+            .unwrap_err();
+        assert!(matches!(err, Error::Avro(_)), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn documents() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let kind = r#"{"type": "enum", "name": "Kind", "symbols": ["A", "B"]}"#;
+        let user = r#"
+{
+  "type": "record",
+  "name": "User",
+  "fields": [{"name": "kind", "type": "Kind"}]
+}
+"#;
+        let g = Generator::new()?;
+        let items = g
+            .items(&Source::Documents(&[
+                ("user.avsc", user),
+                ("kind.avsc", kind),
+            ]))?
+            .collect::<Result<Vec<_>>>()?;
+        let sources = items
+            .iter()
+            .map(|item| (item.name.as_str(), item.source.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("Kind", Some(Path::new("kind.avsc"))),
+                ("User", Some(Path::new("user.avsc")))
+            ],
+            sources
+        );
+
+        let err = g
+            .gen(&Source::Documents(&[("user.avsc", user)]), &mut vec![])
+            .unwrap_err();
+        assert!(err.to_string().contains("Kind"));
+        Ok(())
+    }
+
+    #[test]
+    fn shared_definitions() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let a = r#"
+{
+  "type": "record",
+  "name": "A",
+  "fields": [{"name": "k", "type": {"type": "enum", "name": "Kind", "symbols": ["X"]}}]
+}
+"#;
+        let b = a.replace(r#""A""#, r#""B""#);
+        let g = Generator::new()?;
+        let names = g
+            .items(&Source::Documents(&[("a.avsc", a), ("b.avsc", &b)]))?
+            .map(|item| item.map(|item| item.name))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(vec!["Kind", "B", "A"], names);
+
+        let b = b.replace(r#"["X"]"#, r#"["X", "Y"]"#);
+        let err = g
+            .gen(
+                &Source::Documents(&[("a.avsc", a), ("b.avsc", &b)]),
+                &mut vec![],
+            )
+            .unwrap_err();
+        assert_eq!(
+            "Schema error: Conflicting definitions of the named schema 'Kind'",
+            err.to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn located_errors() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let kind = r#"{"type": "enum", "name": "Kind", "symbols": ["A", "B"]}"#;
+        let user = r#"{
+  "type": "record",
+  "name": "User",
+  "fields": [
+    {"name": "kind", "type": "Kind"},
+    {"name": "ratio", "type": "double", "default": 1}
+  ]
+}"#;
+        let g = Generator::new()?;
+        let err = g
+            .gen(
+                &Source::Documents(&[("kind.avsc", kind), ("user.avsc", user)]),
+                &mut vec![],
+            )
+            .unwrap_err();
+        assert_eq!(
+            "Invalid default value of field `ratio` in record `User` at /fields/1/default: \
+             Expected a number with a fractional part, found 1
+ --> user.avsc:6:52
+  |
+6 |     {\"name\": \"ratio\", \"type\": \"double\", \"default\": 1}
+  |                                                    ^",
+            err.to_string()
+        );
+        assert!(matches!(
+            err,
+            Error::Located { error, line: 6, column: 52, .. }
+                if matches!(*error, Error::InvalidDefault { .. })
+        ));
+
+        let user = user.replace(r#""User","#, r#""User""#);
+        let err = g
+            .gen(
+                &Source::Documents(&[("kind.avsc", kind), ("user.avsc", &user)]),
+                &mut vec![],
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string().ends_with(
+                "at line 4 column 3
+ --> user.avsc:4:3
+  |
+4 |   \"fields\": [
+  |   ^"
+            ),
+            "{err}"
+        );
+
+        // Schemas which are not read from files have no location
+        let err = g
+            .gen(
+                &Source::SchemaStr(&user.replace("User\"", "User\",").replace("Kind", "string")),
+                &mut vec![],
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidDefault { .. }), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn field_order() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "R",
+  "fields": [
+    {"name": "z", "type": "int", "default": 1},
+    {"name": "a", "type": "string", "default": "x"},
+    {"name": "m", "type": {
+      "type": "record",
+      "name": "In",
+      "fields": [{"name": "q", "type": "int"}, {"name": "b", "type": "int"}]
+    }, "default": {"b": 2, "q": 1}}
+  ]
+}
+"#;
+        let mut buf = vec![];
+        Generator::new()?.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+
+        // Struct fields, defaults and field initializers follow the schema order
+        let positions = |needles: &[&str]| {
+            needles
+                .iter()
+                .map(|n| code.find(n).unwrap())
+                .collect::<Vec<_>>()
+        };
+        for needles in [
+            &["pub q: i32", "pub b: i32"][..],
+            &["pub z: i32", "pub a: String", "pub m: In"],
+            &["fn default_r_z", "fn default_r_a", "fn default_r_m"],
+            &["z: default_r_z()", "a: default_r_a()", "m: default_r_m()"],
+        ] {
+            let p = positions(needles);
+            assert!(p.windows(2).all(|w| w[0] < w[1]), "{needles:?}");
+        }
+        assert!(code.contains("In { q: 1, b: 2, }"));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_rendering() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schemas = (0..64)
+            .map(|i| {
+                let field_type = match i {
+                    0 => r#"["int", "string"]"#.to_string(),
+                    _ => format!(r#""R{}""#, (i - 1) / 2),
+                };
+                format!(
+                    r#"{{"type": "record", "name": "R{i}", "fields": [{{"name": "f", "type": {field_type}}}]}}"#
+                )
+            })
+            .collect::<Vec<_>>();
+        let raw_schemas = raw_schemas.iter().map(String::as_str).collect::<Vec<_>>();
+        let schemas = Schema::parse_list(&raw_schemas)?;
+        let g = Generator::new()?;
+
+        let mut parallel = vec![];
+        g.gen(&Source::Schemas(&schemas), &mut parallel)?;
+
+        // Only the first batch is rendered when the first item is requested
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let mut items = g.items(&Source::Schemas(&schemas))?;
+        assert!(items.next().is_some());
+        assert!(items.rendered.len() < threads * PARALLEL_BATCH);
+        if schemas.len() > threads * PARALLEL_BATCH {
+            assert!(!items.deps.is_empty());
+        }
+
+        // Items are rendered sequentially when using a render cache
+        let dir = tempfile::tempdir()?;
+        let output = dir.path().join("out.rs");
+        g.gen_incremental(
+            &Source::Schemas(&schemas),
+            &output,
+            dir.path().join("cache"),
+        )?;
+        assert_eq!(fs::read_to_string(output)?, String::from_utf8(parallel)?);
+        Ok(())
+    }
+
+    #[test]
+    fn nested_types() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "Nest",
+  "fields": [
+    {"name": "a", "type": {"type": "array", "items": {"type": "array", "items": ["int", "string"]}}},
+    {"name": "b", "type": {"type": "map", "values": {"type": "array", "items": ["null", "long"]}}},
+    {"name": "c", "type": ["null", {"type": "map", "values": {"type": "map", "values": ["long", "boolean"]}}]}
+  ]
+}
+"#;
+        let schema = Schema::parse_str(raw_schema)?;
+        let templater = Templater::new()?;
+
+        // Nested types are computed even if they are not registered in the state
+        let gen_state = GenState::new(&[])?;
+        let code = templater.str_record(&schema, &gen_state)?;
+        assert!(code.contains("pub a: Vec<Vec<UnionIntString>>,"));
+        assert!(code.contains("pub b: ::std::collections::HashMap<String, Vec<Option<i64>>>,"));
+        assert!(code.contains(
+            "pub c: Option<::std::collections::HashMap<String, ::std::collections::HashMap<String, UnionLongBoolean>>>,"
+        ));
+
+        let mut registered = GenState::new(&[])?;
+        registered.put_nested_types(std::slice::from_ref(&schema))?;
+        assert_eq!(code, templater.str_record(&schema, &registered)?);
+        let nested = Schema::parse_str(r#"{"type": "map", "values": ["long", "boolean"]}"#)?;
+        assert_eq!(
+            Some(&"::std::collections::HashMap<String, UnionLongBoolean>".to_string()),
+            registered.get_type(&nested)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_defaults() {
+        let err = |raw_schema: &str| {
+            Generator::new()
+                .unwrap()
+                .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+                .unwrap_err()
+                .to_string()
+        };
+
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "User",
+          "namespace": "com.acme",
+          "fields": [
+            {"name": "name", "type": "string", "default": ""},
+            {"name": "email", "type": ["null", "string"], "default": "none"}
+          ]
+        }
+        "#;
+        assert_eq!(
+            "Invalid default value of field `email` in record `com.acme.User` at /fields/1/default: \
+             Expected null, found \"none\"",
+            err(raw_schema)
+        );
+
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "User",
+          "fields": [
+            {"name": "scores", "type": {"type": "map", "values": {
+              "type": "record",
+              "name": "Score",
+              "fields": [{"name": "ratios", "type": {"type": "array", "items": "double"}, "default": [0.5, 1]}]
+            }}}
+          ]
+        }
+        "#;
+        assert_eq!(
+            "Invalid default value of field `ratios` in record `Score` at \
+             /fields/0/type/values/fields/0/default/1: Expected a number with a fractional part, found 1",
+            err(raw_schema)
+        );
+
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "User",
+          "fields": [
+            {"name": "ratios", "type": {"type": "map", "values": ["null", "double"]}, "default": {"a": 1}}
+          ]
+        }
+        "#;
+        assert_eq!(
+            "Invalid default value of field `ratios` in record `User` at /fields/0/default/a: \
+             Expected a value of a variant of the union, found 1",
+            err(raw_schema)
+        );
+
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "User",
+          "fields": [
+            {"name": "id", "type": {"type": "fixed", "name": "Id", "size": 4}, "default": "abc"}
+          ]
+        }
+        "#;
+        assert_eq!(
+            "Invalid default value of field `id` in record `User` at /fields/0/default: \
+             Expected a string of 4 bytes, found 3 bytes in \"abc\"",
+            err(raw_schema)
+        );
+
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "User",
+          "fields": [
+            {"name": "ids", "type": {"type": "array", "items": {"type": "fixed", "name": "Id", "size": 2}}, "default": ["ab", "c"]},
+            {"name": "keys", "type": {"type": "map", "values": "Id"}, "default": {"k": "abc"}}
+          ]
+        }
+        "#;
+        let error = Generator::new()
+            .unwrap()
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                Error::InvalidDefaultSize { record, field, pointer, size: 2, len: 1, literal }
+                    if record == "User" && field == "ids" && pointer == "/fields/0/default/1"
+                        && literal == "\"c\""
+            ),
+            "{error}"
+        );
+        let raw_schema = raw_schema.replace(r#"["ab", "c"]"#, r#"["ab"]"#);
+        assert_eq!(
+            "Invalid default value of field `keys` in record `User` at /fields/1/default/k: \
+             Expected a string of 2 bytes, found 3 bytes in \"abc\"",
+            err(&raw_schema)
+        );
+    }
+
+    #[test]
+    fn examples() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Event",
+          "namespace": "com.acme",
+          "fields": [
+            {"name": "id", "type": {"type": "string", "logicalType": "uuid"}},
+            {"name": "name", "type": "string"},
+            {"name": "count", "type": "int", "default": 42},
+            {"name": "ratio", "type": "double"},
+            {"name": "at", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}},
+            {"name": "hash", "type": {"type": "fixed", "name": "Hash", "size": 4}},
+            {"name": "payload", "type": "bytes"},
+            {"name": "tags", "type": {"type": "array", "items": "string"}},
+            {"name": "labels", "type": {"type": "map", "values": "long"}},
+            {"name": "note", "type": ["null", "string"]},
+            {"name": "parent", "type": ["null", "Event"], "default": null},
+            {"name": "children", "type": {"type": "array", "items": "Event"}}
+          ]
+        }
+        "#;
+        let g = Generator::new()?;
+        let paths = g.gen_examples(&Source::SchemaStr(raw_schema), dir.path())?;
+        assert_eq!(vec![dir.path().join("com.acme.Event.json")], paths);
+
+        let example: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&paths[0])?)?;
+        assert_eq!(
+            serde_json::json!({
+                "id": "00000000-0000-0000-0000-000000000000",
+                "name": "name",
+                "count": 42,
+                "ratio": 1.5,
+                "at": 0,
+                "kind": "A",
+                "hash": "0000",
+                "payload": "bytes",
+                "tags": ["tags"],
+                "labels": {"key": 1},
+                "note": "note",
+                "parent": null,
+                "children": [],
+            }),
+            example
+        );
+
+        let schema = Schema::parse_str(raw_schema)?;
+        apache_avro::types::Value::from(example).resolve(&schema)?;
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn bench() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Event",
+          "fields": [
+            {"name": "name", "type": "string"},
+            {"name": "source", "type": {"type": "record", "name": "Source", "fields": [
+              {"name": "host", "type": "string"}
+            ]}},
+            {"name": "payload", "type": ["int", "string"]}
+          ]
+        }
+        "#;
+        let source = Source::SchemaStr(raw_schema);
+
+        let mut buf = vec![];
+        Generator::new()?.gen_bench(&source, "my_crate::avro", &mut buf)?;
+        let harness = String::from_utf8(buf)?;
+        assert!(harness.contains("use my_crate::avro::*;\n"));
+        assert!(harness.contains("fn source(c: &mut Criterion) {\n"));
+        assert!(harness.contains("apache_avro::from_value::<Source>(&value).unwrap()"));
+        assert!(harness.contains("// Not benchmarked: `Event`.\n"));
+        assert!(
+            harness.ends_with("criterion_group!(benches, source);\ncriterion_main!(benches);\n")
+        );
+
+        let mut buf = vec![];
+        Generator::builder()
+            .use_avro_rs_unions(true)
+            .build()?
+            .gen_bench(&source, "my_crate::avro", &mut buf)?;
+        let harness = String::from_utf8(buf)?;
+        assert!(harness
+            .ends_with("criterion_group!(benches, source, event);\ncriterion_main!(benches);\n"));
+
+        let mut buf = vec![];
+        let raw_schema = r#"{"type": "enum", "name": "Kind", "symbols": ["A", "B"]}"#;
+        Generator::new()?.gen_bench(&Source::SchemaStr(raw_schema), "my_crate", &mut buf)?;
+        assert!(String::from_utf8(buf)?.ends_with("\nfn main() {}\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn registry() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Event",
+          "namespace": "ns",
+          "fields": [
+            {"name": "source", "type": {"type": "record", "name": "Source", "fields": [
+              {"name": "host", "type": "string"}
+            ]}},
+            {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}}
+          ]
+        }
+        "#;
+
+        let mut buf = vec![];
+        Generator::new()?.gen_registry(&Source::SchemaStr(raw_schema), "crate::avro", &mut buf)?;
+        let registry = String::from_utf8(buf)?;
+        assert!(registry.contains(
+            "pub enum Record {\n    Source(crate::avro::Source),\n    Event(crate::avro::Event),\n}"
+        ));
+        assert!(registry
+            .contains(r#"("ns.Event", "{\"fields\":[{\"name\":\"source\",\"type\":{\"fields\""#));
+        assert!(registry
+            .contains(r#""ns.Source" => Record::Source(apache_avro::from_value(&value)?),"#));
+        assert!(!registry.contains("Record::Kind"));
+        assert!(registry.contains("pub enum AnyMessage {\n    Event(crate::avro::Event),\n}"));
+        assert!(registry.contains(
+            "        Some(Record::Event(record)) => Some(AnyMessage::Event(record)),\n        _ => None,"
+        ));
+        assert!(!registry.contains("decode_payload"));
+
+        let g = Generator::new()?;
+        let schema_ids = [(7, "ns.Event"), (12, "ns.Source"), (13, "ns.Source")];
+        let mut buf = vec![];
+        g.gen_registry_with_ids(
+            &Source::SchemaStr(raw_schema),
+            "crate",
+            &schema_ids,
+            &mut buf,
+        )?;
+        let registry = String::from_utf8(buf)?;
+        assert!(registry.contains(
+            "const SCHEMA_IDS: &[(u32, &str)] = &[\n    (7, \"ns.Event\"),\n    (12, \"ns.Source\"),\n    (13, \"ns.Source\"),\n];"
+        ));
+        assert!(registry.contains(
+            "pub fn decode_payload(payload: &[u8]) -> Result<Option<Record>, apache_avro::Error> {"
+        ));
+        let err = |schema_ids: &[(u32, &str)]| {
+            g.gen_registry_with_ids(
+                &Source::SchemaStr(raw_schema),
+                "crate",
+                schema_ids,
+                &mut vec![],
+            )
+            .unwrap_err()
+            .to_string()
+        };
+        assert_eq!(
+            "Schema error: Schema id 3 maps to `ns.Kind`, which is not a generated record",
+            err(&[(3, "ns.Kind")])
+        );
+        assert_eq!(
+            "Schema error: Duplicate schema id 7",
+            err(&[(7, "ns.Event"), (7, "ns.Source")])
+        );
+
+        let mut buf = vec![];
+        let raw_schema = r#"{"type": "enum", "name": "Kind", "symbols": ["A", "B"]}"#;
+        Generator::new()?.gen_registry(&Source::SchemaStr(raw_schema), "crate", &mut buf)?;
+        let registry = String::from_utf8(buf)?;
+        assert!(registry.contains("pub enum Record {\n}"));
+        assert!(registry.contains("    let _ = (fullname, bytes);\n    Ok(None)\n"));
+        assert!(registry.contains("pub enum AnyMessage {\n}"));
+        assert!(registry.contains("    let _ = (schema_fullname, bytes);\n    Ok(None)\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn impls() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Event",
+          "fields": [
+            {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["a"]}, "default": "a"},
+            {"name": "inner", "type": {"type": "record", "name": "Inner", "fields": [
+              {"name": "payload", "type": ["bytes", "int"], "default": "ÿ"}
+            ]}, "default": {}}
+          ]
+        }
+        "#;
+        let gen =
+            |builder: GeneratorBuilder| -> std::result::Result<String, Box<dyn std::error::Error>> {
+                let mut buf = vec![];
+                builder
+                    .build()?
+                    .gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+                Ok(String::from_utf8(buf)?)
+            };
+
+        let code = gen(Generator::builder())?;
+        assert!(code.contains("#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]\n#[serde(default)]\npub struct Event {"));
+        assert!(code.contains("#[serde(rename = \"a\")]"));
+        assert!(code.contains("fn default_event_inner() -> Inner { Inner::default() }"));
+
+        let code = gen(Generator::builder()
+            .debug_impls(false)
+            .default_impls(false)
+            .serde_impls(false))?;
+        assert!(code.contains("#[derive(PartialEq, Eq, Clone)]\npub struct Event {"));
+        assert!(code
+            .contains("#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]\npub enum Kind {"));
+        assert!(code.contains(
+            "#[derive(PartialEq, Eq, Clone)]\npub enum UnionBytesInt {\n    Bytes(Vec<u8>),"
+        ));
+        assert!(code.contains(
+            "fn default_event_inner() -> Inner { Inner { payload: default_inner_payload(), } }"
+        ));
+        for absent in ["Debug", "serde", "impl Default"] {
+            assert!(!code.contains(absent), "{absent} in {code}");
+        }
+
+        let code = gen(Generator::builder().default_impls(false))?;
+        assert!(code.contains("#[serde(default = \"default_event_kind\")]"));
+        assert!(!code.contains("impl Default"));
+
+        let err = |builder: GeneratorBuilder| builder.build().unwrap_err().to_string();
+        assert_eq!(
+            "Configuration error: `round_trip_tests` requires the Debug, Default and serde impls",
+            err(Generator::builder()
+                .round_trip_tests(true)
+                .default_impls(false))
+        );
+        assert_eq!(
+            "Configuration error: `nullable` requires the serde impls",
+            err(Generator::builder().nullable(true).serde_impls(false))
+        );
+        assert_eq!(
+            "Configuration error: `avro_message_impls` requires the serde impls",
+            err(Generator::builder()
+                .avro_message_impls(true)
+                .serde_impls(false))
+        );
+        assert_eq!(
+            "Configuration error: `hex_debug` requires the Debug impls",
+            err(Generator::builder().hex_debug(true).debug_impls(false))
+        );
+        let g = Generator::builder().serde_impls(false).build()?;
+        assert_eq!(
+            "Configuration error: Generating a registry requires the serde impls",
+            g.gen_registry(&Source::SchemaStr(raw_schema), "crate", &mut vec![])
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "Configuration error: Generating an Avro JSON module requires the serde impls",
+            g.gen_avro_json(&mut vec![]).unwrap_err().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn strict_defaults() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Order",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "items", "type": {"type": "array", "items": "string"}},
+            {"name": "quantity", "type": "int", "default": 1}
+          ]
+        }
+        "#;
+        let gen =
+            |builder: GeneratorBuilder,
+             raw_schema: &str|
+             -> std::result::Result<(String, Vec<String>), Box<dyn std::error::Error>> {
+                let mut buf = vec![];
+                let report = builder
+                    .build()?
+                    .gen_with_report(&Source::SchemaStr(raw_schema), &mut buf)?;
+                Ok((String::from_utf8(buf)?, report.warnings().collect()))
+            };
+        let lenient = || Generator::builder().deserialization(DeserializationMode::Lenient);
+
+        let (code, warnings) = gen(lenient(), raw_schema)?;
+        assert!(code.contains("#[serde(default)]\npub struct Order {"));
+        assert!(code.contains("impl Default for Order {"));
+        assert_eq!(
+            vec![
+                "Order: Field 'id' has no default value, `Default` invents 0",
+                "Order: Field 'items' has no default value, `Default` invents vec![]",
+            ],
+            warnings
+        );
+
+        let (code, warnings) = gen(lenient().strict_defaults(true), raw_schema)?;
+        assert!(!code.contains("impl Default"), "{code}");
+        assert!(code.contains("#[serde(default = \"default_order_id\")]\n    pub id: i64,"));
+        assert!(warnings.is_empty());
+
+        let raw_schema = raw_schema
+            .replace(r#""long"}"#, r#""long", "default": 0}"#)
+            .replace(r#""string"}}"#, r#""string"}, "default": []}"#);
+        let (code, _) = gen(lenient().strict_defaults(true), &raw_schema)?;
+        assert!(code.contains("impl Default for Order {"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn doc_examples() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "User",
+          "doc": "A user.",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "email", "type": ["null", "string"]}
+          ]
+        }
+        "#;
+
+        let mut buf = vec![];
+        let g = Generator::builder()
+            .doc_examples("my_crate::avro")
+            .build()?;
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+        assert!(code.contains(
+            "/// A user.\n///\n/// # Examples\n///\n/// ```\n/// # use my_crate::avro::*;\n\
+             /// let record = User { id: 1, email: None, };\n"
+        ));
+        assert!(code.contains("/// // `schema` is the Avro schema of `User`\n/// # let schema"));
+        assert!(code.contains(
+            "/// let datum = apache_avro::to_avro_datum(&schema, apache_avro::to_value(&record)?.resolve(&schema)?)?;\n\
+             /// # Ok::<(), apache_avro::Error>(())\n/// ```\n"
+        ));
+
+        // Decimal examples are not resolved against the schema
+        let raw_decimal = r#"
+        {"type": "record", "name": "Price", "fields": [
+          {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 4}}
+        ]}
+        "#;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_decimal), &mut buf)?;
+        assert!(!String::from_utf8(buf)?.contains("# Examples"));
+
+        let mut buf = vec![];
+        let g = Generator::builder()
+            .doc_examples("crate")
+            .derive_schemas(true)
+            .build()?;
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+        assert!(
+            code.contains("/// let schema = <User as apache_avro::AvroSchema>::get_schema();\n")
+        );
+
+        let err = Generator::builder()
+            .doc_examples("crate")
+            .serde_impls(false)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "Configuration error: `doc_examples` requires the serde impls",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_structs() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Payment",
+          "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "amount", "type": {"type": "bytes", "logicalType": "decimal", "precision": 8, "scale": 3}}
+          ]
+        }
+        "#;
+        let g = Generator::builder()
+            .use_decimal_structs(true)
+            .deserialization(DeserializationMode::Lenient)
+            .build()?;
+        let items = g
+            .items(&Source::SchemaStr(raw_schema))?
+            .collect::<Result<Vec<_>>>()?;
+        let summary = items
+            .iter()
+            .map(|i| (i.kind, i.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (ItemKind::Decimal, "Decimal"),
+                (ItemKind::Record, "Payment")
+            ],
+            summary
+        );
+        assert!(items[1].code.contains("pub amount: Decimal,"));
+        assert!(items[1].code.contains(
+            "fn default_payment_amount() -> Decimal { Decimal { mantissa: 0, scale: 3 } }"
+        ));
+
+        let items = g
+            .items(&Source::SchemaStr(
+                r#"{"type": "record", "name": "Empty", "fields": []}"#,
+            ))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(1, items.len());
+
+        let err = |raw_schema: &str| {
+            g.items(&Source::SchemaStr(raw_schema))
+                .map(|_| ())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            "Configuration error: `use_decimal_structs` does not support decimals in arrays and maps, found in R.a",
+            err(
+                r#"{"type": "record", "name": "R", "fields": [{"name": "a", "type": {"type": "array", "items": {"type": "bytes", "logicalType": "decimal", "precision": 4, "scale": 2}}}]}"#
+            )
+        );
+        assert_eq!(
+            "Schema error: Decimal conflicts with the generated Decimal struct",
+            err(r#"{"type": "record", "name": "Decimal", "fields": []}"#)
+        );
+        assert_eq!(
+            "Schema error: DecimalError conflicts with the generated DecimalError enum",
+            err(r#"{"type": "enum", "name": "DecimalError", "symbols": ["A"]}"#)
+        );
+        assert_eq!(
+            "Configuration error: `use_decimal_structs` does not support `derive_schemas`",
+            Generator::builder()
+                .use_decimal_structs(true)
+                .derive_schemas(true)
+                .build()
+                .unwrap_err()
+                .to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rfc3339_timestamps() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let raw_schema = r#"
+        {
+          "type": "record",
+          "name": "Event",
+          "fields": [
+            {"name": "at", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "seen", "type": ["null", {"type": "long", "logicalType": "timestamp-micros"}], "default": null}
+          ]
+        }
+        "#;
+        let mut buf = vec![];
+        Generator::builder()
+            .use_chrono_dates(true)
+            .nullable(true)
+            .rfc3339_timestamps(true)
+            .build()?
+            .gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        let code = String::from_utf8(buf)?;
+        assert!(code.contains(
+            "#[serde(serialize_with = \"event_timestamps::ts_milliseconds::serialize\")]"
+        ));
+        assert!(code.contains("#[serde(with = \"event_timestamps::ts_microseconds_option\")]"));
+        assert!(code.contains("mod event_timestamps {\n    pub mod ts_microseconds_option {"));
+        assert!(code.contains("\n\n    pub mod ts_milliseconds {"));
+
+        let err = |builder: GeneratorBuilder| builder.build().unwrap_err().to_string();
+        assert_eq!(
+            "Configuration error: `rfc3339_timestamps` requires `use_chrono_dates`",
+            err(Generator::builder().rfc3339_timestamps(true))
+        );
+        assert_eq!(
+            "Configuration error: `rfc3339_timestamps` requires the serde impls",
+            err(Generator::builder()
+                .use_chrono_dates(true)
+                .rfc3339_timestamps(true)
+                .serde_impls(false))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_renderer() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use crate::context::{EnumContext, FixedContext, RecordContext, UnionContext};
+
+        struct Names;
+
+        impl Renderer for Names {
+            fn render_record(&self, ctx: &RecordContext) -> Result<String> {
+                Ok(format!("record {}({})\n", ctx.name, ctx.fields.join(", ")))
+            }
+            fn render_enum(&self, ctx: &EnumContext) -> Result<String> {
+                Ok(format!("enum {}\n", ctx.name))
+            }
+            fn render_fixed(&self, ctx: &FixedContext) -> Result<String> {
+                Ok(format!("fixed {}\n", ctx.name))
+            }
+            fn render_union(&self, _: &UnionContext) -> Result<String> {
+                Err(Error::Template("unions are not supported".to_string()))
+            }
+        }
+
+        let raw_schema = r#"
+{
+  "type": "record",
+  "name": "User",
+  "fields": [
+    {"name": "name", "type": "string"},
+    {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"]}}
+  ]
+}
+"#;
+        let g = Generator::builder().renderer(Names).build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert_eq!(
+            "enum Kind\nrecord User(name, kind)\n",
+            String::from_utf8(buf)?
+        );
+
+        let raw_schema = r#"{"type": "record", "name": "U", "fields": [{"name": "u", "type": ["int", "string"]}]}"#;
+        let err = g
+            .gen(&Source::SchemaStr(raw_schema), &mut vec![])
+            .unwrap_err();
+        assert!(matches!(err, Error::Template(msg) if msg == "unions are not supported"));
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "verify"))]
+    #[test]
+    fn verify_feature() {
+        let e = Generator::builder().verify(true).build().unwrap_err();
+        assert_eq!(
+            "Configuration error: `verify` requires the `verify` feature",
+            e.to_string()
+        );
+    }
+
+    #[cfg(feature = "verify")]
+    #[test]
+    fn verify() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use crate::context::{EnumContext, FixedContext, RecordContext, UnionContext};
+
+        struct Broken;
+
+        impl Renderer for Broken {
+            fn render_record(&self, ctx: &RecordContext) -> Result<String> {
+                Ok(format!(
+                    "/// A record.\npub struct {} {{\n    pub a: i32\n    pub b: i32,\n}}\n",
+                    ctx.name
+                ))
+            }
+            fn render_enum(&self, ctx: &EnumContext) -> Result<String> {
+                Ok(format!("pub enum {} {{}}\n", ctx.name))
+            }
+            fn render_fixed(&self, ctx: &FixedContext) -> Result<String> {
+                Ok(format!("pub type {} = [u8; 1];\n", ctx.name))
+            }
+            fn render_union(&self, ctx: &UnionContext) -> Result<String> {
+                Ok(format!("pub enum {} {{}}\n", ctx.name))
+            }
+        }
+
+        let source = Source::SchemaStr(
+            r#"{"type": "record", "name": "User", "fields": [{"name": "a", "type": "int"}]}"#,
+        );
+        Generator::builder()
+            .renderer(Broken)
+            .build()?
+            .gen(&source, &mut vec![])?;
+
+        let err = Generator::builder()
+            .renderer(Broken)
+            .verify(true)
+            .build()?
+            .gen(&source, &mut vec![])
+            .unwrap_err();
+        assert_eq!(
+            "Invalid Rust code generated for `User`: expected `,`\n  \
+             \x20  2 | pub struct User {\n  \
+             \x20  3 |     pub a: i32\n\
+             >    4 |     pub b: i32,\n  \
+             \x20  5 | }",
+            err.to_string()
+        );
+
+        let g = Generator::builder().verify(true).build()?;
+        for schema in ["complex", "interop", "nested_record_default", "recursive"] {
+            let pattern = format!("tests/schemas/{schema}.avsc");
+            g.gen(&Source::GlobPattern(&pattern), &mut vec![])?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn dumped_templates() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        crate::dump_templates(dir.path())?;
+
+        let source = Source::GlobPattern("tests/schemas/interop.avsc");
+        let mut expected = vec![];
+        Generator::new()?.gen(&source, &mut expected)?;
+        let mut dumped = vec![];
+        Generator::builder()
+            .templates_dir(dir.path())
+            .build()?
+            .gen(&source, &mut dumped)?;
+        assert_eq!(String::from_utf8(expected)?, String::from_utf8(dumped)?);
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn custom_filters_and_functions() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::Write;
+        use tempfile::tempdir;
+
+        let dir = tempdir()?;
+        let mut enum_tera = File::create(dir.path().join("enum.tera"))?;
+        enum_tera.write_all(b"// {{ header() }}\npub enum {{ name | acme }} {}\n")?;
+
+        let raw_schema = r#"{"type": "enum", "name": "color", "symbols": ["RED"]}"#;
+        let g = Generator::builder()
+            .templates_dir(dir.path())
+            .register_filter("acme", |value: &tera::Value, _: &_| {
+                Ok(format!("Acme{}", value.as_str().unwrap_or_default()).into())
+            })
+            .register_function("header", |_: &_| Ok("generated".into()))
+            .build()?;
+        let mut buf = vec![];
+        g.gen(&Source::SchemaStr(raw_schema), &mut buf)?;
+        assert_eq!(
+            "// generated\npub enum AcmeColor {}\n",
+            String::from_utf8(buf)?
+        );
+
+        drop(enum_tera);
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn cross_deps() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use std::fs::File;
+        use std::io::Write;
+        use tempfile::tempdir;
+
+        let dir = tempdir()?;
+
+        let mut schema_a_file = File::create(dir.path().join("schema_a.avsc"))?;
+        let schema_a_str = r#"
+{
+  "name": "A",
+  "type": "record",
+  "fields": [ {"name": "field_one", "type": "float"} ]
+}
+"#;
+        schema_a_file.write_all(schema_a_str.as_bytes())?;
+
+        let mut schema_b_file = File::create(dir.path().join("schema_b.avsc"))?;
+        let schema_b_str = r#"
+{
+  "name": "B",
+  "type": "record",
+  "fields": [ {"name": "field_one", "type": "A"} ]
+}
+"#;
+        schema_b_file.write_all(schema_b_str.as_bytes())?;
+
+        let expected = r#"
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct B {
+    pub field_one: A,
+}
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct A {
+    pub field_one: f32,
+}
+"#;
+
+        let pattern = format!("{}/*.avsc", dir.path().display());
+        let source = Source::GlobPattern(pattern.as_str());
+        let g = Generator::new()?;
+        let mut buf = vec![];
+        g.gen(&source, &mut buf)?;
+        let res = String::from_utf8(buf)?;
+
+        assert_eq!(expected, res);
 
         drop(schema_a_file);
         drop(schema_b_file);
         dir.close()?;
         Ok(())
     }
+
+    #[test]
+    fn versions() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let v1 = r#"
+        {"type": "record", "name": "User", "namespace": "test", "fields": [
+          {"name": "id", "type": "int"},
+          {"name": "name", "type": "string"},
+          {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B", "C"]}},
+          {"name": "email", "type": ["null", "string"]},
+          {"name": "tags", "type": {"type": "array", "items": "int"}},
+          {"name": "value", "type": ["int", "string"]},
+          {"name": "score", "type": "float"}
+        ]}
+"#;
+        let v2 = r#"
+        {"type": "record", "name": "User", "namespace": "test", "fields": [
+          {"name": "id", "type": "long"},
+          {"name": "full_name", "aliases": ["name"], "type": "string"},
+          {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["A", "B"], "default": "A"}},
+          {"name": "email", "type": ["null", "string"]},
+          {"name": "tags", "type": {"type": "array", "items": "long"}},
+          {"name": "value", "type": ["null", "long", "string"]},
+          {"name": "score", "type": ["null", "double"]},
+          {"name": "age", "type": "int", "default": 0}
+        ]}
+"#;
+
+        let g = Generator::new()?;
+        let mut buf = vec![];
+        g.gen_versions(&[Source::SchemaStr(v1), Source::SchemaStr(v2)], &mut buf)?;
+        let code = String::from_utf8(buf)?;
+
+        assert!(code.starts_with("pub mod v1 {\n"));
+        assert!(code.contains("}\npub mod v2 {\n"));
+        assert!(code.contains(
+            r#"
+impl From<super::v1::Kind> for Kind {
+    fn from(v: super::v1::Kind) -> Self {
+        match v {
+            super::v1::Kind::A => Kind::A,
+            super::v1::Kind::B => Kind::B,
+            super::v1::Kind::C => Kind::A,
+        }
+    }
+}
+"#
+        ));
+        assert!(code.ends_with(
+            r#"
+impl From<super::v1::User> for User {
+    fn from(v: super::v1::User) -> Self {
+        User {
+            id: i64::from(v.id),
+            full_name: v.name,
+            kind: v.kind.into(),
+            email: v.email,
+            tags: v.tags.into_iter().map(|v| i64::from(v)).collect(),
+            value: match v.value { super::v1::UnionIntString::Int(v) => Some(UnionLongString::Long(i64::from(v))), super::v1::UnionIntString::String(v) => Some(UnionLongString::String(v)) },
+            score: Some(f64::from(v.score)),
+            age: 0,
+        }
+    }
+}
+}
+"#
+        ));
+
+        let v3 = v2.replace(
+            r#""default": 0"#,
+            r#""default": 0}, {"name": "b", "type": "int""#,
+        );
+        let err = g
+            .gen_versions(
+                &[Source::SchemaStr(v2), Source::SchemaStr(&v3)],
+                &mut vec![],
+            )
+            .unwrap_err();
+        assert_eq!(
+            "Schema error: Field `User.b` of v2 has no default and is missing from v1",
+            err.to_string()
+        );
+
+        let other = v1.replace(r#""namespace": "test""#, r#""namespace": "other""#);
+        let mut buf = vec![];
+        g.gen_versions(
+            &[Source::SchemaStr(&other), Source::SchemaStr(v2)],
+            &mut buf,
+        )?;
+        assert!(!String::from_utf8(buf)?.contains("impl From<super::v1::User>"));
+
+        let aliased = v2
+            .replace(
+                r#""namespace": "test", "fields""#,
+                r#""namespace": "test", "aliases": ["other.User"], "fields""#,
+            )
+            .replace(
+                r#""name": "Kind","#,
+                r#""name": "Kind", "aliases": ["other.Kind"],"#,
+            );
+        let mut buf = vec![];
+        g.gen_versions(
+            &[Source::SchemaStr(&other), Source::SchemaStr(&aliased)],
+            &mut buf,
+        )?;
+        assert!(String::from_utf8(buf)?.contains("impl From<super::v1::User> for User"));
+
+        let bytes = v1.replace(
+            r#""name": "name", "type": "string""#,
+            r#""name": "name", "type": "bytes""#,
+        );
+        let mut buf = vec![];
+        g.gen_versions(
+            &[Source::SchemaStr(&bytes), Source::SchemaStr(v2)],
+            &mut buf,
+        )?;
+        assert!(String::from_utf8(buf)?.contains(
+            r#"
+/// Bytes which are not valid UTF-8 are converted lossily, invalid sequences being replaced with `U+FFFD`: `User.full_name`.
+impl From<super::v1::User> for User {
+    fn from(v: super::v1::User) -> Self {
+        User {
+            id: i64::from(v.id),
+            full_name: String::from_utf8_lossy(&v.name).into_owned(),
+"#
+        ));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,274 @@
+//! Inference of Avro schemas from sample JSON documents.
+
+use heck::ToUpperCamelCase;
+use serde_json::{json, Map, Value};
+
+use crate::error::{Error, Result};
+use crate::Schema;
+
+/// Infers the Avro schema of records named `name` from sample JSON objects, e.g. existing
+/// payloads, to bootstrap a contract before generating Rust types from it.
+///
+/// The schema is widened to accept all the samples:
+/// * integers are `long`, and become `double` when mixed with floating-point numbers,
+/// * fields which are missing from some samples, or are null, are optional,
+/// * values of different types become unions, objects being merged into a single record.
+///
+/// Fields are sorted by name, and nested records are named after their parent record and
+/// field, e.g. `UserAddress`. JSON objects are always inferred as records, and the items of
+/// empty arrays as `null`.
+pub fn infer_schema(name: &str, namespace: Option<&str>, samples: &[Value]) -> Result<Schema> {
+    let mut inferred: Option<Inferred> = None;
+    for (i, sample) in samples.iter().enumerate() {
+        if !sample.is_object() {
+            return Err(Error::Schema(format!(
+                "Sample {i} is not a JSON object: {sample}"
+            )));
+        }
+        let sample = Inferred::of(sample, &format!("/{i}"))?;
+        inferred = Some(match inferred {
+            None => sample,
+            Some(inferred) => inferred.merge(sample),
+        });
+    }
+    let Some(inferred) = inferred else {
+        return Err(Error::Schema(
+            "No sample to infer a schema from".to_string(),
+        ));
+    };
+
+    let mut schema = inferred.to_json(name);
+    if let (Some(namespace), Value::Object(schema)) = (namespace, &mut schema) {
+        schema.insert("namespace".to_string(), json!(namespace));
+    }
+    Ok(Schema::parse(&schema)?)
+}
+
+/// The type inferred from some JSON values.
+#[derive(Debug, Clone, PartialEq)]
+enum Inferred {
+    Null,
+    Boolean,
+    Long,
+    Double,
+    String,
+    /// An array with the type of its items, `None` if all arrays were empty.
+    Array(Option<Box<Inferred>>),
+    /// A record with its fields, and whether they were found in all the objects.
+    Record(Vec<(String, Inferred, bool)>),
+    /// A union of distinct kinds of types, none of them a union.
+    Union(Vec<Inferred>),
+}
+
+impl Inferred {
+    /// Infers the type of a JSON value found at the JSON `pointer` of the samples.
+    fn of(value: &Value, pointer: &str) -> Result<Inferred> {
+        Ok(match value {
+            Value::Null => Inferred::Null,
+            Value::Bool(_) => Inferred::Boolean,
+            Value::Number(n) if n.is_f64() => Inferred::Double,
+            Value::Number(_) => Inferred::Long,
+            Value::String(_) => Inferred::String,
+            Value::Array(values) => {
+                let mut items: Option<Inferred> = None;
+                for (i, value) in values.iter().enumerate() {
+                    let item = Inferred::of(value, &format!("{pointer}/{i}"))?;
+                    items = Some(match items {
+                        None => item,
+                        Some(items) => items.merge(item),
+                    });
+                }
+                Inferred::Array(items.map(Box::new))
+            }
+            Value::Object(fields) => {
+                let mut inferred = vec![];
+                for (name, value) in fields {
+                    if !is_valid_name(name) {
+                        return Err(Error::Schema(format!(
+                            "Key `{name}` at {pointer} is not a valid Avro field name"
+                        )));
+                    }
+                    let value = Inferred::of(value, &format!("{pointer}/{name}"))?;
+                    inferred.push((name.clone(), value, true));
+                }
+                Inferred::Record(inferred)
+            }
+        })
+    }
+
+    /// Returns the type accepting the values of both types.
+    fn merge(self, other: Inferred) -> Inferred {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Inferred::Union(variants), other) | (other, Inferred::Union(variants)) => {
+                let others = match other {
+                    Inferred::Union(others) => others,
+                    other => vec![other],
+                };
+                let mut variants = variants;
+                for other in others {
+                    match variants.iter().position(|v| v.same_kind(&other)) {
+                        Some(i) => {
+                            let variant = variants.remove(i);
+                            variants.insert(i, variant.merge(other));
+                        }
+                        None => variants.push(other),
+                    }
+                }
+                Inferred::Union(variants)
+            }
+            (Inferred::Long, Inferred::Double) | (Inferred::Double, Inferred::Long) => {
+                Inferred::Double
+            }
+            (Inferred::Array(a), Inferred::Array(b)) => Inferred::Array(match (a, b) {
+                (Some(a), Some(b)) => Some(Box::new(a.merge(*b))),
+                (a, b) => a.or(b),
+            }),
+            (Inferred::Record(mut fields), Inferred::Record(others)) => {
+                for (name, _, always) in &mut fields {
+                    *always &= others.iter().any(|(n, _, _)| n == name);
+                }
+                for (name, other, other_always) in others {
+                    match fields.iter_mut().find(|(n, _, _)| *n == name) {
+                        Some((_, value, always)) => {
+                            *value = std::mem::replace(value, Inferred::Null).merge(other);
+                            *always &= other_always;
+                        }
+                        None => fields.push((name, other, false)),
+                    }
+                }
+                Inferred::Record(fields)
+            }
+            (a, b) => Inferred::Union(vec![a]).merge(b),
+        }
+    }
+
+    /// Returns whether two types are merged into the same union variant.
+    fn same_kind(&self, other: &Inferred) -> bool {
+        matches!(
+            (self, other),
+            (
+                Inferred::Long | Inferred::Double,
+                Inferred::Long | Inferred::Double
+            ) | (Inferred::Array(_), Inferred::Array(_))
+                | (Inferred::Record(_), Inferred::Record(_))
+        ) || self == other
+    }
+
+    /// Returns the JSON Avro schema of the type, records being named `name`.
+    fn to_json(&self, name: &str) -> Value {
+        match self {
+            Inferred::Null => json!("null"),
+            Inferred::Boolean => json!("boolean"),
+            Inferred::Long => json!("long"),
+            Inferred::Double => json!("double"),
+            Inferred::String => json!("string"),
+            Inferred::Array(items) => json!({
+                "type": "array",
+                "items": items.as_ref().map_or(json!("null"), |items| items.to_json(name)),
+            }),
+            Inferred::Record(fields) => {
+                let mut fields = fields.iter().collect::<Vec<_>>();
+                fields.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+                let fields = fields
+                    .into_iter()
+                    .map(|(field, value, always)| {
+                        let record = format!("{name}{}", field.to_upper_camel_case());
+                        let mut schema = Map::new();
+                        schema.insert("name".to_string(), json!(field));
+                        if *always && !value.is_nullable() {
+                            schema.insert("type".to_string(), value.to_json(&record));
+                        } else {
+                            schema.insert("type".to_string(), value.optional().to_json(&record));
+                            schema.insert("default".to_string(), Value::Null);
+                        }
+                        Value::Object(schema)
+                    })
+                    .collect::<Vec<_>>();
+                json!({"type": "record", "name": name, "fields": fields})
+            }
+            Inferred::Union(variants) => {
+                // Only the first variant can be the type of defaults, null for optional fields
+                let mut variants = variants.iter().collect::<Vec<_>>();
+                variants.sort_by_key(|v| **v != Inferred::Null);
+                Value::Array(variants.iter().map(|v| v.to_json(name)).collect())
+            }
+        }
+    }
+
+    /// Returns whether the type accepts null.
+    fn is_nullable(&self) -> bool {
+        match self {
+            Inferred::Null => true,
+            Inferred::Union(variants) => variants.contains(&Inferred::Null),
+            _ => false,
+        }
+    }
+
+    /// Returns the type also accepting null.
+    fn optional(&self) -> Inferred {
+        match self {
+            Inferred::Null => Inferred::Null,
+            other => other.clone().merge(Inferred::Null),
+        }
+    }
+}
+
+/// Returns whether a name is a valid Avro name.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inference() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let samples = [
+            json!({"id": 1, "name": "Jane", "score": 1, "tags": [], "address": {"city": "Paris"}}),
+            json!({"id": 2, "name": null, "score": 2.5, "tags": ["a"], "extra": true,
+                   "address": {"city": "Lyon", "zip": 69000}, "value": 1}),
+            json!({"id": 3, "name": "John", "score": 3, "tags": [], "address": {"city": "Nice"},
+                   "value": "x"}),
+        ];
+        let schema = infer_schema("User", Some("com.acme"), &samples)?;
+        let expected = Schema::parse_str(
+            r#"
+        {"type": "record", "name": "User", "namespace": "com.acme", "fields": [
+          {"name": "address", "type": {"type": "record", "name": "UserAddress", "fields": [
+            {"name": "city", "type": "string"},
+            {"name": "zip", "type": ["null", "long"], "default": null}
+          ]}},
+          {"name": "extra", "type": ["null", "boolean"], "default": null},
+          {"name": "id", "type": "long"},
+          {"name": "name", "type": ["null", "string"], "default": null},
+          {"name": "score", "type": "double"},
+          {"name": "tags", "type": {"type": "array", "items": "string"}},
+          {"name": "value", "type": ["null", "long", "string"], "default": null}
+        ]}
+        "#,
+        )?;
+        assert_eq!(expected.canonical_form(), schema.canonical_form());
+        assert_eq!(
+            serde_json::to_value(&expected)?,
+            serde_json::to_value(&schema)?
+        );
+
+        let err = infer_schema("User", None, &[json!({"first-name": "Jane"})]).unwrap_err();
+        assert_eq!(
+            "Schema error: Key `first-name` at /0 is not a valid Avro field name",
+            err.to_string()
+        );
+        let err = infer_schema("User", None, &[json!([1])]).unwrap_err();
+        assert_eq!(
+            "Schema error: Sample 0 is not a JSON object: [1]",
+            err.to_string()
+        );
+        Ok(())
+    }
+}
@@ -1,11 +1,89 @@
 #![doc = include_str!("../README.md")]
 
+mod archive;
+mod avro_json;
+mod bench;
+mod cache;
+mod canonical;
+mod codes;
+mod compat;
+mod config;
+mod context;
+mod decimal;
+mod defaults;
 mod error;
+mod examples;
+mod fingerprint;
 mod gen;
+mod infer;
+mod message;
+mod naming;
+mod progress;
+mod protocol;
+mod registry;
+mod render;
+mod report;
+#[cfg(feature = "reverse")]
+mod reverse;
+#[cfg(feature = "snapshots")]
+mod snapshot;
+mod span;
 mod templates;
+mod types;
+mod validate;
+mod verify;
+mod versions;
 
-pub use crate::error::{Error, Result};
-pub use crate::gen::{Generator, GeneratorBuilder, Source};
+pub use crate::canonical::canonical_form;
+pub use crate::codes::explain_error;
+pub use crate::compat::{check_compatibility, CompatibilityMode, Incompatibility};
+pub use crate::config::{Config, ConfigTarget, CONFIG_FILE};
+pub use crate::context::{
+    EnumContext, FixedContext, RecordContext, UnionContext, UnionVisitorContext,
+};
+pub use crate::error::{Error, ErrorKind, Result};
+pub use crate::fingerprint::rabin_fingerprint;
+#[cfg(feature = "fingerprints")]
+pub use crate::fingerprint::{md5_fingerprint, sha256_fingerprint};
+pub use crate::gen::{
+    GeneratedItem, GeneratedItems, Generator, GeneratorBuilder, ItemKind, Source,
+    DEFAULT_ALLOWED_LINTS, OUT_DIR_INDEX, REGION_BEGIN, REGION_END,
+};
+pub use crate::infer::infer_schema;
+pub use crate::naming::{sanitize, DefaultNameMapper, NameMapper};
+pub use crate::progress::{CancellationToken, Progress, SchemaTimings};
+pub use crate::render::{Renderer, TeraRenderer};
+pub use crate::report::{Manifest, ManifestEntry, Report, ReportItem, Stats};
+#[cfg(feature = "reverse")]
+pub use crate::reverse::schemas_from_rust;
+pub use crate::templates::{
+    dump_templates, DeserializationMode, OptionalFields, UnionJson, ValidateDerive,
+};
+pub use crate::types::{DefaultTypeMapper, TypeMapper, TypeOptions};
 
 pub use apache_avro;
 pub use apache_avro::Schema;
+pub use tera;
+
+/// Includes Rust types generated into `OUT_DIR` by
+/// [`Generator::gen_out_dir`](Generator::gen_out_dir) from a build script.
+///
+/// Without argument, all the generated files are included through their index file.
+/// A single generated file can also be included by name:
+///
+/// ```ignore
+/// rsgen_avro::include_generated!();
+///
+/// mod users {
+///     rsgen_avro::include_generated!("users.rs");
+/// }
+/// ```
+#[macro_export]
+macro_rules! include_generated {
+    () => {
+        include!(concat!(env!("OUT_DIR"), "/rsgen_avro.rs"));
+    };
+    ($file_name:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/", $file_name));
+    };
+}
@@ -1,21 +1,35 @@
 use std::error::Error;
 use std::fs::OpenOptions;
-use std::io::{prelude::*, stdout};
-use std::path::PathBuf;
+use std::io::{prelude::*, stdin, stdout};
+use std::path::{Path, PathBuf};
 use std::process::{self, Command};
+use std::time::Duration;
 
 use clap::Parser;
-use rsgen_avro::{Generator, Source};
+use rsgen_avro::{
+    check_compatibility, infer_schema, schemas_from_rust, CompatibilityMode, DeserializationMode,
+    Generator, ItemKind, OptionalFields, Schema, SchemaTimings, Source, Stats, UnionJson,
+    ValidateDerive,
+};
 
 /// Generate Rust types from Avro schemas
 #[derive(Debug, Parser)]
-#[command(version)]
+#[command(
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Args {
-    /// Glob pattern to select Avro schema files
-    pub glob_pattern: String,
+    #[command(subcommand)]
+    pub command: Option<Subcommand>,
+
+    /// Glob pattern to select Avro schema files, .avro data files or archives, a schema URL, '-' for stdin
+    #[clap(required_unless_present_any = ["dump_templates", "explain"])]
+    pub glob_pattern: Option<String>,
 
     /// The file where Rust types will be written, '-' for stdout
-    pub output_file: PathBuf,
+    #[clap(required_unless_present_any = ["dump_templates", "explain"])]
+    pub output_file: Option<PathBuf>,
 
     /// Run rustfmt on the resulting <output-file>
     #[clap(long)]
@@ -37,6 +51,14 @@ struct Args {
     #[clap(long)]
     pub chrono_dates: bool,
 
+    /// Use a generated Decimal { mantissa, scale } struct for decimal logical types
+    #[clap(long)]
+    pub decimal_structs: bool,
+
+    /// Generate enums as String newtypes with a constant per symbol
+    #[clap(long)]
+    pub string_enums: bool,
+
     /// Derive builders for generated record structs
     #[clap(long)]
     pub derive_builders: bool,
@@ -44,46 +66,920 @@ struct Args {
     /// Derive AvroSchema for generated record structs
     #[clap(long)]
     pub derive_schemas: bool,
+
+    /// Implement AvroSchema for record structs and enums, with their embedded schema
+    #[clap(long, conflicts_with = "derive_schemas")]
+    pub avro_schema_impls: bool,
+
+    /// Implement an object-safe AvroMessage trait for record structs and enums
+    #[clap(long)]
+    pub avro_message_impls: bool,
+
+    /// Generate serde round-trip tests for record structs implementing Default
+    #[clap(long)]
+    pub round_trip_tests: bool,
+
+    /// Implement proptest::arbitrary::Arbitrary for generated types
+    #[clap(long)]
+    pub proptest_arbitrary: bool,
+
+    /// Implement quickcheck::Arbitrary for generated types
+    #[clap(long)]
+    pub quickcheck_arbitrary: bool,
+
+    /// Generate decoders of datums written with other compatible schemas for record structs
+    #[clap(long)]
+    pub writer_schema_decoders: bool,
+
+    /// Implement TryFrom<&[u8]> for record structs, decoding datums of their schema
+    #[clap(long)]
+    pub try_from_bytes: bool,
+
+    /// Implement From both ways between single-field record structs and their field type
+    #[clap(long)]
+    pub wrapper_conversions: bool,
+
+    /// Generate type aliases of named types under the names of their Avro aliases
+    #[clap(long)]
+    pub alias_types: bool,
+
+    /// Generate SCHEMA, SCHEMA_PRETTY and schema_json() for record structs
+    #[clap(long)]
+    pub schema_constants: bool,
+
+    /// Implement zeroize::Zeroize and Drop for records with "rust.sensitive" fields
+    #[clap(long)]
+    pub zeroize: bool,
+
+    /// Do not derive or implement Debug for generated types
+    #[clap(long)]
+    pub no_debug: bool,
+
+    /// Print bytes and fixed fields as truncated hex in the Debug of record structs
+    #[clap(long, conflicts_with = "no_debug")]
+    pub hex_debug: bool,
+
+    /// Generate validate methods checking the invariants, minimum, maximum and pattern of record fields
+    #[clap(long)]
+    pub validators: bool,
+
+    /// Derive the Validate trait of <CRATE> for record structs, from the minimum, maximum and pattern of their fields
+    #[clap(long, value_name = "CRATE", value_enum, conflicts_with = "validators")]
+    pub validate_derive: Option<ValidateCrate>,
+
+    /// Do not implement Default for record structs
+    #[clap(long)]
+    pub no_default: bool,
+
+    /// Do not implement Default for record structs whose fields lack schema defaults
+    #[clap(long)]
+    pub strict_defaults: bool,
+
+    /// Implement Default for record structs whose fields without default are optional, arrays or maps
+    #[clap(long)]
+    pub empty_defaults: bool,
+
+    /// Do not derive or implement serde::Serialize and serde::Deserialize for generated types
+    #[clap(long)]
+    pub no_serde: bool,
+
+    /// Check that the generated code parses as valid Rust
+    #[clap(long)]
+    pub verify: bool,
+
+    /// Directory of .tera files overriding the built-in templates
+    #[clap(long, value_name = "DIR")]
+    pub templates_dir: Option<PathBuf>,
+
+    /// Write the built-in templates to <DIR> and exit
+    #[clap(long, value_name = "DIR", conflicts_with_all = ["glob_pattern", "output_file"])]
+    pub dump_templates: Option<PathBuf>,
+
+    /// Print the explanation of an error code, e.g. E0004, and exit
+    #[clap(long, value_name = "CODE", conflicts_with_all = ["glob_pattern", "output_file"])]
+    pub explain: Option<String>,
+
+    /// Serialize chrono timestamps as RFC 3339 strings in JSON, requires --chrono-dates
+    #[clap(long, requires = "chrono_dates")]
+    pub rfc3339_timestamps: bool,
+
+    /// Require optional fields (strict), or accept missing fields and unknown symbols (lenient)
+    #[clap(long, value_name = "MODE", value_enum, default_value_t = Deserialization::Standard)]
+    pub deserialization: Deserialization,
+
+    /// Generate fields having a default value as Option too, omitted when None (defaults)
+    #[clap(long, value_name = "MODE", value_enum, default_value_t = Optional::Unions)]
+    pub optional_fields: Optional,
+
+    /// Box nested record fields whose struct is estimated larger than <BYTES>
+    #[clap(long, value_name = "BYTES")]
+    pub box_threshold: Option<usize>,
+
+    /// Generate array fields as SmallVec storing up to <N> items inline
+    #[clap(long, value_name = "N")]
+    pub smallvec_arrays: Option<usize>,
+
+    /// Name union enums after <TEMPLATE>, e.g. {Record}{Field}Union, instead of their variants
+    #[clap(long, value_name = "TEMPLATE")]
+    pub union_names: Option<String>,
+
+    /// Represent union enums in JSON by their value, keyed by variant name, or by Avro type
+    #[clap(long, value_name = "MODE", value_enum)]
+    pub union_json: Option<UnionRepr>,
+
+    /// Wrap the generated items in a `pub mod <NAME>`, allowing lints in it
+    #[clap(long, value_name = "NAME")]
+    pub wrap_module: Option<String>,
+
+    /// Comma-separated lints allowed in the --wrap-module module [default: dead_code,clippy::all]
+    #[clap(
+        long,
+        value_name = "LINTS",
+        value_delimiter = ',',
+        requires = "wrap_module"
+    )]
+    pub allow: Option<Vec<String>>,
+
+    /// Add lint attributes to an item kind, e.g. enum=warn(missing_docs), repeatable
+    #[clap(long, value_name = "KIND=ATTR", value_parser = parse_item_lints)]
+    pub item_lints: Vec<(ItemKind, String)>,
+
+    /// Document records with examples, as doctests importing the types from <PATH>
+    #[clap(long, value_name = "PATH")]
+    pub doc_examples: Option<String>,
+
+    /// Path of the apache-avro crate referenced by the generated code, e.g. a re-export
+    #[clap(long, value_name = "PATH", default_value = "apache_avro")]
+    pub avro_crate: String,
+
+    /// Write a JSON report of the generated types to <FILE>
+    #[clap(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+
+    /// Write a JSON index of the generated types by Avro fullname to <FILE>
+    #[clap(long, value_name = "FILE")]
+    pub manifest: Option<PathBuf>,
+
+    /// Path of the module of the generated types, for their paths in the manifest
+    #[clap(
+        long,
+        value_name = "PATH",
+        default_value = "crate",
+        requires = "manifest"
+    )]
+    pub manifest_module: String,
+
+    /// Cache rendered types in <FILE> to only regenerate modified schemas
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["report", "manifest"])]
+    pub cache: Option<PathBuf>,
+
+    /// Print the numbers of schemas, items by kind and warnings, and timings to stderr
+    #[clap(long, conflicts_with_all = ["in_place", "split_namespaces", "cache", "report", "manifest"])]
+    pub stats: bool,
+
+    /// Print the time spent in each phase and on each schema, slowest first, to stderr
+    #[clap(long, conflicts_with_all = ["in_place", "split_namespaces", "cache", "report", "manifest"])]
+    pub timings: bool,
+
+    /// Write an example JSON document for each record to <DIR>
+    #[clap(long, value_name = "DIR")]
+    pub examples: Option<PathBuf>,
+
+    /// Write a criterion benchmark harness of the generated records to <FILE>
+    #[clap(long, value_name = "FILE", requires = "bench_types")]
+    pub bench: Option<PathBuf>,
+
+    /// Path of the module the benchmark harness imports the generated types from
+    #[clap(long, value_name = "PATH", requires = "bench")]
+    pub bench_types: Option<String>,
+
+    /// Write a module decoding the generated records by Avro fullname to <FILE>
+    #[clap(long, value_name = "FILE", requires = "registry_types")]
+    pub registry: Option<PathBuf>,
+
+    /// Path of the module the registry imports the generated types from
+    #[clap(long, value_name = "PATH", requires = "registry")]
+    pub registry_types: Option<String>,
+
+    /// Decode the payloads of a schema registry id as a record in the registry, e.g. 7=ns.User
+    #[clap(long, value_name = "ID=NAME", value_parser = parse_schema_id, requires = "registry")]
+    pub schema_id: Vec<(u32, String)>,
+
+    /// Write a module converting the generated types from and to Avro JSON to <FILE>
+    #[clap(long, value_name = "FILE")]
+    pub avro_json: Option<PathBuf>,
+
+    /// Write between the `// <rsgen-avro:begin>` and `end` comments of <OUTPUT_FILE>
+    #[clap(long, conflicts_with_all = ["report", "manifest", "cache"])]
+    pub in_place: bool,
+
+    /// Write one file per namespace and a mod.rs file into the <OUTPUT_FILE> directory
+    #[clap(long, conflicts_with_all = ["in_place", "report", "manifest", "cache"])]
+    pub split_namespaces: bool,
+
+    /// Fetch a schema URL with the header <NAME> set to the env variable <VAR>, repeatable
+    #[clap(long, value_name = "NAME=VAR", value_parser = parse_header_env)]
+    pub header_env: Vec<(String, String)>,
+
+    /// Print errors as text, or as a JSON object with their code, schema, field and message
+    #[clap(long, value_name = "FORMAT", value_enum, default_value_t = ErrorFormat::Human, global = true)]
+    pub error_format: ErrorFormat,
 }
 
-fn run() -> Result<(), Box<dyn Error>> {
-    let args = Args::parse();
+#[derive(Debug, clap::Subcommand)]
+enum Subcommand {
+    /// Check that two versions of an Avro schema are compatible
+    CheckCompat(CheckCompatArgs),
+    /// Infer an Avro schema from sample JSON documents and generate Rust types from it
+    Infer(InferArgs),
+    /// Derive Avro schemas from the annotated structs and enums of Rust code
+    Rs2avro(Rs2avroArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct CheckCompatArgs {
+    /// The previous version of the schema
+    #[clap(long, value_name = "FILE")]
+    pub old: PathBuf,
+
+    /// The new version of the schema
+    #[clap(long, value_name = "FILE")]
+    pub new: PathBuf,
 
-    let mut out: Box<dyn Write> = if args.output_file == PathBuf::from("-") {
-        Box::new(stdout())
+    /// Whether the new schema must read old data (backward), the reverse (forward), or both
+    #[clap(long, value_enum, default_value_t = Mode::Backward)]
+    pub mode: Mode,
+}
+
+#[derive(Debug, clap::Args)]
+struct InferArgs {
+    /// Glob pattern to select JSON files of sample objects, one or more per file
+    pub glob_pattern: String,
+
+    /// The file where Rust types will be written, '-' for stdout
+    pub output_file: PathBuf,
+
+    /// The name of the inferred record
+    #[clap(long)]
+    pub name: String,
+
+    /// The namespace of the inferred record
+    #[clap(long)]
+    pub namespace: Option<String>,
+
+    /// Also write the inferred Avro schema to <FILE>
+    #[clap(long, value_name = "FILE")]
+    pub schema: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+struct Rs2avroArgs {
+    /// Glob pattern to select Rust files
+    pub glob_pattern: String,
+
+    /// The directory where `<fullname>.avsc` files will be written
+    pub output_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Deserialization {
+    Standard,
+    Strict,
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Optional {
+    Unions,
+    Defaults,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum UnionRepr {
+    Untagged,
+    External,
+    Avro,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum ValidateCrate {
+    Validator,
+    #[value(name = "serde_valid")]
+    SerdeValid,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Mode {
+    Backward,
+    Forward,
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum ErrorFormat {
+    Human,
+    Json,
+}
+
+/// The exit code of invalid schemas and default values.
+const EXIT_SCHEMA: i32 = 3;
+/// The exit code of failures to render or verify the generated code.
+const EXIT_GENERATION: i32 = 4;
+/// The exit code of failures to read or write files, or to fetch schemas.
+const EXIT_IO: i32 = 5;
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    match &args.command {
+        Some(Subcommand::CheckCompat(args)) => return check_compat(args),
+        Some(Subcommand::Infer(args)) => return infer(args),
+        Some(Subcommand::Rs2avro(args)) => return rs2avro(args),
+        None => (),
+    }
+
+    if let Some(dir) = &args.dump_templates {
+        rsgen_avro::dump_templates(dir)?;
+        return Ok(());
+    }
+    if let Some(code) = &args.explain {
+        let explanation = rsgen_avro::explain_error(code)
+            .ok_or_else(|| format!("Unknown error code `{code}`"))?;
+        print!("{explanation}");
+        return Ok(());
+    }
+
+    // Both are required by clap unless templates are dumped or an error code explained
+    let glob_pattern = args.glob_pattern.expect("Missing <GLOB_PATTERN>");
+    let output_file = args.output_file.expect("Missing <OUTPUT_FILE>");
+
+    if args.cache.is_some() && output_file.as_os_str() == "-" {
+        return Err("--cache requires an <OUTPUT_FILE>, not stdout".into());
+    }
+    if args.in_place && output_file.as_os_str() == "-" {
+        return Err("--in-place requires an <OUTPUT_FILE>, not stdout".into());
+    }
+    if args.split_namespaces && output_file.as_os_str() == "-" {
+        return Err("--split-namespaces requires an <OUTPUT_FILE> directory, not stdout".into());
+    }
+
+    // Parsed from stdin, e.g. for schemas piped from another command
+    let stdin_schema;
+    let url_schema;
+    let source = if glob_pattern == "-" {
+        stdin_schema = Schema::parse_reader(&mut stdin().lock())?;
+        Source::Schema(&stdin_schema)
+    } else if glob_pattern.starts_with("https://") || glob_pattern.starts_with("http://") {
+        url_schema = fetch_schema(&glob_pattern, &args.header_env)?;
+        Source::SchemaStr(&url_schema)
     } else {
-        Box::new(
-            OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(&args.output_file)?,
-        )
+        Source::GlobPattern(&glob_pattern)
     };
 
-    let source = Source::GlobPattern(&args.glob_pattern);
-
-    let g = Generator::builder()
+    let mut builder = Generator::builder()
         .precision(args.precision)
         .nullable(args.nullable)
         .use_avro_rs_unions(args.union_deser)
         .use_chrono_dates(args.chrono_dates)
+        .use_decimal_structs(args.decimal_structs)
+        .string_enums(args.string_enums)
         .derive_builders(args.derive_builders)
         .derive_schemas(args.derive_schemas)
-        .build()?;
+        .avro_schema_impls(args.avro_schema_impls)
+        .avro_message_impls(args.avro_message_impls)
+        .round_trip_tests(args.round_trip_tests)
+        .proptest_arbitrary(args.proptest_arbitrary)
+        .quickcheck_arbitrary(args.quickcheck_arbitrary)
+        .writer_schema_decoders(args.writer_schema_decoders)
+        .try_from_bytes(args.try_from_bytes)
+        .wrapper_conversions(args.wrapper_conversions)
+        .alias_types(args.alias_types)
+        .schema_constants(args.schema_constants)
+        .zeroize(args.zeroize)
+        .debug_impls(!args.no_debug)
+        .hex_debug(args.hex_debug)
+        .validators(args.validators)
+        .default_impls(!args.no_default)
+        .strict_defaults(args.strict_defaults)
+        .empty_defaults(args.empty_defaults)
+        .serde_impls(!args.no_serde)
+        .rfc3339_timestamps(args.rfc3339_timestamps)
+        .deserialization(match args.deserialization {
+            Deserialization::Standard => DeserializationMode::Standard,
+            Deserialization::Strict => DeserializationMode::Strict,
+            Deserialization::Lenient => DeserializationMode::Lenient,
+        })
+        .optional_fields(match args.optional_fields {
+            Optional::Unions => OptionalFields::Unions,
+            Optional::Defaults => OptionalFields::Defaults,
+        })
+        .verify(args.verify)
+        .avro_crate(args.avro_crate);
+    if let Some(bytes) = args.box_threshold {
+        builder = builder.box_threshold(bytes);
+    }
+    if let Some(capacity) = args.smallvec_arrays {
+        builder = builder.smallvec_arrays(capacity);
+    }
+    if let Some(template) = args.union_names {
+        builder = builder.union_names(template);
+    }
+    if let Some(repr) = args.union_json {
+        builder = builder.union_json(match repr {
+            UnionRepr::Untagged => UnionJson::Untagged,
+            UnionRepr::External => UnionJson::External,
+            UnionRepr::Avro => UnionJson::Avro,
+        });
+    }
+    if let Some(derive) = args.validate_derive {
+        builder = builder.validate_derive(match derive {
+            ValidateCrate::Validator => ValidateDerive::Validator,
+            ValidateCrate::SerdeValid => ValidateDerive::SerdeValid,
+        });
+    }
+    if let Some(name) = args.wrap_module {
+        builder = builder.wrap_module(name);
+    }
+    if let Some(lints) = args.allow {
+        builder = builder.allowed_lints(lints);
+    }
+    for (kind, attributes) in args.item_lints {
+        builder = builder.item_lints(kind, attributes);
+    }
+    if let Some(dir) = args.templates_dir {
+        builder = builder.templates_dir(dir);
+    }
+    if let Some(types_path) = args.doc_examples {
+        builder = builder.doc_examples(types_path);
+    }
+    let timings = SchemaTimings::new();
+    if args.timings {
+        builder = builder.progress(timings.clone());
+    }
+    let g = builder.build()?;
 
-    g.gen(&source, &mut out)?;
+    if let Some(dir) = &args.examples {
+        g.gen_examples(&source, dir)?;
+    }
+    if let (Some(file), Some(types_path)) = (&args.bench, &args.bench_types) {
+        g.gen_bench(&source, types_path, &mut std::fs::File::create(file)?)?;
+    }
+    if let (Some(file), Some(types_path)) = (&args.registry, &args.registry_types) {
+        let schema_ids: Vec<_> = args
+            .schema_id
+            .iter()
+            .map(|(id, fullname)| (*id, fullname.as_str()))
+            .collect();
+        let mut output = std::fs::File::create(file)?;
+        g.gen_registry_with_ids(&source, types_path, &schema_ids, &mut output)?;
+    }
+    if let Some(file) = &args.avro_json {
+        g.gen_avro_json(&mut std::fs::File::create(file)?)?;
+    }
 
-    if args.fmt && args.output_file != PathBuf::from("-") {
-        Command::new("rustfmt").arg(&args.output_file).status()?;
+    if args.split_namespaces {
+        let paths = g.gen_namespaces(&source, &output_file)?;
+        if args.fmt {
+            // rustfmt also formats the namespace modules declared by mod.rs
+            Command::new("rustfmt").arg(&paths[0]).status()?;
+        }
+        return Ok(());
+    }
+
+    if args.in_place {
+        if !g.gen_in_place(&source, &output_file)? {
+            return Ok(());
+        }
+    } else if let Some(cache_file) = &args.cache {
+        if !g.gen_incremental(&source, &output_file, cache_file)? {
+            return Ok(());
+        }
+    } else if args.report.is_some() || args.manifest.is_some() {
+        let mut report = g.gen_with_report(&source, &mut open_output(&output_file)?)?;
+        if output_file.as_os_str() != "-" {
+            report.set_output(&output_file);
+        }
+        if let Some(report_file) = &args.report {
+            report.write_json(report_file)?;
+        }
+        if let Some(manifest_file) = &args.manifest {
+            report
+                .manifest(&args.manifest_module)
+                .write_json(manifest_file)?;
+        }
+    } else if args.stats || args.timings {
+        let stats = g.gen_with_stats(&source, &mut open_output(&output_file)?)?;
+        if args.stats {
+            eprintln!("{stats}");
+        }
+        if args.timings {
+            print_timings(&stats, &timings);
+        }
+    } else {
+        g.gen(&source, &mut open_output(&output_file)?)?;
+    }
+
+    if args.fmt && output_file.as_os_str() != "-" {
+        Command::new("rustfmt").arg(&output_file).status()?;
     }
 
     Ok(())
 }
 
+/// Prints the time spent in each phase of a generation and on each schema, slowest first.
+fn print_timings(stats: &Stats, timings: &SchemaTimings) {
+    let ms = |duration: Duration| format!("{:>10.3} ms", duration.as_secs_f64() * 1000.0);
+    eprintln!("Phases:");
+    let phases = [
+        ("parse", stats.parsing),
+        ("resolve", stats.resolving),
+        ("render", stats.rendering),
+        ("write", stats.writing),
+    ];
+    for (phase, duration) in phases {
+        eprintln!("{}  {phase}", ms(duration));
+    }
+    eprintln!("Schemas:");
+    for (name, duration) in timings.slowest() {
+        eprintln!("{}  {name}", ms(duration));
+    }
+}
+
+/// Prints the incompatibilities between two versions of a schema, failing if there are any.
+fn check_compat(args: &CheckCompatArgs) -> Result<(), Box<dyn Error>> {
+    let parse = |path: &Path| -> Result<Schema, Box<dyn Error>> {
+        let raw_schema = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+        Ok(Schema::parse_str(&raw_schema)
+            .map_err(|e| format!("Cannot parse {}: {e}", path.display()))?)
+    };
+    let mode = match args.mode {
+        Mode::Backward => CompatibilityMode::Backward,
+        Mode::Forward => CompatibilityMode::Forward,
+        Mode::Full => CompatibilityMode::Full,
+    };
+
+    let incompatibilities = check_compatibility(&parse(&args.old)?, &parse(&args.new)?, mode);
+    for incompatibility in &incompatibilities {
+        println!("{incompatibility}");
+    }
+    match incompatibilities.len() {
+        0 => Ok(()),
+        1 => Err("Found 1 incompatibility".into()),
+        n => Err(format!("Found {n} incompatibilities").into()),
+    }
+}
+
+/// Generates Rust types from the schema inferred from sample JSON documents.
+fn infer(args: &InferArgs) -> Result<(), Box<dyn Error>> {
+    let mut samples = vec![];
+    for entry in glob::glob(&args.glob_pattern)? {
+        let path = entry?;
+        let raw_samples = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+        // Files hold a single object, an array of objects, or a stream of objects
+        for sample in serde_json::Deserializer::from_str(&raw_samples).into_iter() {
+            match sample.map_err(|e| format!("Cannot parse {}: {e}", path.display()))? {
+                serde_json::Value::Array(values) => samples.extend(values),
+                value => samples.push(value),
+            }
+        }
+    }
+
+    let schema = infer_schema(&args.name, args.namespace.as_deref(), &samples)?;
+    if let Some(file) = &args.schema {
+        std::fs::write(file, serde_json::to_string_pretty(&schema)? + "\n")?;
+    }
+    Generator::new()?.gen(
+        &Source::Schema(&schema),
+        &mut open_output(&args.output_file)?,
+    )?;
+    Ok(())
+}
+
+/// Writes the Avro schemas derived from Rust files, one file per schema.
+fn rs2avro(args: &Rs2avroArgs) -> Result<(), Box<dyn Error>> {
+    std::fs::create_dir_all(&args.output_dir)?;
+    for entry in glob::glob(&args.glob_pattern)? {
+        let path = entry?;
+        let code = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+        let schemas =
+            schemas_from_rust(&code).map_err(|e| format!("In {}: {e}", path.display()))?;
+        for schema in &schemas {
+            let Some(name) = schema.name() else {
+                continue;
+            };
+            let file = args
+                .output_dir
+                .join(format!("{}.avsc", name.fullname(None)));
+            std::fs::write(file, serde_json::to_string_pretty(schema)? + "\n")?;
+        }
+    }
+    Ok(())
+}
+
+/// Fetches the schema at an HTTP(S) `url`, sending the headers of `header_env`, their names
+/// and the environment variables holding their value.
+fn fetch_schema(url: &str, header_env: &[(String, String)]) -> Result<String, Box<dyn Error>> {
+    let headers = header_values(url, header_env)?;
+    // Header values are secrets, which are not sent in clear text even when redirected
+    let agent = ureq::AgentBuilder::new()
+        .https_only(!headers.is_empty())
+        .build();
+    let mut request = agent.get(url);
+    for (name, value) in &headers {
+        request = request.set(name, value);
+    }
+    let response = request
+        .call()
+        .map_err(|e| format!("Cannot fetch {url}: {e}"))?;
+    let mut schema = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut schema)
+        .map_err(|e| format!("Cannot read {url}: {e}"))?;
+    Ok(schema)
+}
+
+/// Returns the headers of `header_env` with the value of their environment variable, failing
+/// if `url` is not an `https://` URL or if a value holds a line break, which would inject
+/// other headers.
+fn header_values(
+    url: &str,
+    header_env: &[(String, String)],
+) -> Result<Vec<(String, String)>, String> {
+    if !header_env.is_empty() && !url.starts_with("https://") {
+        return Err(format!(
+            "Cannot send the --header-env headers to {url}, only to an https:// URL"
+        ));
+    }
+    header_env
+        .iter()
+        .map(|(name, var)| {
+            let value = std::env::var(var)
+                .map_err(|e| format!("Cannot read ${var} for the {name} header: {e}"))?;
+            if value.contains(['\r', '\n']) {
+                return Err(format!(
+                    "Cannot send the {name} header: ${var} holds a line break"
+                ));
+            }
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Parses a `--header-env` value, a header name and an environment variable separated by `=`.
+fn parse_header_env(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((name, var)) if !name.trim().is_empty() && !var.trim().is_empty() => {
+            let name = name.trim();
+            // The token characters of RFC 9110
+            let token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+            if !name.chars().all(token) {
+                return Err(format!("invalid header name `{name}`"));
+            }
+            Ok((name.to_string(), var.trim().to_string()))
+        }
+        _ => Err(format!("expected <NAME>=<VAR>, found `{value}`")),
+    }
+}
+
+/// Parses a `--schema-id` value, a schema registry id and a record fullname separated by `=`.
+fn parse_schema_id(value: &str) -> Result<(u32, String), String> {
+    match value.split_once('=') {
+        Some((id, fullname)) if !fullname.trim().is_empty() => match id.trim().parse() {
+            Ok(id) => Ok((id, fullname.trim().to_string())),
+            Err(_) => Err(format!("invalid schema id `{}`", id.trim())),
+        },
+        _ => Err(format!("expected <ID>=<NAME>, found `{value}`")),
+    }
+}
+
+/// Parses a `--item-lints` value, an item kind and its lint attributes separated by `=`.
+fn parse_item_lints(value: &str) -> Result<(ItemKind, String), String> {
+    let (kind, attributes) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected <KIND>=<ATTR>, found `{value}`"))?;
+    let kind = kind
+        .trim()
+        .parse()
+        .map_err(|e: rsgen_avro::Error| e.to_string())?;
+    Ok((kind, attributes.to_string()))
+}
+
+/// Opens the output file, '-' being stdout.
+fn open_output(output_file: &Path) -> std::io::Result<Box<dyn Write>> {
+    if output_file.as_os_str() == "-" {
+        Ok(Box::new(stdout()))
+    } else {
+        Ok(Box::new(
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(output_file)?,
+        ))
+    }
+}
+
+/// A failure of the command, as printed with `--error-format json`.
+#[derive(Debug, serde::Serialize)]
+struct Diagnostic<'a> {
+    /// The stable code of the error, e.g. `E0004`, explained by `--explain`.
+    code: Option<&'static str>,
+    /// What failed, e.g. `schema`, `invalid-default`, `generation` or `io`.
+    kind: &'static str,
+    /// The fullname of the record whose field has an invalid default value.
+    schema: Option<&'a str>,
+    /// The name of the field having an invalid default value.
+    field: Option<&'a str>,
+    /// The schema file of the offending JSON value, and its line and column in it.
+    file: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    message: String,
+    #[serde(skip)]
+    exit_code: i32,
+}
+
+impl<'a> Diagnostic<'a> {
+    fn new(e: &'a (dyn Error + 'static)) -> Self {
+        let Some(error) = e.downcast_ref::<rsgen_avro::Error>() else {
+            return Diagnostic {
+                code: None,
+                kind: "error",
+                schema: None,
+                field: None,
+                file: None,
+                line: None,
+                column: None,
+                message: e.to_string(),
+                exit_code: 1,
+            };
+        };
+        let code = Some(error.code());
+        let (kind, exit_code) = match error {
+            rsgen_avro::Error::Located {
+                error,
+                path,
+                line,
+                column,
+                ..
+            } => {
+                return Diagnostic {
+                    code,
+                    file: Some(path.display().to_string()),
+                    line: Some(*line),
+                    column: Some(*column),
+                    ..Diagnostic::new(error.as_ref())
+                };
+            }
+            rsgen_avro::Error::InvalidDefault { record, field, .. }
+            | rsgen_avro::Error::InvalidDefaultSize { record, field, .. } => {
+                return Diagnostic {
+                    code,
+                    kind: "invalid-default",
+                    schema: Some(record),
+                    field: Some(field),
+                    file: None,
+                    line: None,
+                    column: None,
+                    message: e.to_string(),
+                    exit_code: EXIT_SCHEMA,
+                };
+            }
+            rsgen_avro::Error::Schema(_)
+            | rsgen_avro::Error::InvalidSchema { .. }
+            | rsgen_avro::Error::Avro(_) => ("schema", EXIT_SCHEMA),
+            rsgen_avro::Error::Io(_) => ("io", EXIT_IO),
+            rsgen_avro::Error::GlobPattern(_) => ("glob-pattern", 1),
+            rsgen_avro::Error::Config(_) => ("config", 1),
+            _ => ("generation", EXIT_GENERATION),
+        };
+        Diagnostic {
+            code,
+            kind,
+            schema: None,
+            field: None,
+            file: None,
+            line: None,
+            column: None,
+            message: e.to_string(),
+            exit_code,
+        }
+    }
+}
+
+/// Converts the errors of the dependencies of the library to its own, for them to have a code.
+fn library_error(e: Box<dyn Error>) -> Box<dyn Error> {
+    let e = match e.downcast::<apache_avro::Error>() {
+        Ok(e) => return Box::new(rsgen_avro::Error::from(*e)),
+        Err(e) => e,
+    };
+    let e = match e.downcast::<glob::GlobError>() {
+        Ok(e) => return Box::new(rsgen_avro::Error::Io(e.into_error())),
+        Err(e) => e,
+    };
+    let e = match e.downcast::<glob::PatternError>() {
+        Ok(e) => return Box::new(rsgen_avro::Error::GlobPattern(*e)),
+        Err(e) => e,
+    };
+    match e.downcast::<std::io::Error>() {
+        Ok(e) => Box::new(rsgen_avro::Error::Io(*e)),
+        Err(e) => e,
+    }
+}
+
 fn main() {
-    run().unwrap_or_else(|e| {
-        eprintln!("{e}");
-        process::exit(1);
+    let args = Args::parse();
+    let error_format = args.error_format;
+    run(args).unwrap_or_else(|e| {
+        let e = library_error(e);
+        let diagnostic = Diagnostic::new(e.as_ref());
+        match (error_format, diagnostic.code) {
+            (ErrorFormat::Human, Some(code)) => eprintln!(
+                "error[{code}]: {e}\n\nFor more information about this error, \
+                 try `rsgen-avro --explain {code}`."
+            ),
+            (ErrorFormat::Human, None) => eprintln!("{e}"),
+            (ErrorFormat::Json, _) => eprintln!(
+                "{}",
+                serde_json::to_string(&diagnostic).expect("serializable diagnostic")
+            ),
+        }
+        process::exit(diagnostic.exit_code);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_env() {
+        assert_eq!(
+            Ok(("X-Api-Key".to_string(), "API_KEY".to_string())),
+            parse_header_env(" X-Api-Key = API_KEY")
+        );
+        assert!(parse_header_env("X-Api-Key").is_err());
+        assert!(parse_header_env("X Api Key=API_KEY").is_err());
+        assert!(parse_header_env("X-Api-Key:=API_KEY").is_err());
+    }
+
+    #[test]
+    fn header_values_checks() {
+        std::env::set_var("RSGEN_AVRO_TEST_TOKEN", "secret");
+        std::env::set_var("RSGEN_AVRO_TEST_INJECTED", "secret\r\nX-Admin: true");
+        let header = |var: &str| vec![("Authorization".to_string(), var.to_string())];
+
+        assert_eq!(
+            Ok(vec![("Authorization".to_string(), "secret".to_string())]),
+            header_values("https://acme.com/a.avsc", &header("RSGEN_AVRO_TEST_TOKEN"))
+        );
+        assert_eq!(Ok(vec![]), header_values("http://acme.com/a.avsc", &[]));
+        let e = header_values("http://acme.com/a.avsc", &header("RSGEN_AVRO_TEST_TOKEN"));
+        assert!(e.unwrap_err().contains("only to an https:// URL"));
+        let e = header_values(
+            "https://acme.com/a.avsc",
+            &header("RSGEN_AVRO_TEST_INJECTED"),
+        );
+        assert!(e.unwrap_err().contains("holds a line break"));
+        let e = header_values("https://acme.com/a.avsc", &header("RSGEN_AVRO_TEST_UNSET"));
+        assert!(e
+            .unwrap_err()
+            .contains("Cannot read $RSGEN_AVRO_TEST_UNSET"));
+    }
+
+    #[test]
+    fn fetch() -> Result<(), Box<dyn Error>> {
+        use std::io::BufRead;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let server = std::thread::spawn(move || {
+            for status in ["200 OK", "404 Not Found"] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = std::io::BufReader::new(&stream);
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let body = r#"{"type": "fixed", "name": "Id", "size": 16}"#;
+                write!(
+                    &stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        let url = format!("http://{addr}/id.avsc");
+        let schema = fetch_schema(&url, &[])?;
+        assert_eq!(r#"{"type": "fixed", "name": "Id", "size": 16}"#, schema);
+        let e = fetch_schema(&url, &[]).unwrap_err();
+        assert!(e.to_string().contains("404"), "{e}");
+        server.join().unwrap();
+        Ok(())
+    }
+}
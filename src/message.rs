@@ -0,0 +1,35 @@
+//! The object-safe `AvroMessage` trait, generated and implemented by the record structs and
+//! enums with [`avro_message_impls`](crate::GeneratorBuilder::avro_message_impls).
+
+use crate::gen::ItemKind;
+use crate::templates::Templater;
+
+/// The name of the generated trait.
+pub(crate) const MESSAGE_TRAIT: &str = "AvroMessage";
+
+/// Returns the code of the generated trait.
+pub(crate) fn message_trait(templater: &Templater) -> String {
+    let lints: String = templater
+        .lints(ItemKind::Trait)
+        .iter()
+        .map(|lint| format!("#[{lint}]\n"))
+        .collect();
+    format!(
+        r#"
+/// A message of a generated type, e.g. in a `Vec<Box<dyn AvroMessage>>` of several types.
+{lints}pub trait AvroMessage {{
+    /// Returns the Avro fullname of the schema of the message.
+    fn fullname(&self) -> &'static str;
+
+    /// Encodes the message as an Avro datum of its schema.
+    ///
+    /// Panics if the message does not match its schema, e.g. a `fixed` of the wrong size.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Returns the JSON of the schema of the message, the named types it references being
+    /// inlined.
+    fn schema_json(&self) -> &'static str;
+}}
+"#
+    )
+}
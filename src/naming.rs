@@ -0,0 +1,122 @@
+//! Mapping of Avro names to Rust identifiers.
+
+use std::collections::HashSet;
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref RESERVED: HashSet<String> = {
+        let s: HashSet<_> = vec![
+            "Self", "abstract", "as", "async", "await", "become", "box", "break", "const",
+            "continue", "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn",
+            "for", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut",
+            "override", "priv", "pub", "ref", "return", "self", "static", "struct", "super",
+            "trait", "true", "try", "type", "typeof", "union", "unsafe", "unsized", "use",
+            "virtual", "where", "while", "yield",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        s
+    };
+    static ref UNESCAPABLE: HashSet<String> = {
+        let s: HashSet<_> = ["Self", "self", "super", "extern", "crate"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        s
+    };
+}
+
+/// Makes a valid Rust identifier of a mapped name.
+///
+/// Characters other than letters, digits and `_` are replaced by `_`, identifiers starting
+/// with a digit are prefixed by `_`, identifiers without any letter or digit become
+/// `unnamed`, and Rust reserved words are escaped, e.g. `type` becomes `r#type`.
+pub fn sanitize(s: String) -> String {
+    let mut s = s
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if !s.chars().any(char::is_alphanumeric) {
+        s = "unnamed".to_string();
+    } else if s.starts_with(|c: char| c.is_numeric()) {
+        s.insert(0, '_');
+    }
+
+    if RESERVED.contains(&s) {
+        if UNESCAPABLE.contains(&s) {
+            s.push('_');
+        }
+        s.insert_str(0, "r#");
+        s
+    } else {
+        s
+    }
+}
+
+/// Maps Avro names to the identifiers used in generated Rust code.
+///
+/// Every method has a default implementation, matching [`DefaultNameMapper`](DefaultNameMapper),
+/// so that implementors only override the mappings they need. Returned identifiers must be
+/// valid Rust identifiers, see [`sanitize`](sanitize).
+pub trait NameMapper: Send + Sync {
+    /// Returns the Rust type name of a named schema (record, enum or fixed).
+    ///
+    /// Generation fails if several named schemas are mapped to the same type name, unless
+    /// written to different modules by [`gen_namespaces`](crate::Generator::gen_namespaces).
+    fn type_name(&self, name: &str) -> String {
+        sanitize(name.to_upper_camel_case())
+    }
+
+    /// Returns the Rust field name of a record field.
+    ///
+    /// Generation fails if several fields of a record are mapped to the same field name.
+    fn field_name(&self, name: &str) -> String {
+        sanitize(name.to_snake_case())
+    }
+
+    /// Returns the Rust variant name of an enum symbol.
+    ///
+    /// Generation fails if several symbols of an enum are mapped to the same variant name.
+    fn variant_name(&self, symbol: &str) -> String {
+        sanitize(symbol.to_upper_camel_case())
+    }
+}
+
+impl std::fmt::Debug for dyn NameMapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("NameMapper")
+    }
+}
+
+/// The default [`NameMapper`](NameMapper): `UpperCamelCase` types and variants,
+/// `snake_case` fields.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultNameMapper;
+
+impl NameMapper for DefaultNameMapper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitized_identifiers() {
+        for (name, expected) in [
+            ("name", "name"),
+            ("type", "r#type"),
+            ("self", "r#self_"),
+            ("1st_place", "_1st_place"),
+            ("2", "_2"),
+            ("", "unnamed"),
+            ("__", "unnamed"),
+            ("first-name", "first_name"),
+            ("a.b$c", "a_b_c"),
+            ("émoji✨", "émoji_"),
+        ] {
+            assert_eq!(expected, sanitize(name.to_string()));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Progress reporting and cancellation of long running generations.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::Schema;
+
+/// Receives notifications while a [`Generator`](crate::Generator) processes schemas.
+///
+/// All methods have empty default implementations, so that implementors only need to
+/// override the ones they are interested in. With the `parallel` feature, they are called
+/// concurrently from several threads, not necessarily in `index` order.
+pub trait Progress: Send + Sync {
+    /// Called once the source schemas are parsed and their dependencies resolved, before
+    /// any of the `total` schemas is processed.
+    fn on_generation_start(&self, _total: usize) {}
+
+    /// Called before the schema at `index` (out of `total` schemas) is processed.
+    fn on_schema_start(&self, _schema: &Schema, _index: usize, _total: usize) {}
+
+    /// Called after the schema at `index` (out of `total` schemas) has been processed.
+    fn on_schema_done(&self, _schema: &Schema, _index: usize, _total: usize) {}
+}
+
+/// A token used to cancel a running generation.
+///
+/// The token is checked between schemas, a cancelled generation stops with
+/// [`Error::Cancelled`](crate::Error::Cancelled). Clones share the same cancellation state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new, non-cancelled, [`CancellationToken`](CancellationToken).
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Requests the cancellation of generations using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// A [`Progress`](Progress) measuring the time spent processing each schema, e.g. to find the
+/// ones slowing down a generation.
+///
+/// Clones share the same measures, so that a clone can be given to
+/// [`GeneratorBuilder::progress`](crate::GeneratorBuilder::progress) and the measures read
+/// from the original once the generation is done. The time is not available in WebAssembly,
+/// where schemas are not measured.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaTimings(Arc<Mutex<Measures>>);
+
+/// The measures shared by the clones of [`SchemaTimings`](SchemaTimings).
+#[derive(Debug, Default)]
+struct Measures {
+    /// Index of the schema being processed -> when it was started.
+    started: HashMap<usize, Instant>,
+    /// Index of the schema -> its name and processing time.
+    done: Vec<(usize, String, Duration)>,
+}
+
+impl SchemaTimings {
+    /// Creates new, empty, [`SchemaTimings`](SchemaTimings).
+    pub fn new() -> SchemaTimings {
+        SchemaTimings::default()
+    }
+
+    /// Returns the processing time of the schemas, slowest first, by fullname or, for unnamed
+    /// schemas like unions, by [canonical form](crate::canonical_form).
+    pub fn slowest(&self) -> Vec<(String, Duration)> {
+        let mut done = self.measures().done.clone();
+        done.sort_by(|(i, _, a), (j, _, b)| b.cmp(a).then(i.cmp(j)));
+        done.into_iter()
+            .map(|(_, name, duration)| (name, duration))
+            .collect()
+    }
+
+    fn measures(&self) -> std::sync::MutexGuard<'_, Measures> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Progress for SchemaTimings {
+    fn on_generation_start(&self, _total: usize) {
+        let mut measures = self.measures();
+        measures.started.clear();
+        measures.done.clear();
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    fn on_schema_start(&self, _schema: &Schema, index: usize, _total: usize) {
+        self.measures().started.insert(index, Instant::now());
+    }
+
+    fn on_schema_done(&self, schema: &Schema, index: usize, _total: usize) {
+        let mut measures = self.measures();
+        if let Some(start) = measures.started.remove(&index) {
+            let name = match schema.name() {
+                Some(name) => name.fullname(None),
+                None => crate::canonical_form(schema),
+            };
+            measures.done.push((index, name, start.elapsed()));
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Avro protocols (`.avpr` files) selected by a [`GlobPattern`](crate::Source::GlobPattern):
+//! the types they declare, and the enum of the errors of each message combining its declared
+//! errors with a transport error.
+
+use std::collections::HashSet;
+
+use apache_avro::Schema;
+use serde_json::{Map, Value};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::gen::ItemKind;
+use crate::templates::{GenState, Templater};
+
+/// The types and messages of a protocol.
+pub(crate) struct Protocol {
+    /// The JSON of the declared types, the `error` types being declared as records.
+    pub(crate) types: Vec<String>,
+    /// The messages declaring errors.
+    pub(crate) messages: Vec<Message>,
+}
+
+/// A protocol message declaring errors.
+pub(crate) struct Message {
+    /// The name of the protocol of the message.
+    protocol: String,
+    /// The name of the message.
+    name: String,
+    /// The fullnames of the declared errors, or `string`.
+    errors: Vec<String>,
+}
+
+/// Parses the JSON of a protocol.
+pub(crate) fn parse_protocol(raw_protocol: &str) -> Result<Protocol> {
+    let json: Value = serde_json::from_str(raw_protocol).map_err(|e| Error::InvalidSchema {
+        kind: ErrorKind::Json,
+        message: format!("Invalid JSON: {e}"),
+    })?;
+    let protocol = json
+        .get("protocol")
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::Schema("Protocol without a `protocol` name".to_string()))?;
+    let namespace = json.get("namespace").and_then(Value::as_str);
+
+    let mut types = vec![];
+    for declared in json
+        .get("types")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let mut declared = declared.clone();
+        errors_as_records(&mut declared);
+        if let (Some(namespace), Value::Object(object)) = (namespace, &mut declared) {
+            let relative = object
+                .get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|name| !name.contains('.'));
+            if relative && !object.contains_key("namespace") {
+                object.insert("namespace".to_string(), namespace.into());
+            }
+        }
+        types.push(declared.to_string());
+    }
+
+    let mut messages = vec![];
+    let declarations = json.get("messages").and_then(Value::as_object);
+    for (name, declaration) in declarations.into_iter().flatten() {
+        let errors = declaration
+            .get("errors")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|error| match error.as_str() {
+                Some(error) if error == "string" || error.contains('.') => Ok(error.to_string()),
+                Some(error) => {
+                    Ok(namespace.map_or(error.to_string(), |ns| format!("{ns}.{error}")))
+                }
+                None => Err(Error::Schema(format!(
+                    "Invalid error {error} of message `{name}`, expected the name of a type"
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !errors.is_empty() {
+            messages.push(Message {
+                protocol: protocol.to_string(),
+                name: name.clone(),
+                errors,
+            });
+        }
+    }
+    Ok(Protocol { types, messages })
+}
+
+/// Declares the `error` types found in a declared type as records, which they are but for
+/// their use in messages.
+fn errors_as_records(declared: &mut Value) {
+    match declared {
+        Value::Object(object) => {
+            if is_error(object) {
+                object.insert("type".to_string(), "record".into());
+            }
+            object.values_mut().for_each(errors_as_records);
+        }
+        Value::Array(values) => values.iter_mut().for_each(errors_as_records),
+        _ => {}
+    }
+}
+
+/// Whether an object of a declared type is the definition of an `error` type.
+fn is_error(object: &Map<String, Value>) -> bool {
+    object.get("type").and_then(Value::as_str) == Some("error") && object.contains_key("fields")
+}
+
+/// Returns the name and code of the error enums of protocol messages, and the first error
+/// schema of each one, failing if an enum has the name of another type or two variants of an
+/// enum have the same name.
+///
+/// Without the Debug impls, the declared errors are displayed by their name, and the enums
+/// implement `Debug`, required by `std::error::Error`, with the names of their variants.
+pub(crate) fn message_errors(
+    messages: &[Message],
+    deps: &[Schema],
+    templater: &Templater,
+    gen_state: &GenState,
+) -> Result<Vec<(String, String, Schema)>> {
+    let mut names = HashSet::new();
+    for schema in deps {
+        if matches!(
+            schema,
+            Schema::Record(_) | Schema::Enum(_) | Schema::Fixed(_)
+        ) {
+            names.insert(templater.type_name(schema, gen_state)?);
+        }
+    }
+    let lints: String = templater
+        .lints(ItemKind::Enum)
+        .iter()
+        .map(|lint| format!("#[{lint}]\n"))
+        .collect();
+
+    let mut enums = vec![];
+    for message in messages {
+        let name = format!("{}Error", gen_state.name_mapper().type_name(&message.name));
+        if !names.insert(name.clone()) {
+            return Err(Error::InvalidSchema {
+                kind: ErrorKind::Conflict,
+                message: format!(
+                    "The error enum of message `{}` is named like another type: `{name}`",
+                    message.name
+                ),
+            });
+        }
+
+        let mut variants = String::new();
+        let mut variant_names = HashSet::new();
+        let mut first = None;
+        for error in &message.errors {
+            if error == "string" {
+                if !variant_names.insert("String".to_string()) {
+                    return Err(duplicate_variant(message, "String"));
+                }
+                variants.push_str(
+                    "    /// The `string` error declared by the message.\n    \
+                     #[error(\"{0}\")]\n    String(String),\n",
+                );
+                first.get_or_insert(Schema::String);
+                continue;
+            }
+            let schema = deps
+                .iter()
+                .find(|s| {
+                    matches!(s, Schema::Record(_))
+                        && s.name().map(|n| n.fullname(None)).as_ref() == Some(error)
+                })
+                .ok_or_else(|| Error::InvalidSchema {
+                    kind: ErrorKind::Reference,
+                    message: format!(
+                        "Error '{error}' of message `{}` is not a declared error type",
+                        message.name
+                    ),
+                })?;
+            let type_name = templater.type_name(schema, gen_state)?;
+            if !variant_names.insert(type_name.clone()) {
+                return Err(duplicate_variant(message, &type_name));
+            }
+            let display = match templater.debug_impls {
+                true => "{0:?}".to_string(),
+                false => format!("{type_name} error"),
+            };
+            variants.push_str(&format!(
+                "    /// The `{error}` error declared by the message.\n    \
+                 #[error(\"{display}\")]\n    {type_name}({type_name}),\n"
+            ));
+            first.get_or_insert_with(|| schema.clone());
+        }
+
+        let derive = match templater.debug_impls {
+            true => "Debug, thiserror::Error",
+            false => "thiserror::Error",
+        };
+        let mut code = format!(
+            r#"
+/// The errors of the `{message}` message of the `{protocol}` protocol.
+{lints}#[derive({derive})]
+pub enum {name} {{
+{variants}    /// An error of the transport of the message, e.g. of the connection.
+    #[error("Transport error: {{0}}")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+}}
+"#,
+            message = message.name,
+            protocol = message.protocol,
+        );
+        if !templater.debug_impls {
+            code.push_str(&debug_impl(&name, &variant_names));
+        }
+        enums.push((name, code, first.unwrap_or(Schema::String)));
+    }
+    Ok(enums)
+}
+
+/// Returns the error of a message declaring two errors generated as the same variant.
+fn duplicate_variant(message: &Message, variant: &str) -> Error {
+    Error::InvalidSchema {
+        kind: ErrorKind::Conflict,
+        message: format!(
+            "The errors of message `{}` are both generated as the `{variant}` variant",
+            message.name
+        ),
+    }
+}
+
+/// Returns the `Debug` impl of an error enum, writing the name of its variants, for the
+/// declared errors which do not implement `Debug`.
+fn debug_impl(name: &str, variants: &HashSet<String>) -> String {
+    let mut variants: Vec<_> = variants.iter().collect();
+    variants.sort();
+    let arms: String = variants
+        .iter()
+        .map(|variant| format!("            {name}::{variant}(_) => f.write_str(\"{variant}\"),\n"))
+        .collect();
+    format!(
+        r#"
+impl std::fmt::Debug for {name} {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        match self {{
+{arms}            {name}::Transport(e) => f.debug_tuple("Transport").field(e).finish(),
+        }}
+    }}
+}}
+"#
+    )
+}
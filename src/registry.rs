@@ -0,0 +1,219 @@
+//! Runtime registries of generated types.
+
+use std::collections::HashSet;
+
+use apache_avro::schema::{ArraySchema, MapSchema, Name, RecordSchema};
+use apache_avro::Schema;
+
+use crate::error::{Error, Result};
+use crate::templates::{inlined_schema, GenState, Templater};
+
+/// Returns the code of a module looking up the schemas of the records among `schemas` by
+/// their Avro fullname, and decoding Avro datums of these records into their types, which
+/// are imported from `types_path`.
+///
+/// The module also contains an `AnyMessage` enum of the top-level records only, the ones
+/// no other record has a field of, with `decode_any` decoding their datums.
+///
+/// With `schema_ids` mapping schema registry ids to record fullnames, the module also
+/// dispatches the decoding of payloads in the Confluent wire format by their schema id.
+pub(crate) fn registry_module(
+    schemas: &[&Schema],
+    gen_state: &GenState,
+    types_path: &str,
+    schema_ids: &[(u32, &str)],
+    templater: &Templater,
+) -> Result<String> {
+    let avro = |item: &str| gen_state.avro_path(item);
+    let mut variants = String::new();
+    let mut entries = String::new();
+    let mut arms = String::new();
+    let mut fullnames = vec![];
+    let mut messages = String::new();
+    let mut message_arms = String::new();
+
+    let mut nested = HashSet::new();
+    for schema in schemas {
+        if let Schema::Record(RecordSchema { name, fields, .. }) = schema {
+            let mut names = HashSet::new();
+            for field in fields {
+                nested_records(&field.schema, &mut names);
+            }
+            // A record is still top-level when only referencing itself
+            names.remove(name);
+            nested.extend(names);
+        }
+    }
+
+    for schema in schemas {
+        let Schema::Record(RecordSchema { name, .. }) = schema else {
+            continue;
+        };
+        let fullname = name.fullname(None);
+        fullnames.push(fullname.clone());
+        let type_name = gen_state.name_mapper().type_name(&name.name);
+        let schema_json = inlined_schema(schema, gen_state, &mut Default::default()).to_string();
+        variants.push_str(&format!("    {type_name}({types_path}::{type_name}),\n"));
+        entries.push_str(&format!("    ({fullname:?}, {schema_json:?}),\n"));
+        arms.push_str(&format!(
+            "        {fullname:?} => Record::{type_name}({}(&value)?),\n",
+            avro("from_value")
+        ));
+        if !nested.contains(name) {
+            messages.push_str(&format!("    {type_name}({types_path}::{type_name}),\n"));
+            message_arms.push_str(&format!(
+                "        Some(Record::{type_name}(record)) => Some(AnyMessage::{type_name}(record)),\n"
+            ));
+        }
+    }
+
+    let derives = if templater.debug_impls {
+        "Debug, Clone, PartialEq"
+    } else {
+        "Clone, PartialEq"
+    };
+    let schema_type = avro("Schema");
+    let error = avro("Error");
+    let decode_body = if arms.is_empty() {
+        // Avoids unused variables and unreachable code in the generated code
+        "    let _ = (fullname, bytes);\n    Ok(None)\n".to_string()
+    } else {
+        format!(
+            r#"    let Some(schema) = schema(fullname) else {{
+        return Ok(None);
+    }};
+    let value = {from_avro_datum}(schema, &mut &bytes[..], None)?;
+    let record = match fullname {{
+{arms}        _ => return Ok(None),
+    }};
+    Ok(Some(record))
+"#,
+            from_avro_datum = avro("from_avro_datum")
+        )
+    };
+    let decode_any_body = if message_arms.is_empty() {
+        "    let _ = (schema_fullname, bytes);\n    Ok(None)\n".to_string()
+    } else {
+        format!(
+            "    Ok(match decode(schema_fullname, bytes)? {{\n{message_arms}        _ => None,\n    }})\n"
+        )
+    };
+
+    let mut ids = String::new();
+    for (i, (id, fullname)) in schema_ids.iter().enumerate() {
+        if schema_ids[..i].iter().any(|(other, _)| other == id) {
+            return Err(Error::Schema(format!("Duplicate schema id {id}")));
+        }
+        if !fullnames.iter().any(|name| name == fullname) {
+            return Err(Error::Schema(format!(
+                "Schema id {id} maps to `{fullname}`, which is not a generated record"
+            )));
+        }
+        ids.push_str(&format!("    ({id}, {fullname:?}),\n"));
+    }
+    let dispatch = if ids.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"
+/// The schema registry ids of the generated records, with their fullname.
+const SCHEMA_IDS: &[(u32, &str)] = &[
+{ids}];
+
+/// Returns the fullname of the generated record of a schema registry id, if any.
+pub fn fullname(id: u32) -> Option<&'static str> {{
+    SCHEMA_IDS.iter().find(|(i, _)| *i == id).map(|(_, fullname)| *fullname)
+}}
+
+/// Decodes a payload in the Confluent wire format, a zero magic byte followed by the
+/// big-endian schema id and the Avro datum, into the generated record of its schema id.
+///
+/// Returns `None` if the schema id is not the one of a generated record.
+pub fn decode_payload(payload: &[u8]) -> Result<Option<Record>, {error}> {{
+    let [0, a, b, c, d, bytes @ ..] = payload else {{
+        return Err({error}::DeserializeValue(
+            "Not a payload of the Confluent wire format".to_string(),
+        ));
+    }};
+    match fullname(u32::from_be_bytes([*a, *b, *c, *d])) {{
+        Some(fullname) => decode(fullname, bytes),
+        None => Ok(None),
+    }}
+}}
+"#
+        )
+    };
+    Ok(format!(
+        r#"//! Schemas and decoding of the generated Avro records, by fullname.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A generated record, decoded by [`decode`].
+#[derive({derives})]
+pub enum Record {{
+{variants}}}
+
+/// The Avro fullnames of the generated records, with their schema.
+const SCHEMAS: &[(&str, &str)] = &[
+{entries}];
+
+/// Returns the schemas of the generated records, by Avro fullname.
+pub fn schemas() -> &'static HashMap<&'static str, {schema_type}> {{
+    static PARSED: OnceLock<HashMap<&'static str, {schema_type}>> = OnceLock::new();
+    PARSED.get_or_init(|| {{
+        SCHEMAS
+            .iter()
+            .map(|(name, raw)| (*name, {schema_type}::parse_str(raw).expect("valid generated schema")))
+            .collect()
+    }})
+}}
+
+/// Returns the schema of the generated record named `fullname`, if any.
+pub fn schema(fullname: &str) -> Option<&'static {schema_type}> {{
+    schemas().get(fullname)
+}}
+
+/// Decodes an Avro datum of the generated record named `fullname`, `None` if there is no
+/// such record.
+pub fn decode(fullname: &str, bytes: &[u8]) -> Result<Option<Record>, {error}> {{
+{decode_body}}}
+
+/// A top-level generated record, one no other record has a field of, decoded by
+/// [`decode_any`].
+#[derive({derives})]
+pub enum AnyMessage {{
+{messages}}}
+
+/// Decodes an Avro datum of the top-level generated record named `schema_fullname`, `None`
+/// if there is no such record.
+pub fn decode_any(schema_fullname: &str, bytes: &[u8]) -> Result<Option<AnyMessage>, {error}> {{
+{decode_any_body}}}
+{dispatch}"#
+    ))
+}
+
+/// Collects the names of the records nested in a schema, inlined or referenced.
+fn nested_records<'a>(schema: &'a Schema, names: &mut HashSet<&'a Name>) {
+    match schema {
+        Schema::Record(RecordSchema { name, fields, .. }) => {
+            if !names.insert(name) {
+                return;
+            }
+            for field in fields {
+                nested_records(&field.schema, names);
+            }
+        }
+        Schema::Ref { name } => {
+            names.insert(name);
+        }
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => nested_records(inner, names),
+        Schema::Union(union) => {
+            for variant in union.variants() {
+                nested_records(variant, names);
+            }
+        }
+        _ => (),
+    }
+}
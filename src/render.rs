@@ -0,0 +1,123 @@
+//! Template engine abstraction used to render Rust types.
+
+use std::fs;
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::context::{EnumContext, FixedContext, RecordContext, UnionContext};
+use crate::error::Result;
+use crate::templates::{BUILTIN_TEMPLATES, ENUM_TERA, FIXED_TERA, RECORD_TERA, UNION_TERA};
+
+lazy_static! {
+    // Templates are added from memory, so that no file system is needed (e.g. in wasm), and
+    // only parsed once for all the renderers
+    static ref BUILTIN_TERA: Tera = {
+        let mut tera = Tera::default();
+        tera.add_raw_templates(BUILTIN_TEMPLATES)
+            .expect("Invalid built-in templates");
+        tera
+    };
+}
+
+/// Renders Rust code from typed template contexts.
+///
+/// [`TeraRenderer`](TeraRenderer) is the default implementation, alternative engines can
+/// be plugged in with [`GeneratorBuilder::renderer`](crate::GeneratorBuilder::renderer).
+pub trait Renderer: Send + Sync {
+    /// Renders the Rust struct of an Avro record.
+    fn render_record(&self, ctx: &RecordContext) -> Result<String>;
+
+    /// Renders the Rust enum of an Avro enum.
+    fn render_enum(&self, ctx: &EnumContext) -> Result<String>;
+
+    /// Renders the Rust type of an Avro fixed.
+    fn render_fixed(&self, ctx: &FixedContext) -> Result<String>;
+
+    /// Renders the Rust enum of an anonymous Avro union.
+    fn render_union(&self, ctx: &UnionContext) -> Result<String>;
+}
+
+/// The default [`Renderer`](Renderer), using Tera and the built-in templates.
+///
+/// Named types having a `rust.template` schema attribute are rendered with the template
+/// of that name instead, e.g. `"rust.template": "event_record"` selects a custom
+/// `event_record.tera` template.
+#[derive(Debug)]
+pub struct TeraRenderer {
+    tera: Tera,
+}
+
+impl TeraRenderer {
+    /// Creates a new `TeraRenderer` with the built-in templates.
+    pub fn new() -> Result<TeraRenderer> {
+        Ok(TeraRenderer {
+            tera: BUILTIN_TERA.clone(),
+        })
+    }
+
+    /// Registers a Tera filter usable by custom templates.
+    pub fn register_filter(&mut self, name: &str, filter: impl tera::Filter + 'static) {
+        self.tera.register_filter(name, filter);
+    }
+
+    /// Registers a Tera function usable by custom templates.
+    pub fn register_function(&mut self, name: &str, function: impl tera::Function + 'static) {
+        self.tera.register_function(name, function);
+    }
+
+    /// Loads all the `.tera` files of a directory, overriding built-in templates that have
+    /// the same file name (e.g. `record.tera`).
+    pub fn load_templates_dir(&mut self, dir: impl AsRef<Path>) -> Result<()> {
+        let mut files = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "tera") {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned());
+                files.push((path, name));
+            }
+        }
+        self.tera.add_template_files(files)?;
+        Ok(())
+    }
+
+    /// Renders the `selected` template if any, the `builtin` one otherwise.
+    ///
+    /// Selected templates are looked up by name, with or without their `.tera` extension.
+    fn render(
+        &self,
+        builtin: &str,
+        selected: Option<&str>,
+        ctx: &impl Serialize,
+    ) -> Result<String> {
+        let ctx = Context::from_serialize(ctx)?;
+        let template = match selected {
+            None => builtin.to_string(),
+            Some(name) if self.tera.get_template_names().any(|n| n == name) => name.to_string(),
+            Some(name) => format!("{name}.tera"),
+        };
+        Ok(self.tera.render(&template, &ctx)?)
+    }
+}
+
+impl Renderer for TeraRenderer {
+    fn render_record(&self, ctx: &RecordContext) -> Result<String> {
+        self.render(RECORD_TERA, ctx.template.as_deref(), ctx)
+    }
+
+    fn render_enum(&self, ctx: &EnumContext) -> Result<String> {
+        self.render(ENUM_TERA, ctx.template.as_deref(), ctx)
+    }
+
+    fn render_fixed(&self, ctx: &FixedContext) -> Result<String> {
+        self.render(FIXED_TERA, ctx.template.as_deref(), ctx)
+    }
+
+    fn render_union(&self, ctx: &UnionContext) -> Result<String> {
+        self.render(UNION_TERA, None, ctx)
+    }
+}
@@ -0,0 +1,240 @@
+//! Machine-readable reports of generations.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+
+use crate::error::Result;
+use crate::gen::{GeneratedItem, ItemKind};
+
+/// A report of the Rust items written by a generation.
+///
+/// Created by [`Generator::gen_with_report`](crate::Generator::gen_with_report), it can be
+/// serialized to JSON for consumption by build tooling and audits.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Report {
+    /// The generated items, in the order they were written.
+    pub items: Vec<ReportItem>,
+}
+
+/// A single entry of a [`Report`](Report).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ReportItem {
+    /// The kind of schema this item was generated from.
+    pub kind: ItemKind,
+    /// The name of the generated Rust type.
+    pub name: String,
+    /// The Avro fullname of the schema, if it is a named schema.
+    pub fullname: Option<String>,
+    /// The file of the source schema, if it was read from a file.
+    pub source: Option<PathBuf>,
+    /// The Rabin fingerprint of the schema [canonical form](crate::canonical_form), in hexadecimal.
+    pub fingerprint: String,
+    /// The file the item was written to, if known.
+    pub output: Option<PathBuf>,
+    /// Lossy generation choices made for this item.
+    pub warnings: Vec<String>,
+}
+
+impl From<&GeneratedItem> for ReportItem {
+    fn from(item: &GeneratedItem) -> Self {
+        ReportItem {
+            kind: item.kind,
+            name: item.name.clone(),
+            fullname: item.fullname.clone(),
+            source: item.source.clone(),
+            fingerprint: item.fingerprint.clone(),
+            output: None,
+            warnings: item.warnings.clone(),
+        }
+    }
+}
+
+impl Report {
+    /// Sets the file all items were written to.
+    pub fn set_output(&mut self, output: impl AsRef<Path>) {
+        for item in &mut self.items {
+            item.output = Some(output.as_ref().to_path_buf());
+        }
+    }
+
+    /// Returns all the warnings of the report, prefixed by the name of their item.
+    pub fn warnings(&self) -> impl Iterator<Item = String> + '_ {
+        self.items.iter().flat_map(|item| {
+            item.warnings
+                .iter()
+                .map(move |warning| format!("{}: {}", item.name, warning))
+        })
+    }
+
+    /// Returns the [`Manifest`](Manifest) of the named types of the report, written in the
+    /// Rust module at `module` (e.g. `my_crate::avro`).
+    pub fn manifest(&self, module: &str) -> Manifest {
+        let types = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                let fullname = item.fullname.clone()?;
+                let entry = ManifestEntry {
+                    kind: item.kind,
+                    rust_path: format!("{module}::{}", item.name),
+                    output: item.output.clone(),
+                    fingerprint: item.fingerprint.clone(),
+                };
+                Some((fullname, entry))
+            })
+            .collect();
+        Manifest { types }
+    }
+
+    /// Serializes the report to pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self).map_err(std::io::Error::from)?)
+    }
+
+    /// Writes the report as JSON to a file.
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()? + "\n")?;
+        Ok(())
+    }
+}
+
+/// An index of the generated types by Avro fullname, so that other tools (code generators,
+/// linters, service scaffolding, ...) can locate them.
+///
+/// Created by [`Report::manifest`](Report::manifest), anonymous unions are not part of it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Manifest {
+    /// The generated types, by Avro fullname.
+    pub types: BTreeMap<String, ManifestEntry>,
+}
+
+/// A single type of a [`Manifest`](Manifest).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ManifestEntry {
+    /// The kind of schema the type was generated from.
+    pub kind: ItemKind,
+    /// The path of the generated Rust type.
+    pub rust_path: String,
+    /// The file the type was written to, if known.
+    pub output: Option<PathBuf>,
+    /// The Rabin fingerprint of the schema [canonical form](crate::canonical_form), in hexadecimal.
+    pub fingerprint: String,
+}
+
+impl Manifest {
+    /// Serializes the manifest to pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self).map_err(std::io::Error::from)?)
+    }
+
+    /// Writes the manifest as JSON to a file.
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()? + "\n")?;
+        Ok(())
+    }
+}
+
+/// Statistics of a generation, to track the size and health of the schemas over time.
+///
+/// Created by [`Generator::gen_with_stats`](crate::Generator::gen_with_stats), it is printed
+/// as a one-line summary, and serialized to JSON with the durations in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The number of processed schemas, the named types and unions nested in other schemas
+    /// included.
+    pub schemas: usize,
+    /// The number of generated items, by kind.
+    pub items: BTreeMap<ItemKind, usize>,
+    /// The number of warnings about lossy generation choices.
+    pub warnings: usize,
+    /// The time spent reading and parsing the schemas.
+    #[serde(serialize_with = "millis")]
+    pub parsing: Duration,
+    /// The time spent resolving the dependencies of the schemas and validating them.
+    #[serde(serialize_with = "millis")]
+    pub resolving: Duration,
+    /// The time spent rendering the items.
+    #[serde(serialize_with = "millis")]
+    pub rendering: Duration,
+    /// The time spent writing the items.
+    #[serde(serialize_with = "millis")]
+    pub writing: Duration,
+}
+
+impl Stats {
+    /// Returns the total number of generated items.
+    pub fn total_items(&self) -> usize {
+        self.items.values().sum()
+    }
+
+    /// Returns the total duration of the generation.
+    pub fn elapsed(&self) -> Duration {
+        self.parsing + self.resolving + self.rendering + self.writing
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |n: usize, what: &str| match n {
+            1 => format!("1 {what}"),
+            n => format!("{n} {what}s"),
+        };
+        let kinds = self
+            .items
+            .iter()
+            .map(|(kind, n)| plural(*n, &kind.to_string()))
+            .collect::<Vec<_>>();
+        let ms = |duration: Duration| format!("{:.1} ms", duration.as_secs_f64() * 1000.0);
+        write!(
+            f,
+            "Generated {} from {}",
+            plural(self.total_items(), "item"),
+            plural(self.schemas, "schema"),
+        )?;
+        if !kinds.is_empty() {
+            write!(f, " ({})", kinds.join(", "))?;
+        }
+        write!(
+            f,
+            ", {}, in {} (parsing {}, resolving {}, rendering {}, writing {})",
+            plural(self.warnings, "warning"),
+            ms(self.elapsed()),
+            ms(self.parsing),
+            ms(self.resolving),
+            ms(self.rendering),
+            ms(self.writing),
+        )
+    }
+}
+
+/// Serializes a duration as a number of milliseconds.
+fn millis<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+/// Runs `f`, adding the time it took to `duration`, except in WebAssembly where the time is
+/// not available.
+pub(crate) fn timed<T>(duration: &mut Duration, f: impl FnOnce() -> T) -> T {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    let start = std::time::Instant::now();
+    let result = f();
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        *duration += start.elapsed();
+    }
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    let _ = duration;
+    result
+}
@@ -0,0 +1,476 @@
+//! Derivation of Avro schemas from Rust types, the reverse of code generation.
+
+use std::collections::HashMap;
+
+use heck::ToUpperCamelCase;
+use serde_json::{json, Map, Value};
+use syn::{Attribute, Expr, Fields, GenericArgument, Item, Lit, PathArguments, Type};
+
+use crate::error::{Error, Result};
+use crate::Schema;
+
+/// Derives the Avro schemas of the annotated structs and enums of some Rust code, i.e. the
+/// ones deriving `Serialize` or `AvroSchema`, so that Avro contracts can be published from
+/// types whose source of truth is Rust.
+///
+/// Returns one schema per struct (as a record) and unit-only enum (as an enum), in the order
+/// of the code. Enums whose variants hold a single value, like generated union enums, are
+/// inlined as unions, and `type` aliases of byte arrays (`[u8; N]`) as fixed. References to
+/// other types of the code are resolved by name.
+///
+/// Types are mapped as in generated code, the `#[avro(namespace = "...")]` attribute of
+/// `AvroSchema` sets the namespace of a type, and `#[serde(rename = "...")]`,
+/// `#[serde(skip)]`, `#[avro(doc = "...")]` and `#[avro(default = "<json>")]` are honoured,
+/// as well as doc comments.
+pub fn schemas_from_rust(code: &str) -> Result<Vec<Schema>> {
+    let file =
+        syn::parse_file(code).map_err(|e| Error::Schema(format!("Invalid Rust code: {e}")))?;
+
+    let mut types = HashMap::new();
+    for item in &file.items {
+        let (ident, attrs) = match item {
+            Item::Struct(item) => (&item.ident, &item.attrs),
+            Item::Enum(item) => (&item.ident, &item.attrs),
+            Item::Type(item) => (&item.ident, &item.attrs),
+            _ => continue,
+        };
+        let annotated = matches!(item, Item::Type(_)) || is_annotated(attrs)?;
+        if annotated {
+            types.insert(ident.to_string(), item);
+        }
+    }
+
+    let deriver = Deriver { types };
+    let mut schemas = vec![];
+    for item in &file.items {
+        match item {
+            Item::Struct(item) if deriver.types.contains_key(&item.ident.to_string()) => {
+                schemas.push(deriver.record(item)?)
+            }
+            Item::Enum(item) if deriver.types.contains_key(&item.ident.to_string()) => {
+                if let Some(schema) = deriver.enumeration(item)? {
+                    schemas.push(schema);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let raw_schemas = schemas.iter().map(Value::to_string).collect::<Vec<_>>();
+    Ok(Schema::parse_list(
+        &raw_schemas.iter().map(String::as_str).collect::<Vec<_>>(),
+    )?)
+}
+
+struct Deriver<'a> {
+    /// The annotated structs and enums, and the type aliases, by name.
+    types: HashMap<String, &'a Item>,
+}
+
+impl Deriver<'_> {
+    fn record(&self, item: &syn::ItemStruct) -> Result<Value> {
+        let name = item.ident.to_string();
+        if !item.generics.params.is_empty() {
+            return Err(Error::Schema(format!(
+                "Generic struct `{name}` cannot be derived"
+            )));
+        }
+        let Fields::Named(named) = &item.fields else {
+            return Err(Error::Schema(format!(
+                "Struct `{name}` must have named fields"
+            )));
+        };
+
+        let mut fields = vec![];
+        for field in &named.named {
+            let attrs = attributes(&field.attrs)?;
+            if attrs.skip {
+                continue;
+            }
+            let ident = field.ident.as_ref().map(ToString::to_string);
+            let ident = ident.unwrap_or_default();
+            let field_name = attrs
+                .rename
+                .unwrap_or_else(|| ident.trim_start_matches("r#").to_string());
+            let path = format!("{name}.{field_name}");
+
+            let mut schema = Map::new();
+            schema.insert("name".to_string(), json!(field_name));
+            let field_type = self.schema(&field.ty, &path)?;
+            let optional = field_type.as_array().is_some_and(|v| v[0] == "null");
+            schema.insert("type".to_string(), field_type);
+            if let Some(doc) = attrs.doc {
+                schema.insert("doc".to_string(), json!(doc));
+            }
+            match attrs.default {
+                Some(default) => {
+                    let default = serde_json::from_str::<Value>(&default)
+                        .map_err(|e| Error::Schema(format!("Invalid default of `{path}`: {e}")))?;
+                    schema.insert("default".to_string(), default);
+                }
+                None if optional => {
+                    schema.insert("default".to_string(), Value::Null);
+                }
+                None => (),
+            }
+            fields.push(Value::Object(schema));
+        }
+
+        let attrs = attributes(&item.attrs)?;
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), json!("record"));
+        schema.insert("name".to_string(), json!(attrs.rename.unwrap_or(name)));
+        if let Some(namespace) = attrs.namespace {
+            schema.insert("namespace".to_string(), json!(namespace));
+        }
+        if let Some(doc) = attrs.doc {
+            schema.insert("doc".to_string(), json!(doc));
+        }
+        schema.insert("fields".to_string(), Value::Array(fields));
+        Ok(Value::Object(schema))
+    }
+
+    /// Returns the schema of an enum of unit variants, `None` if it is a union.
+    fn enumeration(&self, item: &syn::ItemEnum) -> Result<Option<Value>> {
+        let name = item.ident.to_string();
+        if item.variants.iter().any(|v| !v.fields.is_empty()) {
+            // Checked when the union is used
+            return Ok(None);
+        }
+
+        let mut symbols = vec![];
+        for variant in &item.variants {
+            let attrs = attributes(&variant.attrs)?;
+            if !attrs.skip {
+                symbols.push(json!(attrs.rename.unwrap_or(variant.ident.to_string())));
+            }
+        }
+
+        let attrs = attributes(&item.attrs)?;
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), json!("enum"));
+        schema.insert("name".to_string(), json!(attrs.rename.unwrap_or(name)));
+        if let Some(namespace) = attrs.namespace {
+            schema.insert("namespace".to_string(), json!(namespace));
+        }
+        if let Some(doc) = attrs.doc {
+            schema.insert("doc".to_string(), json!(doc));
+        }
+        schema.insert("symbols".to_string(), Value::Array(symbols));
+        Ok(Some(Value::Object(schema)))
+    }
+
+    /// Returns the schema of a Rust type, found at `path` for errors.
+    fn schema(&self, ty: &Type, path: &str) -> Result<Value> {
+        let unsupported = || {
+            Err(Error::Schema(format!(
+                "Unsupported type of `{path}`: {}",
+                type_name(ty)
+            )))
+        };
+
+        let segment = match ty {
+            Type::Path(ty) if ty.qself.is_none() => match ty.path.segments.last() {
+                Some(segment) => segment,
+                None => return unsupported(),
+            },
+            Type::Reference(reference) => match reference.elem.as_ref() {
+                Type::Path(ty) if ty.path.is_ident("str") => return Ok(json!("string")),
+                _ => return unsupported(),
+            },
+            Type::Array(array) => return self.fixed(array, &path.to_upper_camel_case(), path),
+            _ => return unsupported(),
+        };
+        let args = match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        let primitive = match segment.ident.to_string().as_str() {
+            "bool" => "boolean",
+            "i8" | "i16" | "i32" | "u8" | "u16" => "int",
+            "i64" | "u32" => "long",
+            "f32" => "float",
+            "f64" => "double",
+            "String" => "string",
+            "Uuid" => return Ok(json!({"type": "string", "logicalType": "uuid"})),
+            "u64" | "i128" | "u128" | "usize" | "isize" | "char" => return unsupported(),
+
+            "Box" | "Rc" | "Arc" => match args.as_slice() {
+                [inner] => return self.schema(inner, path),
+                _ => return unsupported(),
+            },
+            "Vec" => match args.as_slice() {
+                [inner] if is_byte(inner) => "bytes",
+                [inner] => return Ok(json!({"type": "array", "items": self.schema(inner, path)?})),
+                _ => return unsupported(),
+            },
+            "HashMap" | "BTreeMap" => match args.as_slice() {
+                [key, value] if type_name(key) == "String" => {
+                    return Ok(json!({"type": "map", "values": self.schema(value, path)?}))
+                }
+                _ => return unsupported(),
+            },
+            "Option" => match args.as_slice() {
+                [inner] => {
+                    let inner = self.schema(inner, path)?;
+                    let mut variants = vec![json!("null")];
+                    match inner {
+                        // Optional unions are unions with null first
+                        Value::Array(inner) => {
+                            variants.extend(inner.into_iter().filter(|v| *v != "null"))
+                        }
+                        inner => variants.push(inner),
+                    }
+                    return Ok(Value::Array(variants));
+                }
+                _ => return unsupported(),
+            },
+
+            name => return self.named(name, path),
+        };
+        Ok(json!(primitive))
+    }
+
+    /// Returns the schema referencing a struct, enum or type alias of the code.
+    fn named(&self, name: &str, path: &str) -> Result<Value> {
+        let Some(item) = self.types.get(name) else {
+            return Err(Error::Schema(format!(
+                "Type `{name}` of `{path}` is not an annotated struct or enum of the code"
+            )));
+        };
+        match item {
+            Item::Type(item) => match item.ty.as_ref() {
+                Type::Array(array) => self.fixed(array, name, path),
+                ty => self.schema(ty, path),
+            },
+            Item::Enum(item) if item.variants.iter().any(|v| !v.fields.is_empty()) => {
+                let mut variants = vec![];
+                for variant in &item.variants {
+                    match &variant.fields {
+                        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                            variants.push(self.schema(&fields.unnamed[0].ty, path)?)
+                        }
+                        _ => {
+                            return Err(Error::Schema(format!(
+                                "Variant `{name}::{}` of a union must hold a single value",
+                                variant.ident
+                            )))
+                        }
+                    }
+                }
+                Ok(Value::Array(variants))
+            }
+            Item::Struct(syn::ItemStruct { attrs, .. })
+            | Item::Enum(syn::ItemEnum { attrs, .. }) => {
+                let attrs = attributes(attrs)?;
+                let name = attrs.rename.unwrap_or(name.to_string());
+                Ok(match attrs.namespace {
+                    Some(namespace) => json!(format!("{namespace}.{name}")),
+                    None => json!(name),
+                })
+            }
+            _ => unreachable!("only structs, enums and type aliases are collected"),
+        }
+    }
+
+    /// Returns the schema of a fixed, from an array of bytes.
+    fn fixed(&self, array: &syn::TypeArray, name: &str, path: &str) -> Result<Value> {
+        let size = match &array.len {
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Int(size),
+                ..
+            }) if is_byte(&array.elem) => size.base10_parse::<usize>().ok(),
+            _ => None,
+        };
+        match size {
+            Some(size) => Ok(json!({"type": "fixed", "name": name, "size": size})),
+            None => Err(Error::Schema(format!(
+                "Unsupported type of `{path}`: only byte arrays of literal sizes are fixed"
+            ))),
+        }
+    }
+}
+
+/// The serde and `AvroSchema` attributes, and doc comments, of an item.
+#[derive(Default)]
+struct Attributes {
+    rename: Option<String>,
+    skip: bool,
+    namespace: Option<String>,
+    doc: Option<String>,
+    default: Option<String>,
+}
+
+fn attributes(attrs: &[Attribute]) -> Result<Attributes> {
+    let mut attributes = Attributes::default();
+    let mut doc = vec![];
+    for attr in attrs {
+        if attr.path().is_ident("doc") {
+            if let syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) = &attr.meta
+            {
+                doc.push(line.value().trim().to_string());
+            }
+        } else if attr.path().is_ident("serde") || attr.path().is_ident("avro") {
+            attr.parse_nested_meta(|meta| {
+                let key = meta.path.get_ident().map(ToString::to_string);
+                let value =
+                    || -> syn::Result<String> { Ok(meta.value()?.parse::<syn::LitStr>()?.value()) };
+                match key.as_deref() {
+                    Some("rename") if meta.input.peek(syn::Token![=]) => {
+                        attributes.rename = Some(value()?)
+                    }
+                    Some("skip") => attributes.skip = true,
+                    Some("namespace") => attributes.namespace = Some(value()?),
+                    Some("doc") => attributes.doc = Some(value()?),
+                    Some("default") if attr.path().is_ident("avro") => {
+                        attributes.default = Some(value()?)
+                    }
+                    // Other attributes do not change the schema
+                    _ => {
+                        if meta.input.peek(syn::Token![=]) {
+                            meta.value()?.parse::<Expr>()?;
+                        } else if meta.input.peek(syn::token::Paren) {
+                            meta.parse_nested_meta(|meta| {
+                                if meta.input.peek(syn::Token![=]) {
+                                    meta.value()?.parse::<Expr>()?;
+                                }
+                                Ok(())
+                            })?;
+                        }
+                    }
+                }
+                Ok(())
+            })
+            .map_err(|e| Error::Schema(format!("Invalid attribute: {e}")))?;
+        }
+    }
+    if attributes.doc.is_none() && !doc.is_empty() {
+        attributes.doc = Some(doc.join("\n"));
+    }
+    Ok(attributes)
+}
+
+/// Returns whether an item derives `Serialize` or `AvroSchema`.
+fn is_annotated(attrs: &[Attribute]) -> Result<bool> {
+    let mut annotated = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("derive")) {
+        attr.parse_nested_meta(|meta| {
+            let derived = meta.path.segments.last().map(|s| s.ident.to_string());
+            annotated |= matches!(derived.as_deref(), Some("Serialize" | "AvroSchema"));
+            Ok(())
+        })
+        .map_err(|e| Error::Schema(format!("Invalid derive attribute: {e}")))?;
+    }
+    Ok(annotated)
+}
+
+fn is_byte(ty: &Type) -> bool {
+    matches!(ty, Type::Path(ty) if ty.path.is_ident("u8"))
+}
+
+/// Returns the name of a type, for error messages.
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+        _ => "<type>".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_to_avro() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let code = r#"
+use std::collections::HashMap;
+
+pub type Md5 = [u8; 16];
+
+/// A user.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[avro(namespace = "com.acme")]
+pub struct User {
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub r#type: Kind,
+    pub email: Option<String>,
+    /// The checksum of the avatar.
+    pub avatar: Md5,
+    #[avro(default = "[]")]
+    pub tags: Vec<String>,
+    pub attributes: HashMap<String, Vec<u8>>,
+    pub value: Option<UnionIntString>,
+    #[serde(skip)]
+    pub cached: bool,
+}
+
+#[derive(serde::Serialize)]
+#[avro(namespace = "com.acme")]
+pub enum Kind {
+    Admin,
+    #[serde(rename = "USER")]
+    User,
+}
+
+#[derive(serde::Serialize)]
+pub enum UnionIntString {
+    Int(i32),
+    String(String),
+}
+
+pub struct NotAnnotated {}
+"#;
+        let schemas = schemas_from_rust(code)?;
+        let expected = Schema::parse_list(&[
+            r#"
+        {"type": "record", "name": "User", "namespace": "com.acme", "doc": "A user.", "fields": [
+          {"name": "id", "type": "long"},
+          {"name": "type", "type": "com.acme.Kind"},
+          {"name": "email", "type": ["null", "string"], "default": null},
+          {"name": "avatar", "type": {"type": "fixed", "name": "Md5", "size": 16},
+           "doc": "The checksum of the avatar."},
+          {"name": "tags", "type": {"type": "array", "items": "string"}, "default": []},
+          {"name": "attributes", "type": {"type": "map", "values": "bytes"}},
+          {"name": "value", "type": ["null", "int", "string"], "default": null}
+        ]}
+        "#,
+            r#"{"type": "enum", "name": "Kind", "namespace": "com.acme", "symbols": ["Admin", "USER"]}"#,
+        ])?;
+        assert_eq!(
+            serde_json::to_value(&expected)?,
+            serde_json::to_value(&schemas)?
+        );
+
+        let err = schemas_from_rust(
+            "#[derive(serde::Serialize)] struct User { a: u64, b: NotAnnotated }",
+        )
+        .unwrap_err();
+        assert_eq!(
+            "Schema error: Unsupported type of `User.a`: u64",
+            err.to_string()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! Golden-file testing of generated code, see
+//! [`Generator::assert_matches_snapshot`](crate::Generator::assert_matches_snapshot).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::error::Result;
+
+/// The environment variable which, when set to `1`, makes snapshot assertions rewrite the
+/// snapshots instead of comparing them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "RSGEN_AVRO_UPDATE_SNAPSHOTS";
+
+/// The number of unchanged lines shown around changed lines.
+const CONTEXT_LINES: usize = 2;
+
+/// Compares generated items, as `(name, code)` pairs, with the `{name}.rs` snapshots of
+/// `dir`, and returns a report of the differences, if any.
+///
+/// With `update`, snapshots are written for new and changed items and removed for items
+/// that are no longer generated, and no report is returned.
+pub(crate) fn compare(
+    items: &[(String, String)],
+    dir: &Path,
+    update: bool,
+) -> Result<Option<String>> {
+    let mut snapshots = BTreeMap::new();
+    if dir.is_dir() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                let name = path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned();
+                snapshots.insert(name, fs::read_to_string(&path)?);
+            }
+        }
+    }
+
+    if update {
+        fs::create_dir_all(dir)?;
+        for (name, code) in items {
+            if snapshots.remove(name).as_ref() != Some(code) {
+                fs::write(dir.join(format!("{name}.rs")), code)?;
+            }
+        }
+        for name in snapshots.keys() {
+            fs::remove_file(dir.join(format!("{name}.rs")))?;
+        }
+        return Ok(None);
+    }
+
+    let mut report = vec![];
+    for (name, code) in items {
+        match snapshots.remove(name) {
+            None => report.push(format!("new: {name}.rs (no snapshot)")),
+            Some(snapshot) if &snapshot != code => {
+                report.push(format!("changed: {name}.rs\n{}", diff(&snapshot, code)))
+            }
+            Some(_) => (),
+        }
+    }
+    for name in snapshots.keys() {
+        report.push(format!("removed: {name}.rs (no longer generated)"));
+    }
+
+    if report.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Generated code does not match the snapshots of {}:\n\n{}\n\nSet {UPDATE_SNAPSHOTS_ENV}=1 to update them.",
+        dir.display(),
+        report.join("\n\n")
+    )))
+}
+
+/// Returns the changed lines between a snapshot and the generated code, with a few lines
+/// of context and the line numbers of the generated code.
+fn diff(snapshot: &str, code: &str) -> String {
+    // (line number in the generated code, marker, line)
+    let mut lines = vec![];
+    let mut line_nb = 0;
+    let snapshot = snapshot.lines().collect::<Vec<_>>();
+    let code = code.lines().collect::<Vec<_>>();
+    for result in diff::slice(&snapshot, &code) {
+        match result {
+            diff::Result::Left(l) => lines.push((line_nb, '-', l)),
+            diff::Result::Right(r) => {
+                line_nb += 1;
+                lines.push((line_nb, '+', r))
+            }
+            diff::Result::Both(l, _) => {
+                line_nb += 1;
+                lines.push((line_nb, ' ', l))
+            }
+        }
+    }
+
+    let changed = |i: usize| lines.get(i).is_some_and(|(_, marker, _)| *marker != ' ');
+    let mut out = vec![];
+    let mut skipped = false;
+    for (i, (line_nb, marker, line)) in lines.iter().enumerate() {
+        let near_change = (i.saturating_sub(CONTEXT_LINES)..=i + CONTEXT_LINES).any(changed);
+        if !near_change {
+            skipped = true;
+            continue;
+        }
+        if skipped && !out.is_empty() {
+            out.push("      ...".to_string());
+        }
+        skipped = false;
+        match marker {
+            '-' => out.push(format!("     {marker} {line}")),
+            _ => out.push(format!("{line_nb:>4} {marker} {line}")),
+        }
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let items = |user: &str| {
+            vec![
+                ("User".to_string(), user.to_string()),
+                ("Kind".to_string(), "pub enum Kind {}\n".to_string()),
+            ]
+        };
+        let fields = "abcdefghi"
+            .chars()
+            .map(|c| format!("    pub {c}: i32,\n"))
+            .collect::<String>();
+        let user = format!("pub struct User {{\n{fields}}}\n");
+        let user = user.as_str();
+
+        let report = compare(&items(user), dir.path(), false)?.unwrap();
+        assert!(report.contains("new: User.rs (no snapshot)\n\nnew: Kind.rs (no snapshot)"));
+
+        assert_eq!(None, compare(&items(user), dir.path(), true)?);
+        assert_eq!(None, compare(&items(user), dir.path(), false)?);
+
+        fs::write(dir.path().join("Old.rs"), "")?;
+        let changed = user.replace("pub a", "pub aa").replace("pub i", "pub ii");
+        let report = compare(&items(&changed), dir.path(), false)?.unwrap();
+        assert_eq!(
+            format!(
+                "Generated code does not match the snapshots of {}:\n\n\
+                 changed: User.rs\n\
+                 \x20  1   pub struct User {{\n\
+                 \x20    -     pub a: i32,\n\
+                 \x20  2 +     pub aa: i32,\n\
+                 \x20  3       pub b: i32,\n\
+                 \x20  4       pub c: i32,\n\
+                 \x20     ...\n\
+                 \x20  8       pub g: i32,\n\
+                 \x20  9       pub h: i32,\n\
+                 \x20    -     pub i: i32,\n\
+                 \x20 10 +     pub ii: i32,\n\
+                 \x20 11   }}\n\n\
+                 removed: Old.rs (no longer generated)\n\n\
+                 Set RSGEN_AVRO_UPDATE_SNAPSHOTS=1 to update them.",
+                dir.path().display()
+            ),
+            report
+        );
+
+        assert_eq!(None, compare(&items(&changed), dir.path(), true)?);
+        assert!(!dir.path().join("Old.rs").exists());
+        assert_eq!(changed, fs::read_to_string(dir.path().join("User.rs"))?);
+
+        dir.close()?;
+        Ok(())
+    }
+}
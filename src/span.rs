@@ -0,0 +1,245 @@
+//! Locations of errors in the JSON documents of schema files, for them to be reported with
+//! the file, line and column of the offending value and a snippet of the document.
+
+use std::ops::Range;
+use std::path::Path;
+
+use crate::error::{Error, ErrorKind};
+
+/// Returns an error located at the value of `error` in the document of a schema file, if it
+/// points into it (invalid JSON and default values), or the error as it is.
+pub(crate) fn locate(error: Error, raw_schema: &str, path: &Path) -> Error {
+    let span = match &error {
+        Error::InvalidDefault { pointer, .. } | Error::InvalidDefaultSize { pointer, .. } => {
+            pointer_span(raw_schema, pointer)
+        }
+        _ => None,
+    };
+    match span {
+        Some(span) => located(error, raw_schema, path, span),
+        None => error,
+    }
+}
+
+/// Returns the error of a schema document which is not valid JSON, if it is not.
+pub(crate) fn json_error(raw_schema: &str, path: &Path) -> Option<Error> {
+    let e = serde_json::from_str::<serde::de::IgnoredAny>(raw_schema).err()?;
+    // serde_json columns are 1-based, and 0 at the end of lines
+    let line_start = line_offsets(raw_schema)
+        .nth(e.line().saturating_sub(1))
+        .unwrap_or(raw_schema.len());
+    let line_end = raw_schema[line_start..]
+        .find('\n')
+        .map_or(raw_schema.len(), |end| line_start + end);
+    let start = raw_schema[line_start..line_end]
+        .char_indices()
+        .nth(e.column().saturating_sub(1))
+        .map_or(line_end, |(i, _)| line_start + i);
+    let end = raw_schema[start..]
+        .chars()
+        .next()
+        .filter(|c| *c != '\n')
+        .map_or(start, |c| start + c.len_utf8());
+    let error = Error::InvalidSchema {
+        kind: ErrorKind::Json,
+        message: format!("Invalid JSON: {e}"),
+    };
+    Some(located(error, raw_schema, path, start..end))
+}
+
+/// Returns `error` located at the bytes `span` of a schema document.
+fn located(error: Error, raw_schema: &str, path: &Path, span: Range<usize>) -> Error {
+    let line_start = raw_schema[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = raw_schema[span.start..]
+        .find('\n')
+        .map_or(raw_schema.len(), |end| span.start + end);
+    let line = raw_schema[..span.start].matches('\n').count() + 1;
+    let column = raw_schema[line_start..span.start].chars().count() + 1;
+
+    // Values spanning several lines are underlined up to the end of their first line
+    let code = raw_schema[line_start..line_end].trim_end_matches('\r');
+    let width = raw_schema[span.start..span.end.min(line_start + code.len())]
+        .chars()
+        .count()
+        .max(1);
+    let number = line.to_string();
+    let gutter = " ".repeat(number.len());
+    let snippet = format!(
+        "{gutter} |\n{number} | {code}\n{gutter} | {}{}",
+        " ".repeat(column - 1),
+        "^".repeat(width)
+    );
+
+    Error::Located {
+        error: Box::new(error),
+        path: path.to_path_buf(),
+        line,
+        column,
+        snippet,
+    }
+}
+
+/// Returns the byte offsets of the lines of a document.
+fn line_offsets(raw: &str) -> impl Iterator<Item = usize> + '_ {
+    std::iter::once(0).chain(raw.match_indices('\n').map(|(i, _)| i + 1))
+}
+
+/// Returns the bytes of the value at a JSON `pointer` in a JSON document, if there is one.
+fn pointer_span(raw: &str, pointer: &str) -> Option<Range<usize>> {
+    let mut scanner = Scanner { raw, pos: 0 };
+    scanner.whitespace();
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        scanner.member(&token)?;
+    }
+    let start = scanner.pos;
+    scanner.value()?;
+    Some(start..scanner.pos)
+}
+
+/// A cursor over a JSON document, assumed to be valid.
+struct Scanner<'a> {
+    raw: &'a str,
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.raw.as_bytes().get(self.pos).copied()
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips the punctuation `byte` and the whitespace after it.
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.peek() == Some(byte)).then(|| {
+            self.pos += 1;
+            self.whitespace();
+        })
+    }
+
+    /// Moves to the value of the object member or array item named `token`.
+    fn member(&mut self, token: &str) -> Option<()> {
+        match self.peek()? {
+            b'{' => {
+                self.expect(b'{')?;
+                while self.peek()? == b'"' {
+                    let start = self.pos;
+                    self.string()?;
+                    let key: String = serde_json::from_str(&self.raw[start..self.pos]).ok()?;
+                    self.whitespace();
+                    self.expect(b':')?;
+                    if key == token {
+                        return Some(());
+                    }
+                    self.value()?;
+                    self.whitespace();
+                    let _ = self.expect(b',');
+                }
+                None
+            }
+            b'[' => {
+                let index: usize = token.parse().ok()?;
+                self.expect(b'[')?;
+                for _ in 0..index {
+                    self.value()?;
+                    self.whitespace();
+                    self.expect(b',')?;
+                }
+                (self.peek()? != b']').then_some(())
+            }
+            _ => None,
+        }
+    }
+
+    /// Skips a value, without the whitespace after it.
+    fn value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => self.string(),
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.expect(open)?;
+                while self.peek()? != close {
+                    if open == b'{' {
+                        self.string()?;
+                        self.whitespace();
+                        self.expect(b':')?;
+                    }
+                    self.value()?;
+                    self.whitespace();
+                    let _ = self.expect(b',');
+                }
+                self.pos += 1;
+                Some(())
+            }
+            _ => {
+                // Numbers, booleans and null
+                let len = self.raw[self.pos..]
+                    .find(|c: char| matches!(c, ',' | '}' | ']') || c.is_whitespace())
+                    .unwrap_or(self.raw.len() - self.pos);
+                self.pos += len;
+                (len > 0).then_some(())
+            }
+        }
+    }
+
+    /// Skips a string, escapes included.
+    fn string(&mut self) -> Option<()> {
+        let bytes = self.raw.as_bytes();
+        if self.peek()? != b'"' {
+            return None;
+        }
+        let mut i = self.pos + 1;
+        while *bytes.get(i)? != b'"' {
+            i += if bytes[i] == b'\\' { 2 } else { 1 };
+        }
+        self.pos = i + 1;
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointer_spans() {
+        let raw = r#"{
+  "name": "a\"b",
+  "fields": [
+    {"name": "x", "default": [1, {"k/l": null}]},
+    {"name": "y", "default": "abc"}
+  ]
+}"#;
+        let span = |pointer| pointer_span(raw, pointer).map(|span| &raw[span]);
+        assert_eq!(Some(r#""a\"b""#), span("/name"));
+        assert_eq!(Some(r#""abc""#), span("/fields/1/default"));
+        assert_eq!(Some("1"), span("/fields/0/default/0"));
+        assert_eq!(Some("null"), span("/fields/0/default/1/k~1l"));
+        assert_eq!(None, span("/fields/2"));
+        assert_eq!(None, span("/missing"));
+    }
+
+    #[test]
+    fn snippets() {
+        let raw = "{\n  \"fields\": [\n    {\"name\": \"y\", \"default\": \"abc\"}\n  ]\n}";
+        let error = Error::Schema("Invalid".into());
+        let span = pointer_span(raw, "/fields/0/default").unwrap();
+        assert_eq!(
+            "Schema error: Invalid\n --> a.avsc:3:30\n  |\n3 |     {\"name\": \"y\", \"default\": \"abc\"}\n  |                              ^^^^^",
+            located(error, raw, Path::new("a.avsc"), span).to_string()
+        );
+
+        let error = json_error("{\n  \"a\": 1,\n  x\n}", Path::new("a.avsc")).unwrap();
+        assert!(
+            error
+                .to_string()
+                .ends_with(" --> a.avsc:3:3\n  |\n3 |   x\n  |   ^"),
+            "{error}"
+        );
+    }
+}
@@ -3,19 +3,29 @@
 #![allow(clippy::try_err)]
 
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use apache_avro::schema::{
     ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, Name, RecordField,
     RecordSchema, UnionSchema,
 };
 use apache_avro::Schema;
-use heck::{ToSnakeCase, ToUpperCamelCase};
-use lazy_static::lazy_static;
+use heck::{ToShoutySnakeCase, ToSnakeCase};
 use serde_json::Value;
-use tera::{Context, Tera};
 
-use crate::error::{Error, Result};
+use crate::context::{EnumContext, FixedContext, RecordContext, UnionContext, UnionVisitorContext};
+use crate::decimal::{decimal_mantissa, scale_modules};
+use crate::defaults::{item_variant, zero_default};
+use crate::error::{Error, ErrorKind, Result};
+use crate::gen::ItemKind;
+use crate::naming::{sanitize, DefaultNameMapper, NameMapper};
+use crate::render::{Renderer, TeraRenderer};
+use crate::types::{DefaultTypeMapper, TypeMapper, TypeOptions};
 
 pub const RECORD_TERA: &str = "record.tera";
 pub const RECORD_TEMPLATE: &str = r#"
@@ -25,11 +35,29 @@ pub const RECORD_TEMPLATE: &str = r#"
 /// {{ doc_line }}
 {%- endfor %}
 {%- endif %}
-#[derive(Debug, PartialEq{%- if is_eq_derivable %}, Eq{%- endif %}, Clone, serde::Deserialize, serde::Serialize{%- if derive_builders %}, derive_builder::Builder {%- endif %}{%- if derive_schemas %}, apache_avro::AvroSchema {%- endif %})]
+{%- if example %}
+{%- if doc %}
+///
+{%- endif %}
+/// # Examples
+///
+/// ```
+{%- set example_lines = example | split(pat="\n") %}
+{%- for example_line in example_lines %}
+/// {{ example_line }}
+{%- endfor %}
+/// ```
+{%- endif %}
+{%- set all_defaults = default_impls and fields | length == defaults | length %}
+{%- set default_impl = default_impls and fields | length == defaults | length + empty | length %}
+{%- for lint in lints %}
+#[{{ lint }}]
+{%- endfor %}
+#[derive({%- if debug_impls and not sensitive and not hex %}Debug, {% endif %}PartialEq{%- if is_eq_derivable %}, Eq{%- endif %}, Clone{%- if serde_impls %}, serde::Deserialize, serde::Serialize{%- endif %}{%- if derive_builders %}, derive_builder::Builder {%- endif %}{%- if derive_schemas %}, {{ avro_crate }}::AvroSchema {%- endif %}{%- if validate_derive %}, {{ validate_derive }}::Validate{%- endif %})]
 {%- if derive_builders %}
 #[builder(setter(into))]
 {%- endif %}
-{%- if fields | length == defaults | length %}
+{%- if all_defaults and serde_impls %}
 #[serde(default)]
 {%- endif %}
 pub struct {{ name }} {
@@ -41,7 +69,7 @@ pub struct {{ name }} {
     {%- endfor %}
     {%- endif %}
     {%- set type = types[f] %}
-    {%- if f != originals[f] and not f is starting_with("r#") %}
+    {%- if serde_impls and f | trim_start_matches(pat="r#") != originals[f] %}
     #[serde(rename = "{{ originals[f] }}")]
     {%- endif %}
     {%- if nullable and not type is starting_with("Option") %}
@@ -50,12 +78,27 @@ pub struct {{ name }} {
     {%- if nullable and not type is starting_with("Option") and serde_with is containing(f) %}
     #[serde(serialize_with = "{{ serde_with[f] }}::serialize")]
     {%- endif %}
-    {%- if not nullable and serde_with is containing(f) %}
+    {%- if serde_impls and deserialization == "strict" and type is starting_with("Option") and not defaults is containing(f) and not serde_with is containing(f) %}
+    #[serde(deserialize_with = "serde::Deserialize::deserialize")]
+    {%- endif %}
+    {%- if serde_impls and (not nullable or type is starting_with("Option")) and serde_with is containing(f) and not omitted is containing(f) %}
     #[serde(with = "{{ serde_with[f] }}")]
     {%- endif %}
-    {%- if defaults is containing(f) and not fields | length == defaults | length %}
+    {%- if serde_impls and defaults is containing(f) and not all_defaults %}
     #[serde(default = "default_{{ name | lower }}_{{ f | lower | trim_start_matches(pat="r#") }}")]
     {%- endif %}
+    {%- if serde_impls and omitted is containing(f) %}
+    #[serde(deserialize_with = "present_{{ name | lower }}_{{ f | lower | trim_start_matches(pat="r#") }}")]
+    {%- if serde_with is containing(f) %}
+    #[serde(serialize_with = "{{ serde_with[f] }}::serialize")]
+    {%- endif %}
+    #[serde(skip_serializing_if = "Option::is_none")]
+    {%- endif %}
+    {%- if validations[f] %}
+    {%- for validation in validations[f] %}
+    #[{{ validation }}]
+    {%- endfor %}
+    {%- endif %}
     pub {{ f }}: {{ type }},
     {%- endfor %}
 }
@@ -82,25 +125,120 @@ where
 {%- endif %}
 {%- endfor %}
 
+{%- if serde_impls %}
+{%- for f in omitted %}
+{# #}
+#[inline(always)]
+fn present_{{ name | lower }}_{{ f | lower | trim_start_matches(pat="r#") }}<'de, D>(deserializer: D) -> Result<{{ types[f] }}, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    {%- if serde_with is containing(f) %}
+    {#- The module of the inner type, e.g. `serde_avro_bytes` for `serde_avro_bytes_opt` #}
+    {{ serde_with[f] | trim_end_matches(pat="_option") | trim_end_matches(pat="_opt") }}::deserialize(deserializer).map(Some)
+    {%- else %}
+    serde::Deserialize::deserialize(deserializer).map(Some)
+    {%- endif %}
+}
+{%- endfor %}
+{%- endif %}
+
 {%- for f in fields %}
 {%- if defaults is containing(f) %}
 {# #}
+{%- if not serde_impls and not default_impl %}
+#[allow(dead_code)]
+{%- endif %}
 #[inline(always)]
 fn default_{{ name | lower }}_{{ f | lower | trim_start_matches(pat="r#") }}() -> {{ types[f] }} { {{ defaults[f] }} }
 {%- endif %}
 {%- endfor %}
-{%- if fields | length == defaults | length %}
+{%- if default_impl %}
 {# #}
 impl Default for {{ name }} {
     fn default() -> {{ name }} {
         {{ name }} {
             {%- for f in fields %}
+            {%- if empty is containing(f) %}
+            {{ f }}: Default::default(),
+            {%- else %}
             {{ f }}: default_{{ name | lower }}_{{ f | lower | trim_start_matches(pat="r#") }}(),
+            {%- endif %}
             {%- endfor %}
         }
     }
 }
 {%- endif %}
+{%- if sensitive and zeroize %}
+{# #}
+impl zeroize::Zeroize for {{ name }} {
+    fn zeroize(&mut self) {
+        {%- for f in sensitive %}
+        zeroize::Zeroize::zeroize(&mut self.{{ f }});
+        {%- endfor %}
+    }
+}
+
+impl Drop for {{ name }} {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+{%- endif %}
+{%- if debug_impls and (sensitive or hex) %}
+{# #}
+impl std::fmt::Debug for {{ name }} {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        {%- if hex %}
+        struct Hex<'a>(&'a [u8]);
+
+        impl std::fmt::Debug for Hex<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("0x")?;
+                for byte in self.0.iter().take(8) {
+                    write!(f, "{byte:02X}")?;
+                }
+                if self.0.len() > 8 {
+                    f.write_str("…")?;
+                }
+                write!(f, " ({} bytes)", self.0.len())
+            }
+        }
+{# #}
+        {%- endif %}
+        f.debug_struct("{{ name }}")
+            {%- for f in fields %}
+            {%- if sensitive is containing(f) %}
+            .field("{{ f | trim_start_matches(pat="r#") }}", &"****")
+            {%- elif hex is containing(f) and types[f] is starting_with("Option") %}
+            .field("{{ f | trim_start_matches(pat="r#") }}", &self.{{ f }}.as_ref().map(|v| Hex(v)))
+            {%- elif hex is containing(f) %}
+            .field("{{ f | trim_start_matches(pat="r#") }}", &Hex(&self.{{ f }}))
+            {%- else %}
+            .field("{{ f | trim_start_matches(pat="r#") }}", &self.{{ f }})
+            {%- endif %}
+            {%- endfor %}
+            .finish()
+    }
+}
+{%- endif %}
+{%- if wrapper_conversions and fields | length == 1 %}
+{%- set f = fields[0] %}
+{# #}
+impl From<{{ types[f] }}> for {{ name }} {
+    fn from({{ f }}: {{ types[f] }}) -> Self {
+        Self { {{ f }} }
+    }
+}
+{%- if not sensitive or not zeroize %}
+{# #}
+impl From<{{ name }}> for {{ types[f] }} {
+    fn from(value: {{ name }}) -> Self {
+        value.{{ f }}
+    }
+}
+{%- endif %}
+{%- endif %}
 "#;
 
 pub const ENUM_TERA: &str = "enum.tera";
@@ -111,22 +249,111 @@ pub const ENUM_TEMPLATE: &str = r#"
 /// {{ doc_line }}
 {%- endfor %}
 {%- endif %}
-#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+{%- set lenient = serde_impls and deserialization == "lenient" and not string_enums %}
+{%- for lint in lints %}
+#[{{ lint }}]
+{%- endfor %}
+#[derive({%- if debug_impls %}Debug, {% endif %}PartialEq, Eq, Hash, PartialOrd, Ord, Clone{%- if serde_impls %}{%- if not lenient %}, serde::Deserialize{%- endif %}, serde::Serialize{%- endif %})]
+{%- if string_enums %}
+{%- if serde_impls %}
+#[serde(transparent)]
+{%- endif %}
+pub struct {{ name }}(pub String);
+
+impl {{ name }} {
+    {%- for s in symbols %}
+    {%- if docs[s] %}
+    {%- set doc_lines = docs[s] | split(pat="\n") %}
+    {%- for doc_line in doc_lines %}
+    /// {{ doc_line }}
+    {%- endfor %}
+    {%- endif %}
+    pub const {{ constants[s] }}: &'static str = "{{ originals[s] }}";
+    {%- endfor %}
+
+    /// The symbols of the schema of `{{ name | trim_start_matches(pat="r#") }}`.
+    pub const SYMBOLS: &'static [&'static str] = &[{%- for s in symbols %}Self::{{ constants[s] }}{%- if not loop.last %}, {% endif %}{%- endfor %}];
+
+    /// Returns whether the symbol is one of the [`SYMBOLS`](Self::SYMBOLS) of the schema.
+    pub fn is_known(&self) -> bool {
+        Self::SYMBOLS.contains(&self.0.as_str())
+    }
+}
+
+impl From<&str> for {{ name }} {
+    fn from(symbol: &str) -> Self {
+        Self(symbol.to_string())
+    }
+}
+
+impl PartialEq<&str> for {{ name }} {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+{%- else %}
 pub enum {{ name }} {
     {%- for s in symbols %}
-    {%- if s != originals[s] %}
+    {%- if docs[s] %}
+    {%- set doc_lines = docs[s] | split(pat="\n") %}
+    {%- for doc_line in doc_lines %}
+    /// {{ doc_line }}
+    {%- endfor %}
+    {%- endif %}
+    {%- if serde_impls and s != originals[s] %}
     #[serde(rename = "{{ originals[s] }}")]
     {%- endif %}
+    {%- if serde_impls and aliases[s] %}
+    #[serde({% for alias in aliases[s] %}alias = "{{ alias }}"{% if not loop.last %}, {% endif %}{% endfor %})]
+    {%- endif %}
     {{ s }},
     {%- endfor %}
 }
+{%- endif %}
+{%- if lenient %}
+
+impl<'de> serde::Deserialize<'de> for {{ name }} {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "{{ name | trim_start_matches(pat="r#") }}")]
+        enum Lenient {
+            {%- for s in symbols %}
+            {%- if s != originals[s] %}
+            #[serde(rename = "{{ originals[s] }}")]
+            {%- endif %}
+            {%- if aliases[s] %}
+            #[serde({% for alias in aliases[s] %}alias = "{{ alias }}"{% if not loop.last %}, {% endif %}{% endfor %})]
+            {%- endif %}
+            {{ s }},
+            {%- endfor %}
+            #[serde(other)]
+            __Unknown,
+        }
+
+        Ok(match Lenient::deserialize(deserializer)? {
+            {%- for s in symbols %}
+            Lenient::{{ s }} => {{ name }}::{{ s }},
+            {%- endfor %}
+            Lenient::__Unknown => {{ name }}::{{ fallback }},
+        })
+    }
+}
+{%- endif %}
 "#;
 
 pub const UNION_TERA: &str = "union.tera";
 pub const UNION_TEMPLATE: &str = r#"
 /// Auto-generated type for unnamed Avro union variants.
-#[derive(Debug, PartialEq{%- if is_eq_derivable %}, Eq{%- endif %}, Clone, serde::Deserialize, serde::Serialize)]
+{%- for lint in lints %}
+#[{{ lint }}]
+{%- endfor %}
+#[derive({%- if debug_impls %}Debug, {% endif %}PartialEq{%- if is_eq_derivable %}, Eq{%- endif %}, Clone{%- if serde_impls %}, serde::Deserialize, serde::Serialize{%- endif %})]
+{%- if serde_impls %}
 #[serde(remote = "Self")]
+{%- endif %}
 pub enum {{ name }} {
     {%- for s in symbols %}
     {{ s }},
@@ -139,7 +366,7 @@ impl From<{{ v.rust_type }}> for {{ name }} {
         Self::{{ v.variant }}(v)
     }
 }
-{% if visitors | length == 1 %}
+{% if symbols | length == 1 %}
 impl From<{{ name }}> for {{ v.rust_type }} {
     fn from(v: {{ name }}) -> Self {
         let {{ name }}::{{ v.variant }}(v) = v;
@@ -160,12 +387,31 @@ impl TryFrom<{{ name }}> for {{ v.rust_type }} {
 }
 {%- endif %}
 {%- endfor %}
+{%- if serde_impls %}
 
 impl serde::Serialize for {{ name }} {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
+        {%- if json == "external" %}
+        if serializer.is_human_readable() {
+            return Self::serialize(self, serializer);
+        }
+{# #}
+        {%- elif json == "avro" %}
+        if serializer.is_human_readable() {
+            use serde::ser::SerializeMap;
+            let mut map = serializer.serialize_map(Some(1))?;
+            match self {
+                {%- for v in variants %}
+                {{ name }}::{{ v }}(v) => map.serialize_entry("{{ json_names[v] }}", v)?,
+                {%- endfor %}
+            }
+            return map.end();
+        }
+{# #}
+        {%- endif %}
         struct NewtypeVariantSerializer<S>(S);
 
         impl<S> serde::Serializer for NewtypeVariantSerializer<S>
@@ -223,12 +469,40 @@ impl serde::Serialize for {{ name }} {
     }
 }
 {# #}
-{%- if use_avro_rs_unions %}
 impl<'de> serde::Deserialize<'de> for {{ name }} {
-    fn deserialize<D>(deserializer: D) -> Result<{{ name }}, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<{% if use_avro_rs_unions %}{{ name }}{% else %}Self{% endif %}, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
+        {%- if json == "untagged" or json == "avro" %}
+        if deserializer.is_human_readable() {
+            #[derive(serde::Deserialize)]
+            {%- if json == "untagged" %}
+            #[serde(untagged)]
+            {%- endif %}
+            enum Json {
+                {%- for s in symbols %}
+                {%- if json == "avro" %}
+                #[serde(rename = "{{ json_names[variants[loop.index0]] }}")]
+                {%- endif %}
+                {{ s }},
+                {%- endfor %}
+            }
+
+            return Ok(match <Json as serde::Deserialize>::deserialize(deserializer)? {
+                {%- for v in variants %}
+                Json::{{ v }}(v) => {{ name }}::{{ v }}(v),
+                {%- endfor %}
+            });
+        }
+{# #}
+        {%- elif json == "external" and use_avro_rs_unions %}
+        if deserializer.is_human_readable() {
+            return Self::deserialize(deserializer);
+        }
+{# #}
+        {%- endif %}
+        {%- if use_avro_rs_unions %}
         /// Serde visitor for the auto-generated unnamed Avro union type.
         struct {{ name }}Visitor;
 
@@ -252,15 +526,9 @@ impl<'de> serde::Deserialize<'de> for {{ name }} {
         }
 
         deserializer.deserialize_any({{ name }}Visitor)
-    }
-}
-{%- else %}
-impl<'de> serde::Deserialize<'de> for {{ name }} {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
+        {%- else %}
         Self::deserialize(deserializer)
+        {%- endif %}
     }
 }
 {%- endif %}
@@ -268,66 +536,609 @@ impl<'de> serde::Deserialize<'de> for {{ name }} {
 
 pub const FIXED_TERA: &str = "fixed.tera";
 pub const FIXED_TEMPLATE: &str = "
+{%- for lint in lints %}
+#[{{ lint }}]
+{%- endfor %}
 pub type {{ name }} = [u8; {{ size }}];
 ";
 
-lazy_static! {
-    static ref RESERVED: HashSet<String> = {
-        let s: HashSet<_> = vec![
-            "Self", "abstract", "as", "async", "await", "become", "box", "break", "const",
-            "continue", "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn",
-            "for", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut",
-            "override", "priv", "pub", "ref", "return", "self", "static", "struct", "super",
-            "trait", "true", "try", "type", "typeof", "union", "unsafe", "unsized", "use",
-            "virtual", "where", "while", "yield",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-        s
+/// The built-in templates, by name.
+pub const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (RECORD_TERA, RECORD_TEMPLATE),
+    (ENUM_TERA, ENUM_TEMPLATE),
+    (FIXED_TERA, FIXED_TEMPLATE),
+    (UNION_TERA, UNION_TEMPLATE),
+];
+
+/// Writes the built-in templates as `.tera` files in the given directory.
+///
+/// The directory is created if needed. Written templates can be customized and then used
+/// with [`GeneratorBuilder::templates_dir`](crate::GeneratorBuilder::templates_dir).
+pub fn dump_templates(dir: impl AsRef<Path>) -> Result<()> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    for (name, template) in BUILTIN_TEMPLATES {
+        fs::write(dir.join(name), template)?;
+    }
+    Ok(())
+}
+
+/// How strictly generated types are deserialized, see
+/// [`GeneratorBuilder::deserialization`](crate::GeneratorBuilder::deserialization).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeserializationMode {
+    /// Fields without default value must be present, except optional ones which are then
+    /// `None`, and unknown enum symbols are errors.
+    #[default]
+    Standard,
+    /// Fields without default value must be present, including optional ones.
+    Strict,
+    /// Fields without default value take a zero value (e.g. `0`, an empty string, `None`)
+    /// when missing, and unknown enum symbols are deserialized as the enum default symbol,
+    /// or as its first symbol.
+    Lenient,
+}
+
+impl fmt::Display for DeserializationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DeserializationMode::Standard => "standard",
+            DeserializationMode::Strict => "strict",
+            DeserializationMode::Lenient => "lenient",
+        })
+    }
+}
+
+impl FromStr for DeserializationMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<DeserializationMode> {
+        match s {
+            "standard" => Ok(DeserializationMode::Standard),
+            "strict" => Ok(DeserializationMode::Strict),
+            "lenient" => Ok(DeserializationMode::Lenient),
+            _ => Err(Error::Config(format!(
+                "Unknown deserialization mode `{s}`, expected `standard`, `strict` or `lenient`"
+            ))),
+        }
+    }
+}
+
+/// Which record fields are generated as `Option`, see
+/// [`GeneratorBuilder::optional_fields`](crate::GeneratorBuilder::optional_fields).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionalFields {
+    /// Fields whose schema is an union starting with `null`.
+    #[default]
+    Unions,
+    /// Fields whose schema is an union starting with `null`, and fields having a default
+    /// value, which are omitted when `None` for their default value to be written instead.
+    Defaults,
+}
+
+impl fmt::Display for OptionalFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OptionalFields::Unions => "unions",
+            OptionalFields::Defaults => "defaults",
+        })
+    }
+}
+
+impl FromStr for OptionalFields {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<OptionalFields> {
+        match s {
+            "unions" => Ok(OptionalFields::Unions),
+            "defaults" => Ok(OptionalFields::Defaults),
+            _ => Err(Error::Config(format!(
+                "Unknown optional fields `{s}`, expected `unions` or `defaults`"
+            ))),
+        }
+    }
+}
+
+/// How the enums of unions are represented by human-readable formats like JSON, see
+/// [`GeneratorBuilder::union_json`](crate::GeneratorBuilder::union_json).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnionJson {
+    /// The value of the variant alone, e.g. `12` or `"text"`.
+    Untagged,
+    /// The value of the variant keyed by the name of the variant, e.g. `{"Long": 12}`.
+    External,
+    /// The value of the variant keyed by its Avro type name, as in the Avro JSON encoding,
+    /// e.g. `{"long": 12}` or `{"com.acme.User": {...}}`.
+    Avro,
+}
+
+impl fmt::Display for UnionJson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            UnionJson::Untagged => "untagged",
+            UnionJson::External => "external",
+            UnionJson::Avro => "avro",
+        })
+    }
+}
+
+impl FromStr for UnionJson {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<UnionJson> {
+        match s {
+            "untagged" => Ok(UnionJson::Untagged),
+            "external" => Ok(UnionJson::External),
+            "avro" => Ok(UnionJson::Avro),
+            _ => Err(Error::Config(format!(
+                "Unknown union JSON representation `{s}`, expected `untagged`, `external` or `avro`"
+            ))),
+        }
+    }
+}
+
+/// The crate deriving the `Validate` trait of the record structs, see
+/// [`GeneratorBuilder::validate_derive`](crate::GeneratorBuilder::validate_derive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidateDerive {
+    /// The `validator` crate, e.g. `#[validate(range(min = 1, max = 9))]`.
+    Validator,
+    /// The `serde_valid` crate, e.g. `#[validate(minimum = 1)]`.
+    SerdeValid,
+}
+
+impl fmt::Display for ValidateDerive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ValidateDerive::Validator => "validator",
+            ValidateDerive::SerdeValid => "serde_valid",
+        })
+    }
+}
+
+impl FromStr for ValidateDerive {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ValidateDerive> {
+        match s {
+            "validator" => Ok(ValidateDerive::Validator),
+            "serde_valid" => Ok(ValidateDerive::SerdeValid),
+            _ => Err(Error::Config(format!(
+                "Unknown validate derive `{s}`, expected `validator` or `serde_valid`"
+            ))),
+        }
+    }
+}
+
+/// The path of the apache-avro crate referenced by generated code, unless configured
+/// otherwise.
+pub const DEFAULT_AVRO_CRATE: &str = "apache_avro";
+
+/// The schema attribute selecting an alternative template for a named type.
+pub const TEMPLATE_ATTRIBUTE: &str = "rust.template";
+
+/// The record field attribute masking the field value in the generated `Debug` implementation,
+/// or the values of all its fields for a record.
+pub const SENSITIVE_ATTRIBUTE: &str = "rust.sensitive";
+
+/// The record field attribute mapping an `int` or `long` field, possibly optional, to `u32` or
+/// `u64`, for values known never to be negative.
+pub const UNSIGNED_ATTRIBUTE: &str = "rust.unsigned";
+
+/// The record field attribute generating a nested record field, possibly optional, as a
+/// `Box` of its struct, for the struct of the outer record to stay small.
+pub const BOX_ATTRIBUTE: &str = "rust.box";
+
+/// The record field attribute mapping an array field, possibly optional, to a
+/// `smallvec::SmallVec` storing up to the given number of items without allocating.
+pub const SMALLVEC_ATTRIBUTE: &str = "rust.smallvec";
+
+/// The enum attribute documenting its symbols, an object mapping symbols to the doc comments
+/// of their variants, e.g. `{"GREEN": "Ready to go."}`.
+pub const SYMBOL_DOCS_ATTRIBUTE: &str = "symbolDocs";
+
+/// The enum attribute listing the former symbols of renamed symbols, an object mapping
+/// symbols to the arrays of their former symbols, e.g. `{"GO": ["GREEN"]}`, deserialized as
+/// aliases of their variants.
+pub const SYMBOL_ALIASES_ATTRIBUTE: &str = "symbolAliases";
+
+/// Returns the code of a `module` of serde `with` modules named after the `chrono_modules`
+/// (e.g. `ts_milliseconds` for `chrono::serde::ts_milliseconds`), which only use these epoch
+/// timestamps with binary formats like Avro, and RFC 3339 strings with human-readable
+/// formats like JSON.
+fn rfc3339_module(module: &str, chrono_modules: &BTreeSet<String>) -> String {
+    let mut code = format!(
+        "\n/// Timestamps as RFC 3339 strings in human-readable formats, as epoch numbers otherwise.\n\
+         mod {module} {{"
+    );
+    for (i, chrono_module) in chrono_modules.iter().enumerate() {
+        let rust_type = if chrono_module.ends_with("_option") {
+            "Option<chrono::DateTime<chrono::Utc>>"
+        } else {
+            "chrono::DateTime<chrono::Utc>"
+        };
+        if i > 0 {
+            code.push('\n');
+        }
+        let _ = write!(
+            code,
+            r#"
+    pub mod {chrono_module} {{
+        pub fn serialize<S>(value: &{rust_type}, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {{
+            if serializer.is_human_readable() {{
+                serde::Serialize::serialize(value, serializer)
+            }} else {{
+                chrono::serde::{chrono_module}::serialize(value, serializer)
+            }}
+        }}
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<{rust_type}, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {{
+            if deserializer.is_human_readable() {{
+                serde::Deserialize::deserialize(deserializer)
+            }} else {{
+                chrono::serde::{chrono_module}::deserialize(deserializer)
+            }}
+        }}
+    }}"#
+        );
+    }
+    code.push_str("\n}\n");
+    code
+}
+
+/// Returns whether the record or field `of` is marked by the boolean `attribute` of its
+/// attributes, e.g. [`SENSITIVE_ATTRIBUTE`](SENSITIVE_ATTRIBUTE).
+fn bool_attribute(attributes: &BTreeMap<String, Value>, attribute: &str, of: &str) -> Result<bool> {
+    match attributes.get(attribute) {
+        None => Ok(false),
+        Some(Value::Bool(marked)) => Ok(*marked),
+        Some(v) => Err(Error::Generation {
+            kind: ErrorKind::Attribute,
+            message: format!(
+                "Invalid '{attribute}' attribute of {of}, expected a boolean, found {v}"
+            ),
+        }),
+    }
+}
+
+/// Returns variant name -> documentation of the symbols documented by the
+/// [`SYMBOL_DOCS_ATTRIBUTE`](SYMBOL_DOCS_ATTRIBUTE) of an enum.
+fn symbol_docs(
+    attributes: &BTreeMap<String, Value>,
+    symbols: &[String],
+    name: &str,
+    gen_state: &GenState,
+) -> Result<BTreeMap<String, String>> {
+    let invalid = |expected: &str, v: &Value| {
+        let message = format!(
+            "Invalid '{SYMBOL_DOCS_ATTRIBUTE}' attribute of enum '{name}', expected {expected}, found {v}"
+        );
+        Error::Generation {
+            kind: ErrorKind::Attribute,
+            message,
+        }
     };
-    static ref UNESCAPABLE: HashSet<String> = {
-        let s: HashSet<_> = ["Self", "self", "super", "extern", "crate"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        s
+    let docs = match attributes.get(SYMBOL_DOCS_ATTRIBUTE) {
+        None => return Ok(BTreeMap::new()),
+        Some(Value::Object(docs)) => docs,
+        Some(v) => return Err(invalid("an object", v)),
     };
+    let mut variant_docs = BTreeMap::new();
+    for (symbol, doc) in docs {
+        if !symbols.contains(symbol) {
+            let message = format!(
+                "Unknown symbol '{symbol}' in the '{SYMBOL_DOCS_ATTRIBUTE}' attribute of enum '{name}'"
+            );
+            return Err(Error::Generation {
+                kind: ErrorKind::Attribute,
+                message,
+            });
+        }
+        let Value::String(doc) = doc else {
+            return Err(invalid("string documentations", doc));
+        };
+        variant_docs.insert(gen_state.name_mapper().variant_name(symbol), doc.clone());
+    }
+    Ok(variant_docs)
 }
 
-fn sanitize(mut s: String) -> String {
-    if RESERVED.contains(&s) {
-        if UNESCAPABLE.contains(&s) {
-            s.push('_');
+/// Returns variant name -> former symbols of the symbols renamed according to the
+/// [`SYMBOL_ALIASES_ATTRIBUTE`](SYMBOL_ALIASES_ATTRIBUTE) of an enum.
+///
+/// Former symbols must be valid Avro names, and differ from the current symbols and from each
+/// other.
+fn symbol_aliases(
+    attributes: &BTreeMap<String, Value>,
+    symbols: &[String],
+    name: &str,
+    gen_state: &GenState,
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let invalid = |expected: &str, v: &Value| {
+        let message = format!(
+            "Invalid '{SYMBOL_ALIASES_ATTRIBUTE}' attribute of enum '{name}', expected {expected}, found {v}"
+        );
+        Error::Generation {
+            kind: ErrorKind::Attribute,
+            message,
+        }
+    };
+    let aliases = match attributes.get(SYMBOL_ALIASES_ATTRIBUTE) {
+        None => return Ok(BTreeMap::new()),
+        Some(Value::Object(aliases)) => aliases,
+        Some(v) => return Err(invalid("an object", v)),
+    };
+    let mut former_symbols = BTreeSet::new();
+    let mut variant_aliases = BTreeMap::new();
+    for (symbol, former) in aliases {
+        if !symbols.contains(symbol) {
+            let message = format!(
+                "Unknown symbol '{symbol}' in the '{SYMBOL_ALIASES_ATTRIBUTE}' attribute of enum '{name}'"
+            );
+            return Err(Error::Generation {
+                kind: ErrorKind::Attribute,
+                message,
+            });
+        }
+        let Value::Array(former) = former else {
+            return Err(invalid("arrays of former symbols", former));
+        };
+        let mut symbol_aliases = vec![];
+        for alias in former {
+            let alias = match alias {
+                Value::String(alias) if crate::infer::is_valid_name(alias) => alias,
+                _ => return Err(invalid("former symbols", alias)),
+            };
+            if symbols.contains(alias) || !former_symbols.insert(alias) {
+                let message = format!(
+                    "Invalid '{SYMBOL_ALIASES_ATTRIBUTE}' attribute of enum '{name}', former symbol '{alias}' is already a symbol or a former symbol"
+                );
+                return Err(Error::Generation {
+                    kind: ErrorKind::Attribute,
+                    message,
+                });
+            }
+            symbol_aliases.push(alias.clone());
         }
-        s.insert_str(0, "r#");
-        s
+        variant_aliases.insert(gen_state.name_mapper().variant_name(symbol), symbol_aliases);
+    }
+    Ok(variant_aliases)
+}
+
+/// Returns the name of the constant of an enum symbol with
+/// [`string_enums`](crate::GeneratorBuilder::string_enums), e.g. `IN_TRANSIT` for `inTransit`.
+pub(crate) fn symbol_constant(symbol: &str) -> String {
+    sanitize(symbol.to_shouty_snake_case())
+}
+
+/// Returns the Avro type of a field marked by the [`UNSIGNED_ATTRIBUTE`](UNSIGNED_ATTRIBUTE),
+/// `int` or `long`, and whether it is optional, `None` for unmarked fields. The `schema` of
+/// the field is its [optional schema](Templater::optional_field_schema) if it has one.
+fn unsigned_field(field: &RecordField, schema: &Schema) -> Result<Option<(&'static str, bool)>> {
+    let of = format!("field '{}'", field.name);
+    if !bool_attribute(&field.custom_attributes, UNSIGNED_ATTRIBUTE, &of)? {
+        return Ok(None);
+    }
+    let (schema, optional) = match schema {
+        Schema::Union(union) => match union.variants() {
+            [Schema::Null, inner] => (inner, true),
+            _ => (schema, false),
+        },
+        schema => (schema, false),
+    };
+    match schema {
+        Schema::Int => Ok(Some(("int", optional))),
+        Schema::Long => Ok(Some(("long", optional))),
+        _ => Err(Error::Generation {
+            kind: ErrorKind::Attribute,
+            message: format!(
+                "Invalid '{UNSIGNED_ATTRIBUTE}' attribute of {of}, expected an int or long field"
+            ),
+        }),
+    }
+}
+
+/// Returns the unsigned Rust type of the Avro `int` or `long` type of an unsigned field.
+fn unsigned_type(avro_type: &str) -> &'static str {
+    if avro_type == "int" {
+        "u32"
     } else {
-        s
+        "u64"
+    }
+}
+
+/// Returns the default value of a `SmallVec` field from the `vec!` one of its array.
+fn smallvec_default(default: String) -> String {
+    match default.strip_prefix("vec!") {
+        Some(items) => format!("smallvec::smallvec!{items}"),
+        None => default,
+    }
+}
+
+/// Returns the record of a field schema, possibly optional, and whether it is optional.
+fn field_record<'a>(schema: &'a Schema, gen_state: &'a GenState) -> Option<(&'a Schema, bool)> {
+    let (schema, optional) = match schema {
+        Schema::Union(union) => match union.variants() {
+            [Schema::Null, inner] => (inner, true),
+            _ => return None,
+        },
+        schema => (schema, false),
+    };
+    let schema = match schema {
+        Schema::Ref { name } => gen_state.get_schema(name)?,
+        schema => schema,
+    };
+    matches!(schema, Schema::Record(_)).then_some((schema, optional))
+}
+
+/// Returns the name of the type of a resolved union variant in the Avro JSON encoding, its
+/// fullname for named types and the name of the underlying type for logical types.
+fn avro_json_name(schema: &Schema) -> String {
+    match schema {
+        Schema::Record(RecordSchema { name, .. })
+        | Schema::Enum(EnumSchema { name, .. })
+        | Schema::Fixed(FixedSchema { name, .. }) => name.fullname(None),
+        Schema::Decimal(DecimalSchema { inner, .. }) => avro_json_name(inner),
+        Schema::Null => "null".to_string(),
+        Schema::Boolean => "boolean".to_string(),
+        Schema::Int | Schema::Date | Schema::TimeMillis => "int".to_string(),
+        Schema::Long
+        | Schema::TimeMicros
+        | Schema::TimestampMillis
+        | Schema::TimestampMicros
+        | Schema::TimestampNanos
+        | Schema::LocalTimestampMillis
+        | Schema::LocalTimestampMicros
+        | Schema::LocalTimestampNanos => "long".to_string(),
+        Schema::Float => "float".to_string(),
+        Schema::Double => "double".to_string(),
+        Schema::Bytes | Schema::BigDecimal => "bytes".to_string(),
+        Schema::String | Schema::Uuid => "string".to_string(),
+        Schema::Array(_) => "array".to_string(),
+        Schema::Map(_) => "map".to_string(),
+        Schema::Duration => "fixed".to_string(),
+        Schema::Union(_) => "union".to_string(),
+        Schema::Ref { name } => name.fullname(None),
+    }
+}
+
+/// Returns whether the Rust type of a record field has an empty `Default` value, `None` or an
+/// empty array or map.
+fn has_empty_default(schema: &Schema) -> bool {
+    match schema {
+        Schema::Union(union) => matches!(union.variants().first(), Some(Schema::Null)),
+        Schema::Array(_) | Schema::Map(_) => true,
+        _ => false,
+    }
+}
+
+/// Returns whether the schema of a record field is `bytes` or a `fixed`, possibly optional.
+fn is_bytes_field(schema: &Schema, gen_state: &GenState) -> bool {
+    let schema = match schema {
+        Schema::Union(union) => match union.variants() {
+            [Schema::Null, inner] => inner,
+            _ => return false,
+        },
+        schema => schema,
+    };
+    let schema = match schema {
+        Schema::Ref { name } => match gen_state.get_schema(name) {
+            Some(schema) => schema,
+            None => return false,
+        },
+        schema => schema,
+    };
+    matches!(schema, Schema::Bytes | Schema::Fixed(_))
+}
+
+/// Returns the code of a `module` of serde `with` modules named after the Avro types of the
+/// unsigned fields of a record (e.g. `int`, or `int_option` for optional fields), checking
+/// the conversions from and to these signed Avro types.
+fn unsigned_modules(module: &str, avro_types: &BTreeSet<(&str, bool)>) -> String {
+    let mut code = format!(
+        "\n/// Serde functions of the unsigned fields, as the signed integers of their schema.\n\
+         mod {module} {{"
+    );
+    for (avro_type, optional) in avro_types {
+        let unsigned = unsigned_type(avro_type);
+        let signed = if *avro_type == "int" { "i32" } else { "i64" };
+        let (suffix, rust_type, signed_type, serialize, deserialize) = if *optional {
+            (
+                "_option",
+                format!("Option<{unsigned}>"),
+                format!("Option<{signed}>"),
+                format!(
+                    "value.map(|v| {signed}::try_from(v).map_err(|_| overflow(v))).transpose()?"
+                ),
+                format!(
+                    "value.map(|v| {unsigned}::try_from(v).map_err(|_| negative(v))).transpose()"
+                ),
+            )
+        } else {
+            (
+                "",
+                unsigned.to_string(),
+                signed.to_string(),
+                format!("{signed}::try_from(*value).map_err(|_| overflow(*value))?"),
+                format!("{unsigned}::try_from(value).map_err(|_| negative(value))"),
+            )
+        };
+        let _ = write!(
+            code,
+            r#"
+    pub mod {avro_type}{suffix} {{
+        pub fn serialize<S>(value: &{rust_type}, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {{
+            let overflow = |v: {unsigned}| -> S::Error {{
+                serde::ser::Error::custom(format!("{{v}} overflows an Avro {avro_type}"))
+            }};
+            let value = {serialize};
+            serde::Serialize::serialize(&value, serializer)
+        }}
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<{rust_type}, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {{
+            let negative = |v: {signed}| -> D::Error {{
+                serde::de::Error::custom(format!("negative value {{v}} of an unsigned field"))
+            }};
+            let value: {signed_type} = serde::Deserialize::deserialize(deserializer)?;
+            {deserialize}
+        }}
+    }}
+"#
+        );
+    }
+    code.push_str("}\n");
+    code
+}
+
+/// Returns the template selected by the [`TEMPLATE_ATTRIBUTE`](TEMPLATE_ATTRIBUTE) of a schema.
+fn template_attribute(attributes: &BTreeMap<String, Value>) -> Result<Option<String>> {
+    match attributes.get(TEMPLATE_ATTRIBUTE) {
+        None => Ok(None),
+        Some(Value::String(template)) => Ok(Some(template.clone())),
+        Some(v) => Err(Error::Generation {
+            kind: ErrorKind::Attribute,
+            message: format!(
+                "Invalid '{TEMPLATE_ATTRIBUTE}' attribute, expected a string, found {v}"
+            ),
+        }),
     }
 }
 
 macro_rules! err (
+    ($kind:ident: $($arg:tt)*) => (Err(Error::Generation {
+        kind: ErrorKind::$kind,
+        message: format!($($arg)*),
+    }));
     ($($arg:tt)*) => (Err(Error::Template(format!($($arg)*))))
 );
 
-/// A helper struct for apache-avro union deserialization visitors.
-#[derive(Debug, serde::Serialize)]
-struct GenUnionVisitor {
-    variant: String,
-    rust_type: String,
-    serde_visitor: Option<String>,
-}
-
 /// A helper struct for nested schema generation.
 ///
 /// Used to store inner schema String type so that outer schema String type can be created.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct GenState {
     types_by_schema: HashMap<String, String>,
     schemata_by_name: HashMap<Name, Schema>,
     not_eq: HashSet<String>,
-    use_chrono_dates: bool,
+    type_options: TypeOptions,
+    name_mapper: Arc<dyn NameMapper>,
+    type_mapper: Arc<dyn TypeMapper>,
+    union_names: HashMap<String, String>,
 }
 
 impl GenState {
@@ -346,37 +1157,171 @@ impl GenState {
             types_by_schema: HashMap::new(),
             schemata_by_name,
             not_eq,
-            use_chrono_dates: false,
+            type_options: TypeOptions {
+                use_chrono_dates: false,
+                use_decimal_structs: false,
+                avro_crate: DEFAULT_AVRO_CRATE.to_string(),
+            },
+            name_mapper: Arc::new(DefaultNameMapper),
+            type_mapper: Arc::new(DefaultTypeMapper),
+            union_names: HashMap::new(),
         })
     }
 
     pub fn with_chrono_dates(mut self, use_chrono_dates: bool) -> Self {
-        self.use_chrono_dates = use_chrono_dates;
+        self.type_options.use_chrono_dates = use_chrono_dates;
         self
     }
 
-    pub(crate) fn get_schema(&self, name: &Name) -> Option<&Schema> {
-        self.schemata_by_name.get(name)
+    pub fn with_decimal_structs(mut self, use_decimal_structs: bool) -> Self {
+        self.type_options.use_decimal_structs = use_decimal_structs;
+        self
     }
 
-    /// Stores the String type of a given schema.
-    pub fn put_type(&mut self, schema: &Schema, t: String) {
-        let k = serde_json::to_string(schema).expect("Unexpected invalid schema");
-        self.types_by_schema.insert(k, t);
+    pub fn with_avro_crate(mut self, avro_crate: &str) -> Self {
+        self.type_options.avro_crate = avro_crate.to_string();
+        self
     }
 
-    /// Retrieves the String type of a given schema.
+    pub fn with_name_mapper(mut self, name_mapper: Arc<dyn NameMapper>) -> Self {
+        self.name_mapper = name_mapper;
+        self
+    }
+
+    pub fn with_type_mapper(mut self, type_mapper: Arc<dyn TypeMapper>) -> Self {
+        self.type_mapper = type_mapper;
+        self
+    }
+
+    /// Returns the [`NameMapper`](NameMapper) used to name generated items.
+    pub(crate) fn name_mapper(&self) -> &dyn NameMapper {
+        self.name_mapper.as_ref()
+    }
+
+    /// Returns the [`TypeMapper`](TypeMapper) used to type generated items.
+    pub(crate) fn type_mapper(&self) -> &dyn TypeMapper {
+        self.type_mapper.as_ref()
+    }
+
+    /// Returns the Rust type of a primitive or logical schema.
+    pub(crate) fn primitive_type(&self, schema: &Schema) -> Result<String> {
+        self.type_mapper
+            .primitive_type(schema, &self.type_options)
+            .ok_or_else(|| Error::Template(format!("No Rust type for schema {schema:?}")))
+    }
+
+    /// Returns the path of an item of the Avro crate targeted by the generated code.
+    pub(crate) fn avro_path(&self, item: &str) -> String {
+        format!("{}::{}", self.type_options.avro_crate, item)
+    }
+
+    pub(crate) fn get_schema(&self, name: &Name) -> Option<&Schema> {
+        self.schemata_by_name.get(name)
+    }
+
+    /// Retrieves the String type of a given schema.
     pub fn get_type(&self, schema: &Schema) -> Option<&String> {
-        let k = serde_json::to_string(schema).expect("Unexpected invalid schema");
-        self.types_by_schema.get(&k)
+        self.types_by_schema.get(&schema_key(schema))
+    }
+
+    /// Retrieves the String type of an anonymous array, map or union, computing it if it
+    /// was not stored.
+    pub fn nested_type(&self, schema: &Schema) -> Result<String> {
+        match self.get_type(schema) {
+            Some(t) => Ok(t.clone()),
+            None => anonymous_type(schema, self),
+        }
+    }
+
+    /// Stores the String types of all the anonymous arrays, maps and unions nested in the
+    /// given schemas, inner ones first.
+    ///
+    /// Types of anonymous schemas which are not stored are computed when needed, this only
+    /// avoids computing them again. Identical schemas, e.g. repeated in several records,
+    /// are only explored once.
+    pub fn put_nested_types(&mut self, schemas: &[Schema]) -> Result<()> {
+        for schema in schemas {
+            let nested = match schema {
+                Schema::Record(RecordSchema { fields, .. }) => {
+                    for field in fields {
+                        self.put_nested_types(std::slice::from_ref(&field.schema))?;
+                    }
+                    continue;
+                }
+                Schema::Array(ArraySchema { items: inner, .. })
+                | Schema::Map(MapSchema { types: inner, .. }) => {
+                    std::slice::from_ref(inner.as_ref())
+                }
+                Schema::Union(union) => union.variants(),
+                _ => continue,
+            };
+
+            let key = schema_key(schema);
+            if !self.types_by_schema.contains_key(&key) {
+                self.put_nested_types(nested)?;
+                let t = anonymous_type(schema, self)?;
+                self.types_by_schema.insert(key, t);
+            }
+        }
+        Ok(())
+    }
+
+    /// Names the enums of the unions nested in the fields of the records among the given
+    /// schemas after a `template`, see
+    /// [`GeneratorBuilder::union_names`](crate::GeneratorBuilder::union_names).
+    ///
+    /// Must be called before the types are stored. An union shared by several fields is
+    /// named after the first one.
+    pub fn put_union_names(&mut self, schemas: &[Schema], template: &str) -> Result<()> {
+        for schema in schemas {
+            let Schema::Record(RecordSchema { name, fields, .. }) = schema else {
+                continue;
+            };
+            for field in fields {
+                let mut unions = vec![];
+                union_enums(&field.schema, &mut unions);
+                for union in unions {
+                    let key = schema_key(&Schema::Union(union.clone()));
+                    if self.union_names.contains_key(&key) {
+                        continue;
+                    }
+                    let mut variants = String::new();
+                    for variant in union.variants().iter().filter(|v| **v != Schema::Null) {
+                        variants.push_str(&union_enum_variant(variant, self)?);
+                    }
+                    let union_name = sanitize(
+                        template
+                            .replace("{Record}", &self.name_mapper.type_name(&name.name))
+                            .replace("{Field}", &self.name_mapper.type_name(&field.name))
+                            .replace("{Variants}", &variants),
+                    );
+                    if self.union_names.values().any(|other| *other == union_name) {
+                        err!(
+                            Conflict: "The union of field '{}' of record '{}' is named '{union_name}' like another union",
+                            field.name,
+                            name.name
+                        )?
+                    }
+                    self.union_names.insert(key, union_name);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the name given to the enum of an union by the union names template, if any.
+    pub(crate) fn union_name(&self, union: &UnionSchema) -> Option<&String> {
+        if self.union_names.is_empty() {
+            return None;
+        }
+        self.union_names
+            .get(&schema_key(&Schema::Union(union.clone())))
     }
 
     /// Checks that schema does not contains nested type which does not implement Eq trait.
     pub fn is_eq_derivable(&self, schema: &Schema) -> bool {
         match schema {
-            Schema::Union(_) | Schema::Record { .. } => !self
-                .not_eq
-                .contains(&serde_json::to_string(schema).expect("Unexpected invalid schema")),
+            Schema::Union(_) | Schema::Record { .. } => !self.not_eq.contains(&schema_key(schema)),
             _ => true,
         }
     }
@@ -488,95 +1433,315 @@ impl GenState {
                     .filter_map(|n| outer_not_eq.get(n))
                     .any(|&not_eq| not_eq);
             if not_eq {
-                let str_schema = serde_json::to_string(dep).expect("Unexpected invalid schema");
-                schemata_not_eq.insert(str_schema);
+                schemata_not_eq.insert(schema_key(dep));
             }
         }
         Ok(schemata_not_eq)
     }
 }
 
+/// Computes the key identifying the Rust type generated for a given schema.
+///
+/// Named schemas are keyed by their fullname, so that an inline definition and a reference
+/// to it are considered identical. Anonymous schemas (arrays, maps, unions) are keyed by
+/// their structure, ignoring documentation and custom attributes.
+pub(crate) fn schema_key(schema: &Schema) -> String {
+    fn push_key(schema: &Schema, key: &mut String) {
+        match schema {
+            Schema::Record(RecordSchema { name, .. })
+            | Schema::Enum(EnumSchema { name, .. })
+            | Schema::Fixed(FixedSchema { name, .. })
+            | Schema::Ref { name } => key.push_str(&name.fullname(None)),
+            Schema::Array(ArraySchema { items: inner, .. }) => {
+                key.push_str("array<");
+                push_key(inner, key);
+                key.push('>');
+            }
+            Schema::Map(MapSchema { types: inner, .. }) => {
+                key.push_str("map<");
+                push_key(inner, key);
+                key.push('>');
+            }
+            Schema::Union(union) => {
+                key.push_str("union<");
+                for (i, variant) in union.variants().iter().enumerate() {
+                    if i > 0 {
+                        key.push(',');
+                    }
+                    push_key(variant, key);
+                }
+                key.push('>');
+            }
+            Schema::Decimal(DecimalSchema {
+                precision,
+                scale,
+                inner,
+            }) => {
+                key.push_str(&format!("decimal({precision},{scale})<"));
+                push_key(inner, key);
+                key.push('>');
+            }
+            _ => key.push_str(&serde_json::to_string(schema).expect("Unexpected invalid schema")),
+        }
+    }
+
+    let mut key = String::new();
+    push_key(schema, &mut key);
+    key
+}
+
 /// The main, stateless, component for templating Rust types.
 ///
-/// Current implementation uses Tera. Its responsability is to generate String
-/// representing Rust code/types for a given Avro schema.
-#[derive(Debug)]
+/// Its responsability is to generate String representing Rust code/types for a given
+/// Avro schema. Rendering is delegated to a [`Renderer`](Renderer), Tera by default.
+///
+/// Per-generation state is kept in a [`GenState`](GenState), so that rendering only takes
+/// `&self` and a single `Templater` can be shared across threads.
 pub struct Templater {
-    tera: Tera,
+    pub renderer: Box<dyn Renderer>,
     pub precision: usize,
     pub nullable: bool,
     pub use_avro_rs_unions: bool,
     pub use_chrono_dates: bool,
+    pub use_decimal_structs: bool,
+    pub string_enums: bool,
     pub derive_builders: bool,
     pub derive_schemas: bool,
+    pub avro_schema_impls: bool,
+    pub avro_message_impls: bool,
+    pub round_trip_tests: bool,
+    pub proptest_arbitrary: bool,
+    pub quickcheck_arbitrary: bool,
+    pub writer_schema_decoders: bool,
+    pub try_from_bytes: bool,
+    pub wrapper_conversions: bool,
+    pub alias_types: bool,
+    pub schema_constants: bool,
+    pub zeroize: bool,
+    pub debug_impls: bool,
+    pub hex_debug: bool,
+    pub validators: bool,
+    pub validate_derive: Option<ValidateDerive>,
+    pub default_impls: bool,
+    pub strict_defaults: bool,
+    pub empty_defaults: bool,
+    pub serde_impls: bool,
+    pub doc_examples: Option<String>,
+    pub deserialization: DeserializationMode,
+    pub optional_fields: OptionalFields,
+    pub box_threshold: Option<usize>,
+    pub smallvec_arrays: Option<usize>,
+    pub union_names: Option<String>,
+    pub union_json: Option<UnionJson>,
+    pub rfc3339_timestamps: bool,
+    pub item_lints: BTreeMap<ItemKind, Vec<String>>,
+    pub avro_crate: String,
+    pub name_mapper: Arc<dyn NameMapper>,
+    pub type_mapper: Arc<dyn TypeMapper>,
+}
+
+impl std::fmt::Debug for Templater {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Templater")
+            .field("precision", &self.precision)
+            .field("nullable", &self.nullable)
+            .field("use_avro_rs_unions", &self.use_avro_rs_unions)
+            .field("use_chrono_dates", &self.use_chrono_dates)
+            .field("use_decimal_structs", &self.use_decimal_structs)
+            .field("string_enums", &self.string_enums)
+            .field("derive_builders", &self.derive_builders)
+            .field("derive_schemas", &self.derive_schemas)
+            .field("avro_schema_impls", &self.avro_schema_impls)
+            .field("avro_message_impls", &self.avro_message_impls)
+            .field("round_trip_tests", &self.round_trip_tests)
+            .field("proptest_arbitrary", &self.proptest_arbitrary)
+            .field("quickcheck_arbitrary", &self.quickcheck_arbitrary)
+            .field("writer_schema_decoders", &self.writer_schema_decoders)
+            .field("try_from_bytes", &self.try_from_bytes)
+            .field("wrapper_conversions", &self.wrapper_conversions)
+            .field("alias_types", &self.alias_types)
+            .field("schema_constants", &self.schema_constants)
+            .field("zeroize", &self.zeroize)
+            .field("debug_impls", &self.debug_impls)
+            .field("hex_debug", &self.hex_debug)
+            .field("validators", &self.validators)
+            .field("validate_derive", &self.validate_derive)
+            .field("default_impls", &self.default_impls)
+            .field("strict_defaults", &self.strict_defaults)
+            .field("empty_defaults", &self.empty_defaults)
+            .field("serde_impls", &self.serde_impls)
+            .field("doc_examples", &self.doc_examples)
+            .field("deserialization", &self.deserialization)
+            .field("optional_fields", &self.optional_fields)
+            .field("box_threshold", &self.box_threshold)
+            .field("smallvec_arrays", &self.smallvec_arrays)
+            .field("union_names", &self.union_names)
+            .field("union_json", &self.union_json)
+            .field("rfc3339_timestamps", &self.rfc3339_timestamps)
+            .field("item_lints", &self.item_lints)
+            .field("avro_crate", &self.avro_crate)
+            .field("name_mapper", &self.name_mapper)
+            .field("type_mapper", &self.type_mapper)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Templater {
     /// Creates a new `Templater.`
     pub fn new() -> Result<Templater> {
-        let dir = tempfile::tempdir()?;
-        let mut tera = Tera::new(&format!("{}", dir.path().join("*").display()))?;
-
-        tera.add_raw_template(RECORD_TERA, RECORD_TEMPLATE)?;
-        tera.add_raw_template(ENUM_TERA, ENUM_TEMPLATE)?;
-        tera.add_raw_template(FIXED_TERA, FIXED_TEMPLATE)?;
-        tera.add_raw_template(UNION_TERA, UNION_TEMPLATE)?;
-
         Ok(Templater {
-            tera,
+            renderer: Box::new(TeraRenderer::new()?),
             precision: 3,
             nullable: false,
             use_avro_rs_unions: false,
             use_chrono_dates: false,
+            use_decimal_structs: false,
+            string_enums: false,
             derive_builders: false,
             derive_schemas: false,
+            avro_schema_impls: false,
+            avro_message_impls: false,
+            round_trip_tests: false,
+            proptest_arbitrary: false,
+            quickcheck_arbitrary: false,
+            writer_schema_decoders: false,
+            try_from_bytes: false,
+            wrapper_conversions: false,
+            alias_types: false,
+            schema_constants: false,
+            zeroize: false,
+            debug_impls: true,
+            hex_debug: false,
+            validators: false,
+            validate_derive: None,
+            default_impls: true,
+            strict_defaults: false,
+            empty_defaults: false,
+            serde_impls: true,
+            doc_examples: None,
+            deserialization: DeserializationMode::Standard,
+            optional_fields: OptionalFields::Unions,
+            box_threshold: None,
+            smallvec_arrays: None,
+            union_names: None,
+            union_json: None,
+            rfc3339_timestamps: false,
+            item_lints: BTreeMap::new(),
+            avro_crate: DEFAULT_AVRO_CRATE.to_string(),
+            name_mapper: Arc::new(DefaultNameMapper),
+            type_mapper: Arc::new(DefaultTypeMapper),
         })
     }
 
+    /// Returns the lint attributes of the items of the given kind, e.g. `allow(dead_code)`.
+    pub(crate) fn lints(&self, kind: ItemKind) -> Vec<String> {
+        self.item_lints.get(&kind).cloned().unwrap_or_default()
+    }
+
+    /// Returns the name of the Rust type generated for a named schema or an union.
+    pub fn type_name(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        match schema {
+            Schema::Record(RecordSchema {
+                name: Name { name, .. },
+                ..
+            }) => Ok(gen_state.name_mapper().type_name(name)),
+            Schema::Enum(EnumSchema {
+                name: Name { name, .. },
+                ..
+            })
+            | Schema::Fixed(FixedSchema {
+                name: Name { name, .. },
+                ..
+            }) => Ok(gen_state.name_mapper().type_name(name)),
+            Schema::Union(union) => union_type(union, gen_state, false),
+            _ => err!("Requires a named schema or an union, found {:?}", schema),
+        }
+    }
+
     /// Generates a Rust type based on a `Schema::Fixed` schema.
-    pub fn str_fixed(&self, schema: &Schema) -> Result<String> {
+    pub fn str_fixed(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
         if let Schema::Fixed(FixedSchema {
             name: Name { name, .. },
             size,
+            attributes,
             ..
         }) = schema
         {
-            let mut ctx = Context::new();
-            ctx.insert("name", &sanitize(name.to_upper_camel_case()));
-            ctx.insert("size", size);
-            Ok(self.tera.render(FIXED_TERA, &ctx)?)
+            let ctx = FixedContext {
+                name: gen_state.name_mapper().type_name(name),
+                template: template_attribute(attributes)?,
+                lints: self.lints(ItemKind::Fixed),
+                size: *size,
+                attributes: attributes.clone(),
+            };
+            self.renderer.render_fixed(&ctx)
         } else {
             err!("Requires Schema::Fixed, found {:?}", schema)?
         }
     }
 
     /// Generates a Rust enum based on a `Schema::Enum` schema
-    pub fn str_enum(&self, schema: &Schema) -> Result<String> {
+    pub fn str_enum(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
         if let Schema::Enum(EnumSchema {
             name: Name { name, .. },
             symbols,
             doc,
+            default,
+            attributes,
             ..
         }) = schema
         {
             if symbols.is_empty() {
                 err!("No symbol for enum: {:?}", name)?
             }
-            let mut ctx = Context::new();
-            ctx.insert("name", &sanitize(name.to_upper_camel_case()));
-            let doc = if let Some(d) = doc { d } else { "" };
-            ctx.insert("doc", doc);
-            let o: HashMap<_, _> = symbols
-                .iter()
-                .map(|s| (sanitize(s.to_upper_camel_case()), s))
-                .collect();
-            let s: Vec<_> = symbols
-                .iter()
-                .map(|s| sanitize(s.to_upper_camel_case()))
-                .collect();
-            ctx.insert("originals", &o);
-            ctx.insert("symbols", &s);
-            Ok(self.tera.render(ENUM_TERA, &ctx)?)
+            let mut originals = BTreeMap::new();
+            for symbol in symbols {
+                let variant = gen_state.name_mapper().variant_name(symbol);
+                if let Some(other) = originals.insert(variant.clone(), symbol.clone()) {
+                    err!(
+                        Conflict: "Symbols '{other}' and '{symbol}' of enum '{name}' are both mapped to the variant '{variant}'"
+                    )?
+                }
+            }
+            let mut constants = BTreeMap::new();
+            if self.string_enums {
+                let mut constant_symbols = BTreeMap::new();
+                for symbol in symbols {
+                    let constant = symbol_constant(symbol);
+                    if constant == "SYMBOLS" {
+                        err!("Symbol '{symbol}' of enum '{name}' is mapped to the constant 'SYMBOLS', which lists the symbols")?
+                    }
+                    if let Some(other) = constant_symbols.insert(constant.clone(), symbol) {
+                        err!(
+                            Conflict: "Symbols '{other}' and '{symbol}' of enum '{name}' are both mapped to the constant '{constant}'"
+                        )?
+                    }
+                    constants.insert(gen_state.name_mapper().variant_name(symbol), constant);
+                }
+            }
+            let ctx = EnumContext {
+                name: gen_state.name_mapper().type_name(name),
+                template: template_attribute(attributes)?,
+                lints: self.lints(ItemKind::Enum),
+                doc: doc.clone().unwrap_or_default(),
+                symbols: symbols
+                    .iter()
+                    .map(|s| gen_state.name_mapper().variant_name(s))
+                    .collect(),
+                originals,
+                constants,
+                docs: symbol_docs(attributes, symbols, name, gen_state)?,
+                aliases: symbol_aliases(attributes, symbols, name, gen_state)?,
+                attributes: attributes.clone(),
+                debug_impls: self.debug_impls,
+                serde_impls: self.serde_impls,
+                deserialization: self.deserialization,
+                string_enums: self.string_enums,
+                fallback: gen_state
+                    .name_mapper()
+                    .variant_name(default.as_ref().unwrap_or(&symbols[0])),
+            };
+            self.renderer.render_enum(&ctx)
         } else {
             err!("Requires Schema::Enum, found {:?}", schema)?
         }
@@ -586,48 +1751,80 @@ impl Templater {
     ///
     /// Makes use of a [`GenState`](GenState) for nested schemas (i.e. Array/Map/Union).
     pub fn str_record(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
-        if let Schema::Record(RecordSchema {
-            name: Name { name, .. },
-            fields,
-            doc,
-            ..
-        }) = schema
+        if let Schema::Record(
+            record @ RecordSchema {
+                name: Name { name, .. },
+                fields,
+                doc,
+                attributes,
+                ..
+            },
+        ) = schema
         {
-            let mut ctx = Context::new();
-            ctx.insert("name", &name.to_upper_camel_case());
-            let doc = if let Some(d) = doc { d } else { "" };
-            ctx.insert("doc", doc);
-            ctx.insert("derive_builders", &self.derive_builders);
-            ctx.insert("derive_schemas", &self.derive_schemas);
-
             let mut f = Vec::new(); // field names;
-            let mut t = HashMap::new(); // field name -> field type
-            let mut o = HashMap::new(); // field name -> original name
-            let mut d = HashMap::new(); // field name -> default value
-            let mut w = HashMap::new(); // field name -> serde with
-            let mut c = HashMap::new(); // field name -> comment/doc
+            let mut t = BTreeMap::new(); // field name -> field type
+            let mut o = BTreeMap::new(); // field name -> original name
+            let mut d = BTreeMap::new(); // field name -> default value
+            let mut w = BTreeMap::new(); // field name -> serde with
+            let mut decimals = BTreeMap::new(); // field name -> decimal scale, optional
+            let mut unsigned = BTreeMap::new(); // field name -> Avro type, optional
+            let mut c = BTreeMap::new(); // field name -> comment/doc
+            let mut a = BTreeMap::new(); // field name -> custom attributes
+            let mut s = Vec::new(); // sensitive field names
+            let mut e = Vec::new(); // field names omitted when `None`
+            let mut h = Vec::new(); // bytes field names printed as hexadecimal
+            let mut empty = Vec::new(); // field names without default value but an empty one
+            let null_default = Some(Value::Null);
+            let sensitive =
+                bool_attribute(attributes, SENSITIVE_ATTRIBUTE, &format!("record '{name}'"))?;
 
             let mut fields_by_pos = fields.iter().clone().collect::<Vec<_>>();
             fields_by_pos.sort_by_key(|f| f.position);
 
-            for RecordField {
-                schema,
-                name,
-                default,
-                doc,
-                ..
-            } in fields_by_pos.iter()
-            {
-                let name_std = sanitize(name.to_snake_case());
-                o.insert(name_std.clone(), name);
+            for field in fields_by_pos.iter() {
+                let RecordField {
+                    schema,
+                    name,
+                    default,
+                    doc,
+                    ..
+                } = field;
+                let name_std = gen_state.name_mapper().field_name(name);
+                let optional = self.optional_field_schema(field);
+                let (schema, default) = match &optional {
+                    Some(optional) => {
+                        e.push(name_std.clone());
+                        (optional, &null_default)
+                    }
+                    None => (schema, default),
+                };
+                if let Some(other) = o.insert(name_std.clone(), name.clone()) {
+                    err!(
+                        Conflict: "Fields '{other}' and '{name}' of record '{}' are both mapped to the field '{name_std}'",
+                        record.name.name
+                    )?
+                }
                 if let Some(d) = doc {
-                    c.insert(name_std.clone(), d);
+                    c.insert(name_std.clone(), d.clone());
+                }
+                if !field.custom_attributes.is_empty() {
+                    a.insert(name_std.clone(), field.custom_attributes.clone());
+                }
+                let of = format!("field '{name}'");
+                if sensitive || bool_attribute(&field.custom_attributes, SENSITIVE_ATTRIBUTE, &of)?
+                {
+                    s.push(name_std.clone());
+                } else if self.hex_debug && is_bytes_field(schema, gen_state) {
+                    h.push(name_std.clone());
                 }
 
                 let schema = if let Schema::Ref { ref name } = schema {
-                    gen_state.get_schema(name).ok_or_else(|| {
-                        Error::Template(format!("Schema reference '{name:?}' cannot be resolved"))
-                    })?
+                    gen_state
+                        .get_schema(name)
+                        .ok_or_else(|| Error::Generation {
+                            kind: ErrorKind::Reference,
+                            message: format!("Schema reference '{name:?}' cannot be resolved"),
+                        })?
                 } else {
                     schema
                 };
@@ -636,7 +1833,7 @@ impl Templater {
                     Schema::Ref { .. } => {} // already resolved above
                     Schema::Boolean => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "bool".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -645,11 +1842,8 @@ impl Templater {
 
                     Schema::Date if self.use_chrono_dates => {
                         f.push(name_std.clone());
-                        t.insert(
-                            name_std.clone(),
-                            "chrono::DateTime<chrono::Utc>".to_string(),
-                        );
-                        w.insert(name_std.clone(), "chrono::serde::ts_seconds");
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
+                        w.insert(name_std.clone(), "chrono::serde::ts_seconds".to_string());
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -660,11 +1854,11 @@ impl Templater {
                         if self.use_chrono_dates =>
                     {
                         f.push(name_std.clone());
-                        t.insert(
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
+                        w.insert(
                             name_std.clone(),
-                            "chrono::DateTime<chrono::Utc>".to_string(),
+                            "chrono::serde::ts_milliseconds".to_string(),
                         );
-                        w.insert(name_std.clone(), "chrono::serde::ts_milliseconds");
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -675,11 +1869,11 @@ impl Templater {
                         if self.use_chrono_dates =>
                     {
                         f.push(name_std.clone());
-                        t.insert(
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
+                        w.insert(
                             name_std.clone(),
-                            "chrono::DateTime<chrono::Utc>".to_string(),
+                            "chrono::serde::ts_microseconds".to_string(),
                         );
-                        w.insert(name_std.clone(), "chrono::serde::ts_microseconds");
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -690,11 +1884,11 @@ impl Templater {
                         if self.use_chrono_dates =>
                     {
                         f.push(name_std.clone());
-                        t.insert(
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
+                        w.insert(
                             name_std.clone(),
-                            "chrono::DateTime<chrono::Utc>".to_string(),
+                            "chrono::serde::ts_nanoseconds".to_string(),
                         );
-                        w.insert(name_std.clone(), "chrono::serde::ts_nanoseconds");
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -703,7 +1897,7 @@ impl Templater {
 
                     Schema::Int | Schema::Date | Schema::TimeMillis => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "i32".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -719,7 +1913,7 @@ impl Templater {
                     | Schema::TimestampNanos
                     | Schema::LocalTimestampNanos => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "i64".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -728,7 +1922,7 @@ impl Templater {
 
                     Schema::Float => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "f32".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -737,7 +1931,7 @@ impl Templater {
 
                     Schema::Double => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "f64".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -746,8 +1940,8 @@ impl Templater {
 
                     Schema::Bytes => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "Vec<u8>".to_string());
-                        w.insert(name_std.clone(), "apache_avro::serde_avro_bytes");
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
+                        w.insert(name_std.clone(), gen_state.avro_path("serde_avro_bytes"));
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -756,7 +1950,7 @@ impl Templater {
 
                     Schema::String => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "String".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -765,7 +1959,7 @@ impl Templater {
 
                     Schema::Uuid => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "apache_avro::Uuid".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -774,25 +1968,28 @@ impl Templater {
 
                     Schema::Duration => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "apache_avro::Duration".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
                         }
                     }
 
-                    Schema::Decimal { .. } => {
+                    Schema::Decimal(DecimalSchema { scale, .. }) => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "apache_avro::Decimal".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
                         }
+                        if self.use_decimal_structs {
+                            decimals.insert(name_std.clone(), (*scale, false));
+                        }
                     }
 
                     Schema::BigDecimal => {
                         f.push(name_std.clone());
-                        t.insert(name_std.clone(), "apache_avro::BigDecimal".to_string());
+                        t.insert(name_std.clone(), gen_state.primitive_type(schema)?);
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
                             d.insert(name_std.clone(), default);
@@ -803,9 +2000,9 @@ impl Templater {
                         name: Name { name: f_name, .. },
                         ..
                     }) => {
-                        let f_name = sanitize(f_name.to_upper_camel_case());
+                        let f_name = gen_state.name_mapper().type_name(f_name);
                         f.push(name_std.clone());
-                        w.insert(name_std.clone(), "apache_avro::serde_avro_fixed");
+                        w.insert(name_std.clone(), gen_state.avro_path("serde_avro_fixed"));
                         t.insert(name_std.clone(), f_name.clone());
                         if let Some(default) = default {
                             let default = self.parse_default(schema, gen_state, default)?;
@@ -814,9 +2011,9 @@ impl Templater {
                     }
 
                     Schema::Array(ArraySchema { items: inner, .. }) => match inner.as_ref() {
-                        Schema::Null => err!("Invalid use of Schema::Null")?,
+                        Schema::Null => err!(Union: "Invalid use of Schema::Null")?,
                         _ => {
-                            let type_str = array_type(inner, gen_state)?;
+                            let type_str = gen_state.nested_type(schema)?;
                             f.push(name_std.clone());
                             t.insert(name_std.clone(), type_str);
                             if let Some(default) = default {
@@ -827,9 +2024,9 @@ impl Templater {
                     },
 
                     Schema::Map(MapSchema { types: inner, .. }) => match inner.as_ref() {
-                        Schema::Null => err!("Invalid use of Schema::Null")?,
+                        Schema::Null => err!(Union: "Invalid use of Schema::Null")?,
                         _ => {
-                            let type_str = map_type(inner, gen_state)?;
+                            let type_str = gen_state.nested_type(schema)?;
                             f.push(name_std.clone());
                             t.insert(name_std.clone(), type_str);
                             if let Some(default) = default {
@@ -843,7 +2040,7 @@ impl Templater {
                         name: Name { name: r_name, .. },
                         ..
                     }) => {
-                        let r_name = sanitize(r_name.to_upper_camel_case());
+                        let r_name = gen_state.name_mapper().type_name(r_name);
                         f.push(name_std.clone());
                         t.insert(name_std.clone(), r_name.clone());
                         if let Some(default) = default {
@@ -856,7 +2053,7 @@ impl Templater {
                         name: Name { name: e_name, .. },
                         ..
                     }) => {
-                        let e_name = sanitize(e_name.to_upper_camel_case());
+                        let e_name = gen_state.name_mapper().type_name(e_name);
                         f.push(name_std.clone());
                         t.insert(name_std.clone(), e_name);
                         if let Some(default) = default {
@@ -866,7 +2063,7 @@ impl Templater {
                     }
 
                     Schema::Union(union) => {
-                        let type_str = union_type(union, gen_state, true)?;
+                        let type_str = gen_state.nested_type(schema)?;
                         f.push(name_std.clone());
                         t.insert(name_std.clone(), type_str);
                         if let Some(default) = default {
@@ -877,12 +2074,18 @@ impl Templater {
                             && union.variants().len() == 2
                             && matches!(union.variants()[1], Schema::Bytes)
                         {
-                            w.insert(name_std.clone(), "apache_avro::serde_avro_bytes_opt");
+                            w.insert(
+                                name_std.clone(),
+                                gen_state.avro_path("serde_avro_bytes_opt"),
+                            );
                         } else if union.is_nullable()
                             && union.variants().len() == 2
                             && matches!(union.variants()[1], Schema::Fixed(_))
                         {
-                            w.insert(name_std.clone(), "apache_avro::serde_avro_fixed_opt");
+                            w.insert(
+                                name_std.clone(),
+                                gen_state.avro_path("serde_avro_fixed_opt"),
+                            );
                         } else if union.is_nullable()
                             && union.variants().len() == 2
                             && matches!(
@@ -890,7 +2093,10 @@ impl Templater {
                                 Schema::TimestampMillis | Schema::LocalTimestampMillis
                             )
                         {
-                            w.insert(name_std.clone(), "chrono::serde::ts_milliseconds_option");
+                            w.insert(
+                                name_std.clone(),
+                                "chrono::serde::ts_milliseconds_option".to_string(),
+                            );
                         } else if union.is_nullable()
                             && union.variants().len() == 2
                             && matches!(
@@ -898,7 +2104,10 @@ impl Templater {
                                 Schema::TimestampMicros | Schema::LocalTimestampMicros
                             )
                         {
-                            w.insert(name_std.clone(), "chrono::serde::ts_microseconds_option");
+                            w.insert(
+                                name_std.clone(),
+                                "chrono::serde::ts_microseconds_option".to_string(),
+                            );
                         } else if union.is_nullable()
                             && union.variants().len() == 2
                             && matches!(
@@ -906,26 +2115,209 @@ impl Templater {
                                 Schema::TimestampNanos | Schema::LocalTimestampNanos
                             )
                         {
-                            w.insert(name_std.clone(), "chrono::serde::ts_nanoseconds_option");
+                            w.insert(
+                                name_std.clone(),
+                                "chrono::serde::ts_nanoseconds_option".to_string(),
+                            );
                         };
+                        if let [Schema::Null, Schema::Decimal(DecimalSchema { scale, .. })] =
+                            union.variants()
+                        {
+                            if self.use_decimal_structs {
+                                decimals.insert(name_std.clone(), (*scale, true));
+                            }
+                        }
                     }
 
-                    Schema::Null => err!("Invalid use of Schema::Null")?,
+                    Schema::Null => err!(Union: "Invalid use of Schema::Null")?,
                 };
+
+                if let Some((avro_type, optional)) = unsigned_field(field, schema)? {
+                    if self.derive_schemas {
+                        err!(Unsupported: "Unsigned field '{name}' does not support `derive_schemas`")?
+                    }
+                    if let Some(Value::Number(n)) = default {
+                        if n.as_u64().is_none() {
+                            err!(Default: "Invalid default {n} of unsigned field '{name}'")?
+                        }
+                    }
+                    let rust_type = unsigned_type(avro_type);
+                    let rust_type = if optional {
+                        gen_state.type_mapper().option_type(rust_type)
+                    } else {
+                        rust_type.to_string()
+                    };
+                    t.insert(name_std.clone(), rust_type);
+                    unsigned.insert(name_std.clone(), (avro_type, optional));
+                }
+
+                // Zero values which cannot be written as Rust values leave the field required
+                if self.deserialization == DeserializationMode::Lenient
+                    && !d.contains_key(&name_std)
+                {
+                    let zero = zero_default(schema, gen_state);
+                    if let Ok(default) = self.parse_default(schema, gen_state, &zero) {
+                        d.insert(name_std.clone(), default);
+                    }
+                }
+
+                if let Some((capacity, items, optional)) = self.smallvec_field(field, schema)? {
+                    if self.derive_schemas {
+                        err!(Unsupported: "SmallVec field '{name}' does not support `derive_schemas`")?
+                    }
+                    let rust_type = format!(
+                        "smallvec::SmallVec<[{}; {capacity}]>",
+                        inner_type(items, gen_state)?
+                    );
+                    let rust_type = if optional {
+                        gen_state.type_mapper().option_type(&rust_type)
+                    } else {
+                        if let Some(default) = d.remove(&name_std) {
+                            d.insert(name_std.clone(), smallvec_default(default));
+                        }
+                        rust_type
+                    };
+                    t.insert(name_std.clone(), rust_type);
+                }
+
+                if let Some((r_name, optional)) = self.boxed_field(field, schema, gen_state)? {
+                    let rust_type = format!("Box<{r_name}>");
+                    let rust_type = if optional {
+                        gen_state.type_mapper().option_type(&rust_type)
+                    } else {
+                        if let Some(default) = d.remove(&name_std) {
+                            d.insert(name_std.clone(), format!("Box::new({default})"));
+                        }
+                        rust_type
+                    };
+                    t.insert(name_std.clone(), rust_type);
+                }
+
+                if self.empty_defaults && !d.contains_key(&name_std) && has_empty_default(schema) {
+                    empty.push(name_std.clone());
+                }
+
+                if let Some(rust_type) = t.remove(&name_std) {
+                    let rust_type = gen_state.type_mapper().field_type(record, field, rust_type);
+                    t.insert(name_std, rust_type);
+                }
             }
 
-            ctx.insert("fields", &f);
-            ctx.insert("types", &t);
-            ctx.insert("originals", &o);
-            ctx.insert("defaults", &d);
-            ctx.insert("docs", &c);
-            ctx.insert("serde_with", &w);
-            ctx.insert("is_eq_derivable", &gen_state.is_eq_derivable(schema));
-            if self.nullable {
-                ctx.insert("nullable", &true);
+            let type_name = gen_state.name_mapper().type_name(name);
+            let module_prefix = type_name.to_snake_case();
+            let module_prefix = module_prefix.trim_start_matches("r#");
+            let mut timestamps = BTreeSet::new();
+            let timestamps_module = format!("{module_prefix}_timestamps");
+            if self.rfc3339_timestamps {
+                for (f, with) in w.iter_mut() {
+                    if let Some(chrono_module) = with.strip_prefix("chrono::serde::") {
+                        timestamps.insert(chrono_module.to_string());
+                        if e.contains(f) {
+                            // Deserialized with the module of the inner type when present
+                            let inner = chrono_module.trim_end_matches("_option");
+                            timestamps.insert(inner.to_string());
+                        }
+                        *with = format!("{timestamps_module}::{chrono_module}");
+                    }
+                }
+            }
+            let mut scales = BTreeSet::new();
+            let decimals_module = format!("{module_prefix}_decimals");
+            if self.serde_impls {
+                for (f, (scale, optional)) in decimals {
+                    let suffix = if optional { "_option" } else { "" };
+                    if e.contains(&f) {
+                        scales.insert((scale, false));
+                    }
+                    w.insert(f, format!("{decimals_module}::scale_{scale}{suffix}"));
+                    scales.insert((scale, optional));
+                }
+            }
+            let mut avro_types = BTreeSet::new();
+            let unsigned_module = format!("{module_prefix}_unsigned");
+            if self.serde_impls {
+                for (f, (avro_type, optional)) in unsigned {
+                    let suffix = if optional { "_option" } else { "" };
+                    if e.contains(&f) {
+                        avro_types.insert((avro_type, false));
+                    }
+                    w.insert(f, format!("{unsigned_module}::{avro_type}{suffix}"));
+                    avro_types.insert((avro_type, optional));
+                }
             }
 
-            Ok(self.tera.render(RECORD_TERA, &ctx)?)
+            let mut validations = BTreeMap::new();
+            let mut pattern_statics = String::new();
+            if let Some(derive) = self.validate_derive {
+                for field in fields {
+                    let (inner, _, unsigned) = self.constrained_field(field, gen_state)?;
+                    let field_name = gen_state.name_mapper().field_name(&field.name);
+                    let pattern_static = format!(
+                        "{}_{}_PATTERN",
+                        module_prefix.to_uppercase(),
+                        field_name.trim_start_matches("r#").to_uppercase()
+                    );
+                    let (attributes, statics) = crate::validate::derive_attributes(
+                        field,
+                        &inner,
+                        unsigned,
+                        derive,
+                        &pattern_static,
+                        gen_state,
+                    )?;
+                    if !attributes.is_empty() {
+                        validations.insert(field_name, attributes);
+                    }
+                    pattern_statics.push_str(&statics);
+                }
+            }
+
+            let ctx = RecordContext {
+                name: type_name,
+                template: template_attribute(attributes)?,
+                lints: self.lints(ItemKind::Record),
+                doc: doc.clone().unwrap_or_default(),
+                example: self.record_example(schema, gen_state),
+                fields: f,
+                types: t,
+                originals: o,
+                defaults: d,
+                docs: c,
+                attributes: attributes.clone(),
+                field_attributes: a,
+                serde_with: w,
+                sensitive: s,
+                hex: h,
+                omitted: e,
+                empty,
+                zeroize: self.zeroize,
+                wrapper_conversions: self.wrapper_conversions,
+                debug_impls: self.debug_impls,
+                default_impls: self.default_impls
+                    && (!self.strict_defaults
+                        || self.invented_defaults(fields, gen_state).is_empty()),
+                serde_impls: self.serde_impls,
+                is_eq_derivable: gen_state.is_eq_derivable(schema),
+                deserialization: self.deserialization,
+                nullable: self.nullable,
+                derive_builders: self.derive_builders,
+                derive_schemas: self.derive_schemas,
+                validate_derive: self.validate_derive,
+                validations,
+                avro_crate: self.avro_crate.clone(),
+            };
+            let mut code = self.renderer.render_record(&ctx)?;
+            code.push_str(&pattern_statics);
+            if !timestamps.is_empty() {
+                code.push_str(&rfc3339_module(&timestamps_module, &timestamps));
+            }
+            if !scales.is_empty() {
+                code.push_str(&scale_modules(&decimals_module, &scales));
+            }
+            if !avro_types.is_empty() {
+                code.push_str(&unsigned_modules(&unsigned_module, &avro_types));
+            }
+            Ok(code)
         } else {
             err!("Requires Schema::Record, found {:?}", schema)?
         }
@@ -936,9 +2328,9 @@ impl Templater {
             let variants = union.variants();
 
             if variants.is_empty() {
-                err!("Invalid empty Schema::Union")?
+                err!(Union: "Invalid empty Schema::Union")?
             } else if union.is_nullable() && variants.len() == 2 {
-                err!("Attempt to generate a union enum for an optional")?
+                err!(Union: "Attempt to generate a union enum for an optional")?
             }
 
             let schemas = if variants[0] == Schema::Null {
@@ -951,12 +2343,16 @@ impl Templater {
 
             let mut symbols = vec![];
             let mut visitors = vec![];
+            let mut variants = vec![];
+            let mut json_names = BTreeMap::new();
             for mut sc in schemas {
                 // Resolve potentially nested schema ref
                 while let Schema::Ref { ref name } = sc {
                     match gen_state.get_schema(name) {
                         Some(s) => sc = s,
-                        None => err!("Schema reference '{:?}' cannot be resolved", name)?,
+                        None => {
+                            err!(Reference: "Schema reference '{:?}' cannot be resolved", name)?
+                        }
                     }
                 }
                 let symbol_str = match sc {
@@ -966,21 +2362,23 @@ impl Templater {
                     Schema::Long => "Long(i64)".into(),
                     Schema::Float => "Float(f32)".into(),
                     Schema::Double => "Double(f64)".into(),
-                    Schema::Bytes => {
-                        r#"Bytes(#[serde(with = "apache_avro::serde_avro_bytes")] Vec<u8>)"#.into()
-                    }
+                    Schema::Bytes if self.serde_impls => format!(
+                        r#"Bytes(#[serde(with = "{}")] Vec<u8>)"#,
+                        gen_state.avro_path("serde_avro_bytes")
+                    ),
+                    Schema::Bytes => "Bytes(Vec<u8>)".into(),
                     Schema::String => "String(String)".into(),
                     Schema::Array(ArraySchema { items: inner, .. }) => {
                         format!(
                             "Array{}({})",
                             union_enum_variant(inner.as_ref(), gen_state)?,
-                            array_type(inner.as_ref(), gen_state)?
+                            gen_state.nested_type(sc)?
                         )
                     }
                     Schema::Map(MapSchema { types: inner, .. }) => format!(
                         "Map{}({})",
                         union_enum_variant(inner.as_ref(), gen_state)?,
-                        map_type(sc, gen_state)?
+                        gen_state.nested_type(sc)?
                     ),
                     Schema::Union(union) => {
                         format!("{u}({u})", u = union_type(union, gen_state, false)?)
@@ -989,23 +2387,30 @@ impl Templater {
                         name: Name { name, .. },
                         ..
                     }) => {
-                        format!("{rec}({rec})", rec = name.to_upper_camel_case())
+                        format!(
+                            "{rec}({rec})",
+                            rec = gen_state.name_mapper().type_name(name)
+                        )
                     }
                     Schema::Enum(EnumSchema {
                         name: Name { name, .. },
                         ..
                     }) => {
-                        format!("{e}({e})", e = sanitize(name.to_upper_camel_case()))
+                        format!("{e}({e})", e = gen_state.name_mapper().type_name(name))
                     }
                     Schema::Fixed(FixedSchema {
                         name: Name { name, .. },
                         ..
                     }) => {
-                        format!("{f}({f})", f = sanitize(name.to_upper_camel_case()))
+                        format!("{f}({f})", f = gen_state.name_mapper().type_name(name))
                     }
-                    Schema::Decimal { .. } => "Decimal(apache_avro::Decimal)".into(),
-                    Schema::BigDecimal => "BigDecimal(apache_avro::BigDecimal)".into(),
-                    Schema::Uuid => "Uuid(apache_avro::Uuid)".into(),
+                    Schema::Decimal { .. } => {
+                        format!("Decimal({})", gen_state.avro_path("Decimal"))
+                    }
+                    Schema::BigDecimal => {
+                        format!("BigDecimal({})", gen_state.avro_path("BigDecimal"))
+                    }
+                    Schema::Uuid => format!("Uuid({})", gen_state.avro_path("Uuid")),
                     Schema::Date
                     | Schema::TimeMillis
                     | Schema::TimeMicros
@@ -1028,53 +2433,56 @@ impl Templater {
                     Schema::LocalTimestampMillis => "LocalTimestampMillis(i64)".into(),
                     Schema::LocalTimestampMicros => "LocalTimestampMicros(i64)".into(),
                     Schema::LocalTimestampNanos => "LocalTimestampNanos(i64)".into(),
-                    Schema::Duration => "Duration(apache_avro::Duration)".into(),
+                    Schema::Duration => format!("Duration({})", gen_state.avro_path("Duration")),
                     Schema::Null => err!(
-                        "Invalid Schema::Null not in first position on an UnionSchema variants"
+                        Union: "Invalid Schema::Null not in first position on an UnionSchema variants"
                     )?,
                 };
+                let variant = symbol_str.split('(').next().unwrap_or_default().to_string();
+                json_names.insert(variant.clone(), avro_json_name(sc));
+                variants.push(variant);
                 symbols.push(symbol_str);
 
                 match sc {
                     Schema::Record(RecordSchema {
                         name: Name { name, .. },
                         ..
-                    }) => visitors.push(GenUnionVisitor {
-                        variant: name.to_upper_camel_case(),
-                        rust_type: name.to_upper_camel_case(),
+                    }) => visitors.push(UnionVisitorContext {
+                        variant: gen_state.name_mapper().type_name(name),
+                        rust_type: gen_state.name_mapper().type_name(name),
                         serde_visitor: None,
                     }),
-                    Schema::Boolean => visitors.push(GenUnionVisitor {
+                    Schema::Boolean => visitors.push(UnionVisitorContext {
                         variant: String::from("Boolean"),
                         rust_type: String::from("bool"),
                         serde_visitor: String::from("bool").into(),
                     }),
-                    Schema::Int => visitors.push(GenUnionVisitor {
+                    Schema::Int => visitors.push(UnionVisitorContext {
                         variant: String::from("Int"),
                         rust_type: String::from("i32"),
                         serde_visitor: String::from("i32").into(),
                     }),
-                    Schema::Long => visitors.push(GenUnionVisitor {
+                    Schema::Long => visitors.push(UnionVisitorContext {
                         variant: String::from("Long"),
                         rust_type: String::from("i64"),
                         serde_visitor: String::from("i64").into(),
                     }),
-                    Schema::Float => visitors.push(GenUnionVisitor {
+                    Schema::Float => visitors.push(UnionVisitorContext {
                         variant: String::from("Float"),
                         rust_type: String::from("f32"),
                         serde_visitor: String::from("f32").into(),
                     }),
-                    Schema::Double => visitors.push(GenUnionVisitor {
+                    Schema::Double => visitors.push(UnionVisitorContext {
                         variant: String::from("Double"),
                         rust_type: String::from("f64"),
                         serde_visitor: String::from("f64").into(),
                     }),
-                    Schema::String => visitors.push(GenUnionVisitor {
+                    Schema::String => visitors.push(UnionVisitorContext {
                         variant: String::from("String"),
                         rust_type: String::from("String"),
                         serde_visitor: String::from("&str").into(),
                     }),
-                    Schema::Bytes => visitors.push(GenUnionVisitor {
+                    Schema::Bytes => visitors.push(UnionVisitorContext {
                         variant: String::from("Bytes"),
                         rust_type: String::from("Vec<u8>"),
                         serde_visitor: String::from("&[u8]").into(),
@@ -1083,20 +2491,1207 @@ impl Templater {
                 };
             }
 
-            let mut ctx = Context::new();
-            ctx.insert("name", &e_name);
-            ctx.insert("symbols", &symbols);
-            ctx.insert("visitors", &visitors);
-            ctx.insert("use_avro_rs_unions", &self.use_avro_rs_unions);
-            ctx.insert("is_eq_derivable", &gen_state.is_eq_derivable(schema));
+            let ctx = UnionContext {
+                name: e_name,
+                lints: self.lints(ItemKind::Union),
+                variants,
+                symbols,
+                visitors,
+                json: self.union_json,
+                json_names,
+                use_avro_rs_unions: self.use_avro_rs_unions,
+                is_eq_derivable: gen_state.is_eq_derivable(schema),
+                debug_impls: self.debug_impls,
+                serde_impls: self.serde_impls,
+            };
+            self.renderer.render_union(&ctx)
+        } else {
+            err!("Requires Schema::Union, found {:?}", schema)?
+        }
+    }
+
+    /// Generates the rustdoc example of a record, building its example value and encoding it,
+    /// if enabled.
+    ///
+    /// The example value is the [example](crate::Generator::gen_examples) of the record,
+    /// with null optional fields. Records whose example cannot be written as a Rust value
+    /// and encoded (e.g. decimals) have no example.
+    fn record_example(&self, schema: &Schema, gen_state: &GenState) -> Option<String> {
+        let types_path = self.doc_examples.as_ref()?;
+        let Schema::Record(RecordSchema { name, .. }) = schema else {
+            return None;
+        };
+        let example = crate::examples::default_example(schema, gen_state);
+        let inlined = inlined_schema(schema, gen_state, &mut Default::default());
+        let resolves = Schema::parse(&inlined)
+            .map(|s| {
+                apache_avro::types::Value::from(example.clone())
+                    .resolve(&s)
+                    .is_ok()
+            })
+            .unwrap_or(false);
+        if !resolves {
+            return None;
+        }
+        let value = self.parse_default(schema, gen_state, &example).ok()?;
+
+        let avro = |item: &str| gen_state.avro_path(item);
+        let type_name = gen_state.name_mapper().type_name(&name.name);
+        let schema_line = if self.derive_schemas {
+            format!(
+                "let schema = <{type_name} as {}>::get_schema();",
+                avro("AvroSchema")
+            )
+        } else {
+            format!(
+                "// `schema` is the Avro schema of `{type_name}`\n# let schema = {}({:?})?;",
+                avro("Schema::parse_str"),
+                inlined.to_string()
+            )
+        };
+        Some(format!(
+            "# use {types_path}::*;\n\
+             let record = {value};\n\
+             {schema_line}\n\
+             let datum = {to_avro_datum}(&schema, {to_value}(&record)?.resolve(&schema)?)?;\n\
+             # Ok::<(), {error}>(())",
+            to_avro_datum = avro("to_avro_datum"),
+            to_value = avro("to_value"),
+            error = avro("Error"),
+        ))
+    }
+
+    /// Returns the optional union (i.e. `["null", ...]`) generated instead of the schema of a
+    /// field having a default value, with the [`Defaults`](OptionalFields::Defaults)
+    /// optional fields, `None` for other fields.
+    pub(crate) fn optional_field_schema(&self, field: &RecordField) -> Option<Schema> {
+        if self.optional_fields != OptionalFields::Defaults || field.default.is_none() {
+            return None;
+        }
+        let variants = match &field.schema {
+            Schema::Union(union) if union.is_nullable() => return None,
+            Schema::Union(union) => union.variants().to_vec(),
+            schema => vec![schema.clone()],
+        };
+        let variants = std::iter::once(Schema::Null).chain(variants).collect();
+        UnionSchema::new(variants).ok().map(Schema::Union)
+    }
+
+    /// Returns the inline capacity of the `SmallVec` of an array field, set by the
+    /// [`SMALLVEC_ATTRIBUTE`](SMALLVEC_ATTRIBUTE) or by
+    /// [`smallvec_arrays`](Templater::smallvec_arrays), with the items schema of the array and
+    /// whether it is optional, `None` for other fields. The `schema` of the field is its
+    /// [optional schema](Templater::optional_field_schema) if it has one.
+    fn smallvec_field<'a>(
+        &self,
+        field: &RecordField,
+        schema: &'a Schema,
+    ) -> Result<Option<(usize, &'a Schema, bool)>> {
+        let of = format!("field '{}'", field.name);
+        let capacity = match field.custom_attributes.get(SMALLVEC_ATTRIBUTE) {
+            None => None,
+            Some(Value::Number(n)) if n.as_u64().is_some_and(|n| n > 0) => {
+                n.as_u64().and_then(|n| usize::try_from(n).ok())
+            }
+            Some(v) => err!(
+                Attribute: "Invalid '{SMALLVEC_ATTRIBUTE}' attribute of {of}, expected a positive capacity, found {v}"
+            )?,
+        };
+        let (array, optional) = match schema {
+            Schema::Union(union) => match union.variants() {
+                [Schema::Null, inner] => (inner, true),
+                _ => (schema, false),
+            },
+            schema => (schema, false),
+        };
+        match array {
+            Schema::Array(ArraySchema { items, .. }) => Ok(capacity
+                .or(self.smallvec_arrays)
+                .map(|capacity| (capacity, items.as_ref(), optional))),
+            _ if capacity.is_some() => {
+                err!(Attribute: "Invalid '{SMALLVEC_ATTRIBUTE}' attribute of {of}, expected an array field")
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the struct name of the nested record of a field whose schema is `schema`, and
+    /// whether it is optional, if the field is generated as a `Box` of this struct: when
+    /// marked by the `rust.box` attribute, or when the record is estimated larger than
+    /// [`box_threshold`](Templater::box_threshold) bytes.
+    pub(crate) fn boxed_field(
+        &self,
+        field: &RecordField,
+        schema: &Schema,
+        gen_state: &GenState,
+    ) -> Result<Option<(String, bool)>> {
+        self.boxed_field_in(field, schema, gen_state, &mut HashSet::new())
+    }
+
+    /// Same as [`boxed_field`](Templater::boxed_field), the records being estimated being
+    /// `visiting`.
+    fn boxed_field_in(
+        &self,
+        field: &RecordField,
+        schema: &Schema,
+        gen_state: &GenState,
+        visiting: &mut HashSet<Name>,
+    ) -> Result<Option<(String, bool)>> {
+        let of = format!("field '{}'", field.name);
+        let marked = bool_attribute(&field.custom_attributes, BOX_ATTRIBUTE, &of)?;
+        let Some((record, optional)) = field_record(schema, gen_state) else {
+            if marked {
+                err!(Attribute: "Invalid '{BOX_ATTRIBUTE}' attribute of {of}, expected a record field")?
+            }
+            return Ok(None);
+        };
+        let boxed = marked
+            || self.box_threshold.is_some_and(|threshold| {
+                self.estimated_size(record, gen_state, visiting) > threshold
+            });
+        Ok(match record {
+            Schema::Record(RecordSchema { name, .. }) if boxed => {
+                Some((gen_state.name_mapper().type_name(&name.name), optional))
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns a rough estimate of the size in bytes of the Rust type of a schema, summing the
+    /// sizes of the record fields, without padding, and counting the tag of unions.
+    ///
+    /// The records being estimated are `visiting`, recursive ones counting as a pointer.
+    fn estimated_size(
+        &self,
+        schema: &Schema,
+        gen_state: &GenState,
+        visiting: &mut HashSet<Name>,
+    ) -> usize {
+        const POINTER: usize = 8;
+        match schema {
+            Schema::Ref { name } => match gen_state.get_schema(name) {
+                Some(schema) => self.estimated_size(schema, gen_state, visiting),
+                None => POINTER,
+            },
+            Schema::Null => 0,
+            Schema::Boolean | Schema::Enum(_) => 1,
+            Schema::Date
+            | Schema::TimeMillis
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos
+                if self.use_chrono_dates =>
+            {
+                12
+            }
+            Schema::Int | Schema::Float | Schema::Date | Schema::TimeMillis => 4,
+            Schema::Long
+            | Schema::Double
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos => 8,
+            Schema::Duration => 12,
+            Schema::Uuid => 16,
+            Schema::Bytes | Schema::String | Schema::Array(_) => 3 * POINTER,
+            Schema::Map(_) => 6 * POINTER,
+            Schema::Decimal(_) if self.use_decimal_structs => 32,
+            Schema::Decimal(_) | Schema::BigDecimal => 5 * POINTER,
+            Schema::Fixed(FixedSchema { size, .. }) => *size,
+            Schema::Union(union) => {
+                let variants = union.variants().iter();
+                let largest = variants
+                    .map(|v| self.estimated_size(v, gen_state, visiting))
+                    .max();
+                largest.unwrap_or(0) + POINTER
+            }
+            Schema::Record(RecordSchema { name, fields, .. }) => {
+                if !visiting.insert(name.clone()) {
+                    return POINTER;
+                }
+                let mut size = 0;
+                for field in fields {
+                    let optional = self.optional_field_schema(field);
+                    let schema = optional.as_ref().unwrap_or(&field.schema);
+                    // Invalid attributes are reported when generating the record itself
+                    let boxed = self.boxed_field_in(field, schema, gen_state, visiting);
+                    size += if matches!(boxed, Ok(Some(_))) {
+                        POINTER
+                    } else {
+                        let tag = if optional.is_some() { POINTER } else { 0 };
+                        self.estimated_size(&field.schema, gen_state, visiting) + tag
+                    };
+                }
+                visiting.remove(name);
+                size
+            }
+        }
+    }
+
+    /// Generates a `decode_with_writer_schema` function for a record, if enabled.
+    ///
+    /// The function decodes an Avro datum written with another schema, resolved against the
+    /// schema of the record, whose parsing is done once.
+    pub fn str_writer_schema_decoder(
+        &self,
+        schema: &Schema,
+        gen_state: &GenState,
+    ) -> Result<String> {
+        let Schema::Record(RecordSchema { name, .. }) = schema else {
+            return Ok(String::new());
+        };
+        if !self.writer_schema_decoders {
+            return Ok(String::new());
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let avro = &self.avro_crate;
+        let schema_json = inlined_schema(schema, gen_state, &mut HashSet::new()).to_string();
+        Ok(format!(
+            r#"
+impl {name_std} {{
+    /// Decodes an Avro datum written with `writer_schema`, resolving it against the schema
+    /// of `{name_std}`: fields are matched by name whatever their order, missing fields
+    /// take their default value and numbers are promoted.
+    pub fn decode_with_writer_schema(
+        bytes: &[u8],
+        writer_schema: &{avro}::Schema,
+    ) -> Result<Self, {avro}::Error> {{
+        static SCHEMA: std::sync::OnceLock<{avro}::Schema> = std::sync::OnceLock::new();
+        let schema = SCHEMA.get_or_init(|| {{
+            {avro}::Schema::parse_str({schema_json:?}).expect("valid generated schema")
+        }});
+        let value = {avro}::from_avro_datum(writer_schema, &mut &bytes[..], Some(schema))?;
+        {avro}::from_value(&value)
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Generates the `TryFrom<&[u8]>` implementation of a record, if enabled.
+    ///
+    /// The implementation decodes a single Avro datum written with the schema of the record,
+    /// whose parsing is done once, failing if bytes are left after the datum.
+    pub fn str_try_from_bytes(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        let Schema::Record(RecordSchema { name, .. }) = schema else {
+            return Ok(String::new());
+        };
+        if !self.try_from_bytes {
+            return Ok(String::new());
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let avro = &self.avro_crate;
+        let schema_json = inlined_schema(schema, gen_state, &mut HashSet::new()).to_string();
+        Ok(format!(
+            r#"
+impl TryFrom<&[u8]> for {name_std} {{
+    type Error = {avro}::Error;
+
+    /// Decodes an Avro datum written with the schema of `{name_std}`.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {{
+        static SCHEMA: std::sync::OnceLock<{avro}::Schema> = std::sync::OnceLock::new();
+        let schema = SCHEMA.get_or_init(|| {{
+            {avro}::Schema::parse_str({schema_json:?}).expect("valid generated schema")
+        }});
+        let mut reader = bytes;
+        let value = {avro}::from_avro_datum(schema, &mut reader, None)?;
+        if !reader.is_empty() {{
+            return Err({avro}::Error::DeserializeValue(format!(
+                "{{}} bytes left after the datum of `{name_std}`",
+                reader.len()
+            )));
+        }}
+        {avro}::from_value(&value)
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Generates the `AvroSchema` implementation of a record or an enum, if enabled.
+    ///
+    /// The schema is self-contained, the named types it references being inlined, and parsed
+    /// once.
+    pub fn str_avro_schema_impl(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        let (Schema::Record(RecordSchema { name, .. }) | Schema::Enum(EnumSchema { name, .. })) =
+            schema
+        else {
+            return Ok(String::new());
+        };
+        if !self.avro_schema_impls {
+            return Ok(String::new());
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let avro = &self.avro_crate;
+        let schema_json = inlined_schema(schema, gen_state, &mut HashSet::new()).to_string();
+        Ok(format!(
+            r#"
+impl {avro}::AvroSchema for {name_std} {{
+    fn get_schema() -> {avro}::Schema {{
+        static SCHEMA: std::sync::OnceLock<{avro}::Schema> = std::sync::OnceLock::new();
+        SCHEMA
+            .get_or_init(|| {{
+                {avro}::Schema::parse_str({schema_json:?}).expect("valid generated schema")
+            }})
+            .clone()
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Generates the `AvroMessage` implementation of a record or an enum, if enabled.
+    ///
+    /// The schema is self-contained, the named types it references being inlined, and parsed
+    /// once.
+    pub fn str_avro_message_impl(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        let (Schema::Record(RecordSchema { name, .. }) | Schema::Enum(EnumSchema { name, .. })) =
+            schema
+        else {
+            return Ok(String::new());
+        };
+        if !self.avro_message_impls {
+            return Ok(String::new());
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let fullname = name.fullname(None);
+        let avro = &self.avro_crate;
+        let schema_json = inlined_schema(schema, gen_state, &mut HashSet::new()).to_string();
+        Ok(format!(
+            r#"
+impl AvroMessage for {name_std} {{
+    fn fullname(&self) -> &'static str {{
+        {fullname:?}
+    }}
+
+    fn encode(&self) -> Vec<u8> {{
+        static SCHEMA: std::sync::OnceLock<{avro}::Schema> = std::sync::OnceLock::new();
+        let schema = SCHEMA.get_or_init(|| {{
+            {avro}::Schema::parse_str(self.schema_json()).expect("valid generated schema")
+        }});
+        {avro}::to_value(self)
+            .and_then(|value| value.resolve(schema))
+            .and_then(|value| {avro}::to_avro_datum(schema, value))
+            .unwrap_or_else(|e| panic!("Cannot encode a `{name_std}`: {{e}}"))
+    }}
+
+    fn schema_json(&self) -> &'static str {{
+        {schema_json:?}
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Generates the `validate` method of a record, if enabled, checking the constraints of the
+    /// attributes of its fields and validating the records they hold.
+    pub fn str_validate_impl(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        let Schema::Record(RecordSchema { name, fields, .. }) = schema else {
+            return Ok(String::new());
+        };
+        if !self.validators {
+            return Ok(String::new());
+        }
+
+        let mut fields_by_pos = fields.iter().collect::<Vec<_>>();
+        fields_by_pos.sort_by_key(|f| f.position);
+        let mut checks = String::new();
+        for field in fields_by_pos {
+            let (inner, optional, unsigned) = self.constrained_field(field, gen_state)?;
+            let field_name = gen_state.name_mapper().field_name(&field.name);
+            checks.push_str(&crate::validate::field_checks(
+                field,
+                &inner,
+                optional,
+                unsigned,
+                &field_name,
+                self,
+                gen_state,
+            )?);
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let body = if checks.is_empty() {
+            "\n        Ok(())".to_string()
+        } else {
+            format!(
+                "\n        let mut errors = ValidationErrors::default();{checks}\n        errors.into_result()"
+            )
+        };
+        Ok(format!(
+            r#"
+impl {name_std} {{
+    /// Checks the values of the fields against the constraints of the schema, e.g. the
+    /// precision of decimals, the symbols of string enums or the `minimum`, `maximum` and
+    /// `pattern` of fields, and validates the records they hold.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {{{body}
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Returns the resolved schema of a record field checked by validation, the inner schema
+    /// of optional fields, and whether the field is optional and unsigned.
+    fn constrained_field(
+        &self,
+        field: &RecordField,
+        gen_state: &GenState,
+    ) -> Result<(Schema, bool, bool)> {
+        let field_schema = self
+            .optional_field_schema(field)
+            .unwrap_or_else(|| field.schema.clone());
+        let (inner, optional) = match &field_schema {
+            Schema::Union(union) => match union.variants() {
+                [Schema::Null, inner] => (inner, true),
+                _ => (&field_schema, false),
+            },
+            schema => (schema, false),
+        };
+        let inner = match inner {
+            Schema::Ref { name } => gen_state.get_schema(name).unwrap_or(inner),
+            schema => schema,
+        };
+        let unsigned = unsigned_field(field, &field_schema)?.is_some();
+        Ok((inner.clone(), optional, unsigned))
+    }
+
+    /// Generates type aliases of a record, an enum or a fixed named after its Avro aliases, if
+    /// enabled, for code using the former names of a renamed type to keep compiling.
+    ///
+    /// Aliases named like the type itself are skipped, and aliases named like another type
+    /// are an error.
+    pub fn str_alias_types(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        let (Schema::Record(RecordSchema { name, aliases, .. })
+        | Schema::Enum(EnumSchema { name, aliases, .. })
+        | Schema::Fixed(FixedSchema { name, aliases, .. })) = schema
+        else {
+            return Ok(String::new());
+        };
+        if !self.alias_types {
+            return Ok(String::new());
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let mut alias_names = BTreeSet::new();
+        let mut code = String::new();
+        for alias in aliases.iter().flatten() {
+            let alias_std = gen_state.name_mapper().type_name(&alias.name());
+            if alias_std == name_std || !alias_names.insert(alias_std.clone()) {
+                continue;
+            }
+            if let Some(other) = gen_state
+                .schemata_by_name
+                .keys()
+                .find(|other| gen_state.name_mapper().type_name(&other.name) == alias_std)
+            {
+                err!(
+                    Conflict: "Alias '{}' of '{}' conflicts with the type of '{}'",
+                    alias.fullname(None),
+                    name.fullname(None),
+                    other.fullname(None)
+                )?
+            }
+            code.push_str(&format!(
+                "\n/// The former name of [`{name_std}`], an alias of its schema.\n\
+                 pub type {alias_std} = {name_std};\n"
+            ));
+        }
+        Ok(code)
+    }
+
+    /// Generates the `SCHEMA` and `SCHEMA_PRETTY` constants and the `schema_json` function
+    /// of a record, if enabled.
+    ///
+    /// The schemas are self-contained: the named types they reference are inlined.
+    pub fn str_schema_constants(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        let Schema::Record(RecordSchema { name, .. }) = schema else {
+            return Ok(String::new());
+        };
+        if !self.schema_constants {
+            return Ok(String::new());
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let inlined = inlined_schema(schema, gen_state, &mut HashSet::new());
+        let compact = inlined.to_string();
+        let pretty = serde_json::to_string_pretty(&inlined).expect("Unexpected invalid schema");
+        // A raw string literal keeps the pretty-printed schema readable in the generated code
+        let mut hashes = "#".to_string();
+        while pretty.contains(&format!("\"{hashes}")) {
+            hashes.push('#');
+        }
+        Ok(format!(
+            r#"
+impl {name_std} {{
+    /// The Avro schema of `{name_std}`, as compact JSON.
+    pub const SCHEMA: &'static str = {compact:?};
+
+    /// The Avro schema of `{name_std}`, as pretty-printed JSON.
+    pub const SCHEMA_PRETTY: &'static str = r{hashes}"{pretty}"{hashes};
+
+    /// Returns the Avro schema of `{name_std}`, as a JSON value.
+    pub fn schema_json() -> serde_json::Value {{
+        serde_json::from_str(Self::SCHEMA).expect("valid generated schema")
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Generates a `#[cfg(test)]` module checking that a record survives serde round trips
+    /// through Avro datums, if enabled and if the record implements `Default`.
+    ///
+    /// Besides encoding and decoding `Default::default()`, the module checks that schema
+    /// default values are decoded as `Default::default()`, unless some of them are coerced
+    /// (see [`warnings`](Templater::warnings)).
+    pub fn str_round_trip_tests(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        let Schema::Record(RecordSchema { name, fields, .. }) = schema else {
+            return Ok(String::new());
+        };
+        // Fields omitted when `None` are read back with their default value
+        if !self.round_trip_tests
+            || fields
+                .iter()
+                .any(|f| f.default.is_none() || self.optional_field_schema(f).is_some())
+        {
+            return Ok(String::new());
+        }
+
+        let name_std = gen_state.name_mapper().type_name(&name.name);
+        let avro = &self.avro_crate;
+        let schema_json = inlined_schema(schema, gen_state, &mut HashSet::new()).to_string();
+        let mut code = format!(
+            r#"
+#[cfg(test)]
+mod {module}_round_trip {{
+    use super::{name_std};
+
+    const SCHEMA: &str = {schema_json:?};
+
+    #[test]
+    fn default_value() {{
+        let schema = {avro}::Schema::parse_str(SCHEMA).unwrap();
+        let expected = {name_std}::default();
+        let value = {avro}::to_value(&expected).unwrap().resolve(&schema).unwrap();
+        let datum = {avro}::to_avro_datum(&schema, value).unwrap();
+        let value = {avro}::from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+        assert_eq!(expected, {avro}::from_value::<{name_std}>(&value).unwrap());
+    }}
+"#,
+            module = name_std.to_snake_case().trim_start_matches("r#"),
+        );
+        if self.warnings(schema, gen_state).is_empty() {
+            let writer_json = serde_json::json!({
+                "type": "record",
+                "name": name.fullname(None),
+                "fields": [],
+            })
+            .to_string();
+            code.push_str(&format!(
+                r#"
+    #[test]
+    fn schema_defaults() {{
+        let schema = {avro}::Schema::parse_str(SCHEMA).unwrap();
+        let writer = {avro}::Schema::parse_str({writer_json:?}).unwrap();
+        let value = {avro}::from_avro_datum(&writer, &mut &[][..], Some(&schema)).unwrap();
+        assert_eq!({name_std}::default(), {avro}::from_value::<{name_std}>(&value).unwrap());
+    }}
+"#
+            ));
+        }
+        code.push_str("}\n");
+        Ok(code)
+    }
+
+    /// Generates a `proptest::arbitrary::Arbitrary` implementation for a record, enum or
+    /// union enum, if enabled.
+    ///
+    /// Strategies respect the schema constraints, e.g. fixed sizes and enum symbols. They
+    /// produce the default Rust types of schemas, custom [`TypeMapper`](TypeMapper) types
+    /// are not supported. Recursive records are supported through optional, array and map
+    /// fields, which are then left empty.
+    pub fn str_proptest_arbitrary(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        if !self.proptest_arbitrary {
+            return Ok(String::new());
+        }
+        let (name_std, strategy) = match schema {
+            Schema::Record(RecordSchema { name, fields, .. }) => {
+                let name_std = gen_state.name_mapper().type_name(&name.name);
+                let mut fields_by_pos = fields.iter().collect::<Vec<_>>();
+                fields_by_pos.sort_by_key(|f| f.position);
+                let mut strategies = vec![];
+                for field in fields_by_pos {
+                    let optional = self.optional_field_schema(field);
+                    let schema = optional.as_ref().unwrap_or(&field.schema);
+                    let strategy = match unsigned_field(field, schema)? {
+                        Some((avro_type, optional)) => {
+                            let signed = if avro_type == "int" { "i32" } else { "i64" };
+                            let range =
+                                format!("(0..={signed}::MAX as {})", unsigned_type(avro_type));
+                            if optional {
+                                call_expr("prop::option::of", &[range])
+                            } else {
+                                range
+                            }
+                        }
+                        None => self.proptest_strategy(schema, gen_state, Some(name))?,
+                    };
+                    let strategy = match self.boxed_field(field, schema, gen_state)? {
+                        Some((_, false)) => format!("{strategy}\n    .prop_map(Box::new)"),
+                        Some((_, true)) => {
+                            format!("{strategy}\n    .prop_map(|v| v.map(Box::new))")
+                        }
+                        None => strategy,
+                    };
+                    let strategy = match self.smallvec_field(field, schema)? {
+                        Some((_, _, false)) => {
+                            format!("{strategy}\n    .prop_map(smallvec::SmallVec::from_vec)")
+                        }
+                        Some((_, _, true)) => format!(
+                            "{strategy}\n    .prop_map(|v| v.map(smallvec::SmallVec::from_vec))"
+                        ),
+                        None => strategy,
+                    };
+                    strategies.push((strategy, gen_state.name_mapper().field_name(&field.name)));
+                }
+                let field_names = strategies
+                    .iter()
+                    .map(|(_, f)| f.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let (strategy, pattern) = tuple_tree(strategies);
+                let strategy = format!(
+                    "{strategy}\n    .prop_map(|{pattern}| {name_std} {{ {field_names} }})\n    .boxed()"
+                );
+                (name_std, strategy)
+            }
+            Schema::Enum(EnumSchema { name, symbols, .. }) => {
+                let name_std = gen_state.name_mapper().type_name(&name.name);
+                let symbols = symbols
+                    .iter()
+                    .map(|s| self.symbol_value(&name_std, s, gen_state))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (
+                    name_std,
+                    format!("proptest::sample::select(vec![{symbols}]).boxed()"),
+                )
+            }
+            Schema::Union(union) => {
+                let name_std = union_type(union, gen_state, false)?;
+                let mut strategies = vec![];
+                for variant in union.variants().iter().filter(|v| **v != Schema::Null) {
+                    strategies.push(format!(
+                        "{}.prop_map({name_std}::{}).boxed()",
+                        self.proptest_strategy(variant, gen_state, None)?,
+                        self.union_variant_name(variant, gen_state)?,
+                    ));
+                }
+                let strategy = format!(
+                    "proptest::strategy::Union::new(vec![\n    {},\n])\n.boxed()",
+                    strategies.join(",\n    ")
+                );
+                (name_std, strategy)
+            }
+            _ => return Ok(String::new()),
+        };
+        let strategy = strategy.replace('\n', "\n        ");
+        Ok(format!(
+            r#"
+impl proptest::arbitrary::Arbitrary for {name_std} {{
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {{
+        use proptest::prelude::*;
+        {strategy}
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Generates a `quickcheck::Arbitrary` implementation for a record, enum or union enum,
+    /// if enabled.
+    ///
+    /// As for [`str_proptest_arbitrary`](Templater::str_proptest_arbitrary), values respect
+    /// the schema constraints, have the default Rust types of schemas, and recursive records
+    /// are supported through optional, array and map fields.
+    pub fn str_quickcheck_arbitrary(
+        &self,
+        schema: &Schema,
+        gen_state: &GenState,
+    ) -> Result<String> {
+        if !self.quickcheck_arbitrary {
+            return Ok(String::new());
+        }
+        let (name_std, value) = match schema {
+            Schema::Record(RecordSchema { name, fields, .. }) => {
+                let name_std = gen_state.name_mapper().type_name(&name.name);
+                let mut fields_by_pos = fields.iter().collect::<Vec<_>>();
+                fields_by_pos.sort_by_key(|f| f.position);
+                let mut value = format!("{name_std} {{");
+                for field in fields_by_pos {
+                    let optional = self.optional_field_schema(field);
+                    let schema = optional.as_ref().unwrap_or(&field.schema);
+                    let field_value = match unsigned_field(field, schema)? {
+                        // Halved to fit in the signed Avro types
+                        Some((avro_type, optional)) => {
+                            let value = format!(
+                                "<{} as quickcheck::Arbitrary>::arbitrary(g) >> 1",
+                                unsigned_type(avro_type)
+                            );
+                            if optional {
+                                format!(
+                                    "if quickcheck::Arbitrary::arbitrary(g) {{ Some({value}) }} else {{ None }}"
+                                )
+                            } else {
+                                value
+                            }
+                        }
+                        None => self.quickcheck_value(schema, gen_state, Some(name))?,
+                    };
+                    let field_value = match self.smallvec_field(field, schema)? {
+                        Some((_, _, false)) => {
+                            format!("smallvec::SmallVec::from_vec({field_value})")
+                        }
+                        Some((_, _, true)) => {
+                            format!("Option::map({field_value}, smallvec::SmallVec::from_vec)")
+                        }
+                        None => field_value,
+                    };
+                    value.push_str(&format!(
+                        "\n            {}: {},",
+                        gen_state.name_mapper().field_name(&field.name),
+                        field_value
+                    ));
+                }
+                value.push_str("\n        }");
+                (name_std, value)
+            }
+            Schema::Enum(EnumSchema { name, symbols, .. }) => {
+                let name_std = gen_state.name_mapper().type_name(&name.name);
+                let symbols = symbols
+                    .iter()
+                    .map(|s| self.symbol_value(&name_std, s, gen_state))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (name_std, format!("g.choose(&[{symbols}]).unwrap().clone()"))
+            }
+            Schema::Union(union) => {
+                let name_std = union_type(union, gen_state, false)?;
+                let mut variants = vec![];
+                for variant in union.variants().iter().filter(|v| **v != Schema::Null) {
+                    variants.push(format!(
+                        "{name_std}::{}({})",
+                        self.union_variant_name(variant, gen_state)?,
+                        self.quickcheck_value(variant, gen_state, None)?,
+                    ));
+                }
+                let value = match &variants[..] {
+                    [variant] => variant.clone(),
+                    _ => {
+                        let last = variants.len() - 1;
+                        let mut value = format!(
+                            "match <usize as quickcheck::Arbitrary>::arbitrary(g) % {} {{",
+                            variants.len()
+                        );
+                        for (idx, variant) in variants.iter().enumerate() {
+                            let pattern = match idx {
+                                idx if idx == last => "_".to_string(),
+                                idx => idx.to_string(),
+                            };
+                            value.push_str(&format!("\n            {pattern} => {variant},"));
+                        }
+                        value.push_str("\n        }");
+                        value
+                    }
+                };
+                (name_std, value)
+            }
+            _ => return Ok(String::new()),
+        };
+        Ok(format!(
+            r#"
+impl quickcheck::Arbitrary for {name_std} {{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {{
+        {value}
+    }}
+}}
+"#
+        ))
+    }
+
+    /// Returns an expression generating a value of the Rust type of a schema from a
+    /// `quickcheck::Gen` named `g`.
+    ///
+    /// Containers of schemas reaching the `recursive` record are generated empty.
+    fn quickcheck_value(
+        &self,
+        schema: &Schema,
+        gen_state: &GenState,
+        recursive: Option<&Name>,
+    ) -> Result<String> {
+        const ARBITRARY: &str = "quickcheck::Arbitrary::arbitrary(g)";
+        let reaches_recursive =
+            |s: &Schema| recursive.is_some_and(|r| reaches(s, r, gen_state, &mut HashSet::new()));
+        let value = match schema {
+            Schema::Ref { name } => match gen_state.get_schema(name) {
+                Some(s) => return self.quickcheck_value(s, gen_state, recursive),
+                None => err!(Reference: "Schema reference '{:?}' cannot be resolved", name)?,
+            },
+
+            Schema::Date
+            | Schema::TimeMillis
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos
+                if self.use_chrono_dates =>
+            {
+                // Up to the end of year 9999
+                "chrono::DateTime::from_timestamp_millis(\
+                 <i64 as quickcheck::Arbitrary>::arbitrary(g).rem_euclid(253_402_300_800_000))\
+                 .unwrap()"
+                    .to_string()
+            }
+
+            Schema::Uuid => format!("{}::from_u128({ARBITRARY})", gen_state.avro_path("Uuid")),
+            Schema::Decimal(DecimalSchema { scale, .. }) if self.use_decimal_structs => {
+                format!("Decimal {{ mantissa: {ARBITRARY}, scale: {scale} }}")
+            }
+            Schema::Decimal { .. } => format!(
+                "{}::from(<Vec<u8> as quickcheck::Arbitrary>::arbitrary(g))",
+                gen_state.avro_path("Decimal")
+            ),
+            Schema::BigDecimal => format!(
+                "{}::from(<i64 as quickcheck::Arbitrary>::arbitrary(g))",
+                gen_state.avro_path("BigDecimal")
+            ),
+            Schema::Duration => format!(
+                "{}::from(std::array::from_fn::<u8, 12, _>(|_| {ARBITRARY}))",
+                gen_state.avro_path("Duration")
+            ),
+            Schema::Fixed(_) => format!("std::array::from_fn(|_| {ARBITRARY})"),
+
+            Schema::Array(ArraySchema { items, .. }) => {
+                if reaches_recursive(items) {
+                    "Vec::new()".to_string()
+                } else {
+                    match self.quickcheck_value(items, gen_state, recursive)? {
+                        item if item == ARBITRARY => item,
+                        item => format!(
+                            "(0..<usize as quickcheck::Arbitrary>::arbitrary(g) % 4)\
+                             .map(|_| {item}).collect()"
+                        ),
+                    }
+                }
+            }
+            Schema::Map(MapSchema { types, .. }) => {
+                if reaches_recursive(types) {
+                    "::std::collections::HashMap::new()".to_string()
+                } else {
+                    match self.quickcheck_value(types, gen_state, recursive)? {
+                        value if value == ARBITRARY => value,
+                        value => format!(
+                            "(0..<usize as quickcheck::Arbitrary>::arbitrary(g) % 4)\
+                             .map(|_| ({ARBITRARY}, {value})).collect()"
+                        ),
+                    }
+                }
+            }
+            Schema::Union(union) => {
+                let variants = union.variants();
+                let inner = if union.is_nullable() && variants.len() == 2 {
+                    self.quickcheck_value(&variants[1], gen_state, recursive)?
+                } else {
+                    ARBITRARY.to_string()
+                };
+                if !union.is_nullable() {
+                    inner
+                } else if reaches_recursive(schema) {
+                    "None".to_string()
+                } else if inner == ARBITRARY {
+                    inner
+                } else {
+                    format!("if {ARBITRARY} {{ Some({inner}) }} else {{ None }}")
+                }
+            }
+
+            // Other types, including generated ones, implement `quickcheck::Arbitrary`
+            _ => ARBITRARY.to_string(),
+        };
+        Ok(value)
+    }
 
-            Ok(self.tera.render(UNION_TERA, &ctx)?)
+    /// Returns the expression of an enum symbol, a variant or, with `string_enums`, a newtype
+    /// built from the constant of the symbol.
+    fn symbol_value(&self, e_name: &str, symbol: &str, gen_state: &GenState) -> String {
+        if self.string_enums {
+            format!("{e_name}::from({e_name}::{})", symbol_constant(symbol))
         } else {
-            err!("Requires Schema::Union, found {:?}", schema)?
+            format!("{e_name}::{}", gen_state.name_mapper().variant_name(symbol))
+        }
+    }
+
+    /// Returns the name of the variant generated for a schema in an union enum.
+    fn union_variant_name(&self, schema: &Schema, gen_state: &GenState) -> Result<String> {
+        match schema {
+            Schema::Date
+            | Schema::TimeMillis
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos
+                if self.use_chrono_dates =>
+            {
+                Ok("NaiveDateTime".into())
+            }
+            _ => union_enum_variant(schema, gen_state),
+        }
+    }
+
+    /// Returns a proptest strategy expression producing values of the Rust type of a schema.
+    ///
+    /// Containers of schemas reaching the `recursive` record are generated empty.
+    fn proptest_strategy(
+        &self,
+        schema: &Schema,
+        gen_state: &GenState,
+        recursive: Option<&Name>,
+    ) -> Result<String> {
+        let reaches_recursive =
+            |s: &Schema| recursive.is_some_and(|r| reaches(s, r, gen_state, &mut HashSet::new()));
+        let strategy = match schema {
+            Schema::Ref { name } => match gen_state.get_schema(name) {
+                Some(s) => return self.proptest_strategy(s, gen_state, recursive),
+                None => err!(Reference: "Schema reference '{:?}' cannot be resolved", name)?,
+            },
+            Schema::Null => "Just(())".to_string(),
+            Schema::Boolean => "any::<bool>()".to_string(),
+            Schema::Int => "any::<i32>()".to_string(),
+            Schema::Long => "any::<i64>()".to_string(),
+            Schema::Float => "any::<f32>()".to_string(),
+            Schema::Double => "any::<f64>()".to_string(),
+            Schema::Bytes => "any::<Vec<u8>>()".to_string(),
+            Schema::String => "any::<String>()".to_string(),
+
+            Schema::Date
+            | Schema::TimeMillis
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos
+                if self.use_chrono_dates =>
+            {
+                // Up to the end of year 9999
+                "(0i64..253_402_300_800_000)\n    .prop_map(|ms| chrono::DateTime::from_timestamp_millis(ms).unwrap())".to_string()
+            }
+            Schema::Date | Schema::TimeMillis => "any::<i32>()".to_string(),
+            Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos => "any::<i64>()".to_string(),
+
+            Schema::Uuid => format!(
+                "any::<u128>().prop_map({}::from_u128)",
+                gen_state.avro_path("Uuid")
+            ),
+            Schema::Decimal(DecimalSchema { scale, .. }) if self.use_decimal_structs => {
+                format!("any::<i128>().prop_map(|mantissa| Decimal {{ mantissa, scale: {scale} }})")
+            }
+            Schema::Decimal { .. } => format!(
+                "any::<Vec<u8>>().prop_map({}::from)",
+                gen_state.avro_path("Decimal")
+            ),
+            Schema::BigDecimal => format!(
+                "any::<i64>().prop_map({}::from)",
+                gen_state.avro_path("BigDecimal")
+            ),
+            Schema::Duration => format!(
+                "any::<[u8; 12]>().prop_map({}::from)",
+                gen_state.avro_path("Duration")
+            ),
+            Schema::Fixed(FixedSchema { size, .. }) => format!(
+                "prop::collection::vec(any::<u8>(), {size})\n    .prop_map(|v| <[u8; {size}]>::try_from(v).unwrap())"
+            ),
+            Schema::Record(RecordSchema { name, .. }) | Schema::Enum(EnumSchema { name, .. }) => {
+                format!("any::<{}>()", gen_state.name_mapper().type_name(&name.name))
+            }
+
+            Schema::Array(ArraySchema { items, .. }) => {
+                if reaches_recursive(items) {
+                    "Just(Vec::new())".to_string()
+                } else {
+                    call_expr(
+                        "prop::collection::vec",
+                        &[
+                            self.proptest_strategy(items, gen_state, recursive)?,
+                            "0..4".to_string(),
+                        ],
+                    )
+                }
+            }
+            Schema::Map(MapSchema { types, .. }) => {
+                if reaches_recursive(types) {
+                    "Just(::std::collections::HashMap::new())".to_string()
+                } else {
+                    call_expr(
+                        "prop::collection::hash_map",
+                        &[
+                            "any::<String>()".to_string(),
+                            self.proptest_strategy(types, gen_state, recursive)?,
+                            "0..4".to_string(),
+                        ],
+                    )
+                }
+            }
+            Schema::Union(union) => {
+                let variants = union.variants();
+                let inner = if union.is_nullable() && variants.len() == 2 {
+                    self.proptest_strategy(&variants[1], gen_state, recursive)?
+                } else {
+                    format!("any::<{}>()", union_type(union, gen_state, false)?)
+                };
+                if !union.is_nullable() {
+                    inner
+                } else if reaches_recursive(schema) {
+                    "Just(None)".to_string()
+                } else {
+                    call_expr("prop::option::of", &[inner])
+                }
+            }
+        };
+        Ok(strategy)
+    }
+
+    /// Returns the fields of a record without default value and their invented default
+    /// values, if they all have one, i.e. the zero values of `lenient` deserialization.
+    fn invented_defaults<'a>(
+        &self,
+        fields: &'a [RecordField],
+        gen_state: &GenState,
+    ) -> Vec<(&'a str, String)> {
+        if self.deserialization != DeserializationMode::Lenient {
+            return vec![];
+        }
+        let mut invented = vec![];
+        for field in fields
+            .iter()
+            .filter(|f| f.default.is_none() && self.optional_field_schema(f).is_none())
+        {
+            let zero = zero_default(&field.schema, gen_state);
+            match self.parse_default(&field.schema, gen_state, &zero) {
+                Ok(default_str) => invented.push((field.name.as_str(), default_str)),
+                // Fields without zero value are required, the record has no `Default` impl
+                Err(_) => return vec![],
+            }
+        }
+        invented
+    }
+
+    /// Returns warnings about lossy generation choices for a schema, e.g. coerced or invented
+    /// default values, or union variants without apache-avro union deserialization support.
+    pub fn warnings(&self, schema: &Schema, gen_state: &GenState) -> Vec<String> {
+        let mut warnings = vec![];
+        match schema {
+            Schema::Record(RecordSchema { fields, .. }) => {
+                for RecordField {
+                    name,
+                    schema,
+                    default,
+                    ..
+                } in fields
+                {
+                    let Some(Value::Number(n)) = default else {
+                        continue;
+                    };
+                    let Ok(default_str) =
+                        self.parse_default(schema, gen_state, &Value::Number(n.clone()))
+                    else {
+                        continue;
+                    };
+                    let coerced = match schema {
+                        Schema::Int | Schema::Date | Schema::TimeMillis => {
+                            n.as_i64().and_then(|n| i32::try_from(n).ok()).is_none()
+                        }
+                        Schema::Float => {
+                            default_str.parse::<f32>().ok() != n.as_f64().map(|n| n as f32)
+                        }
+                        Schema::Double => default_str.parse::<f64>().ok() != n.as_f64(),
+                        _ => false,
+                    };
+                    if coerced {
+                        warnings.push(format!(
+                            "Default value {n} of field '{name}' coerced to {default_str}"
+                        ));
+                    }
+                }
+                if self.default_impls && !self.strict_defaults {
+                    for (name, default_str) in self.invented_defaults(fields, gen_state) {
+                        warnings.push(format!(
+                            "Field '{name}' has no default value, `Default` invents {default_str}"
+                        ));
+                    }
+                }
+            }
+
+            Schema::Union(union) if self.use_avro_rs_unions => {
+                for variant in union.variants() {
+                    let variant = match variant {
+                        Schema::Ref { name } => gen_state.get_schema(name).unwrap_or(variant),
+                        _ => variant,
+                    };
+                    if !matches!(
+                        variant,
+                        Schema::Null
+                            | Schema::Record(_)
+                            | Schema::Boolean
+                            | Schema::Int
+                            | Schema::Long
+                            | Schema::Float
+                            | Schema::Double
+                            | Schema::String
+                            | Schema::Bytes
+                    ) {
+                        warnings.push(format!(
+                            "Union variant {} cannot be deserialized from apache-avro union values",
+                            union_enum_variant(variant, gen_state).unwrap_or_default()
+                        ));
+                    }
+                }
+            }
+
+            _ => (),
         }
+        warnings
     }
 
-    fn parse_default(
+    pub(crate) fn parse_default(
         &self,
         schema: &Schema,
         gen_state: &GenState,
@@ -1105,12 +3700,12 @@ impl Templater {
         let default_str = match schema {
             Schema::Ref { name } => match gen_state.get_schema(name) {
                 Some(s) => self.parse_default(s, gen_state, default)?,
-                None => err!("Schema reference '{:?}' cannot be resolved", name)?,
+                None => err!(Reference: "Schema reference '{:?}' cannot be resolved", name)?,
             },
 
             Schema::Boolean => match default {
                 Value::Bool(b) => b.to_string(),
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Date if self.use_chrono_dates => match default {
@@ -1118,7 +3713,7 @@ impl Templater {
                     "chrono::DateTime::<chrono::Utc>::from_timestamp({}, 0).unwrap()",
                     n.as_i64().unwrap()
                 ),
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::TimeMillis | Schema::TimestampMillis | Schema::LocalTimestampMillis
@@ -1129,7 +3724,7 @@ impl Templater {
                         "chrono::DateTime::<chrono::Utc>::from_timestamp_millis({}).unwrap()",
                         n.as_i64().unwrap()
                     ),
-                    _ => err!("Invalid default: {:?}", default)?,
+                    _ => err!(Default: "Invalid default: {:?}", default)?,
                 }
             }
 
@@ -1141,23 +3736,23 @@ impl Templater {
                         "chrono::DateTime::<chrono::Utc>::from_timestamp_micros({}).unwrap()",
                         n.as_i64().unwrap()
                     ),
-                    _ => err!("Invalid default: {:?}", default)?,
+                    _ => err!(Default: "Invalid default: {:?}", default)?,
                 }
             }
 
             Schema::TimestampNanos | Schema::LocalTimestampNanos if self.use_chrono_dates => {
                 match default {
                     Value::Number(n) if n.is_i64() => format!(
-                        "chrono::DateTime::<chrono::Utc>::from_timestamp_nanos({}).unwrap()",
+                        "chrono::DateTime::<chrono::Utc>::from_timestamp_nanos({})",
                         n.as_i64().unwrap()
                     ),
-                    _ => err!("Invalid default: {:?}", default)?,
+                    _ => err!(Default: "Invalid default: {:?}", default)?,
                 }
             }
 
             Schema::Int | Schema::Date | Schema::TimeMillis => match default {
                 Value::Number(n) if n.is_i64() => (n.as_i64().unwrap() as i32).to_string(),
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Long
@@ -1169,7 +3764,7 @@ impl Templater {
             | Schema::LocalTimestampMicros
             | Schema::LocalTimestampNanos => match default {
                 Value::Number(n) if n.is_i64() => n.to_string(),
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Float => match default {
@@ -1181,7 +3776,7 @@ impl Templater {
                         format!("{:.*}", self.precision, n)
                     }
                 }
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Double => match default {
@@ -1193,7 +3788,7 @@ impl Templater {
                         format!("{:.*}", self.precision, n)
                     }
                 }
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Bytes => match default {
@@ -1201,82 +3796,118 @@ impl Templater {
                     let bytes = s.clone().into_bytes();
                     format!("vec!{:?}", bytes)
                 }
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::String => match default {
                 Value::String(s) => format!("\"{}\".to_owned()", s),
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Uuid => match default {
                 Value::String(s) => {
                     format!(
-                        r#"apache_avro::Uuid::parse_str("{}").unwrap()"#,
+                        r#"{}::parse_str("{}").unwrap()"#,
+                        gen_state.avro_path("Uuid"),
                         apache_avro::Uuid::parse_str(s)
                             .map_err(|e| Error::Template(e.to_string()))?
                     )
                 }
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Duration => match default {
                 Value::String(s) => {
                     let bytes = s.clone().into_bytes();
                     if bytes.len() != 12 {
-                        err!("Invalid default: {:?}", bytes)?
+                        err!(
+                            Default: "Invalid default {s:?}: expected 12 bytes, found {}",
+                            bytes.len()
+                        )?
                     }
                     format!("{:?}", bytes)
                 }
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
+            Schema::Decimal(DecimalSchema { inner, scale, .. }) if self.use_decimal_structs => {
+                match default {
+                    Value::String(s) => {
+                        let bytes = s.clone().into_bytes();
+                        if let Schema::Fixed(FixedSchema { size, .. }) = inner.as_ref() {
+                            if bytes.len() != *size {
+                                err!(
+                                    Default: "Invalid default {s:?}: expected {size} bytes, found {}",
+                                    bytes.len()
+                                )?
+                            }
+                        }
+                        match decimal_mantissa(&bytes) {
+                            Some(mantissa) => {
+                                format!("Decimal {{ mantissa: {mantissa}, scale: {scale} }}")
+                            }
+                            None => err!(Default: "Invalid default: {:?}", bytes)?,
+                        }
+                    }
+                    _ => err!(Default: "Invalid default: {:?}", default)?,
+                }
+            }
             Schema::Decimal(DecimalSchema { inner, .. }) => match inner.as_ref() {
                 Schema::Bytes => match default {
                     Value::String(s) => {
                         let bytes = s.clone().into_bytes();
-                        format!("vec!{:?}", bytes)
+                        format!("{}::from(vec!{:?})", gen_state.avro_path("Decimal"), bytes)
                     }
-                    _ => err!("Invalid default: {:?}", default)?,
+                    _ => err!(Default: "Invalid default: {:?}", default)?,
                 },
                 Schema::Fixed(FixedSchema { size, .. }) => match default {
                     Value::String(s) => {
                         let bytes = s.clone().into_bytes();
                         if bytes.len() != *size {
-                            err!("Invalid default: {:?}", bytes)?
+                            err!(
+                                Default: "Invalid default {s:?}: expected {size} bytes, found {}",
+                                bytes.len()
+                            )?
                         }
-                        format!("{:?}", bytes)
+                        format!("{}::from({:?})", gen_state.avro_path("Decimal"), bytes)
                     }
-                    _ => err!("Invalid default: {:?}", default)?,
+                    _ => err!(Default: "Invalid default: {:?}", default)?,
                 },
                 _ => err!("Invalid Decimal inner Schema: {:?}", inner)?,
             },
 
             Schema::BigDecimal => match default {
                 Value::String(s) => {
-                    format!(r#"apache_avro::BigDecimal::parse_str("{}").unwrap()"#, s)
+                    format!(
+                        r#""{}".parse::<{}>().unwrap()"#,
+                        s,
+                        gen_state.avro_path("BigDecimal")
+                    )
                 }
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Fixed(FixedSchema { size, .. }) => match default {
                 Value::String(s) => {
                     let bytes = s.clone().into_bytes();
                     if bytes.len() != *size {
-                        err!("Invalid default: {:?}", bytes)?
+                        err!(
+                            Default: "Invalid default {s:?}: expected {size} bytes, found {}",
+                            bytes.len()
+                        )?
                     }
                     format!("{:?}", bytes)
                 }
-                _ => err!("Invalid default: {:?}", default)?,
+                _ => err!(Default: "Invalid default: {:?}", default)?,
             },
 
             Schema::Array(ArraySchema { items: inner, .. }) => match inner.as_ref() {
-                Schema::Null => err!("Invalid use of Schema::Null")?,
+                Schema::Null => err!(Union: "Invalid use of Schema::Null")?,
                 _ => self.array_default(inner, gen_state, default)?,
             },
 
             Schema::Map(MapSchema { types: inner, .. }) => match inner.as_ref() {
-                Schema::Null => err!("Invalid use of Schema::Null")?,
+                Schema::Null => err!(Union: "Invalid use of Schema::Null")?,
                 _ => self.map_default(inner, gen_state, default)?,
             },
 
@@ -1287,27 +3918,26 @@ impl Templater {
                 symbols,
                 ..
             }) => {
-                let e_name = sanitize(e_name.to_upper_camel_case());
+                let e_name = gen_state.name_mapper().type_name(e_name);
                 let valids: HashSet<_> = symbols
                     .iter()
-                    .map(|s| sanitize(s.to_upper_camel_case()))
+                    .map(|s| gen_state.name_mapper().variant_name(s))
                     .collect();
                 match default {
                     Value::String(ref s) => {
-                        let s = sanitize(s.to_upper_camel_case());
-                        if valids.contains(&s) {
-                            format!("{}::{}", e_name, s)
+                        if valids.contains(&gen_state.name_mapper().variant_name(s)) {
+                            self.symbol_value(&e_name, s, gen_state)
                         } else {
-                            err!("Invalid default: {:?}", default)?
+                            err!(Default: "Invalid default: {:?}", default)?
                         }
                     }
-                    _ => err!("Invalid default: {:?}", default)?,
+                    _ => err!(Default: "Invalid default: {:?}", default)?,
                 }
             }
 
             Schema::Union(union) => self.union_default(union, gen_state, default)?,
 
-            Schema::Null => err!("Invalid use of Schema::Null")?,
+            Schema::Null => err!(Union: "Invalid use of Schema::Null")?,
         };
 
         Ok(default_str)
@@ -1321,15 +3951,17 @@ impl Templater {
         default: &Value,
     ) -> Result<String> {
         if let Value::Array(vals) = default {
-            let vals = vals
-                .iter()
-                .map(|d| self.parse_default(inner, gen_state, d))
-                .collect::<Result<Vec<String>>>()?
-                .as_slice()
-                .join(", ");
-            Ok(format!("vec![{}]", vals))
+            let mut default_str = String::from("vec![");
+            for (i, d) in vals.iter().enumerate() {
+                if i > 0 {
+                    default_str.push_str(", ");
+                }
+                default_str.push_str(&self.item_default(inner, gen_state, d)?);
+            }
+            default_str.push(']');
+            Ok(default_str)
         } else {
-            err!("Invalid default: {:?}, expected: Array", default)
+            err!(Default: "Invalid default: {:?}, expected: Array", default)
         }
     }
 
@@ -1339,26 +3971,62 @@ impl Templater {
             if o.is_empty() {
                 Ok("::std::collections::HashMap::new()".to_string())
             } else {
-                let vals = o
-                    .iter()
-                    .map(|(k, v)| {
-                        Ok(format!(
-                            r#"m.insert("{}".to_owned(), {});"#,
-                            k,
-                            self.parse_default(inner, gen_state, v)?
-                        ))
-                    })
-                    .collect::<Result<Vec<String>>>()?
-                    .as_slice()
-                    .join(" ");
-                Ok(format!(
-                    "{{ let mut m = ::std::collections::HashMap::new(); {} m }}",
-                    vals
-                ))
+                let mut default_str =
+                    String::from("{ let mut m = ::std::collections::HashMap::new();");
+                for (k, v) in o {
+                    let v = self.item_default(inner, gen_state, v)?;
+                    let _ = write!(default_str, r#" m.insert("{k}".to_owned(), {v});"#);
+                }
+                default_str.push_str(" m }");
+                Ok(default_str)
             }
         } else {
-            err!("Invalid default: {:?}, expected: Map", default)
+            err!(Default: "Invalid default: {:?}, expected: Map", default)
+        }
+    }
+
+    /// Generates the Rust value of an item of an Avro array or map default value, whose type
+    /// is the first variant accepting it when items are unions, unlike union field defaults
+    /// which are always of the first variant.
+    fn item_default(
+        &self,
+        inner: &Schema,
+        gen_state: &GenState,
+        default: &Value,
+    ) -> Result<String> {
+        let Schema::Union(union) = inner else {
+            return self.parse_default(inner, gen_state, default);
+        };
+        let (optional, variants) = match union.variants() {
+            [Schema::Null, variants @ ..] => (true, variants),
+            variants => (false, variants),
+        };
+        if optional && default.is_null() {
+            return Ok("None".to_string());
         }
+        let Some(variant) = item_variant(variants, gen_state, default) else {
+            err!(
+                Default: "Invalid default: {:?}, expected a value of {:?}",
+                default,
+                union
+            )?
+        };
+        let value = self.parse_default(variant, gen_state, default)?;
+        // Optional unions of a single type are options of that type
+        let value = if optional && variants.len() == 1 {
+            value
+        } else {
+            format!(
+                "{}::{}({value})",
+                union_type(union, gen_state, false)?,
+                self.union_variant_name(variant, gen_state)?
+            )
+        };
+        Ok(if optional {
+            format!("Some({value})")
+        } else {
+            value
+        })
     }
 
     /// Generates Rust default values for an Avro record
@@ -1375,27 +4043,44 @@ impl Templater {
                 ..
             }) => {
                 let default_str = if let Value::Object(o) = default {
-                    if !o.is_empty() {
-                        let vals = fields
-                            .iter()
-                            .map(|rf| {
-                                let f = sanitize(rf.name.to_snake_case());
-                                let d = if let Some(v) = o.get(&rf.name) {
-                                    self.parse_default(&rf.schema, gen_state, v)?
-                                } else {
-                                    format!("default_{}_{}()", name.to_lowercase(), f)
-                                };
-                                Ok(format!("{}: {},", f, d))
-                            })
-                            .collect::<Result<Vec<String>>>()?
-                            .as_slice()
-                            .join(" ");
-                        format!("{} {{ {} }}", sanitize(name.to_upper_camel_case()), vals)
+                    // Without `Default` impls, the field defaults are used one by one
+                    if !o.is_empty() || !self.default_impls {
+                        let mut default_str = gen_state.name_mapper().type_name(name);
+                        default_str.push_str(" {");
+                        for rf in fields {
+                            let f = gen_state.name_mapper().field_name(&rf.name);
+                            let _ = match o.get(&rf.name) {
+                                Some(v) => {
+                                    let mut d = self.parse_default(&rf.schema, gen_state, v)?;
+                                    // Optional records and arrays default to `None`
+                                    if let Some((_, false)) =
+                                        self.boxed_field(rf, &rf.schema, gen_state)?
+                                    {
+                                        d = format!("Box::new({d})");
+                                    }
+                                    if let Some((_, _, false)) =
+                                        self.smallvec_field(rf, &rf.schema)?
+                                    {
+                                        d = smallvec_default(d);
+                                    }
+                                    if self.optional_field_schema(rf).is_some() {
+                                        d = format!("Some({d})");
+                                    }
+                                    write!(default_str, " {f}: {d},")
+                                }
+                                None => {
+                                    let name = name.to_lowercase();
+                                    write!(default_str, " {f}: default_{name}_{f}(),")
+                                }
+                            };
+                        }
+                        default_str.push_str(" }");
+                        default_str
                     } else {
-                        format!("{}::default()", sanitize(name.to_upper_camel_case()))
+                        format!("{}::default()", gen_state.name_mapper().type_name(name))
                     }
                 } else {
-                    err!("Invalid default: {:?}, expected: Object", default)?
+                    err!(Default: "Invalid default: {:?}, expected: Object", default)?
                 };
                 Ok(default_str)
             }
@@ -1413,78 +4098,27 @@ impl Templater {
         if union.is_nullable() {
             let default_str = match default {
                 Value::Null => "None".into(),
-                _ => err!("Invalid optional union default: {:?}", default)?,
+                _ => err!(Default: "Invalid optional union default: {:?}", default)?,
             };
             Ok(default_str)
         } else {
             let e_name = union_type(union, gen_state, false)?;
-            let e_variant = union_enum_variant(&union.variants()[0], gen_state)?;
+            let e_variant = self.union_variant_name(&union.variants()[0], gen_state)?;
             let default_str = self.parse_default(&union.variants()[0], gen_state, default)?;
             Ok(format!("{}::{}({})", e_name, e_variant, default_str))
         }
     }
 }
 
-/// Generates the Rust type of the inner schema of an Avro array.
-pub(crate) fn array_type(inner: &Schema, gen_state: &GenState) -> Result<String> {
+/// Generates the Rust type of a schema nested in an Avro array, map or optional union.
+fn inner_type(inner: &Schema, gen_state: &GenState) -> Result<String> {
     let type_str = match inner {
         Schema::Ref { name } => match gen_state.get_schema(name) {
-            Some(s) => array_type(s, gen_state)?,
-            None => err!("Schema reference '{:?}' cannot be resolved", name)?,
+            Some(s) => inner_type(s, gen_state)?,
+            None => err!(Reference: "Schema reference '{:?}' cannot be resolved", name)?,
         },
-        Schema::Boolean => "Vec<bool>".into(),
-        Schema::Int => "Vec<i32>".into(),
-        Schema::Long => "Vec<i64>".into(),
-        Schema::Float => "Vec<f32>".into(),
-        Schema::Double => "Vec<f64>".into(),
-        Schema::Bytes => "Vec<Vec<u8>>".into(),
-        Schema::String => "Vec<String>".into(),
-
-        Schema::Date
-        | Schema::TimeMillis
-        | Schema::TimeMicros
-        | Schema::TimestampMillis
-        | Schema::TimestampMicros
-        | Schema::TimestampNanos
-        | Schema::LocalTimestampMillis
-        | Schema::LocalTimestampMicros
-        | Schema::LocalTimestampNanos
-            if gen_state.use_chrono_dates =>
-        {
-            "Vec<chrono::DateTime<chrono::Utc>>".into()
-        }
-
-        Schema::Date | Schema::TimeMillis => "Vec<i32>".into(),
-        Schema::TimeMicros
-        | Schema::TimestampMillis
-        | Schema::TimestampMicros
-        | Schema::TimestampNanos
-        | Schema::LocalTimestampMillis
-        | Schema::LocalTimestampMicros
-        | Schema::LocalTimestampNanos => "Vec<i64>".into(),
 
-        Schema::Uuid => "Vec<apache_avro::Uuid>".into(),
-        Schema::Decimal { .. } => "Vec<apache_avro::Decimal>".into(),
-        Schema::BigDecimal => "Vec<apache_avro::BigDecimal>".into(),
-        Schema::Duration { .. } => "Vec<apache_avro::Duration>".into(),
-
-        Schema::Fixed(FixedSchema {
-            name: Name { name: f_name, .. },
-            ..
-        }) => {
-            let f_name = sanitize(f_name.to_upper_camel_case());
-            format!("Vec<{}>", f_name)
-        }
-
-        Schema::Array(..) | Schema::Map(..) | Schema::Union(..) => {
-            let nested_type = gen_state.get_type(inner).ok_or_else(|| {
-                Error::Template(format!(
-                    "Didn't find schema {:?} in state {:?}",
-                    inner, &gen_state
-                ))
-            })?;
-            format!("Vec<{}>", nested_type)
-        }
+        Schema::Array(..) | Schema::Map(..) | Schema::Union(..) => gen_state.nested_type(inner)?,
 
         Schema::Record(RecordSchema {
             name: Name { name, .. },
@@ -1493,95 +4127,169 @@ pub(crate) fn array_type(inner: &Schema, gen_state: &GenState) -> Result<String>
         | Schema::Enum(EnumSchema {
             name: Name { name, .. },
             ..
-        }) => format!("Vec<{}>", &sanitize(name.to_upper_camel_case())),
+        })
+        | Schema::Fixed(FixedSchema {
+            name: Name { name, .. },
+            ..
+        }) => gen_state.name_mapper().type_name(name),
+
+        Schema::Null => err!(Union: "Invalid use of Schema::Null")?,
 
-        Schema::Null => err!("Invalid use of Schema::Null")?,
+        _ => gen_state.primitive_type(inner)?,
     };
     Ok(type_str)
 }
 
+/// Generates the Rust type of an anonymous Avro array, map or union.
+fn anonymous_type(schema: &Schema, gen_state: &GenState) -> Result<String> {
+    match schema {
+        Schema::Array(ArraySchema { items: inner, .. }) => array_type(inner, gen_state),
+        Schema::Map(MapSchema { types: inner, .. }) => map_type(inner, gen_state),
+        Schema::Union(union) => union_type(union, gen_state, true),
+        _ => err!("Requires an anonymous schema, found {:?}", schema),
+    }
+}
+
+/// Generates the Rust type of the inner schema of an Avro array.
+pub(crate) fn array_type(inner: &Schema, gen_state: &GenState) -> Result<String> {
+    Ok(gen_state
+        .type_mapper()
+        .array_type(&inner_type(inner, gen_state)?))
+}
+
 /// Generates the Rust type of the inner schema of an Avro map.
 pub(crate) fn map_type(inner: &Schema, gen_state: &GenState) -> Result<String> {
-    fn map_of(t: &str) -> String {
-        format!("::std::collections::HashMap<String, {}>", t)
-    }
+    Ok(gen_state
+        .type_mapper()
+        .map_type(&inner_type(inner, gen_state)?))
+}
 
-    let type_str = match inner {
+/// Returns the JSON of a schema where references are replaced by the definition of the
+/// referenced schemas, making it parsable on its own.
+pub(crate) fn inlined_schema<'a>(
+    schema: &'a Schema,
+    gen_state: &'a GenState,
+    defined: &mut HashSet<&'a Name>,
+) -> Value {
+    let mut json = serde_json::to_value(schema).unwrap_or_default();
+    match schema {
         Schema::Ref { name } => match gen_state.get_schema(name) {
-            Some(s) => map_type(s, gen_state)?,
-            None => err!("Schema reference '{:?}' cannot be resolved", name)?,
+            Some(s) if defined.insert(name) => json = inlined_schema(s, gen_state, defined),
+            _ => (),
         },
-        Schema::Boolean => map_of("bool"),
-        Schema::Int => map_of("i32"),
-        Schema::Long => map_of("i64"),
-        Schema::Float => map_of("f32"),
-        Schema::Double => map_of("f64"),
-        Schema::Bytes => map_of("Vec<u8>"),
-        Schema::String => map_of("String"),
-
-        Schema::Date
-        | Schema::TimeMillis
-        | Schema::TimeMicros
-        | Schema::TimestampMillis
-        | Schema::TimestampMicros
-        | Schema::LocalTimestampMillis
-        | Schema::LocalTimestampMicros
-            if gen_state.use_chrono_dates =>
-        {
-            map_of("chrono::DateTime<chrono::Utc>")
+        Schema::Record(RecordSchema { name, fields, .. }) => {
+            defined.insert(name);
+            for (field, json_field) in fields
+                .iter()
+                .zip(json["fields"].as_array_mut().into_iter().flatten())
+            {
+                json_field["type"] = inlined_schema(&field.schema, gen_state, defined);
+            }
         }
-
-        Schema::Date | Schema::TimeMillis => map_of("i32"),
-        Schema::TimeMicros
-        | Schema::TimestampMillis
-        | Schema::TimestampMicros
-        | Schema::TimestampNanos
-        | Schema::LocalTimestampMillis
-        | Schema::LocalTimestampMicros
-        | Schema::LocalTimestampNanos => map_of("i64"),
-
-        Schema::Uuid => map_of("apache_avro::Uuid"),
-        Schema::Decimal { .. } => map_of("apache_avro::Decimal"),
-        Schema::BigDecimal => map_of("apache_avro::BigDecimal"),
-        Schema::Duration { .. } => map_of("apache_avro::Duration"),
-
-        Schema::Fixed(FixedSchema {
-            name: Name { name: f_name, .. },
-            ..
-        }) => {
-            let f_name = sanitize(f_name.to_upper_camel_case());
-            map_of(&f_name)
+        Schema::Enum(EnumSchema { name, .. }) | Schema::Fixed(FixedSchema { name, .. }) => {
+            defined.insert(name);
+        }
+        Schema::Array(ArraySchema { items, .. }) => {
+            json["items"] = inlined_schema(items, gen_state, defined);
         }
+        Schema::Map(MapSchema { types, .. }) => {
+            json["values"] = inlined_schema(types, gen_state, defined);
+        }
+        Schema::Union(union) => {
+            json = Value::Array(
+                union
+                    .variants()
+                    .iter()
+                    .map(|variant| inlined_schema(variant, gen_state, defined))
+                    .collect(),
+            );
+        }
+        _ => (),
+    }
+    json
+}
 
-        Schema::Array(..) | Schema::Map(..) | Schema::Union(..) => {
-            let nested_type = gen_state.get_type(inner).ok_or_else(|| {
-                Error::Template(format!(
-                    "Didn't find schema {:?} in state {:?}",
-                    inner, &gen_state
-                ))
-            })?;
-            map_of(nested_type)
+/// Returns whether a schema is, or contains, the named `target` schema.
+pub(crate) fn reaches<'a>(
+    schema: &'a Schema,
+    target: &Name,
+    gen_state: &'a GenState,
+    visited: &mut HashSet<&'a Name>,
+) -> bool {
+    match schema {
+        Schema::Ref { name } if name == target => true,
+        Schema::Ref { name } => {
+            visited.insert(name)
+                && gen_state
+                    .get_schema(name)
+                    .is_some_and(|s| reaches(s, target, gen_state, visited))
+        }
+        Schema::Record(RecordSchema { name, fields, .. }) => {
+            name == target
+                || (visited.insert(name)
+                    && fields
+                        .iter()
+                        .any(|f| reaches(&f.schema, target, gen_state, visited)))
         }
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => reaches(inner, target, gen_state, visited),
+        Schema::Union(union) => union
+            .variants()
+            .iter()
+            .any(|v| reaches(v, target, gen_state, visited)),
+        _ => false,
+    }
+}
 
-        Schema::Record(RecordSchema {
-            name: Name { name, .. },
-            ..
-        })
-        | Schema::Enum(EnumSchema {
-            name: Name { name, .. },
-            ..
-        }) => map_of(&sanitize(name.to_upper_camel_case())),
+/// Joins expressions with one per line, re-indenting multi-line ones.
+fn indent_items(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| item.replace('\n', "\n    "))
+        .collect::<Vec<_>>()
+        .join(",\n    ")
+}
 
-        Schema::Null => err!("Invalid use of Schema::Null")?,
-    };
-    Ok(type_str)
+/// Formats a call expression, with an argument per line if some are multi-line.
+fn call_expr(function: &str, args: &[String]) -> String {
+    if args.iter().any(|arg| arg.contains('\n')) {
+        format!("{function}(\n    {},\n)", indent_items(args))
+    } else {
+        format!("{function}({})", args.join(", "))
+    }
+}
+
+/// Combines `(strategy, binding)` pairs into a strategy of nested tuples, as proptest only
+/// implements strategies for small tuples, and returns it with the matching pattern.
+fn tuple_tree(items: Vec<(String, String)>) -> (String, String) {
+    const MAX_ARITY: usize = 10;
+    if items.len() <= MAX_ARITY {
+        let (strategies, patterns): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+        return match strategies.len() {
+            0 => ("Just(())".to_string(), "()".to_string()),
+            1 => (
+                format!("({},)", strategies[0]),
+                format!("({},)", patterns[0]),
+            ),
+            _ => (
+                format!("(\n    {},\n)", indent_items(&strategies)),
+                format!("({})", patterns.join(", ")),
+            ),
+        };
+    }
+    let mut groups = vec![];
+    let mut items = items.into_iter().peekable();
+    while items.peek().is_some() {
+        groups.push(tuple_tree(items.by_ref().take(MAX_ARITY).collect()));
+    }
+    tuple_tree(groups)
 }
 
-fn union_enum_variant(schema: &Schema, gen_state: &GenState) -> Result<String> {
+pub(crate) fn union_enum_variant(schema: &Schema, gen_state: &GenState) -> Result<String> {
     let variant_str = match schema {
         Schema::Ref { name } => match gen_state.get_schema(name) {
             Some(s) => union_enum_variant(s, gen_state)?,
-            None => err!("Schema reference '{:?}' cannot be resolved", name)?,
+            None => err!(Reference: "Schema reference '{:?}' cannot be resolved", name)?,
         },
         Schema::Boolean => "Boolean".into(),
         Schema::Int => "Int".into(),
@@ -1596,19 +4304,24 @@ fn union_enum_variant(schema: &Schema, gen_state: &GenState) -> Result<String> {
         Schema::Map(MapSchema { types: inner, .. }) => {
             format!("Map{}", union_enum_variant(inner.as_ref(), gen_state)?)
         }
-        Schema::Union(union) => union_type(union, gen_state, false)?,
+        // Nullable unions of arrays and maps, e.g. `MapOptionLong` for `HashMap<String, Option<i64>>`
+        Schema::Union(union) => match union.variants() {
+            [Schema::Null, inner] => format!("Option{}", union_enum_variant(inner, gen_state)?),
+            [Schema::Null, ..] => format!("Option{}", union_type(union, gen_state, false)?),
+            _ => union_type(union, gen_state, false)?,
+        },
         Schema::Record(RecordSchema {
             name: Name { name, .. },
             ..
-        }) => name.to_upper_camel_case(),
+        }) => gen_state.name_mapper().type_name(name),
         Schema::Enum(EnumSchema {
             name: Name { name, .. },
             ..
-        }) => sanitize(name.to_upper_camel_case()),
+        }) => gen_state.name_mapper().type_name(name),
         Schema::Fixed(FixedSchema {
             name: Name { name, .. },
             ..
-        }) => sanitize(name.to_upper_camel_case()),
+        }) => gen_state.name_mapper().type_name(name),
 
         Schema::Decimal { .. } => "Decimal".into(),
         Schema::BigDecimal => "BigDecimal".into(),
@@ -1624,7 +4337,7 @@ fn union_enum_variant(schema: &Schema, gen_state: &GenState) -> Result<String> {
         Schema::LocalTimestampNanos => "LocalTimestampNanos".into(),
         Schema::Duration => "Duration".into(),
         Schema::Null => {
-            err!("Invalid Schema::Null not in first position on an UnionSchema variants")?
+            err!(Union: "Invalid Schema::Null not in first position on an UnionSchema variants")?
         }
     };
 
@@ -1639,9 +4352,9 @@ pub(crate) fn union_type(
     let variants = union.variants();
 
     if variants.is_empty() {
-        err!("Invalid empty Schema::Union")?
+        err!(Union: "Invalid empty Schema::Union")?
     } else if variants.len() == 1 && variants[0] == Schema::Null {
-        err!("Invalid Schema::Union of only Schema::Null")?
+        err!(Union: "Invalid Schema::Union of only Schema::Null")?
     }
 
     if union.is_nullable() && variants.len() == 2 {
@@ -1654,10 +4367,16 @@ pub(crate) fn union_type(
         variants
     };
 
-    let mut type_str = String::from("Union");
-    for sc in schemas {
-        type_str.push_str(&union_enum_variant(sc, gen_state)?);
-    }
+    let type_str = match gen_state.union_name(union) {
+        Some(name) => name.clone(),
+        None => {
+            let mut type_str = String::from("Union");
+            for sc in schemas {
+                type_str.push_str(&union_enum_variant(sc, gen_state)?);
+            }
+            type_str
+        }
+    };
 
     if variants[0] == Schema::Null && wrap_if_optional {
         Ok(format!("Option<{}>", type_str))
@@ -1666,76 +4385,27 @@ pub(crate) fn union_type(
     }
 }
 
-/// Generates the Rust type of the inner schema of an Avro optional union.
-pub(crate) fn option_type(inner: &Schema, gen_state: &GenState) -> Result<String> {
-    let type_str = match inner {
-        Schema::Ref { name } => match gen_state.get_schema(name) {
-            Some(s) => option_type(s, gen_state)?,
-            None => err!("Schema reference '{:?}' cannot be resolved", name)?,
-        },
-        Schema::Boolean => "Option<bool>".into(),
-        Schema::Int => "Option<i32>".into(),
-        Schema::Long => "Option<i64>".into(),
-        Schema::Float => "Option<f32>".into(),
-        Schema::Double => "Option<f64>".into(),
-        Schema::Bytes => "Option<Vec<u8>>".into(),
-        Schema::String => "Option<String>".into(),
-
-        Schema::Date
-        | Schema::TimeMillis
-        | Schema::TimeMicros
-        | Schema::TimestampMillis
-        | Schema::TimestampMicros
-        | Schema::TimestampNanos
-        | Schema::LocalTimestampMillis
-        | Schema::LocalTimestampMicros
-        | Schema::LocalTimestampNanos
-            if gen_state.use_chrono_dates =>
-        {
-            "Option<chrono::DateTime<chrono::Utc>>".into()
-        }
-        Schema::Date | Schema::TimeMillis => "Option<i32>".into(),
-        Schema::TimeMicros
-        | Schema::TimestampMillis
-        | Schema::TimestampMicros
-        | Schema::TimestampNanos
-        | Schema::LocalTimestampMillis
-        | Schema::LocalTimestampMicros
-        | Schema::LocalTimestampNanos => "Option<i64>".into(),
-
-        Schema::Uuid => "Option<apache_avro::Uuid>".into(),
-        Schema::Decimal { .. } => "Option<apache_avro::Decimal>".into(),
-        Schema::BigDecimal => "Option<apache_avro::BigDecimal>".into(),
-        Schema::Duration { .. } => "Option<apache_avro::Duration>".into(),
-
-        Schema::Fixed(FixedSchema {
-            name: Name { name: f_name, .. },
-            ..
-        }) => {
-            let f_name = sanitize(f_name.to_upper_camel_case());
-            format!("Option<{}>", f_name)
-        }
-
-        Schema::Array(..) | Schema::Map(..) | Schema::Union(..) => {
-            let nested_type = gen_state.get_type(inner).ok_or_else(|| {
-                Error::Template(format!(
-                    "Didn't find schema {:?} in state {:?}",
-                    inner, &gen_state
-                ))
-            })?;
-            format!("Option<{}>", nested_type)
+/// Collects the unions generated as enums among a schema and its anonymous nested schemas,
+/// outer ones first.
+fn union_enums<'a>(schema: &'a Schema, unions: &mut Vec<&'a UnionSchema>) {
+    match schema {
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => union_enums(inner, unions),
+        Schema::Union(union) => {
+            if !(union.is_nullable() && union.variants().len() == 2) {
+                unions.push(union);
+            }
+            for variant in union.variants() {
+                union_enums(variant, unions);
+            }
         }
+        _ => {}
+    }
+}
 
-        Schema::Record(RecordSchema {
-            name: Name { name, .. },
-            ..
-        })
-        | Schema::Enum(EnumSchema {
-            name: Name { name, .. },
-            ..
-        }) => format!("Option<{}>", &sanitize(name.to_upper_camel_case())),
-
-        Schema::Null => err!("Invalid use of Schema::Null")?,
-    };
-    Ok(type_str)
+/// Generates the Rust type of the inner schema of an Avro optional union.
+pub(crate) fn option_type(inner: &Schema, gen_state: &GenState) -> Result<String> {
+    Ok(gen_state
+        .type_mapper()
+        .option_type(&inner_type(inner, gen_state)?))
 }
@@ -0,0 +1,107 @@
+//! Mapping of Avro schemas to Rust types.
+
+use apache_avro::schema::{RecordField, RecordSchema};
+use apache_avro::Schema;
+
+/// Generator options affecting the Rust types of schemas.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TypeOptions {
+    /// Whether date/time logical types are mapped to `chrono` types.
+    pub use_chrono_dates: bool,
+    /// Whether decimal logical types are mapped to the generated `Decimal` struct.
+    pub use_decimal_structs: bool,
+    /// The path of the Avro crate referenced by the generated code.
+    pub avro_crate: String,
+}
+
+/// Maps Avro schemas to the Rust types used in generated code.
+///
+/// Every method has a default implementation, matching [`DefaultTypeMapper`](DefaultTypeMapper),
+/// so that implementors only override the mappings they need. Types of named schemas are
+/// given by the [`NameMapper`](crate::NameMapper).
+///
+/// Note that variants of generated union enums always hold the default primitive types.
+pub trait TypeMapper: Send + Sync {
+    /// Returns the Rust type of a primitive or logical schema, `None` for other schemas.
+    fn primitive_type(&self, schema: &Schema, options: &TypeOptions) -> Option<String> {
+        let avro_path = |item: &str| format!("{}::{}", options.avro_crate, item);
+        let type_str = match schema {
+            Schema::Boolean => "bool".to_string(),
+            Schema::Int => "i32".to_string(),
+            Schema::Long => "i64".to_string(),
+            Schema::Float => "f32".to_string(),
+            Schema::Double => "f64".to_string(),
+            Schema::Bytes => "Vec<u8>".to_string(),
+            Schema::String => "String".to_string(),
+
+            Schema::Date
+            | Schema::TimeMillis
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos
+                if options.use_chrono_dates =>
+            {
+                "chrono::DateTime<chrono::Utc>".to_string()
+            }
+
+            Schema::Date | Schema::TimeMillis => "i32".to_string(),
+            Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos => "i64".to_string(),
+
+            Schema::Uuid => avro_path("Uuid"),
+            Schema::Decimal { .. } if options.use_decimal_structs => "Decimal".to_string(),
+            Schema::Decimal { .. } => avro_path("Decimal"),
+            Schema::BigDecimal => avro_path("BigDecimal"),
+            Schema::Duration => avro_path("Duration"),
+
+            _ => return None,
+        };
+        Some(type_str)
+    }
+
+    /// Returns the Rust type of an Avro array, given the Rust type of its items.
+    fn array_type(&self, item_type: &str) -> String {
+        format!("Vec<{}>", item_type)
+    }
+
+    /// Returns the Rust type of an Avro map, given the Rust type of its values.
+    fn map_type(&self, value_type: &str) -> String {
+        format!("::std::collections::HashMap<String, {}>", value_type)
+    }
+
+    /// Returns the Rust type of an Avro optional union (i.e. `["null", T]`), given the Rust
+    /// type of `T`.
+    fn option_type(&self, inner_type: &str) -> String {
+        format!("Option<{}>", inner_type)
+    }
+
+    /// Returns the Rust type of a record field, given the type mapped from its schema.
+    ///
+    /// Allows overriding the type of specific fields, e.g. based on their custom attributes.
+    fn field_type(&self, record: &RecordSchema, field: &RecordField, rust_type: String) -> String {
+        let _ = (record, field);
+        rust_type
+    }
+}
+
+impl std::fmt::Debug for dyn TypeMapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TypeMapper")
+    }
+}
+
+/// The default [`TypeMapper`](TypeMapper).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultTypeMapper;
+
+impl TypeMapper for DefaultTypeMapper {}
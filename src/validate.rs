@@ -0,0 +1,435 @@
+//! The `validate` methods of the record structs with
+//! [`validators`](crate::GeneratorBuilder::validators), checking the structural invariants of
+//! their fields and the constraints of their custom attributes, and their generated
+//! `ValidationErrors`, and the `#[validate(...)]` attributes of the `Validate` derive of
+//! [`validate_derive`](crate::GeneratorBuilder::validate_derive).
+
+use std::fmt::Write as _;
+
+use apache_avro::schema::{
+    ArraySchema, DecimalSchema, EnumSchema, FixedSchema, MapSchema, RecordField,
+};
+use apache_avro::Schema;
+use serde_json::{Number, Value};
+
+use crate::decimal::DECIMAL_STRUCT;
+use crate::error::{Error, ErrorKind, Result};
+use crate::gen::ItemKind;
+use crate::templates::{GenState, Templater, ValidateDerive};
+
+/// The name of the generated error.
+pub(crate) const VALIDATION_ERRORS: &str = "ValidationErrors";
+
+/// The record field attribute setting the smallest value of an `int`, `long`, `float` or
+/// `double` field, possibly optional.
+pub const MINIMUM_ATTRIBUTE: &str = "minimum";
+
+/// The record field attribute setting the largest value of an `int`, `long`, `float` or
+/// `double` field, possibly optional.
+pub const MAXIMUM_ATTRIBUTE: &str = "maximum";
+
+/// The record field attribute setting a regular expression matched by the values of a
+/// `string` field, possibly optional, anywhere in the string unless anchored.
+pub const PATTERN_ATTRIBUTE: &str = "pattern";
+
+/// The name of the generated import of the `Validate` trait of the validator crate.
+pub(crate) const VALIDATOR_TRAIT: &str = "Validate";
+
+/// Returns the code importing the `Validate` trait of the validator crate, whose derive calls
+/// the `validate` method of nested records.
+pub(crate) fn validator_trait() -> String {
+    r#"
+/// Brings the `validate` method of the nested records in scope of their `Validate` derive.
+#[allow(unused_imports)]
+use validator::Validate as _;
+"#
+    .to_string()
+}
+
+/// Returns the code of the generated error, listing the invalid fields of a record.
+pub(crate) fn validation_errors(templater: &Templater) -> String {
+    let lints: String = templater
+        .lints(ItemKind::Validation)
+        .iter()
+        .map(|lint| format!("#[{lint}]\n"))
+        .collect();
+    format!(
+        r#"
+/// The fields of a record violating the constraints of their schema, returned by its
+/// `validate` method.
+{lints}#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationErrors {{
+    /// The path of each invalid field, e.g. `address.zip`, and the constraint it violates.
+    pub errors: Vec<(String, String)>,
+}}
+
+impl ValidationErrors {{
+    /// Adds a violated constraint of the field at `path`.
+    pub fn push(&mut self, path: &str, message: &str) {{
+        self.errors.push((path.to_string(), message.to_string()));
+    }}
+
+    /// Adds the errors of a nested record, their paths being prefixed by the `path` of its field.
+    pub fn nest(&mut self, path: &str, result: Result<(), ValidationErrors>) {{
+        if let Err(nested) = result {{
+            for (nested_path, message) in nested.errors {{
+                self.errors.push((format!("{{path}}.{{nested_path}}"), message));
+            }}
+        }}
+    }}
+
+    /// Returns the errors, if there are some.
+    pub fn into_result(self) -> Result<(), ValidationErrors> {{
+        if self.errors.is_empty() {{
+            Ok(())
+        }} else {{
+            Err(self)
+        }}
+    }}
+}}
+
+impl std::fmt::Display for ValidationErrors {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        for (i, (path, message)) in self.errors.iter().enumerate() {{
+            if i > 0 {{
+                f.write_str(", ")?;
+            }}
+            write!(f, "`{{path}}` {{message}}")?;
+        }}
+        Ok(())
+    }}
+}}
+
+impl std::error::Error for ValidationErrors {{}}
+"#
+    )
+}
+
+/// Returns the checks of a record field within the `validate` method of its struct: the
+/// constraints of its attributes, the precision and scale of its decimal structs, the symbols
+/// of its string enums, and the `validate` calls of the records it holds.
+///
+/// `optional` fields are `Option`s of the type of their resolved `schema`, and `unsigned`
+/// ones are `u32` or `u64`.
+pub(crate) fn field_checks(
+    field: &RecordField,
+    schema: &Schema,
+    optional: bool,
+    unsigned: bool,
+    field_name: &str,
+    templater: &Templater,
+    gen_state: &GenState,
+) -> Result<String> {
+    let path = &field.name;
+    // The expressions of the value, e.g. `self.id` or `*value`, and of a reference to it
+    let (value, reference) = if optional {
+        ("*value".to_string(), "value".to_string())
+    } else {
+        (format!("self.{field_name}"), format!("&self.{field_name}"))
+    };
+    let mut checks = String::new();
+    let constraints = constraints(field, schema, unsigned)?;
+
+    for (attribute, bound, literal) in constraints.bounds {
+        let (operator, message) = if attribute == MINIMUM_ATTRIBUTE {
+            ("<", format!("must be at least {bound}"))
+        } else {
+            (">", format!("must be at most {bound}"))
+        };
+        let _ = write!(
+            checks,
+            "\n        if {value} {operator} {literal} {{\n            \
+             errors.push({path:?}, {message:?});\n        }}"
+        );
+    }
+
+    if let Some(pattern) = constraints.pattern {
+        let message = format!("must match the pattern `{pattern}`");
+        let check = format!(
+            r#"
+        static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let pattern =
+            PATTERN.get_or_init(|| regex::Regex::new({pattern:?}).expect("valid pattern"));
+        if !pattern.is_match({reference}) {{
+            errors.push({path:?}, {message:?});
+        }}"#
+        );
+        if optional {
+            // Scoped by the block of the optional value
+            checks.push_str(&check);
+        } else {
+            let check = check.replace('\n', "\n    ");
+            let _ = write!(checks, "\n        {{{check}\n        }}");
+        }
+    }
+
+    // The method calls of the value, e.g. `self.id.validate()` or `value.validate()`
+    let receiver = if optional { "value" } else { &value };
+    if let Schema::Decimal(DecimalSchema {
+        precision,
+        scale,
+        inner,
+    }) = schema
+    {
+        if templater.use_decimal_structs {
+            let size = match inner.as_ref() {
+                Schema::Fixed(FixedSchema { size, .. }) => format!("Some({size})"),
+                _ => "None".to_string(),
+            };
+            let message = format!("must fit the decimal precision {precision} and scale {scale}");
+            let try_new = format!(
+                "{DECIMAL_STRUCT}::try_new(decimal.mantissa, {scale}, {precision}, {size})"
+            );
+            let _ = write!(
+                checks,
+                "\n        let decimal = {receiver}.rescale({scale});\n        \
+                 if !decimal.is_some_and(|decimal| {try_new}.is_ok()) {{\n            \
+                 errors.push({path:?}, {message:?});\n        }}"
+            );
+        }
+    }
+
+    // The check of a value held by the field, directly or as an item of an array or a map
+    let item_check = |schema: &Schema, item: &str, item_path: &str| {
+        let schema = match schema {
+            Schema::Ref { name } => gen_state.get_schema(name)?,
+            schema => schema,
+        };
+        match schema {
+            Schema::Record(_) => Some(format!("errors.nest({item_path}, {item}.validate());")),
+            Schema::Enum(EnumSchema { name, .. }) if templater.string_enums => {
+                let type_name = gen_state.name_mapper().type_name(&name.name);
+                let message = format!("must be a symbol of `{type_name}`");
+                Some(format!(
+                    "if !{item}.is_known() {{\n    errors.push({item_path}, {message:?});\n}}"
+                ))
+            }
+            _ => None,
+        }
+    };
+    let indent = |check: String, level: &str| check.replace('\n', &format!("\n{level}"));
+    match schema {
+        Schema::Array(ArraySchema { items, .. }) => {
+            let item_path = format!("&format!(\"{path}.{{i}}\")");
+            if let Some(check) = item_check(items, "item", &item_path) {
+                let check = indent(check, "            ");
+                let _ = write!(
+                    checks,
+                    "\n        for (i, item) in {receiver}.iter().enumerate() {{\n            \
+                     {check}\n        }}"
+                );
+            }
+        }
+        Schema::Map(MapSchema { types, .. }) => {
+            let item_path = format!("&format!(\"{path}.{{key}}\")");
+            if let Some(check) = item_check(types, "item", &item_path) {
+                let check = indent(check, "            ");
+                let _ = write!(
+                    checks,
+                    "\n        for (key, item) in {reference} {{\n            {check}\n        }}"
+                );
+            }
+        }
+        schema => {
+            if let Some(check) = item_check(schema, receiver, &format!("{path:?}")) {
+                let _ = write!(checks, "\n        {}", indent(check, "        "));
+            }
+        }
+    }
+
+    if checks.is_empty() || !optional {
+        Ok(checks)
+    } else {
+        let checks = checks.replace('\n', "\n    ");
+        Ok(format!(
+            "\n        if let Some(value) = &self.{field_name} {{{checks}\n        }}"
+        ))
+    }
+}
+
+/// Returns the `#[...]` attributes of a record field for the `Validate` derive of `derive`,
+/// mapping the constraints of its attributes and validating the records it holds, and the
+/// code of the statics they reference, the `validator` crate matching `regex::Regex` statics.
+///
+/// `pattern_static` is the name of the static of a pattern, unique to the field.
+pub(crate) fn derive_attributes(
+    field: &RecordField,
+    schema: &Schema,
+    unsigned: bool,
+    derive: ValidateDerive,
+    pattern_static: &str,
+    gen_state: &GenState,
+) -> Result<(Vec<String>, String)> {
+    let constraints = constraints(field, schema, unsigned)?;
+    let mut attributes = vec![];
+    let mut statics = String::new();
+    match derive {
+        ValidateDerive::Validator => {
+            if !constraints.bounds.is_empty() {
+                let bounds: Vec<_> = constraints
+                    .bounds
+                    .iter()
+                    .map(|(attribute, _, literal)| {
+                        let key = if *attribute == MINIMUM_ATTRIBUTE {
+                            "min"
+                        } else {
+                            "max"
+                        };
+                        format!("{key} = {literal}")
+                    })
+                    .collect();
+                attributes.push(format!("validate(range({}))", bounds.join(", ")));
+            }
+            if let Some(pattern) = constraints.pattern {
+                attributes.push(format!("validate(regex(path = *{pattern_static}))"));
+                let _ = write!(
+                    statics,
+                    "\nstatic {pattern_static}: std::sync::LazyLock<regex::Regex> =\n    \
+                     std::sync::LazyLock::new(|| regex::Regex::new({pattern:?}).expect(\"valid pattern\"));\n"
+                );
+            }
+            if holds_records(schema, gen_state) {
+                attributes.push("validate(nested)".to_string());
+            }
+        }
+        ValidateDerive::SerdeValid => {
+            for (attribute, _, literal) in &constraints.bounds {
+                attributes.push(format!("validate({attribute} = {literal})"));
+            }
+            if let Some(pattern) = constraints.pattern {
+                attributes.push(format!("validate(pattern = {pattern:?})"));
+            }
+            if holds_records(schema, gen_state) {
+                attributes.push("validate".to_string());
+            }
+        }
+    }
+    Ok((attributes, statics))
+}
+
+/// The constraints of the custom attributes of a record field, checked against its schema.
+struct Constraints<'a> {
+    /// The `minimum` and `maximum` attributes, their values and the Rust literals of their
+    /// values, without the bounds of the field type which need no check.
+    bounds: Vec<(&'static str, &'a Value, String)>,
+    /// The regular expression of the `pattern` attribute.
+    pattern: Option<&'a str>,
+}
+
+/// Returns the constraints of the attributes of a record field, failing if they do not apply
+/// to its resolved `schema`, `u32` or `u64` if `unsigned`.
+fn constraints<'a>(
+    field: &'a RecordField,
+    schema: &Schema,
+    unsigned: bool,
+) -> Result<Constraints<'a>> {
+    let invalid = |attribute: &str, message: String| Error::Generation {
+        kind: ErrorKind::Attribute,
+        message: format!(
+            "Invalid '{attribute}' attribute of field '{}', {message}",
+            field.name
+        ),
+    };
+    let mut bounds = vec![];
+    for attribute in [MINIMUM_ATTRIBUTE, MAXIMUM_ATTRIBUTE] {
+        let Some(bound) = field.custom_attributes.get(attribute) else {
+            continue;
+        };
+        if !matches!(
+            schema,
+            Schema::Int | Schema::Long | Schema::Float | Schema::Double
+        ) {
+            let expected = "expected an int, long, float or double field";
+            return Err(invalid(attribute, expected.to_string()));
+        }
+        let Some(literal) = (match bound {
+            Value::Number(number) => bound_literal(schema, unsigned, attribute, number),
+            _ => None,
+        }) else {
+            let expected = "expected a number in the range of the field type";
+            return Err(invalid(attribute, format!("{expected}, found {bound}")));
+        };
+        if let Some(literal) = literal {
+            bounds.push((attribute, bound, literal));
+        }
+    }
+
+    let pattern = match field.custom_attributes.get(PATTERN_ATTRIBUTE) {
+        Some(_) if *schema != Schema::String => {
+            let expected = "expected a string field";
+            return Err(invalid(PATTERN_ATTRIBUTE, expected.to_string()));
+        }
+        Some(Value::String(pattern)) if is_regex(pattern) => Some(pattern.as_str()),
+        Some(pattern) => {
+            let expected = "expected a regular expression";
+            return Err(invalid(
+                PATTERN_ATTRIBUTE,
+                format!("{expected}, found {pattern}"),
+            ));
+        }
+        None => None,
+    };
+    Ok(Constraints { bounds, pattern })
+}
+
+/// Returns whether a `pattern` attribute is a valid regular expression, with the
+/// `pattern-checks` feature.
+#[cfg(feature = "pattern-checks")]
+fn is_regex(pattern: &str) -> bool {
+    regex::Regex::new(pattern).is_ok()
+}
+
+/// Accepts any `pattern` attribute, without the `pattern-checks` feature.
+#[cfg(not(feature = "pattern-checks"))]
+fn is_regex(_pattern: &str) -> bool {
+    true
+}
+
+/// Returns whether a resolved field `schema` holds records, directly or as the items of an
+/// array or a map.
+fn holds_records(schema: &Schema, gen_state: &GenState) -> bool {
+    let is_record = |schema: &Schema| match schema {
+        Schema::Ref { name } => matches!(gen_state.get_schema(name), Some(Schema::Record(_))),
+        schema => matches!(schema, Schema::Record(_)),
+    };
+    match schema {
+        Schema::Array(ArraySchema { items, .. }) => is_record(items),
+        Schema::Map(MapSchema { types, .. }) => is_record(types),
+        schema => is_record(schema),
+    }
+}
+
+/// Returns the Rust literal of the `minimum` or `maximum` of a number field, `None` if it is
+/// out of the range of the field type, and `Some(None)` if it is the bound of the type, which
+/// needs no check.
+fn bound_literal(
+    schema: &Schema,
+    unsigned: bool,
+    attribute: &str,
+    number: &Number,
+) -> Option<Option<String>> {
+    let (min, max) = match (schema, unsigned) {
+        (Schema::Int, false) => (i32::MIN as i128, i32::MAX as i128),
+        (Schema::Int, true) => (0, u32::MAX as i128),
+        (Schema::Long, false) => (i64::MIN as i128, i64::MAX as i128),
+        (Schema::Long, true) => (0, u64::MAX as i128),
+        (Schema::Float, _) => {
+            let bound = number.as_f64()?;
+            return (bound.abs() <= f32::MAX as f64).then(|| Some(format!("{bound:?}")));
+        }
+        _ => return Some(Some(format!("{:?}", number.as_f64()?))),
+    };
+    let bound = number
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| number.as_u64().map(i128::from))?;
+    if bound < min || bound > max {
+        return None;
+    }
+    let type_bound = if attribute == MINIMUM_ATTRIBUTE {
+        min
+    } else {
+        max
+    };
+    Some((bound != type_bound).then(|| bound.to_string()))
+}
@@ -0,0 +1,48 @@
+//! Verification of generated code with the `verify` feature, see
+//! [`GeneratorBuilder::verify`](crate::GeneratorBuilder::verify).
+
+#[cfg(feature = "verify")]
+use crate::error::Error;
+use crate::error::Result;
+use crate::gen::GeneratedItem;
+
+/// The number of lines shown before and after the offending line of invalid code.
+#[cfg(feature = "verify")]
+const CONTEXT_LINES: usize = 2;
+
+/// Checks that the code of a generated item parses as Rust items.
+#[cfg(feature = "verify")]
+pub(crate) fn verify(item: &GeneratedItem) -> Result<()> {
+    let Err(e) = syn::parse_file(&item.code) else {
+        return Ok(());
+    };
+
+    // Line numbers are 1-based, and only known with the `span-locations` feature of proc-macro2
+    let line = e.span().start().line.max(1);
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let snippet = item
+        .code
+        .lines()
+        .enumerate()
+        .skip(first - 1)
+        .take(line + CONTEXT_LINES + 1 - first)
+        .map(|(i, code)| {
+            let marker = if i + 1 == line { '>' } else { ' ' };
+            format!("{marker} {:>4} | {code}", i + 1)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Err(Error::InvalidCode {
+        item: item.name.clone(),
+        reason: e.to_string(),
+        snippet,
+    })
+}
+
+/// Never called without the `verify` feature, generators then failing to build with
+/// [`verify`](crate::GeneratorBuilder::verify).
+#[cfg(not(feature = "verify"))]
+pub(crate) fn verify(_item: &GeneratedItem) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,472 @@
+//! Conversions between the Rust types generated from successive versions of Avro schemas,
+//! see [`Generator::gen_versions`](crate::Generator::gen_versions).
+//!
+//! Conversions follow the Avro
+//! [schema resolution](https://avro.apache.org/docs/1.11.1/specification/#schema-resolution)
+//! rules, with the old version as writer and the new version as reader.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use apache_avro::schema::{ArraySchema, EnumSchema, MapSchema, Name, RecordSchema};
+use apache_avro::Schema;
+
+use crate::error::{Error, Result};
+use crate::templates::{symbol_constant, union_enum_variant, union_type, GenState, Templater};
+
+/// A version of the generated types.
+pub(crate) struct Version<'a> {
+    /// The name of the module of the types, e.g. `v1`.
+    pub(crate) module: &'a str,
+    /// The schemas the types were generated from.
+    pub(crate) schemas: &'a [Schema],
+    pub(crate) gen_state: &'a GenState,
+}
+
+/// Returns `From` impls converting the records and enums of the old version into the ones of
+/// the new version with the same fullname, or a fullname among their aliases, meant to be
+/// written in the module of the new version.
+pub(crate) fn conversions(templater: &Templater, old: &Version, new: &Version) -> Result<String> {
+    let old_schemas =
+        old.schemas
+            .iter()
+            .filter_map(|s| match s {
+                Schema::Record(RecordSchema { name, .. })
+                | Schema::Enum(EnumSchema { name, .. }) => Some((name.fullname(None), s)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+    let converter = Converter {
+        templater,
+        old,
+        new,
+        lossy: RefCell::new(vec![]),
+    };
+    let mut code = String::new();
+    for schema in new.schemas.iter().rev() {
+        let (name, aliases) = match schema {
+            Schema::Record(RecordSchema { name, aliases, .. })
+            | Schema::Enum(EnumSchema { name, aliases, .. }) => (name, aliases),
+            _ => continue,
+        };
+        let old_schema = std::iter::once(name.fullname(None))
+            .chain(
+                aliases
+                    .iter()
+                    .flatten()
+                    .map(|alias| alias.fullname(name.namespace.clone())),
+            )
+            .find_map(|name| old_schemas.get(&name));
+        match (old_schema, schema) {
+            (Some(Schema::Record(w)), Schema::Record(r)) => {
+                code.push_str(&converter.record_from(w, r)?)
+            }
+            (Some(Schema::Enum(w)), Schema::Enum(r)) => code.push_str(&converter.enum_from(w, r)?),
+            (Some(_), _) => {
+                return Err(Error::Schema(format!(
+                    "`{}` of {} and {} are not of the same type",
+                    name.fullname(None),
+                    old.module,
+                    new.module
+                )))
+            }
+            (None, _) => (),
+        }
+    }
+    Ok(code)
+}
+
+struct Converter<'a> {
+    templater: &'a Templater,
+    old: &'a Version<'a>,
+    new: &'a Version<'a>,
+    /// The paths of the bytes converted into strings by the impl being generated.
+    lossy: RefCell<Vec<String>>,
+}
+
+impl Converter<'_> {
+    fn old_type(&self, name: &Name) -> String {
+        let type_name = self.old.gen_state.name_mapper().type_name(&name.name);
+        format!("super::{}::{type_name}", self.old.module)
+    }
+
+    fn new_type(&self, name: &Name) -> String {
+        self.new.gen_state.name_mapper().type_name(&name.name)
+    }
+
+    fn record_from(&self, w: &RecordSchema, r: &RecordSchema) -> Result<String> {
+        let (old_mapper, new_mapper) = (
+            self.old.gen_state.name_mapper(),
+            self.new.gen_state.name_mapper(),
+        );
+        let record = r.name.name.as_str();
+
+        let mut fields = vec![];
+        let mut uses_writer = false;
+        for field in &r.fields {
+            let aliases = field.aliases.iter().flatten();
+            let writer_field = w
+                .fields
+                .iter()
+                .find(|f| f.name == field.name || aliases.clone().any(|alias| *alias == f.name));
+            let value = match (writer_field, &field.default) {
+                (Some(writer_field), _) => {
+                    uses_writer = true;
+                    self.convert(
+                        &format!("v.{}", old_mapper.field_name(&writer_field.name)),
+                        &writer_field.schema,
+                        &field.schema,
+                        &format!("{record}.{}", field.name),
+                    )?
+                }
+                (None, Some(default)) => {
+                    self.templater
+                        .parse_default(&field.schema, self.new.gen_state, default)?
+                }
+                (None, None) => {
+                    return Err(Error::Schema(format!(
+                        "Field `{record}.{}` of {} has no default and is missing from {}",
+                        field.name, self.new.module, self.old.module
+                    )))
+                }
+            };
+            fields.push(format!(
+                "            {}: {value},\n",
+                new_mapper.field_name(&field.name)
+            ));
+        }
+
+        let old_type = self.old_type(&w.name);
+        let arg = if uses_writer { "v" } else { "_" };
+        let lossy = self.lossy.take();
+        let doc = match lossy.is_empty() {
+            true => String::new(),
+            false => format!(
+                "\n/// Bytes which are not valid UTF-8 are converted lossily, invalid sequences \
+                 being replaced with `U+FFFD`: `{}`.",
+                lossy.join("`, `")
+            ),
+        };
+        Ok(format!(
+            "{doc}\nimpl From<{old_type}> for {name} {{\n    \
+             fn from({arg}: {old_type}) -> Self {{\n        \
+             {name} {{\n{fields}        }}\n    }}\n}}\n",
+            name = self.new_type(&r.name),
+            fields = fields.concat(),
+        ))
+    }
+
+    fn enum_from(&self, w: &EnumSchema, r: &EnumSchema) -> Result<String> {
+        let (old_mapper, new_mapper) = (
+            self.old.gen_state.name_mapper(),
+            self.new.gen_state.name_mapper(),
+        );
+        let (old_type, new_type) = (self.old_type(&w.name), self.new_type(&r.name));
+        if self.templater.string_enums {
+            return Ok(string_enum_from(&old_type, &new_type, w, r));
+        }
+
+        let mut arms = String::new();
+        for symbol in &w.symbols {
+            let reader_symbol = match &r.default {
+                _ if r.symbols.contains(symbol) => symbol,
+                Some(default) => default,
+                None => {
+                    return Err(Error::Schema(format!(
+                        "Symbol `{symbol}` of `{}` is missing from {} which has no default",
+                        r.name.name, self.new.module
+                    )))
+                }
+            };
+            arms.push_str(&format!(
+                "            {old_type}::{} => {new_type}::{},\n",
+                old_mapper.variant_name(symbol),
+                new_mapper.variant_name(reader_symbol)
+            ));
+        }
+        Ok(format!(
+            "\nimpl From<{old_type}> for {new_type} {{\n    \
+             fn from(v: {old_type}) -> Self {{\n        \
+             match v {{\n{arms}        }}\n    }}\n}}\n"
+        ))
+    }
+
+    /// Returns the expression converting `expr`, a value of the writer schema, into a value
+    /// of the reader schema, `path` locating the value for errors.
+    fn convert(&self, expr: &str, writer: &Schema, reader: &Schema, path: &str) -> Result<String> {
+        let writer = resolve(writer, self.old.gen_state);
+        let reader = resolve(reader, self.new.gen_state);
+        let fail = || {
+            Err(Error::Schema(format!(
+                "`{path}` of {} cannot be converted from {}",
+                self.new.module, self.old.module
+            )))
+        };
+
+        if writer == reader && is_shared(writer) {
+            return Ok(expr.to_string());
+        }
+        if matches!(writer, Schema::Union(_)) || matches!(reader, Schema::Union(_)) {
+            return self.convert_union(expr, writer, reader, path);
+        }
+
+        let converted = match (writer, reader) {
+            (
+                Schema::Record(RecordSchema { name: w, .. }),
+                Schema::Record(RecordSchema { name: r, .. }),
+            )
+            | (
+                Schema::Enum(EnumSchema { name: w, .. }),
+                Schema::Enum(EnumSchema { name: r, .. }),
+            ) if same_name(w, reader) || w.fullname(None) == r.fullname(None) => {
+                format!("{expr}.into()")
+            }
+            (Schema::Record(_) | Schema::Enum(_), _) => return fail(),
+
+            (
+                Schema::Array(ArraySchema { items: w, .. }),
+                Schema::Array(ArraySchema { items: r, .. }),
+            ) => match self.convert("v", w, r, &format!("{path}[]"))?.as_str() {
+                "v" => expr.to_string(),
+                item => format!("{expr}.into_iter().map(|v| {item}).collect()"),
+            },
+            (Schema::Map(MapSchema { types: w, .. }), Schema::Map(MapSchema { types: r, .. })) => {
+                match self.convert("v", w, r, &format!("{path}{{}}"))?.as_str() {
+                    "v" => expr.to_string(),
+                    value => format!("{expr}.into_iter().map(|(k, v)| (k, {value})).collect()"),
+                }
+            }
+
+            (Schema::Int, Schema::Long) => format!("i64::from({expr})"),
+            (Schema::Int, Schema::Float) => format!("{expr} as f32"),
+            (Schema::Int, Schema::Double) => format!("f64::from({expr})"),
+            (Schema::Long, Schema::Float) => format!("{expr} as f32"),
+            (Schema::Long, Schema::Double) => format!("{expr} as f64"),
+            (Schema::Float, Schema::Double) => format!("f64::from({expr})"),
+            (Schema::String, Schema::Bytes) => format!("{expr}.into_bytes()"),
+            (Schema::Bytes, Schema::String) => {
+                self.lossy.borrow_mut().push(path.to_string());
+                format!("String::from_utf8_lossy(&{expr}).into_owned()")
+            }
+
+            _ => return fail(),
+        };
+        Ok(converted)
+    }
+
+    /// Converts values when the writer or reader schema is a union, each writer variant being
+    /// converted into the first reader variant of the same kind, or else the first one which
+    /// can read it.
+    fn convert_union(
+        &self,
+        expr: &str,
+        writer: &Schema,
+        reader: &Schema,
+        path: &str,
+    ) -> Result<String> {
+        let writer_variants = variants(writer, self.old.gen_state, Some(self.old.module))?;
+        let reader_variants = variants(reader, self.new.gen_state, None)?;
+
+        let mut arms = vec![];
+        for writer_variant in &writer_variants {
+            // Values of writer schemas which are not unions are not matched
+            let binding = if writer_variant.prefix.is_empty() {
+                expr
+            } else {
+                "v"
+            };
+            let exact = reader_variants
+                .iter()
+                .find(|r| same_kind(writer_variant.schema, r.schema));
+            let value = exact.into_iter().chain(&reader_variants).find_map(|r| {
+                match (writer_variant.schema, r.schema) {
+                    (Schema::Null, Schema::Null) => Some(r.prefix.to_string()),
+                    (Schema::Null, _) | (_, Schema::Null) => None,
+                    (w, schema) => self
+                        .convert(binding, w, schema, path)
+                        .ok()
+                        .map(|value| format!("{}{value}{}", r.prefix, r.suffix)),
+                }
+            });
+            match value {
+                Some(value) => arms.push((writer_variant.pattern(), value)),
+                None => {
+                    return Err(Error::Schema(format!(
+                        "`{path}` of {} cannot be converted from {}",
+                        self.new.module, self.old.module
+                    )))
+                }
+            }
+        }
+
+        match arms.as_slice() {
+            [(None, value)] => Ok(value.clone()),
+            _ => Ok(format!(
+                "match {expr} {{ {} }}",
+                arms.iter()
+                    .map(|(pattern, value)| format!(
+                        "{} => {value}",
+                        pattern.as_deref().unwrap_or("v")
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+}
+
+/// A variant of a union, or the whole value of another schema, with the code wrapping a
+/// value of the variant into a value of the union.
+struct Variant<'a> {
+    prefix: String,
+    suffix: &'static str,
+    schema: &'a Schema,
+}
+
+impl Variant<'_> {
+    /// Returns the pattern matching the variant and binding its value to `v`, if its schema
+    /// is a union.
+    fn pattern(&self) -> Option<String> {
+        match self.schema {
+            _ if self.prefix.is_empty() => None,
+            Schema::Null => Some(self.prefix.clone()),
+            _ => Some(format!("{}v{}", self.prefix, self.suffix)),
+        }
+    }
+}
+
+/// Returns the variants of a schema, whose types are in `module` for the old version, and a
+/// single variant for schemas which are not unions.
+fn variants<'a>(
+    schema: &'a Schema,
+    gen_state: &'a GenState,
+    module: Option<&str>,
+) -> Result<Vec<Variant<'a>>> {
+    let Schema::Union(union) = schema else {
+        return Ok(vec![Variant {
+            prefix: String::new(),
+            suffix: "",
+            schema,
+        }]);
+    };
+    let none = |schema| Variant {
+        prefix: "None".to_string(),
+        suffix: "",
+        schema,
+    };
+
+    let union_variants = union.variants();
+    if union.is_nullable() && union_variants.len() == 2 {
+        return Ok(union_variants
+            .iter()
+            .map(|variant| match variant {
+                Schema::Null => none(variant),
+                _ => Variant {
+                    prefix: "Some(".to_string(),
+                    suffix: ")",
+                    schema: resolve(variant, gen_state),
+                },
+            })
+            .collect());
+    }
+
+    let mut type_name = union_type(union, gen_state, false)?;
+    if let Some(module) = module {
+        type_name = format!("super::{module}::{type_name}");
+    }
+    let optional = union_variants.first() == Some(&Schema::Null);
+    let mut variants = vec![];
+    for variant in union_variants {
+        if *variant == Schema::Null {
+            variants.push(none(variant));
+            continue;
+        }
+        let variant_name = union_enum_variant(variant, gen_state)?;
+        let (prefix, suffix) = match optional {
+            true => (format!("Some({type_name}::{variant_name}("), "))"),
+            false => (format!("{type_name}::{variant_name}("), ")"),
+        };
+        variants.push(Variant {
+            prefix,
+            suffix,
+            schema: resolve(variant, gen_state),
+        });
+    }
+    Ok(variants)
+}
+
+/// Returns the `From` impl converting string enums: symbols missing from the reader schema
+/// become its default, other symbols, including unknown ones, are kept.
+fn string_enum_from(old_type: &str, new_type: &str, w: &EnumSchema, r: &EnumSchema) -> String {
+    let body = match &r.default {
+        Some(default) if w.symbols.iter().any(|s| !r.symbols.contains(s)) => {
+            let mut arms = String::new();
+            for symbol in w.symbols.iter().filter(|s| !r.symbols.contains(s)) {
+                arms.push_str(&format!(
+                    "            {old_type}::{} => {new_type}::from({new_type}::{}),\n",
+                    symbol_constant(symbol),
+                    symbol_constant(default)
+                ));
+            }
+            format!("match v.0.as_str() {{\n{arms}            _ => {new_type}(v.0),\n        }}")
+        }
+        _ => format!("{new_type}(v.0)"),
+    };
+    format!(
+        "\nimpl From<{old_type}> for {new_type} {{\n    \
+         fn from(v: {old_type}) -> Self {{\n        {body}\n    }}\n}}\n"
+    )
+}
+
+/// Returns whether the Rust type of a schema is the same in all versions, i.e. it is not or
+/// does not contain a generated record, enum or union type. Fixed are type aliases, identical
+/// if their names and sizes match.
+fn is_shared(schema: &Schema) -> bool {
+    match schema {
+        Schema::Record(_) | Schema::Enum(_) | Schema::Ref { .. } => false,
+        Schema::Array(ArraySchema { items: inner, .. })
+        | Schema::Map(MapSchema { types: inner, .. }) => is_shared(inner),
+        Schema::Union(union) => {
+            let variants = union.variants();
+            union.is_nullable() && variants.len() == 2 && variants.iter().all(is_shared)
+        }
+        _ => true,
+    }
+}
+
+/// Returns the definition of a named schema reference.
+fn resolve<'a>(schema: &'a Schema, gen_state: &'a GenState) -> &'a Schema {
+    match schema {
+        Schema::Ref { name } => gen_state.get_schema(name).unwrap_or(schema),
+        _ => schema,
+    }
+}
+
+/// Returns whether the fullname of a writer schema is one of the aliases of a reader schema.
+fn same_name(writer: &Name, reader: &Schema) -> bool {
+    let (name, aliases) = match reader {
+        Schema::Record(RecordSchema { name, aliases, .. })
+        | Schema::Enum(EnumSchema { name, aliases, .. }) => (name, aliases),
+        _ => return false,
+    };
+    aliases
+        .iter()
+        .flatten()
+        .any(|alias| alias.fullname(name.namespace.clone()) == writer.fullname(None))
+}
+
+/// Returns whether two union variants are of the same kind, which is preferred over
+/// promotions when picking the reader variant of a writer variant.
+fn same_kind(writer: &Schema, reader: &Schema) -> bool {
+    match (writer, reader) {
+        (
+            Schema::Record(RecordSchema { name: w, .. }),
+            Schema::Record(RecordSchema { name: r, .. }),
+        )
+        | (Schema::Enum(EnumSchema { name: w, .. }), Schema::Enum(EnumSchema { name: r, .. })) => {
+            w.fullname(None) == r.fullname(None) || same_name(w, reader)
+        }
+        _ => std::mem::discriminant(writer) == std::mem::discriminant(reader),
+    }
+}
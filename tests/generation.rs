@@ -2,7 +2,9 @@
 mod schemas;
 
 use pretty_assertions::assert_eq;
-use rsgen_avro::{Generator, Source};
+use rsgen_avro::{
+    DeserializationMode, Generator, ItemKind, OptionalFields, Source, UnionJson, ValidateDerive,
+};
 
 fn validate_generation(file_name: &str, g: Generator) {
     let schema = format!("tests/schemas/{file_name}.avsc");
@@ -81,13 +83,32 @@ fn gen_multi_valued_union_map() {
 
 #[test]
 fn gen_multi_valued_union_nested() {
-    let schemas = format!("tests/schemas/multi_valued_union_nested_*.avsc");
-    let src = Source::GlobPattern(&schemas);
+    let src = Source::GlobPattern("tests/schemas/multi_valued_union_nested_*.avsc");
     let mut buf = vec![];
     Generator::new().unwrap().gen(&src, &mut buf).unwrap();
     let generated = String::from_utf8(buf).unwrap();
-    let expected =
-        std::fs::read_to_string(format!("tests/schemas/multi_valued_union_nested.rs")).unwrap();
+    let expected = std::fs::read_to_string("tests/schemas/multi_valued_union_nested.rs").unwrap();
+    validate(expected, generated)
+}
+
+#[test]
+fn gen_protocol() {
+    let src = Source::GlobPattern("tests/schemas/protocol.avpr");
+    let mut buf = vec![];
+    Generator::new().unwrap().gen(&src, &mut buf).unwrap();
+    let generated = String::from_utf8(buf).unwrap();
+    let expected = std::fs::read_to_string("tests/schemas/protocol.rs").unwrap();
+    validate(expected, generated)
+}
+
+#[test]
+fn gen_protocol_no_debug() {
+    let src = Source::GlobPattern("tests/schemas/protocol_no_debug.avpr");
+    let mut buf = vec![];
+    let g = Generator::builder().debug_impls(false).build().unwrap();
+    g.gen(&src, &mut buf).unwrap();
+    let generated = String::from_utf8(buf).unwrap();
+    let expected = std::fs::read_to_string("tests/schemas/protocol_no_debug.rs").unwrap();
     validate(expected, generated)
 }
 
@@ -135,6 +156,48 @@ fn gen_nullable_logical_dates() {
     );
 }
 
+#[test]
+fn gen_rfc3339_timestamps() {
+    validate_generation(
+        "rfc3339_timestamps",
+        Generator::builder()
+            .use_chrono_dates(true)
+            .rfc3339_timestamps(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_decimal_structs() {
+    validate_generation(
+        "decimal_structs",
+        Generator::builder()
+            .use_decimal_structs(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_decimal_structs_collections() {
+    // Decimals in arrays and maps cannot be deserialized with the scale of their schema
+    let g = Generator::builder()
+        .use_decimal_structs(true)
+        .build()
+        .unwrap();
+    let err = g
+        .gen(
+            &Source::GlobPattern("tests/schemas/decimal_structs_collections.avsc"),
+            &mut vec![],
+        )
+        .unwrap_err();
+    assert_eq!(
+        "Configuration error: `use_decimal_structs` does not support decimals in arrays and maps, found in shop.Basket.prices",
+        err.to_string()
+    );
+}
+
 #[test]
 fn gen_decimals() {
     validate_generation("decimals", Generator::builder().build().unwrap());
@@ -148,6 +211,25 @@ fn gen_logical_dates() {
     );
 }
 
+#[test]
+fn gen_logical_defaults() {
+    validate_generation(
+        "logical_defaults",
+        Generator::builder().use_chrono_dates(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_optional_defaults() {
+    validate_generation(
+        "optional_defaults",
+        Generator::builder()
+            .optional_fields(OptionalFields::Defaults)
+            .build()
+            .unwrap(),
+    );
+}
+
 #[test]
 fn gen_record() {
     validate_generation("record", Generator::new().unwrap());
@@ -183,6 +265,22 @@ fn gen_map_multiple_def() {
     validate_generation("map_multiple_def", Generator::new().unwrap());
 }
 
+#[test]
+fn gen_nullable_array() {
+    validate_generation(
+        "nullable_array",
+        Generator::builder()
+            .use_avro_rs_unions(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_nullable_map() {
+    validate_generation("nullable_map", Generator::new().unwrap());
+}
+
 #[test]
 fn gen_enums() {
     validate_generation("enums", Generator::new().unwrap());
@@ -213,7 +311,329 @@ fn gen_recursive() {
     validate_generation("recursive", Generator::new().unwrap());
 }
 
+#[test]
+fn gen_proptest_arbitrary() {
+    validate_generation(
+        "proptest_arbitrary",
+        Generator::builder()
+            .proptest_arbitrary(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_quickcheck_arbitrary() {
+    validate_generation(
+        "quickcheck_arbitrary",
+        Generator::builder()
+            .quickcheck_arbitrary(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_round_trip_tests() {
+    validate_generation(
+        "round_trip_tests",
+        Generator::builder().round_trip_tests(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_avro_schema_impls() {
+    validate_generation(
+        "avro_schema_impls",
+        Generator::builder()
+            .avro_schema_impls(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_avro_message_impls() {
+    validate_generation(
+        "avro_message_impls",
+        Generator::builder()
+            .avro_message_impls(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_empty_defaults() {
+    validate_generation(
+        "empty_defaults",
+        Generator::builder().empty_defaults(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_string_enums() {
+    validate_generation(
+        "string_enums",
+        Generator::builder().string_enums(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_try_from_bytes() {
+    validate_generation(
+        "try_from_bytes",
+        Generator::builder().try_from_bytes(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_avro_json() {
+    let mut buf = vec![];
+    Generator::new().unwrap().gen_avro_json(&mut buf).unwrap();
+    let expected = std::fs::read_to_string("tests/schemas/avro_json.rs").unwrap();
+    validate(expected, String::from_utf8(buf).unwrap())
+}
+
+#[test]
+fn gen_union_json() {
+    validate_generation(
+        "union_json",
+        Generator::builder()
+            .union_json(UnionJson::Avro)
+            .use_avro_rs_unions(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_writer_schema_decoders() {
+    validate_generation(
+        "writer_schema_decoders",
+        Generator::builder()
+            .writer_schema_decoders(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_schema_constants() {
+    validate_generation(
+        "schema_constants",
+        Generator::builder().schema_constants(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_lenient() {
+    validate_generation(
+        "lenient",
+        Generator::builder()
+            .deserialization(DeserializationMode::Lenient)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_strict() {
+    validate_generation(
+        "strict",
+        Generator::builder()
+            .deserialization(DeserializationMode::Strict)
+            .build()
+            .unwrap(),
+    );
+}
+
 #[test]
 fn gen_interop() {
     validate_generation("interop", Generator::new().unwrap());
 }
+
+#[test]
+fn gen_union_dedup() {
+    validate_generation("union_dedup", Generator::new().unwrap());
+}
+
+#[test]
+fn gen_names_sanitize() {
+    validate_generation("names_sanitize", Generator::new().unwrap());
+}
+
+#[test]
+fn gen_symbol_aliases() {
+    validate_generation("symbol_aliases", Generator::new().unwrap());
+}
+
+#[test]
+fn gen_symbol_docs() {
+    validate_generation("symbol_docs", Generator::new().unwrap());
+}
+
+#[test]
+fn gen_sensitive() {
+    validate_generation("sensitive", Generator::new().unwrap());
+}
+
+#[test]
+fn gen_union_collection_defaults() {
+    validate_generation("union_collection_defaults", Generator::new().unwrap());
+}
+
+#[test]
+fn gen_boxed() {
+    validate_generation(
+        "boxed",
+        Generator::builder().box_threshold(64).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_union_names() {
+    validate_generation(
+        "union_names",
+        Generator::builder()
+            .union_names("{Record}{Field}Union")
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_hex_debug() {
+    validate_generation(
+        "hex_debug",
+        Generator::builder().hex_debug(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_item_lints() {
+    validate_generation(
+        "item_lints",
+        Generator::builder()
+            .item_lints(ItemKind::Record, "allow(clippy::struct_excessive_bools)")
+            .item_lints(ItemKind::Enum, "deny(clippy::enum_variant_names)")
+            .item_lints(ItemKind::Fixed, "allow(non_camel_case_types)")
+            .item_lints(ItemKind::Union, "allow(clippy::large_enum_variant)")
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_wrapper_conversions() {
+    validate_generation(
+        "wrapper_conversions",
+        Generator::builder()
+            .wrapper_conversions(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_alias_types() {
+    validate_generation(
+        "alias_types",
+        Generator::builder().alias_types(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_wrap_module() {
+    validate_generation(
+        "wrap_module",
+        Generator::builder()
+            .wrap_module("avro_gen")
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_unsigned() {
+    validate_generation("unsigned", Generator::new().unwrap());
+}
+
+#[test]
+fn gen_validators() {
+    validate_generation(
+        "validators",
+        Generator::builder().validators(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_structural_validators() {
+    validate_generation(
+        "structural_validators",
+        Generator::builder()
+            .validators(true)
+            .string_enums(true)
+            .use_decimal_structs(true)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_validate_derive_validator() {
+    validate_generation(
+        "validate_derive_validator",
+        Generator::builder()
+            .validate_derive(ValidateDerive::Validator)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_validate_derive_serde_valid() {
+    validate_generation(
+        "validate_derive_serde_valid",
+        Generator::builder()
+            .validate_derive(ValidateDerive::SerdeValid)
+            .build()
+            .unwrap(),
+    );
+}
+
+#[test]
+fn gen_zeroize() {
+    validate_generation(
+        "zeroize",
+        Generator::builder().zeroize(true).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_smallvec() {
+    validate_generation(
+        "smallvec",
+        Generator::builder().smallvec_arrays(2).build().unwrap(),
+    );
+}
+
+#[test]
+fn gen_avro_crate() {
+    validate_generation(
+        "avro_crate",
+        Generator::builder()
+            .avro_crate("rsgen_avro::apache_avro")
+            .build()
+            .unwrap(),
+    );
+}
+
+#[cfg(feature = "snapshots")]
+#[test]
+fn gen_snapshots() {
+    Generator::new().unwrap().assert_matches_snapshot(
+        &Source::GlobPattern("tests/schemas/interop.avsc"),
+        "tests/snapshots/interop",
+    );
+}
@@ -0,0 +1,37 @@
+#[rustfmt::skip]
+mod schemas;
+
+use apache_avro::types::Value;
+use chrono::DateTime;
+
+use crate::schemas::rfc3339_timestamps::Event;
+
+#[test]
+fn rfc3339_timestamps() {
+    let event = Event {
+        started_at: DateTime::from_timestamp_millis(1500).unwrap(),
+        ended_at: DateTime::from_timestamp_micros(7),
+    };
+    let json =
+        r#"{"started_at":"1970-01-01T00:00:01.500Z","ended_at":"1970-01-01T00:00:00.000007Z"}"#;
+    assert_eq!(json, serde_json::to_string(&event).unwrap());
+    assert_eq!(event, serde_json::from_str(json).unwrap());
+
+    // Process-wide, hence this separate test crate
+    apache_avro::set_serde_human_readable(false);
+    let schema =
+        apache_avro::Schema::parse_str(include_str!("schemas/rfc3339_timestamps.avsc")).unwrap();
+    let value = apache_avro::to_value(&event)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    let expected = Value::Record(vec![
+        ("started_at".into(), Value::TimestampMillis(1500)),
+        (
+            "ended_at".into(),
+            Value::Union(1, Box::new(Value::TimestampMicros(7))),
+        ),
+    ]);
+    assert_eq!(expected, value);
+    assert_eq!(event, apache_avro::from_value(&value).unwrap());
+}
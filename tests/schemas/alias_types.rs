@@ -0,0 +1,26 @@
+
+pub type Token = [u8; 4];
+
+/// The former name of [`Token`], an alias of its schema.
+pub type Secret = Token;
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Tier {
+    #[serde(rename = "GOLD")]
+    Gold,
+    #[serde(rename = "SILVER")]
+    Silver,
+}
+
+/// The former name of [`Tier`], an alias of its schema.
+pub type Level = Tier;
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Client {
+    pub tier: Tier,
+    #[serde(with = "apache_avro::serde_avro_fixed")]
+    pub token: Token,
+}
+
+/// The former name of [`Client`], an alias of its schema.
+pub type Customer = Client;
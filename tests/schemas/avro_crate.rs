@@ -0,0 +1,134 @@
+
+/// Auto-generated type for unnamed Avro union variants.
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Self")]
+pub enum UnionBytesLong {
+    Bytes(#[serde(with = "rsgen_avro::apache_avro::serde_avro_bytes")] Vec<u8>),
+    Long(i64),
+}
+
+impl From<Vec<u8>> for UnionBytesLong {
+    fn from(v: Vec<u8>) -> Self {
+        Self::Bytes(v)
+    }
+}
+
+impl TryFrom<UnionBytesLong> for Vec<u8> {
+    type Error = UnionBytesLong;
+
+    fn try_from(v: UnionBytesLong) -> Result<Self, Self::Error> {
+        if let UnionBytesLong::Bytes(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl From<i64> for UnionBytesLong {
+    fn from(v: i64) -> Self {
+        Self::Long(v)
+    }
+}
+
+impl TryFrom<UnionBytesLong> for i64 {
+    type Error = UnionBytesLong;
+
+    fn try_from(v: UnionBytesLong) -> Result<Self, Self::Error> {
+        if let UnionBytesLong::Long(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl serde::Serialize for UnionBytesLong {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct NewtypeVariantSerializer<S>(S);
+
+        impl<S> serde::Serializer for NewtypeVariantSerializer<S>
+        where
+            S: serde::Serializer,
+        {
+            type Ok = S::Ok;
+            type Error = S::Error;
+            type SerializeSeq = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTuple = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeMap = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStructVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_none(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_some<T: ?Sized + serde::Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error>{ unimplemented!() }
+            fn serialize_unit(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_variant(self ,_name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(self, _name: &'static str, _value: &T,) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_seq(self,_len: Option<usize>,) -> Result<Self::SerializeSeq, Self::Error> { unimplemented!() }
+            fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> { unimplemented!() }
+            fn serialize_tuple_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> { unimplemented!() }
+            fn serialize_tuple_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> { unimplemented!() }
+            fn serialize_map(self,_len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> { unimplemented!() }
+            fn serialize_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeStruct, Self::Error> { unimplemented!() }
+            fn serialize_struct_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeStructVariant, Self::Error> { unimplemented!() }
+            fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+                self,
+                _name: &'static str,
+                _variant_index: u32,
+                _variant: &'static str,
+                value: &T,
+            ) -> Result<Self::Ok, Self::Error> {
+                value.serialize(self.0)
+            }
+        }
+
+        Self::serialize(self, NewtypeVariantSerializer(serializer))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UnionBytesLong {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::deserialize(deserializer)
+    }
+}
+
+pub type Hash = [u8; 4];
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Payload {
+    pub id: rsgen_avro::apache_avro::Uuid,
+    #[serde(with = "rsgen_avro::apache_avro::serde_avro_bytes")]
+    pub data: Vec<u8>,
+    #[serde(with = "rsgen_avro::apache_avro::serde_avro_fixed")]
+    pub hash: Hash,
+    #[serde(with = "rsgen_avro::apache_avro::serde_avro_bytes_opt")]
+    #[serde(default = "default_payload_extra")]
+    pub extra: Option<Vec<u8>>,
+    pub amount: rsgen_avro::apache_avro::Decimal,
+    pub choice: UnionBytesLong,
+}
+
+#[inline(always)]
+fn default_payload_extra() -> Option<Vec<u8>> { None }
@@ -0,0 +1,76 @@
+
+/// A message of a generated type, e.g. in a `Vec<Box<dyn AvroMessage>>` of several types.
+pub trait AvroMessage {
+    /// Returns the Avro fullname of the schema of the message.
+    fn fullname(&self) -> &'static str;
+
+    /// Encodes the message as an Avro datum of its schema.
+    ///
+    /// Panics if the message does not match its schema, e.g. a `fixed` of the wrong size.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Returns the JSON of the schema of the message, the named types it references being
+    /// inlined.
+    fn schema_json(&self) -> &'static str;
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum ShipmentStatus {
+    #[serde(rename = "PENDING")]
+    Pending,
+    #[serde(rename = "SENT")]
+    Sent,
+}
+
+impl AvroMessage for ShipmentStatus {
+    fn fullname(&self) -> &'static str {
+        "test.ShipmentStatus"
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        static SCHEMA: std::sync::OnceLock<apache_avro::Schema> = std::sync::OnceLock::new();
+        let schema = SCHEMA.get_or_init(|| {
+            apache_avro::Schema::parse_str(self.schema_json()).expect("valid generated schema")
+        });
+        apache_avro::to_value(self)
+            .and_then(|value| value.resolve(schema))
+            .and_then(|value| apache_avro::to_avro_datum(schema, value))
+            .unwrap_or_else(|e| panic!("Cannot encode a `ShipmentStatus`: {e}"))
+    }
+
+    fn schema_json(&self) -> &'static str {
+        "{\"name\":\"ShipmentStatus\",\"namespace\":\"test\",\"symbols\":[\"PENDING\",\"SENT\"],\"type\":\"enum\"}"
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Shipment {
+    pub id: String,
+    pub status: ShipmentStatus,
+    #[serde(default = "default_shipment_note")]
+    pub note: Option<String>,
+}
+
+#[inline(always)]
+fn default_shipment_note() -> Option<String> { None }
+
+impl AvroMessage for Shipment {
+    fn fullname(&self) -> &'static str {
+        "test.Shipment"
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        static SCHEMA: std::sync::OnceLock<apache_avro::Schema> = std::sync::OnceLock::new();
+        let schema = SCHEMA.get_or_init(|| {
+            apache_avro::Schema::parse_str(self.schema_json()).expect("valid generated schema")
+        });
+        apache_avro::to_value(self)
+            .and_then(|value| value.resolve(schema))
+            .and_then(|value| apache_avro::to_avro_datum(schema, value))
+            .unwrap_or_else(|e| panic!("Cannot encode a `Shipment`: {e}"))
+    }
+
+    fn schema_json(&self) -> &'static str {
+        "{\"fields\":[{\"name\":\"id\",\"type\":\"string\"},{\"name\":\"status\",\"type\":{\"name\":\"ShipmentStatus\",\"namespace\":\"test\",\"symbols\":[\"PENDING\",\"SENT\"],\"type\":\"enum\"}},{\"default\":null,\"name\":\"note\",\"type\":[\"null\",\"string\"]}],\"name\":\"Shipment\",\"namespace\":\"test\",\"type\":\"record\"}"
+    }
+}
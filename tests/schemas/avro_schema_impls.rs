@@ -0,0 +1,42 @@
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Unit {
+    #[serde(rename = "CELSIUS")]
+    Celsius,
+    #[serde(rename = "KELVIN")]
+    Kelvin,
+}
+
+impl apache_avro::AvroSchema for Unit {
+    fn get_schema() -> apache_avro::Schema {
+        static SCHEMA: std::sync::OnceLock<apache_avro::Schema> = std::sync::OnceLock::new();
+        SCHEMA
+            .get_or_init(|| {
+                apache_avro::Schema::parse_str("{\"name\":\"Unit\",\"namespace\":\"test\",\"symbols\":[\"CELSIUS\",\"KELVIN\"],\"type\":\"enum\"}").expect("valid generated schema")
+            })
+            .clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Reading {
+    pub sensor: String,
+    pub value: f64,
+    pub unit: Unit,
+    #[serde(default = "default_reading_previous")]
+    pub previous: Option<Unit>,
+}
+
+#[inline(always)]
+fn default_reading_previous() -> Option<Unit> { None }
+
+impl apache_avro::AvroSchema for Reading {
+    fn get_schema() -> apache_avro::Schema {
+        static SCHEMA: std::sync::OnceLock<apache_avro::Schema> = std::sync::OnceLock::new();
+        SCHEMA
+            .get_or_init(|| {
+                apache_avro::Schema::parse_str("{\"fields\":[{\"name\":\"sensor\",\"type\":\"string\"},{\"name\":\"value\",\"type\":\"double\"},{\"name\":\"unit\",\"type\":{\"name\":\"Unit\",\"namespace\":\"test\",\"symbols\":[\"CELSIUS\",\"KELVIN\"],\"type\":\"enum\"}},{\"default\":null,\"name\":\"previous\",\"type\":[\"null\",\"test.Unit\"]}],\"name\":\"Reading\",\"namespace\":\"test\",\"type\":\"record\"}").expect("valid generated schema")
+            })
+            .clone()
+    }
+}
@@ -0,0 +1,48 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Note {
+    pub text: String,
+}
+
+pub type Digest = [u8; 32];
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Customer {
+    pub name: String,
+    pub email: String,
+    #[serde(with = "apache_avro::serde_avro_fixed")]
+    pub avatar: Digest,
+    pub address: Address,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Order {
+    pub id: i64,
+    pub customer: Box<Customer>,
+    #[serde(default = "default_order_billing")]
+    pub billing: Option<Box<Customer>>,
+    #[serde(default = "default_order_shipping")]
+    pub shipping: Option<Address>,
+    #[serde(default = "default_order_note")]
+    pub note: Box<Note>,
+    #[serde(default = "default_order_parent")]
+    pub parent: Option<Box<Order>>,
+}
+
+#[inline(always)]
+fn default_order_billing() -> Option<Box<Customer>> { None }
+
+#[inline(always)]
+fn default_order_shipping() -> Option<Address> { None }
+
+#[inline(always)]
+fn default_order_note() -> Box<Note> { Box::new(Note { text: "".to_owned(), }) }
+
+#[inline(always)]
+fn default_order_parent() -> Option<Box<Order>> { None }
@@ -0,0 +1,274 @@
+
+/// A decimal number of the Avro `decimal` logical type, `mantissa * 10^-scale`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Decimal {
+    /// The unscaled value.
+    pub mantissa: i128,
+    /// The number of digits of the fractional part.
+    pub scale: u32,
+}
+
+impl Decimal {
+    /// Returns the decimal `mantissa * 10^-scale` of a schema of the given `precision` and
+    /// `scale`, and `size` for `fixed` decimals (`None` for `bytes` ones), or the error of
+    /// a value invalid for the schema, which would fail when encoded.
+    pub fn try_new(
+        mantissa: i128,
+        scale: u32,
+        precision: u32,
+        size: Option<usize>,
+    ) -> Result<Decimal, DecimalError> {
+        if scale > precision {
+            return Err(DecimalError::Scale { scale, precision });
+        }
+        let digits = mantissa.unsigned_abs().checked_ilog10().map_or(1, |log| log + 1);
+        if digits > precision {
+            return Err(DecimalError::Precision { digits, precision });
+        }
+        // The length of the shortest big-endian two's complement bytes of the mantissa
+        let bits = 129 - if mantissa < 0 { !mantissa } else { mantissa }.leading_zeros();
+        let len = bits.div_ceil(8) as usize;
+        if let Some(size) = size.filter(|size| len > *size) {
+            return Err(DecimalError::Size { len, size });
+        }
+        Ok(Decimal { mantissa, scale })
+    }
+
+    /// Decodes the Avro encoding of a decimal of the given `scale`, the big-endian two's
+    /// complement bytes of its mantissa, `None` if the mantissa does not fit in an `i128`.
+    pub fn from_avro_bytes(bytes: &[u8], scale: u32) -> Option<Decimal> {
+        let sign = if bytes.first().is_some_and(|b| *b >= 0x80) { 0xff } else { 0 };
+        let mut bytes = bytes;
+        if bytes.len() > 16 {
+            let (extension, rest) = bytes.split_at(bytes.len() - 16);
+            if extension.iter().any(|b| *b != sign) || (rest[0] ^ sign) >= 0x80 {
+                return None;
+            }
+            bytes = rest;
+        }
+        let mut buf = [sign; 16];
+        buf[16 - bytes.len()..].copy_from_slice(bytes);
+        Some(Decimal { mantissa: i128::from_be_bytes(buf), scale })
+    }
+
+    /// Returns the Avro encoding of the decimal, the big-endian two's complement bytes of
+    /// its mantissa.
+    pub fn to_avro_bytes(&self) -> [u8; 16] {
+        self.mantissa.to_be_bytes()
+    }
+
+    /// Returns the same number with the given `scale`, `None` if digits would be lost or if
+    /// the mantissa would overflow.
+    pub fn rescale(&self, scale: u32) -> Option<Decimal> {
+        let mantissa = if scale >= self.scale {
+            self.mantissa.checked_mul(10i128.checked_pow(scale - self.scale)?)?
+        } else {
+            let factor = 10i128.checked_pow(self.scale - scale)?;
+            if self.mantissa % factor != 0 {
+                return None;
+            }
+            self.mantissa / factor
+        };
+        Some(Decimal { mantissa, scale })
+    }
+
+    /// Serializes the decimal as the Avro bytes of its mantissa in the given `scale`.
+    pub fn serialize_with_scale<S>(&self, scale: u32, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let decimal = self.rescale(scale).ok_or_else(|| {
+            serde::ser::Error::custom(format!("{self} cannot be written with scale {scale}"))
+        })?;
+        serializer.serialize_bytes(&decimal.to_avro_bytes())
+    }
+
+    /// Deserializes a decimal of the given `scale` from the Avro bytes of its mantissa.
+    pub fn deserialize_with_scale<'de, D>(deserializer: D, scale: u32) -> Result<Decimal, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor(u32);
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Decimal;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("the bytes of a decimal")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Decimal, E> {
+                Decimal::from_avro_bytes(v, self.0)
+                    .ok_or_else(|| E::custom("decimal mantissa overflowing i128"))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Decimal, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut bytes = vec![];
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+        }
+
+        deserializer.deserialize_bytes(Visitor(scale))
+    }
+
+    /// Serializes an optional decimal like [`serialize_with_scale`](Decimal::serialize_with_scale).
+    pub fn serialize_option_with_scale<S>(
+        value: &Option<Decimal>,
+        scale: u32,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct Scaled<'a>(&'a Decimal, u32);
+
+        impl serde::Serialize for Scaled<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.serialize_with_scale(self.1, serializer)
+            }
+        }
+
+        match value {
+            Some(decimal) => serializer.serialize_some(&Scaled(decimal, scale)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes an optional decimal like
+    /// [`deserialize_with_scale`](Decimal::deserialize_with_scale).
+    pub fn deserialize_option_with_scale<'de, D>(
+        deserializer: D,
+        scale: u32,
+    ) -> Result<Option<Decimal>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Scaled(Decimal);
+
+        impl<'de> serde::Deserialize<'de> for Scaled {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                // The scale is set once deserialized
+                Decimal::deserialize_with_scale(deserializer, 0).map(Scaled)
+            }
+        }
+
+        let value = <Option<Scaled> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(value.map(|Scaled(decimal)| Decimal { scale, ..decimal }))
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{sign}{integer}.{fraction}")
+    }
+}
+
+/// The error of a decimal invalid for its schema, see [`Decimal::try_new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalError {
+    /// The mantissa has more digits than the precision of the schema.
+    Precision { digits: u32, precision: u32 },
+    /// The scale of the schema is greater than its precision.
+    Scale { scale: u32, precision: u32 },
+    /// The mantissa is encoded in more bytes than the size of the fixed of the schema.
+    Size { len: usize, size: usize },
+}
+
+impl std::fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecimalError::Precision { digits, precision } => {
+                write!(f, "{digits} digits exceed the decimal precision {precision}")
+            }
+            DecimalError::Scale { scale, precision } => {
+                write!(f, "The decimal scale {scale} exceeds its precision {precision}")
+            }
+            DecimalError::Size { len, size } => {
+                write!(f, "{len} bytes exceed the decimal fixed size {size}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecimalError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Invoice {
+    #[serde(with = "invoice_decimals::scale_2")]
+    pub total: Decimal,
+    #[serde(with = "invoice_decimals::scale_4")]
+    pub rate: Decimal,
+    #[serde(with = "invoice_decimals::scale_2_option")]
+    #[serde(default = "default_invoice_discount")]
+    pub discount: Option<Decimal>,
+}
+
+#[inline(always)]
+fn default_invoice_discount() -> Option<Decimal> { None }
+
+/// Serde functions of the decimal fields, with the scale of their schema.
+mod invoice_decimals {
+    use super::Decimal;
+
+    pub mod scale_2 {
+        pub fn serialize<S>(value: &super::Decimal, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            value.serialize_with_scale(2, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<super::Decimal, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            super::Decimal::deserialize_with_scale(deserializer, 2)
+        }
+    }
+
+    pub mod scale_2_option {
+        pub fn serialize<S>(value: &Option<super::Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            super::Decimal::serialize_option_with_scale(value, 2, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<super::Decimal>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            super::Decimal::deserialize_option_with_scale(deserializer, 2)
+        }
+    }
+
+    pub mod scale_4 {
+        pub fn serialize<S>(value: &super::Decimal, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            value.serialize_with_scale(4, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<super::Decimal, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            super::Decimal::deserialize_with_scale(deserializer, 4)
+        }
+    }
+}
@@ -0,0 +1,29 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Item {
+    pub id: i64,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Basket {
+    pub note: Option<String>,
+    pub items: Vec<Item>,
+    pub attributes: ::std::collections::HashMap<String, String>,
+    #[serde(default = "default_basket_quantity")]
+    pub quantity: i32,
+}
+
+#[inline(always)]
+fn default_basket_quantity() -> i32 { 1 }
+
+impl Default for Basket {
+    fn default() -> Basket {
+        Basket {
+            note: Default::default(),
+            items: Default::default(),
+            attributes: Default::default(),
+            quantity: default_basket_quantity(),
+        }
+    }
+}
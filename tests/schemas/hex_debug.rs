@@ -0,0 +1,45 @@
+
+pub type Digest = [u8; 16];
+
+#[derive(PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Blob {
+    pub id: i64,
+    #[serde(with = "apache_avro::serde_avro_bytes")]
+    pub data: Vec<u8>,
+    #[serde(with = "apache_avro::serde_avro_fixed")]
+    pub digest: Digest,
+    #[serde(default = "default_blob_previous")]
+    pub previous: Option<Digest>,
+    #[serde(with = "apache_avro::serde_avro_bytes")]
+    pub secret: Vec<u8>,
+}
+
+#[inline(always)]
+fn default_blob_previous() -> Option<Digest> { None }
+
+impl std::fmt::Debug for Blob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        struct Hex<'a>(&'a [u8]);
+
+        impl std::fmt::Debug for Hex<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("0x")?;
+                for byte in self.0.iter().take(8) {
+                    write!(f, "{byte:02X}")?;
+                }
+                if self.0.len() > 8 {
+                    f.write_str("…")?;
+                }
+                write!(f, " ({} bytes)", self.0.len())
+            }
+        }
+
+        f.debug_struct("Blob")
+            .field("id", &self.id)
+            .field("data", &Hex(&self.data))
+            .field("digest", &Hex(&self.digest))
+            .field("previous", &self.previous.as_ref().map(|v| Hex(v)))
+            .field("secret", &"****")
+            .finish()
+    }
+}
@@ -0,0 +1,111 @@
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Serialize)]
+pub enum Priority {
+    #[serde(rename = "LOW")]
+    Low,
+    #[serde(rename = "HIGH")]
+    High,
+}
+
+impl<'de> serde::Deserialize<'de> for Priority {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Priority")]
+        enum Lenient {
+            #[serde(rename = "LOW")]
+            Low,
+            #[serde(rename = "HIGH")]
+            High,
+            #[serde(other)]
+            __Unknown,
+        }
+
+        Ok(match Lenient::deserialize(deserializer)? {
+            Lenient::Low => Priority::Low,
+            Lenient::High => Priority::High,
+            Lenient::__Unknown => Priority::Low,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Serialize)]
+pub enum Status {
+    #[serde(rename = "NEW")]
+    New,
+    #[serde(rename = "SHIPPED")]
+    Shipped,
+    #[serde(rename = "UNKNOWN")]
+    Unknown,
+}
+
+impl<'de> serde::Deserialize<'de> for Status {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Status")]
+        enum Lenient {
+            #[serde(rename = "NEW")]
+            New,
+            #[serde(rename = "SHIPPED")]
+            Shipped,
+            #[serde(rename = "UNKNOWN")]
+            Unknown,
+            #[serde(other)]
+            __Unknown,
+        }
+
+        Ok(match Lenient::deserialize(deserializer)? {
+            Lenient::New => Status::New,
+            Lenient::Shipped => Status::Shipped,
+            Lenient::Unknown => Status::Unknown,
+            Lenient::__Unknown => Status::Unknown,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Order {
+    pub id: i64,
+    pub note: Option<String>,
+    pub items: Vec<String>,
+    pub status: Status,
+    pub priority: Priority,
+    pub quantity: i32,
+}
+
+#[inline(always)]
+fn default_order_id() -> i64 { 0 }
+
+#[inline(always)]
+fn default_order_note() -> Option<String> { None }
+
+#[inline(always)]
+fn default_order_items() -> Vec<String> { vec![] }
+
+#[inline(always)]
+fn default_order_status() -> Status { Status::Unknown }
+
+#[inline(always)]
+fn default_order_priority() -> Priority { Priority::Low }
+
+#[inline(always)]
+fn default_order_quantity() -> i32 { 1 }
+
+impl Default for Order {
+    fn default() -> Order {
+        Order {
+            id: default_order_id(),
+            note: default_order_note(),
+            items: default_order_items(),
+            status: default_order_status(),
+            priority: default_order_priority(),
+            quantity: default_order_quantity(),
+        }
+    }
+}
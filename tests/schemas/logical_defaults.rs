@@ -0,0 +1,238 @@
+
+/// Auto-generated type for unnamed Avro union variants.
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Self")]
+pub enum UnionUuidLong {
+    Uuid(apache_avro::Uuid),
+    Long(i64),
+}
+
+impl From<i64> for UnionUuidLong {
+    fn from(v: i64) -> Self {
+        Self::Long(v)
+    }
+}
+
+impl TryFrom<UnionUuidLong> for i64 {
+    type Error = UnionUuidLong;
+
+    fn try_from(v: UnionUuidLong) -> Result<Self, Self::Error> {
+        if let UnionUuidLong::Long(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl serde::Serialize for UnionUuidLong {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct NewtypeVariantSerializer<S>(S);
+
+        impl<S> serde::Serializer for NewtypeVariantSerializer<S>
+        where
+            S: serde::Serializer,
+        {
+            type Ok = S::Ok;
+            type Error = S::Error;
+            type SerializeSeq = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTuple = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeMap = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStructVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_none(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_some<T: ?Sized + serde::Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error>{ unimplemented!() }
+            fn serialize_unit(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_variant(self ,_name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(self, _name: &'static str, _value: &T,) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_seq(self,_len: Option<usize>,) -> Result<Self::SerializeSeq, Self::Error> { unimplemented!() }
+            fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> { unimplemented!() }
+            fn serialize_tuple_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> { unimplemented!() }
+            fn serialize_tuple_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> { unimplemented!() }
+            fn serialize_map(self,_len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> { unimplemented!() }
+            fn serialize_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeStruct, Self::Error> { unimplemented!() }
+            fn serialize_struct_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeStructVariant, Self::Error> { unimplemented!() }
+            fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+                self,
+                _name: &'static str,
+                _variant_index: u32,
+                _variant: &'static str,
+                value: &T,
+            ) -> Result<Self::Ok, Self::Error> {
+                value.serialize(self.0)
+            }
+        }
+
+        Self::serialize(self, NewtypeVariantSerializer(serializer))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UnionUuidLong {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::deserialize(deserializer)
+    }
+}
+
+/// Auto-generated type for unnamed Avro union variants.
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Self")]
+pub enum UnionTimestampMicrosString {
+    NaiveDateTime(chrono::DateTime<chrono::Utc>),
+    String(String),
+}
+
+impl From<String> for UnionTimestampMicrosString {
+    fn from(v: String) -> Self {
+        Self::String(v)
+    }
+}
+
+impl TryFrom<UnionTimestampMicrosString> for String {
+    type Error = UnionTimestampMicrosString;
+
+    fn try_from(v: UnionTimestampMicrosString) -> Result<Self, Self::Error> {
+        if let UnionTimestampMicrosString::String(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl serde::Serialize for UnionTimestampMicrosString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct NewtypeVariantSerializer<S>(S);
+
+        impl<S> serde::Serializer for NewtypeVariantSerializer<S>
+        where
+            S: serde::Serializer,
+        {
+            type Ok = S::Ok;
+            type Error = S::Error;
+            type SerializeSeq = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTuple = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeMap = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStructVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_none(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_some<T: ?Sized + serde::Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error>{ unimplemented!() }
+            fn serialize_unit(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_variant(self ,_name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(self, _name: &'static str, _value: &T,) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_seq(self,_len: Option<usize>,) -> Result<Self::SerializeSeq, Self::Error> { unimplemented!() }
+            fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> { unimplemented!() }
+            fn serialize_tuple_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> { unimplemented!() }
+            fn serialize_tuple_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> { unimplemented!() }
+            fn serialize_map(self,_len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> { unimplemented!() }
+            fn serialize_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeStruct, Self::Error> { unimplemented!() }
+            fn serialize_struct_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeStructVariant, Self::Error> { unimplemented!() }
+            fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+                self,
+                _name: &'static str,
+                _variant_index: u32,
+                _variant: &'static str,
+                value: &T,
+            ) -> Result<Self::Ok, Self::Error> {
+                value.serialize(self.0)
+            }
+        }
+
+        Self::serialize(self, NewtypeVariantSerializer(serializer))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UnionTimestampMicrosString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Schedule {
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub starts_at: chrono::DateTime<chrono::Utc>,
+    pub id: apache_avro::Uuid,
+    pub deadline: UnionTimestampMicrosString,
+    pub owner: UnionUuidLong,
+    pub reminders: Vec<chrono::DateTime<chrono::Utc>>,
+    #[serde(with = "chrono::serde::ts_milliseconds_option")]
+    pub cancelled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[inline(always)]
+fn default_schedule_starts_at() -> chrono::DateTime<chrono::Utc> { chrono::DateTime::<chrono::Utc>::from_timestamp_millis(1700000000000).unwrap() }
+
+#[inline(always)]
+fn default_schedule_id() -> apache_avro::Uuid { apache_avro::Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap() }
+
+#[inline(always)]
+fn default_schedule_deadline() -> UnionTimestampMicrosString { UnionTimestampMicrosString::NaiveDateTime(chrono::DateTime::<chrono::Utc>::from_timestamp_micros(1700000000000000).unwrap()) }
+
+#[inline(always)]
+fn default_schedule_owner() -> UnionUuidLong { UnionUuidLong::Uuid(apache_avro::Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap()) }
+
+#[inline(always)]
+fn default_schedule_reminders() -> Vec<chrono::DateTime<chrono::Utc>> { vec![chrono::DateTime::<chrono::Utc>::from_timestamp_millis(0).unwrap(), chrono::DateTime::<chrono::Utc>::from_timestamp_millis(60000).unwrap()] }
+
+#[inline(always)]
+fn default_schedule_cancelled_at() -> Option<chrono::DateTime<chrono::Utc>> { None }
+
+impl Default for Schedule {
+    fn default() -> Schedule {
+        Schedule {
+            starts_at: default_schedule_starts_at(),
+            id: default_schedule_id(),
+            deadline: default_schedule_deadline(),
+            owner: default_schedule_owner(),
+            reminders: default_schedule_reminders(),
+            cancelled_at: default_schedule_cancelled_at(),
+        }
+    }
+}
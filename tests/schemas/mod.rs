@@ -1,16 +1,31 @@
+#![allow(dead_code, clippy::all)]
+
+pub mod alias_types;
 pub mod array_3d;
+pub mod avro_crate;
+pub mod avro_json;
+pub mod avro_message_impls;
+pub mod avro_schema_impls;
+pub mod boxed;
 pub mod complex;
+pub mod decimal_structs;
 pub mod decimals;
+pub mod empty_defaults;
 pub mod enums;
 pub mod enums_casing;
 pub mod enums_multiline_doc;
 pub mod enums_sanitize;
 #[allow(dead_code)]
 pub mod fixed;
+pub mod hex_debug;
 pub mod interop;
+pub mod item_lints;
+pub mod lenient;
 pub mod logical_dates;
+pub mod logical_defaults;
 pub mod map_default;
 pub mod map_multiple_def;
+pub mod names_sanitize;
 pub mod mono_valued_union;
 pub mod multi_valued_union;
 pub mod multi_valued_union_map;
@@ -20,15 +35,45 @@ pub mod multi_valued_union_with_avro_rs_unions;
 pub mod nested_record_default;
 pub mod nested_record_partial_default;
 pub mod nullable;
+pub mod nullable_array;
 pub mod nullable_bytes;
 pub mod nullable_logical_dates;
+pub mod nullable_map;
 pub mod optional_array;
+pub mod optional_defaults;
 pub mod optional_arrays;
+pub mod proptest_arbitrary;
+pub mod protocol;
+pub mod protocol_no_debug;
+pub mod quickcheck_arbitrary;
 pub mod record;
 pub mod record_default;
 pub mod record_multiline_doc;
 pub mod recursive;
+pub mod rfc3339_timestamps;
+pub mod round_trip_tests;
+pub mod schema_constants;
+pub mod sensitive;
 pub mod simple;
 pub mod simple_with_builders;
 pub mod simple_with_schemas;
+pub mod smallvec;
+pub mod strict;
+pub mod string_enums;
+pub mod structural_validators;
+pub mod symbol_aliases;
+pub mod symbol_docs;
 pub mod nested_with_float;
+pub mod try_from_bytes;
+pub mod union_collection_defaults;
+pub mod union_dedup;
+pub mod union_json;
+pub mod union_names;
+pub mod unsigned;
+pub mod validate_derive_serde_valid;
+pub mod validate_derive_validator;
+pub mod validators;
+pub mod wrap_module;
+pub mod wrapper_conversions;
+pub mod writer_schema_decoders;
+pub mod zeroize;
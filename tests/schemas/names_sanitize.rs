@@ -0,0 +1,17 @@
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Axis {
+    _2d,
+    _3d,
+    #[serde(rename = "Self")]
+    r#Self_,
+}
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct _3dPoint {
+    pub _1st: f64,
+    #[serde(rename = "self")]
+    pub r#self_: f64,
+    pub r#type: f64,
+    pub axis: Axis,
+}
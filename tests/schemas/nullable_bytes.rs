@@ -5,6 +5,7 @@ pub struct BytesData {
     #[serde(deserialize_with = "nullable_bytesdata_b")]
     #[serde(serialize_with = "apache_avro::serde_avro_bytes::serialize")]
     pub b: Vec<u8>,
+    #[serde(with = "apache_avro::serde_avro_bytes_opt")]
     pub nb: Option<Vec<u8>>,
 }
 
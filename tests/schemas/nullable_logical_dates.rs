@@ -6,6 +6,7 @@ pub struct DateLogicalType {
     #[serde(deserialize_with = "nullable_datelogicaltype_birthday")]
     #[serde(serialize_with = "chrono::serde::ts_seconds::serialize")]
     pub birthday: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "chrono::serde::ts_milliseconds_option")]
     pub meeting_time: Option<chrono::DateTime<chrono::Utc>>,
     #[serde(deserialize_with = "nullable_datelogicaltype_release_datetime_micro")]
     #[serde(serialize_with = "chrono::serde::ts_microseconds::serialize")]
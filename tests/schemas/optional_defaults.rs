@@ -0,0 +1,83 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Limits {
+    #[serde(deserialize_with = "present_limits_max")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+}
+
+#[inline(always)]
+fn present_limits_max<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
+#[inline(always)]
+fn default_limits_max() -> Option<i64> { None }
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            max: default_limits_max(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Settings {
+    pub name: String,
+    #[serde(default = "default_settings_retries")]
+    #[serde(deserialize_with = "present_settings_retries")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<i32>,
+    #[serde(default = "default_settings_token")]
+    #[serde(deserialize_with = "present_settings_token")]
+    #[serde(serialize_with = "apache_avro::serde_avro_bytes_opt::serialize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<Vec<u8>>,
+    #[serde(default = "default_settings_parent")]
+    pub parent: Option<String>,
+    #[serde(default = "default_settings_limits")]
+    #[serde(deserialize_with = "present_settings_limits")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Limits>,
+}
+
+#[inline(always)]
+fn present_settings_retries<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
+#[inline(always)]
+fn present_settings_token<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    apache_avro::serde_avro_bytes::deserialize(deserializer).map(Some)
+}
+
+#[inline(always)]
+fn present_settings_limits<'de, D>(deserializer: D) -> Result<Option<Limits>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
+#[inline(always)]
+fn default_settings_retries() -> Option<i32> { None }
+
+#[inline(always)]
+fn default_settings_token() -> Option<Vec<u8>> { None }
+
+#[inline(always)]
+fn default_settings_parent() -> Option<String> { None }
+
+#[inline(always)]
+fn default_settings_limits() -> Option<Limits> { None }
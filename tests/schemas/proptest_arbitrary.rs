@@ -0,0 +1,194 @@
+
+/// Auto-generated type for unnamed Avro union variants.
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Self")]
+pub enum UnionStringBytesKind {
+    String(String),
+    Bytes(#[serde(with = "apache_avro::serde_avro_bytes")] Vec<u8>),
+    Kind(Kind),
+}
+
+impl From<String> for UnionStringBytesKind {
+    fn from(v: String) -> Self {
+        Self::String(v)
+    }
+}
+
+impl TryFrom<UnionStringBytesKind> for String {
+    type Error = UnionStringBytesKind;
+
+    fn try_from(v: UnionStringBytesKind) -> Result<Self, Self::Error> {
+        if let UnionStringBytesKind::String(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl From<Vec<u8>> for UnionStringBytesKind {
+    fn from(v: Vec<u8>) -> Self {
+        Self::Bytes(v)
+    }
+}
+
+impl TryFrom<UnionStringBytesKind> for Vec<u8> {
+    type Error = UnionStringBytesKind;
+
+    fn try_from(v: UnionStringBytesKind) -> Result<Self, Self::Error> {
+        if let UnionStringBytesKind::Bytes(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl serde::Serialize for UnionStringBytesKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct NewtypeVariantSerializer<S>(S);
+
+        impl<S> serde::Serializer for NewtypeVariantSerializer<S>
+        where
+            S: serde::Serializer,
+        {
+            type Ok = S::Ok;
+            type Error = S::Error;
+            type SerializeSeq = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTuple = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeMap = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStructVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_none(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_some<T: ?Sized + serde::Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error>{ unimplemented!() }
+            fn serialize_unit(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_variant(self ,_name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(self, _name: &'static str, _value: &T,) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_seq(self,_len: Option<usize>,) -> Result<Self::SerializeSeq, Self::Error> { unimplemented!() }
+            fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> { unimplemented!() }
+            fn serialize_tuple_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> { unimplemented!() }
+            fn serialize_tuple_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> { unimplemented!() }
+            fn serialize_map(self,_len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> { unimplemented!() }
+            fn serialize_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeStruct, Self::Error> { unimplemented!() }
+            fn serialize_struct_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeStructVariant, Self::Error> { unimplemented!() }
+            fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+                self,
+                _name: &'static str,
+                _variant_index: u32,
+                _variant: &'static str,
+                value: &T,
+            ) -> Result<Self::Ok, Self::Error> {
+                value.serialize(self.0)
+            }
+        }
+
+        Self::serialize(self, NewtypeVariantSerializer(serializer))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UnionStringBytesKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::deserialize(deserializer)
+    }
+}
+
+impl proptest::arbitrary::Arbitrary for UnionStringBytesKind {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+        proptest::strategy::Union::new(vec![
+            any::<String>().prop_map(UnionStringBytesKind::String).boxed(),
+            any::<Vec<u8>>().prop_map(UnionStringBytesKind::Bytes).boxed(),
+            any::<Kind>().prop_map(UnionStringBytesKind::Kind).boxed(),
+        ])
+        .boxed()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Event {
+    pub id: apache_avro::Uuid,
+    pub kind: Kind,
+    #[serde(with = "apache_avro::serde_avro_fixed")]
+    pub hash: Digest,
+    pub amount: apache_avro::Decimal,
+    pub tags: Vec<String>,
+    pub attributes: ::std::collections::HashMap<String, Option<i64>>,
+    pub payload: Option<UnionStringBytesKind>,
+    pub a1: i32,
+    pub a2: i32,
+    pub a3: i32,
+    pub a4: i32,
+}
+
+impl proptest::arbitrary::Arbitrary for Event {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+        (
+            (
+                any::<u128>().prop_map(apache_avro::Uuid::from_u128),
+                any::<Kind>(),
+                prop::collection::vec(any::<u8>(), 40)
+                    .prop_map(|v| <[u8; 40]>::try_from(v).unwrap()),
+                any::<Vec<u8>>().prop_map(apache_avro::Decimal::from),
+                prop::collection::vec(any::<String>(), 0..4),
+                prop::collection::hash_map(any::<String>(), prop::option::of(any::<i64>()), 0..4),
+                prop::option::of(any::<UnionStringBytesKind>()),
+                any::<i32>(),
+                any::<i32>(),
+                any::<i32>(),
+            ),
+            (any::<i32>(),),
+        )
+            .prop_map(|((id, kind, hash, amount, tags, attributes, payload, a1, a2, a3), (a4,))| Event { id, kind, hash, amount, tags, attributes, payload, a1, a2, a3, a4 })
+            .boxed()
+    }
+}
+
+pub type Digest = [u8; 40];
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Kind {
+    #[serde(rename = "CREATED")]
+    Created,
+    #[serde(rename = "DELETED")]
+    Deleted,
+}
+
+impl proptest::arbitrary::Arbitrary for Kind {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+        proptest::sample::select(vec![Kind::Created, Kind::Deleted]).boxed()
+    }
+}
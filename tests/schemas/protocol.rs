@@ -0,0 +1,30 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct QuotaExceeded {
+    pub limit: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Rejected {
+    pub reason: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Message {
+    pub to: String,
+    pub body: String,
+}
+
+/// The errors of the `send` message of the `Mail` protocol.
+#[derive(Debug, thiserror::Error)]
+pub enum SendError {
+    /// The `com.acme.mail.Rejected` error declared by the message.
+    #[error("{0:?}")]
+    Rejected(Rejected),
+    /// The `com.acme.mail.QuotaExceeded` error declared by the message.
+    #[error("{0:?}")]
+    QuotaExceeded(QuotaExceeded),
+    /// An error of the transport of the message, e.g. of the connection.
+    #[error("Transport error: {0}")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
@@ -0,0 +1,29 @@
+
+#[derive(PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct NotFound {
+    pub id: String,
+}
+
+/// The errors of the `get` message of the `Users` protocol.
+#[derive(thiserror::Error)]
+pub enum GetError {
+    /// The `acme.NotFound` error declared by the message.
+    #[error("NotFound error")]
+    NotFound(NotFound),
+    /// The `string` error declared by the message.
+    #[error("{0}")]
+    String(String),
+    /// An error of the transport of the message, e.g. of the connection.
+    #[error("Transport error: {0}")]
+    Transport(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl std::fmt::Debug for GetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GetError::NotFound(_) => f.write_str("NotFound"),
+            GetError::String(_) => f.write_str("String"),
+            GetError::Transport(e) => f.debug_tuple("Transport").field(e).finish(),
+        }
+    }
+}
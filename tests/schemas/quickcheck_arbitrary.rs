@@ -0,0 +1,176 @@
+
+/// Auto-generated type for unnamed Avro union variants.
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Self")]
+pub enum UnionStringBytesKind {
+    String(String),
+    Bytes(#[serde(with = "apache_avro::serde_avro_bytes")] Vec<u8>),
+    Kind(Kind),
+}
+
+impl From<String> for UnionStringBytesKind {
+    fn from(v: String) -> Self {
+        Self::String(v)
+    }
+}
+
+impl TryFrom<UnionStringBytesKind> for String {
+    type Error = UnionStringBytesKind;
+
+    fn try_from(v: UnionStringBytesKind) -> Result<Self, Self::Error> {
+        if let UnionStringBytesKind::String(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl From<Vec<u8>> for UnionStringBytesKind {
+    fn from(v: Vec<u8>) -> Self {
+        Self::Bytes(v)
+    }
+}
+
+impl TryFrom<UnionStringBytesKind> for Vec<u8> {
+    type Error = UnionStringBytesKind;
+
+    fn try_from(v: UnionStringBytesKind) -> Result<Self, Self::Error> {
+        if let UnionStringBytesKind::Bytes(v) = v {
+            Ok(v)
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl serde::Serialize for UnionStringBytesKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct NewtypeVariantSerializer<S>(S);
+
+        impl<S> serde::Serializer for NewtypeVariantSerializer<S>
+        where
+            S: serde::Serializer,
+        {
+            type Ok = S::Ok;
+            type Error = S::Error;
+            type SerializeSeq = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTuple = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeTupleVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeMap = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStruct = serde::ser::Impossible<S::Ok, S::Error>;
+            type SerializeStructVariant = serde::ser::Impossible<S::Ok, S::Error>;
+            fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_none(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_some<T: ?Sized + serde::Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error>{ unimplemented!() }
+            fn serialize_unit(self) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_unit_variant(self ,_name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(self, _name: &'static str, _value: &T,) -> Result<Self::Ok, Self::Error> { unimplemented!() }
+            fn serialize_seq(self,_len: Option<usize>,) -> Result<Self::SerializeSeq, Self::Error> { unimplemented!() }
+            fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> { unimplemented!() }
+            fn serialize_tuple_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> { unimplemented!() }
+            fn serialize_tuple_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> { unimplemented!() }
+            fn serialize_map(self,_len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> { unimplemented!() }
+            fn serialize_struct(self,_name: &'static str,_len: usize) -> Result<Self::SerializeStruct, Self::Error> { unimplemented!() }
+            fn serialize_struct_variant(self,_name: &'static str,_variant_index: u32,_variant: &'static str,_len: usize) -> Result<Self::SerializeStructVariant, Self::Error> { unimplemented!() }
+            fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+                self,
+                _name: &'static str,
+                _variant_index: u32,
+                _variant: &'static str,
+                value: &T,
+            ) -> Result<Self::Ok, Self::Error> {
+                value.serialize(self.0)
+            }
+        }
+
+        Self::serialize(self, NewtypeVariantSerializer(serializer))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UnionStringBytesKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::deserialize(deserializer)
+    }
+}
+
+impl quickcheck::Arbitrary for UnionStringBytesKind {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        match <usize as quickcheck::Arbitrary>::arbitrary(g) % 3 {
+            0 => UnionStringBytesKind::String(quickcheck::Arbitrary::arbitrary(g)),
+            1 => UnionStringBytesKind::Bytes(quickcheck::Arbitrary::arbitrary(g)),
+            _ => UnionStringBytesKind::Kind(quickcheck::Arbitrary::arbitrary(g)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Event {
+    pub id: apache_avro::Uuid,
+    pub kind: Kind,
+    #[serde(with = "apache_avro::serde_avro_fixed")]
+    pub hash: Digest,
+    pub amount: apache_avro::Decimal,
+    pub tags: Vec<String>,
+    pub attributes: ::std::collections::HashMap<String, Option<i64>>,
+    pub payload: Option<UnionStringBytesKind>,
+    pub a1: i32,
+    pub a2: i32,
+    pub a3: i32,
+    pub a4: i32,
+}
+
+impl quickcheck::Arbitrary for Event {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Event {
+            id: apache_avro::Uuid::from_u128(quickcheck::Arbitrary::arbitrary(g)),
+            kind: quickcheck::Arbitrary::arbitrary(g),
+            hash: std::array::from_fn(|_| quickcheck::Arbitrary::arbitrary(g)),
+            amount: apache_avro::Decimal::from(<Vec<u8> as quickcheck::Arbitrary>::arbitrary(g)),
+            tags: quickcheck::Arbitrary::arbitrary(g),
+            attributes: quickcheck::Arbitrary::arbitrary(g),
+            payload: quickcheck::Arbitrary::arbitrary(g),
+            a1: quickcheck::Arbitrary::arbitrary(g),
+            a2: quickcheck::Arbitrary::arbitrary(g),
+            a3: quickcheck::Arbitrary::arbitrary(g),
+            a4: quickcheck::Arbitrary::arbitrary(g),
+        }
+    }
+}
+
+pub type Digest = [u8; 40];
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Kind {
+    #[serde(rename = "CREATED")]
+    Created,
+    #[serde(rename = "DELETED")]
+    Deleted,
+}
+
+impl quickcheck::Arbitrary for Kind {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        g.choose(&[Kind::Created, Kind::Deleted]).unwrap().clone()
+    }
+}
@@ -0,0 +1,63 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Event {
+    #[serde(with = "event_timestamps::ts_milliseconds")]
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "event_timestamps::ts_microseconds_option")]
+    #[serde(default = "default_event_ended_at")]
+    pub ended_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[inline(always)]
+fn default_event_ended_at() -> Option<chrono::DateTime<chrono::Utc>> { None }
+
+/// Timestamps as RFC 3339 strings in human-readable formats, as epoch numbers otherwise.
+mod event_timestamps {
+    pub mod ts_microseconds_option {
+        pub fn serialize<S>(value: &Option<chrono::DateTime<chrono::Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            if serializer.is_human_readable() {
+                serde::Serialize::serialize(value, serializer)
+            } else {
+                chrono::serde::ts_microseconds_option::serialize(value, serializer)
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<chrono::DateTime<chrono::Utc>>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                serde::Deserialize::deserialize(deserializer)
+            } else {
+                chrono::serde::ts_microseconds_option::deserialize(deserializer)
+            }
+        }
+    }
+
+    pub mod ts_milliseconds {
+        pub fn serialize<S>(value: &chrono::DateTime<chrono::Utc>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            if serializer.is_human_readable() {
+                serde::Serialize::serialize(value, serializer)
+            } else {
+                chrono::serde::ts_milliseconds::serialize(value, serializer)
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<chrono::DateTime<chrono::Utc>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                serde::Deserialize::deserialize(deserializer)
+            } else {
+                chrono::serde::ts_milliseconds::deserialize(deserializer)
+            }
+        }
+    }
+}
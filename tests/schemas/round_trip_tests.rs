@@ -0,0 +1,141 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Inner {
+    pub b: i32,
+}
+
+#[inline(always)]
+fn default_inner_b() -> i32 { 2 }
+
+impl Default for Inner {
+    fn default() -> Inner {
+        Inner {
+            b: default_inner_b(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod inner_round_trip {
+    use super::Inner;
+
+    const SCHEMA: &str = "{\"fields\":[{\"default\":2,\"name\":\"b\",\"type\":\"int\"}],\"name\":\"Inner\",\"namespace\":\"ns\",\"type\":\"record\"}";
+
+    #[test]
+    fn default_value() {
+        let schema = apache_avro::Schema::parse_str(SCHEMA).unwrap();
+        let expected = Inner::default();
+        let value = apache_avro::to_value(&expected).unwrap().resolve(&schema).unwrap();
+        let datum = apache_avro::to_avro_datum(&schema, value).unwrap();
+        let value = apache_avro::from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+        assert_eq!(expected, apache_avro::from_value::<Inner>(&value).unwrap());
+    }
+
+    #[test]
+    fn schema_defaults() {
+        let schema = apache_avro::Schema::parse_str(SCHEMA).unwrap();
+        let writer = apache_avro::Schema::parse_str("{\"fields\":[],\"name\":\"ns.Inner\",\"type\":\"record\"}").unwrap();
+        let value = apache_avro::from_avro_datum(&writer, &mut &[][..], Some(&schema)).unwrap();
+        assert_eq!(Inner::default(), apache_avro::from_value::<Inner>(&value).unwrap());
+    }
+}
+
+pub type Id = [u8; 2];
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Color {
+    #[serde(rename = "RED")]
+    Red,
+    #[serde(rename = "GREEN")]
+    Green,
+}
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Outer {
+    pub a: String,
+    pub inner: Inner,
+    pub inner2: Inner,
+    pub opt: Option<Inner>,
+    pub color: Color,
+    pub arr: Vec<i64>,
+    pub m: ::std::collections::HashMap<String, f64>,
+    #[serde(with = "apache_avro::serde_avro_fixed")]
+    pub id: Id,
+    #[serde(with = "apache_avro::serde_avro_bytes")]
+    pub bytes: Vec<u8>,
+    pub f: f32,
+}
+
+#[inline(always)]
+fn default_outer_a() -> String { "x".to_owned() }
+
+#[inline(always)]
+fn default_outer_inner() -> Inner { Inner { b: 3, } }
+
+#[inline(always)]
+fn default_outer_inner2() -> Inner { Inner { b: 4, } }
+
+#[inline(always)]
+fn default_outer_opt() -> Option<Inner> { None }
+
+#[inline(always)]
+fn default_outer_color() -> Color { Color::Green }
+
+#[inline(always)]
+fn default_outer_arr() -> Vec<i64> { vec![1, 2] }
+
+#[inline(always)]
+fn default_outer_m() -> ::std::collections::HashMap<String, f64> { { let mut m = ::std::collections::HashMap::new(); m.insert("k".to_owned(), 1.500); m } }
+
+#[inline(always)]
+fn default_outer_id() -> Id { [97, 98] }
+
+#[inline(always)]
+fn default_outer_bytes() -> Vec<u8> { vec![195, 191] }
+
+#[inline(always)]
+fn default_outer_f() -> f32 { 1.250 }
+
+impl Default for Outer {
+    fn default() -> Outer {
+        Outer {
+            a: default_outer_a(),
+            inner: default_outer_inner(),
+            inner2: default_outer_inner2(),
+            opt: default_outer_opt(),
+            color: default_outer_color(),
+            arr: default_outer_arr(),
+            m: default_outer_m(),
+            id: default_outer_id(),
+            bytes: default_outer_bytes(),
+            f: default_outer_f(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod outer_round_trip {
+    use super::Outer;
+
+    const SCHEMA: &str = "{\"fields\":[{\"default\":\"x\",\"name\":\"a\",\"type\":\"string\"},{\"default\":{\"b\":3},\"name\":\"inner\",\"type\":{\"fields\":[{\"default\":2,\"name\":\"b\",\"type\":\"int\"}],\"name\":\"Inner\",\"namespace\":\"ns\",\"type\":\"record\"}},{\"default\":{\"b\":4},\"name\":\"inner2\",\"type\":\"ns.Inner\"},{\"default\":null,\"name\":\"opt\",\"type\":[\"null\",\"ns.Inner\"]},{\"default\":\"GREEN\",\"name\":\"color\",\"type\":{\"name\":\"Color\",\"namespace\":\"ns\",\"symbols\":[\"RED\",\"GREEN\"],\"type\":\"enum\"}},{\"default\":[1,2],\"name\":\"arr\",\"type\":{\"items\":\"long\",\"type\":\"array\"}},{\"default\":{\"k\":1.5},\"name\":\"m\",\"type\":{\"type\":\"map\",\"values\":\"double\"}},{\"default\":\"ab\",\"name\":\"id\",\"type\":{\"name\":\"Id\",\"namespace\":\"ns\",\"size\":2,\"type\":\"fixed\"}},{\"default\":\"ÿ\",\"name\":\"bytes\",\"type\":\"bytes\"},{\"default\":1.25,\"name\":\"f\",\"type\":\"float\"}],\"name\":\"Outer\",\"namespace\":\"ns\",\"type\":\"record\"}";
+
+    #[test]
+    fn default_value() {
+        let schema = apache_avro::Schema::parse_str(SCHEMA).unwrap();
+        let expected = Outer::default();
+        let value = apache_avro::to_value(&expected).unwrap().resolve(&schema).unwrap();
+        let datum = apache_avro::to_avro_datum(&schema, value).unwrap();
+        let value = apache_avro::from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+        assert_eq!(expected, apache_avro::from_value::<Outer>(&value).unwrap());
+    }
+
+    #[test]
+    fn schema_defaults() {
+        let schema = apache_avro::Schema::parse_str(SCHEMA).unwrap();
+        let writer = apache_avro::Schema::parse_str("{\"fields\":[],\"name\":\"ns.Outer\",\"type\":\"record\"}").unwrap();
+        let value = apache_avro::from_avro_datum(&writer, &mut &[][..], Some(&schema)).unwrap();
+        assert_eq!(Outer::default(), apache_avro::from_value::<Outer>(&value).unwrap());
+    }
+}
@@ -0,0 +1,83 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Customer {
+    pub name: String,
+}
+
+impl Customer {
+    /// The Avro schema of `Customer`, as compact JSON.
+    pub const SCHEMA: &'static str = "{\"fields\":[{\"name\":\"name\",\"type\":\"string\"}],\"name\":\"Customer\",\"type\":\"record\"}";
+
+    /// The Avro schema of `Customer`, as pretty-printed JSON.
+    pub const SCHEMA_PRETTY: &'static str = r#"{
+  "fields": [
+    {
+      "name": "name",
+      "type": "string"
+    }
+  ],
+  "name": "Customer",
+  "type": "record"
+}"#;
+
+    /// Returns the Avro schema of `Customer`, as a JSON value.
+    pub fn schema_json() -> serde_json::Value {
+        serde_json::from_str(Self::SCHEMA).expect("valid generated schema")
+    }
+}
+
+/// An order, tagged "#1" when urgent
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Order {
+    pub id: i64,
+    pub customer: Customer,
+    #[serde(default = "default_order_referrer")]
+    pub referrer: Option<Customer>,
+}
+
+#[inline(always)]
+fn default_order_referrer() -> Option<Customer> { None }
+
+impl Order {
+    /// The Avro schema of `Order`, as compact JSON.
+    pub const SCHEMA: &'static str = "{\"doc\":\"An order, tagged \\\"#1\\\" when urgent\",\"fields\":[{\"name\":\"id\",\"type\":\"long\"},{\"name\":\"customer\",\"type\":{\"fields\":[{\"name\":\"name\",\"type\":\"string\"}],\"name\":\"Customer\",\"type\":\"record\"}},{\"default\":null,\"name\":\"referrer\",\"type\":[\"null\",\"Customer\"]}],\"name\":\"Order\",\"type\":\"record\"}";
+
+    /// The Avro schema of `Order`, as pretty-printed JSON.
+    pub const SCHEMA_PRETTY: &'static str = r##"{
+  "doc": "An order, tagged \"#1\" when urgent",
+  "fields": [
+    {
+      "name": "id",
+      "type": "long"
+    },
+    {
+      "name": "customer",
+      "type": {
+        "fields": [
+          {
+            "name": "name",
+            "type": "string"
+          }
+        ],
+        "name": "Customer",
+        "type": "record"
+      }
+    },
+    {
+      "default": null,
+      "name": "referrer",
+      "type": [
+        "null",
+        "Customer"
+      ]
+    }
+  ],
+  "name": "Order",
+  "type": "record"
+}"##;
+
+    /// Returns the Avro schema of `Order`, as a JSON value.
+    pub fn schema_json() -> serde_json::Value {
+        serde_json::from_str(Self::SCHEMA).expect("valid generated schema")
+    }
+}
@@ -0,0 +1,23 @@
+
+#[derive(PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Customer {
+    pub id: i64,
+    pub email: String,
+    pub r#type: String,
+    #[serde(default = "default_customer_ssn")]
+    pub ssn: Option<String>,
+}
+
+#[inline(always)]
+fn default_customer_ssn() -> Option<String> { None }
+
+impl std::fmt::Debug for Customer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Customer")
+            .field("id", &self.id)
+            .field("email", &"****")
+            .field("type", &self.r#type)
+            .field("ssn", &"****")
+            .finish()
+    }
+}
@@ -0,0 +1,16 @@
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Packet {
+    #[serde(default = "default_packet_tags")]
+    pub tags: smallvec::SmallVec<[String; 2]>,
+    pub samples: smallvec::SmallVec<[i32; 8]>,
+    #[serde(default = "default_packet_hops")]
+    pub hops: Option<smallvec::SmallVec<[i64; 2]>>,
+    pub matrix: smallvec::SmallVec<[Vec<f64>; 2]>,
+}
+
+#[inline(always)]
+fn default_packet_tags() -> smallvec::SmallVec<[String; 2]> { smallvec::smallvec!["hot".to_owned()] }
+
+#[inline(always)]
+fn default_packet_hops() -> Option<smallvec::SmallVec<[i64; 2]>> { None }
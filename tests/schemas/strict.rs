@@ -0,0 +1,12 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Order {
+    pub id: i64,
+    #[serde(deserialize_with = "serde::Deserialize::deserialize")]
+    pub note: Option<String>,
+    #[serde(default = "default_order_coupon")]
+    pub coupon: Option<String>,
+}
+
+#[inline(always)]
+fn default_order_coupon() -> Option<String> { None }
@@ -0,0 +1,50 @@
+
+/// The colors of a traffic light.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub struct Color(pub String);
+
+impl Color {
+    pub const RED: &'static str = "red";
+    pub const AMBER: &'static str = "amber";
+    pub const GREEN: &'static str = "green";
+    /// Proceed with caution.
+    pub const FLASHING_AMBER: &'static str = "flashingAmber";
+
+    /// The symbols of the schema of `Color`.
+    pub const SYMBOLS: &'static [&'static str] = &[Self::RED, Self::AMBER, Self::GREEN, Self::FLASHING_AMBER];
+
+    /// Returns whether the symbol is one of the [`SYMBOLS`](Self::SYMBOLS) of the schema.
+    pub fn is_known(&self) -> bool {
+        Self::SYMBOLS.contains(&self.0.as_str())
+    }
+}
+
+impl From<&str> for Color {
+    fn from(symbol: &str) -> Self {
+        Self(symbol.to_string())
+    }
+}
+
+impl PartialEq<&str> for Color {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Light {
+    pub color: Color,
+}
+
+#[inline(always)]
+fn default_light_color() -> Color { Color::from(Color::GREEN) }
+
+impl Default for Light {
+    fn default() -> Light {
+        Light {
+            color: default_light_color(),
+        }
+    }
+}
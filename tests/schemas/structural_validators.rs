@@ -0,0 +1,398 @@
+
+/// A decimal number of the Avro `decimal` logical type, `mantissa * 10^-scale`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Decimal {
+    /// The unscaled value.
+    pub mantissa: i128,
+    /// The number of digits of the fractional part.
+    pub scale: u32,
+}
+
+impl Decimal {
+    /// Returns the decimal `mantissa * 10^-scale` of a schema of the given `precision` and
+    /// `scale`, and `size` for `fixed` decimals (`None` for `bytes` ones), or the error of
+    /// a value invalid for the schema, which would fail when encoded.
+    pub fn try_new(
+        mantissa: i128,
+        scale: u32,
+        precision: u32,
+        size: Option<usize>,
+    ) -> Result<Decimal, DecimalError> {
+        if scale > precision {
+            return Err(DecimalError::Scale { scale, precision });
+        }
+        let digits = mantissa.unsigned_abs().checked_ilog10().map_or(1, |log| log + 1);
+        if digits > precision {
+            return Err(DecimalError::Precision { digits, precision });
+        }
+        // The length of the shortest big-endian two's complement bytes of the mantissa
+        let bits = 129 - if mantissa < 0 { !mantissa } else { mantissa }.leading_zeros();
+        let len = bits.div_ceil(8) as usize;
+        if let Some(size) = size.filter(|size| len > *size) {
+            return Err(DecimalError::Size { len, size });
+        }
+        Ok(Decimal { mantissa, scale })
+    }
+
+    /// Decodes the Avro encoding of a decimal of the given `scale`, the big-endian two's
+    /// complement bytes of its mantissa, `None` if the mantissa does not fit in an `i128`.
+    pub fn from_avro_bytes(bytes: &[u8], scale: u32) -> Option<Decimal> {
+        let sign = if bytes.first().is_some_and(|b| *b >= 0x80) { 0xff } else { 0 };
+        let mut bytes = bytes;
+        if bytes.len() > 16 {
+            let (extension, rest) = bytes.split_at(bytes.len() - 16);
+            if extension.iter().any(|b| *b != sign) || (rest[0] ^ sign) >= 0x80 {
+                return None;
+            }
+            bytes = rest;
+        }
+        let mut buf = [sign; 16];
+        buf[16 - bytes.len()..].copy_from_slice(bytes);
+        Some(Decimal { mantissa: i128::from_be_bytes(buf), scale })
+    }
+
+    /// Returns the Avro encoding of the decimal, the big-endian two's complement bytes of
+    /// its mantissa.
+    pub fn to_avro_bytes(&self) -> [u8; 16] {
+        self.mantissa.to_be_bytes()
+    }
+
+    /// Returns the same number with the given `scale`, `None` if digits would be lost or if
+    /// the mantissa would overflow.
+    pub fn rescale(&self, scale: u32) -> Option<Decimal> {
+        let mantissa = if scale >= self.scale {
+            self.mantissa.checked_mul(10i128.checked_pow(scale - self.scale)?)?
+        } else {
+            let factor = 10i128.checked_pow(self.scale - scale)?;
+            if self.mantissa % factor != 0 {
+                return None;
+            }
+            self.mantissa / factor
+        };
+        Some(Decimal { mantissa, scale })
+    }
+
+    /// Serializes the decimal as the Avro bytes of its mantissa in the given `scale`.
+    pub fn serialize_with_scale<S>(&self, scale: u32, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let decimal = self.rescale(scale).ok_or_else(|| {
+            serde::ser::Error::custom(format!("{self} cannot be written with scale {scale}"))
+        })?;
+        serializer.serialize_bytes(&decimal.to_avro_bytes())
+    }
+
+    /// Deserializes a decimal of the given `scale` from the Avro bytes of its mantissa.
+    pub fn deserialize_with_scale<'de, D>(deserializer: D, scale: u32) -> Result<Decimal, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor(u32);
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Decimal;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("the bytes of a decimal")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Decimal, E> {
+                Decimal::from_avro_bytes(v, self.0)
+                    .ok_or_else(|| E::custom("decimal mantissa overflowing i128"))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Decimal, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut bytes = vec![];
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+        }
+
+        deserializer.deserialize_bytes(Visitor(scale))
+    }
+
+    /// Serializes an optional decimal like [`serialize_with_scale`](Decimal::serialize_with_scale).
+    pub fn serialize_option_with_scale<S>(
+        value: &Option<Decimal>,
+        scale: u32,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct Scaled<'a>(&'a Decimal, u32);
+
+        impl serde::Serialize for Scaled<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.serialize_with_scale(self.1, serializer)
+            }
+        }
+
+        match value {
+            Some(decimal) => serializer.serialize_some(&Scaled(decimal, scale)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes an optional decimal like
+    /// [`deserialize_with_scale`](Decimal::deserialize_with_scale).
+    pub fn deserialize_option_with_scale<'de, D>(
+        deserializer: D,
+        scale: u32,
+    ) -> Result<Option<Decimal>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Scaled(Decimal);
+
+        impl<'de> serde::Deserialize<'de> for Scaled {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                // The scale is set once deserialized
+                Decimal::deserialize_with_scale(deserializer, 0).map(Scaled)
+            }
+        }
+
+        let value = <Option<Scaled> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(value.map(|Scaled(decimal)| Decimal { scale, ..decimal }))
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{sign}{integer}.{fraction}")
+    }
+}
+
+/// The error of a decimal invalid for its schema, see [`Decimal::try_new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalError {
+    /// The mantissa has more digits than the precision of the schema.
+    Precision { digits: u32, precision: u32 },
+    /// The scale of the schema is greater than its precision.
+    Scale { scale: u32, precision: u32 },
+    /// The mantissa is encoded in more bytes than the size of the fixed of the schema.
+    Size { len: usize, size: usize },
+}
+
+impl std::fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecimalError::Precision { digits, precision } => {
+                write!(f, "{digits} digits exceed the decimal precision {precision}")
+            }
+            DecimalError::Scale { scale, precision } => {
+                write!(f, "The decimal scale {scale} exceeds its precision {precision}")
+            }
+            DecimalError::Size { len, size } => {
+                write!(f, "{len} bytes exceed the decimal fixed size {size}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecimalError {}
+
+/// The fields of a record violating the constraints of their schema, returned by its
+/// `validate` method.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationErrors {
+    /// The path of each invalid field, e.g. `address.zip`, and the constraint it violates.
+    pub errors: Vec<(String, String)>,
+}
+
+impl ValidationErrors {
+    /// Adds a violated constraint of the field at `path`.
+    pub fn push(&mut self, path: &str, message: &str) {
+        self.errors.push((path.to_string(), message.to_string()));
+    }
+
+    /// Adds the errors of a nested record, their paths being prefixed by the `path` of its field.
+    pub fn nest(&mut self, path: &str, result: Result<(), ValidationErrors>) {
+        if let Err(nested) = result {
+            for (nested_path, message) in nested.errors {
+                self.errors.push((format!("{path}.{nested_path}"), message));
+            }
+        }
+    }
+
+    /// Returns the errors, if there are some.
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (path, message)) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{path}` {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Line {
+    #[serde(default = "default_line_stage")]
+    pub stage: Option<Stage>,
+    pub quantity: i32,
+}
+
+#[inline(always)]
+fn default_line_stage() -> Option<Stage> { None }
+
+impl Line {
+    /// Checks the values of the fields against the constraints of the schema, e.g. the
+    /// precision of decimals, the symbols of string enums or the `minimum`, `maximum` and
+    /// `pattern` of fields, and validates the records they hold.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if let Some(value) = &self.stage {
+            if !value.is_known() {
+                errors.push("stage", "must be a symbol of `Stage`");
+            }
+        }
+        if self.quantity < 1 {
+            errors.push("quantity", "must be at least 1");
+        }
+        errors.into_result()
+    }
+}
+
+pub type Checksum = [u8; 4];
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Invoice {
+    pub stage: Stage,
+    pub history: Vec<Stage>,
+    #[serde(with = "invoice_decimals::scale_2")]
+    pub price: Decimal,
+    #[serde(with = "invoice_decimals::scale_1_option")]
+    #[serde(default = "default_invoice_discount")]
+    pub discount: Option<Decimal>,
+    pub lines: ::std::collections::HashMap<String, Line>,
+    #[serde(with = "apache_avro::serde_avro_fixed")]
+    pub checksum: Checksum,
+}
+
+#[inline(always)]
+fn default_invoice_discount() -> Option<Decimal> { None }
+
+/// Serde functions of the decimal fields, with the scale of their schema.
+mod invoice_decimals {
+    use super::Decimal;
+
+    pub mod scale_1_option {
+        pub fn serialize<S>(value: &Option<super::Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            super::Decimal::serialize_option_with_scale(value, 1, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<super::Decimal>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            super::Decimal::deserialize_option_with_scale(deserializer, 1)
+        }
+    }
+
+    pub mod scale_2 {
+        pub fn serialize<S>(value: &super::Decimal, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            value.serialize_with_scale(2, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<super::Decimal, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            super::Decimal::deserialize_with_scale(deserializer, 2)
+        }
+    }
+}
+
+impl Invoice {
+    /// Checks the values of the fields against the constraints of the schema, e.g. the
+    /// precision of decimals, the symbols of string enums or the `minimum`, `maximum` and
+    /// `pattern` of fields, and validates the records they hold.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if !self.stage.is_known() {
+            errors.push("stage", "must be a symbol of `Stage`");
+        }
+        for (i, item) in self.history.iter().enumerate() {
+            if !item.is_known() {
+                errors.push(&format!("history.{i}"), "must be a symbol of `Stage`");
+            }
+        }
+        let decimal = self.price.rescale(2);
+        if !decimal.is_some_and(|decimal| Decimal::try_new(decimal.mantissa, 2, 4, Some(2)).is_ok()) {
+            errors.push("price", "must fit the decimal precision 4 and scale 2");
+        }
+        if let Some(value) = &self.discount {
+            let decimal = value.rescale(1);
+            if !decimal.is_some_and(|decimal| Decimal::try_new(decimal.mantissa, 1, 3, None).is_ok()) {
+                errors.push("discount", "must fit the decimal precision 3 and scale 1");
+            }
+        }
+        for (key, item) in &self.lines {
+            errors.nest(&format!("lines.{key}"), item.validate());
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub struct Stage(pub String);
+
+impl Stage {
+    pub const OPEN: &'static str = "open";
+    pub const SHIPPED: &'static str = "shipped";
+
+    /// The symbols of the schema of `Stage`.
+    pub const SYMBOLS: &'static [&'static str] = &[Self::OPEN, Self::SHIPPED];
+
+    /// Returns whether the symbol is one of the [`SYMBOLS`](Self::SYMBOLS) of the schema.
+    pub fn is_known(&self) -> bool {
+        Self::SYMBOLS.contains(&self.0.as_str())
+    }
+}
+
+impl From<&str> for Stage {
+    fn from(symbol: &str) -> Self {
+        Self(symbol.to_string())
+    }
+}
+
+impl PartialEq<&str> for Stage {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
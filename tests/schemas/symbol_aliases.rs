@@ -0,0 +1,13 @@
+
+/// A traffic signal, whose colors were renamed.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Signal {
+    #[serde(rename = "STOP")]
+    #[serde(alias = "RED")]
+    Stop,
+    #[serde(rename = "CAUTION")]
+    Caution,
+    #[serde(rename = "GO")]
+    #[serde(alias = "GREEN", alias = "green")]
+    Go,
+}
@@ -0,0 +1,14 @@
+
+/// A traffic light.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Light {
+    /// Stop.
+    #[serde(rename = "RED")]
+    Red,
+    #[serde(rename = "AMBER")]
+    Amber,
+    /// Go.
+    /// Unless the way is not clear.
+    #[serde(rename = "GREEN")]
+    Green,
+}
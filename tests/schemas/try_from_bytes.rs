@@ -0,0 +1,36 @@
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Origin {
+    #[serde(rename = "LOCAL")]
+    Local,
+    #[serde(rename = "REMOTE")]
+    Remote,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Ping {
+    pub seq: i64,
+    pub host: String,
+    pub origin: Origin,
+}
+
+impl TryFrom<&[u8]> for Ping {
+    type Error = apache_avro::Error;
+
+    /// Decodes an Avro datum written with the schema of `Ping`.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        static SCHEMA: std::sync::OnceLock<apache_avro::Schema> = std::sync::OnceLock::new();
+        let schema = SCHEMA.get_or_init(|| {
+            apache_avro::Schema::parse_str("{\"fields\":[{\"name\":\"seq\",\"type\":\"long\"},{\"name\":\"host\",\"type\":\"string\"},{\"name\":\"origin\",\"type\":{\"name\":\"Origin\",\"namespace\":\"test\",\"symbols\":[\"LOCAL\",\"REMOTE\"],\"type\":\"enum\"}}],\"name\":\"Ping\",\"namespace\":\"test\",\"type\":\"record\"}").expect("valid generated schema")
+        });
+        let mut reader = bytes;
+        let value = apache_avro::from_avro_datum(schema, &mut reader, None)?;
+        if !reader.is_empty() {
+            return Err(apache_avro::Error::DeserializeValue(format!(
+                "{} bytes left after the datum of `Ping`",
+                reader.len()
+            )));
+        }
+        apache_avro::from_value(&value)
+    }
+}
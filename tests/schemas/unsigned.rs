@@ -0,0 +1,94 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Counters {
+    #[serde(with = "counters_unsigned::long")]
+    pub id: u64,
+    #[serde(with = "counters_unsigned::int")]
+    #[serde(default = "default_counters_hits")]
+    pub hits: u32,
+    #[serde(with = "counters_unsigned::long_option")]
+    #[serde(default = "default_counters_parent")]
+    pub parent: Option<u64>,
+    pub delta: i32,
+}
+
+#[inline(always)]
+fn default_counters_hits() -> u32 { 0 }
+
+#[inline(always)]
+fn default_counters_parent() -> Option<u64> { None }
+
+/// Serde functions of the unsigned fields, as the signed integers of their schema.
+mod counters_unsigned {
+    pub mod int {
+        pub fn serialize<S>(value: &u32, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let overflow = |v: u32| -> S::Error {
+                serde::ser::Error::custom(format!("{v} overflows an Avro int"))
+            };
+            let value = i32::try_from(*value).map_err(|_| overflow(*value))?;
+            serde::Serialize::serialize(&value, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u32, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let negative = |v: i32| -> D::Error {
+                serde::de::Error::custom(format!("negative value {v} of an unsigned field"))
+            };
+            let value: i32 = serde::Deserialize::deserialize(deserializer)?;
+            u32::try_from(value).map_err(|_| negative(value))
+        }
+    }
+
+    pub mod long {
+        pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let overflow = |v: u64| -> S::Error {
+                serde::ser::Error::custom(format!("{v} overflows an Avro long"))
+            };
+            let value = i64::try_from(*value).map_err(|_| overflow(*value))?;
+            serde::Serialize::serialize(&value, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let negative = |v: i64| -> D::Error {
+                serde::de::Error::custom(format!("negative value {v} of an unsigned field"))
+            };
+            let value: i64 = serde::Deserialize::deserialize(deserializer)?;
+            u64::try_from(value).map_err(|_| negative(value))
+        }
+    }
+
+    pub mod long_option {
+        pub fn serialize<S>(value: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let overflow = |v: u64| -> S::Error {
+                serde::ser::Error::custom(format!("{v} overflows an Avro long"))
+            };
+            let value = value.map(|v| i64::try_from(v).map_err(|_| overflow(v))).transpose()?;
+            serde::Serialize::serialize(&value, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let negative = |v: i64| -> D::Error {
+                serde::de::Error::custom(format!("negative value {v} of an unsigned field"))
+            };
+            let value: Option<i64> = serde::Deserialize::deserialize(deserializer)?;
+            value.map(|v| u64::try_from(v).map_err(|_| negative(v))).transpose()
+        }
+    }
+}
@@ -0,0 +1,68 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize, serde_valid::Validate)]
+pub struct Address {
+    #[validate(pattern = "^[0-9]{5}$")]
+    pub zip: String,
+    #[validate(minimum = -5)]
+    pub floor: Option<i32>,
+}
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize, serde_valid::Validate)]
+pub struct Signup {
+    #[validate(minimum = 13)]
+    #[validate(maximum = 150)]
+    pub age: i32,
+    #[validate(minimum = 0.0)]
+    #[validate(maximum = 1.5)]
+    pub score: f64,
+    #[serde(with = "signup_unsigned::long")]
+    #[validate(maximum = 1000)]
+    pub visits: u64,
+    #[serde(default = "default_signup_email")]
+    #[validate(pattern = "^[^@]+@[^@]+$")]
+    pub email: Option<String>,
+    #[validate]
+    pub address: Address,
+    #[serde(default = "default_signup_billing")]
+    #[validate]
+    pub billing: Option<Address>,
+    #[validate]
+    pub previous: Vec<Address>,
+    #[validate]
+    pub others: ::std::collections::HashMap<String, Address>,
+    #[validate(maximum = 2.5)]
+    pub ratio: f32,
+}
+
+#[inline(always)]
+fn default_signup_email() -> Option<String> { None }
+
+#[inline(always)]
+fn default_signup_billing() -> Option<Address> { None }
+
+/// Serde functions of the unsigned fields, as the signed integers of their schema.
+mod signup_unsigned {
+    pub mod long {
+        pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let overflow = |v: u64| -> S::Error {
+                serde::ser::Error::custom(format!("{v} overflows an Avro long"))
+            };
+            let value = i64::try_from(*value).map_err(|_| overflow(*value))?;
+            serde::Serialize::serialize(&value, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let negative = |v: i64| -> D::Error {
+                serde::de::Error::custom(format!("negative value {v} of an unsigned field"))
+            };
+            let value: i64 = serde::Deserialize::deserialize(deserializer)?;
+            u64::try_from(value).map_err(|_| negative(value))
+        }
+    }
+}
@@ -0,0 +1,76 @@
+
+/// Brings the `validate` method of the nested records in scope of their `Validate` derive.
+#[allow(unused_imports)]
+use validator::Validate as _;
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize, validator::Validate)]
+pub struct Address {
+    #[validate(regex(path = *ADDRESS_ZIP_PATTERN))]
+    pub zip: String,
+    #[validate(range(min = -5))]
+    pub floor: Option<i32>,
+}
+
+static ADDRESS_ZIP_PATTERN: std::sync::LazyLock<regex::Regex> =
+    std::sync::LazyLock::new(|| regex::Regex::new("^[0-9]{5}$").expect("valid pattern"));
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize, validator::Validate)]
+pub struct Signup {
+    #[validate(range(min = 13, max = 150))]
+    pub age: i32,
+    #[validate(range(min = 0.0, max = 1.5))]
+    pub score: f64,
+    #[serde(with = "signup_unsigned::long")]
+    #[validate(range(max = 1000))]
+    pub visits: u64,
+    #[serde(default = "default_signup_email")]
+    #[validate(regex(path = *SIGNUP_EMAIL_PATTERN))]
+    pub email: Option<String>,
+    #[validate(nested)]
+    pub address: Address,
+    #[serde(default = "default_signup_billing")]
+    #[validate(nested)]
+    pub billing: Option<Address>,
+    #[validate(nested)]
+    pub previous: Vec<Address>,
+    #[validate(nested)]
+    pub others: ::std::collections::HashMap<String, Address>,
+    #[validate(range(max = 2.5))]
+    pub ratio: f32,
+}
+
+#[inline(always)]
+fn default_signup_email() -> Option<String> { None }
+
+#[inline(always)]
+fn default_signup_billing() -> Option<Address> { None }
+
+static SIGNUP_EMAIL_PATTERN: std::sync::LazyLock<regex::Regex> =
+    std::sync::LazyLock::new(|| regex::Regex::new("^[^@]+@[^@]+$").expect("valid pattern"));
+
+/// Serde functions of the unsigned fields, as the signed integers of their schema.
+mod signup_unsigned {
+    pub mod long {
+        pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let overflow = |v: u64| -> S::Error {
+                serde::ser::Error::custom(format!("{v} overflows an Avro long"))
+            };
+            let value = i64::try_from(*value).map_err(|_| overflow(*value))?;
+            serde::Serialize::serialize(&value, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let negative = |v: i64| -> D::Error {
+                serde::de::Error::custom(format!("negative value {v} of an unsigned field"))
+            };
+            let value: i64 = serde::Deserialize::deserialize(deserializer)?;
+            u64::try_from(value).map_err(|_| negative(value))
+        }
+    }
+}
@@ -0,0 +1,171 @@
+
+/// The fields of a record violating the constraints of their schema, returned by its
+/// `validate` method.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationErrors {
+    /// The path of each invalid field, e.g. `address.zip`, and the constraint it violates.
+    pub errors: Vec<(String, String)>,
+}
+
+impl ValidationErrors {
+    /// Adds a violated constraint of the field at `path`.
+    pub fn push(&mut self, path: &str, message: &str) {
+        self.errors.push((path.to_string(), message.to_string()));
+    }
+
+    /// Adds the errors of a nested record, their paths being prefixed by the `path` of its field.
+    pub fn nest(&mut self, path: &str, result: Result<(), ValidationErrors>) {
+        if let Err(nested) = result {
+            for (nested_path, message) in nested.errors {
+                self.errors.push((format!("{path}.{nested_path}"), message));
+            }
+        }
+    }
+
+    /// Returns the errors, if there are some.
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (path, message)) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{path}` {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Address {
+    pub zip: String,
+    pub floor: Option<i32>,
+}
+
+impl Address {
+    /// Checks the values of the fields against the constraints of the schema, e.g. the
+    /// precision of decimals, the symbols of string enums or the `minimum`, `maximum` and
+    /// `pattern` of fields, and validates the records they hold.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        {
+            static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+            let pattern =
+                PATTERN.get_or_init(|| regex::Regex::new("^[0-9]{5}$").expect("valid pattern"));
+            if !pattern.is_match(&self.zip) {
+                errors.push("zip", "must match the pattern `^[0-9]{5}$`");
+            }
+        }
+        if let Some(value) = &self.floor {
+            if *value < -5 {
+                errors.push("floor", "must be at least -5");
+            }
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Signup {
+    pub age: i32,
+    pub score: f64,
+    #[serde(with = "signup_unsigned::long")]
+    pub visits: u64,
+    #[serde(default = "default_signup_email")]
+    pub email: Option<String>,
+    pub address: Address,
+    #[serde(default = "default_signup_billing")]
+    pub billing: Option<Address>,
+    pub previous: Vec<Address>,
+    pub others: ::std::collections::HashMap<String, Address>,
+    pub ratio: f32,
+}
+
+#[inline(always)]
+fn default_signup_email() -> Option<String> { None }
+
+#[inline(always)]
+fn default_signup_billing() -> Option<Address> { None }
+
+/// Serde functions of the unsigned fields, as the signed integers of their schema.
+mod signup_unsigned {
+    pub mod long {
+        pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let overflow = |v: u64| -> S::Error {
+                serde::ser::Error::custom(format!("{v} overflows an Avro long"))
+            };
+            let value = i64::try_from(*value).map_err(|_| overflow(*value))?;
+            serde::Serialize::serialize(&value, serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let negative = |v: i64| -> D::Error {
+                serde::de::Error::custom(format!("negative value {v} of an unsigned field"))
+            };
+            let value: i64 = serde::Deserialize::deserialize(deserializer)?;
+            u64::try_from(value).map_err(|_| negative(value))
+        }
+    }
+}
+
+impl Signup {
+    /// Checks the values of the fields against the constraints of the schema, e.g. the
+    /// precision of decimals, the symbols of string enums or the `minimum`, `maximum` and
+    /// `pattern` of fields, and validates the records they hold.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        if self.age < 13 {
+            errors.push("age", "must be at least 13");
+        }
+        if self.age > 150 {
+            errors.push("age", "must be at most 150");
+        }
+        if self.score < 0.0 {
+            errors.push("score", "must be at least 0");
+        }
+        if self.score > 1.5 {
+            errors.push("score", "must be at most 1.5");
+        }
+        if self.visits > 1000 {
+            errors.push("visits", "must be at most 1000");
+        }
+        if let Some(value) = &self.email {
+            static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+            let pattern =
+                PATTERN.get_or_init(|| regex::Regex::new("^[^@]+@[^@]+$").expect("valid pattern"));
+            if !pattern.is_match(value) {
+                errors.push("email", "must match the pattern `^[^@]+@[^@]+$`");
+            }
+        }
+        errors.nest("address", self.address.validate());
+        if let Some(value) = &self.billing {
+            errors.nest("billing", value.validate());
+        }
+        for (i, item) in self.previous.iter().enumerate() {
+            errors.nest(&format!("previous.{i}"), item.validate());
+        }
+        for (key, item) in &self.others {
+            errors.nest(&format!("others.{key}"), item.validate());
+        }
+        if self.ratio > 2.5 {
+            errors.push("ratio", "must be at most 2.5");
+        }
+        errors.into_result()
+    }
+}
@@ -0,0 +1,40 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Nickname {
+    pub r#type: Option<String>,
+}
+
+impl From<Option<String>> for Nickname {
+    fn from(r#type: Option<String>) -> Self {
+        Self { r#type }
+    }
+}
+
+impl From<Nickname> for Option<String> {
+    fn from(value: Nickname) -> Self {
+        value.r#type
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct UserId {
+    pub value: i64,
+}
+
+impl From<i64> for UserId {
+    fn from(value: i64) -> Self {
+        Self { value }
+    }
+}
+
+impl From<UserId> for i64 {
+    fn from(value: UserId) -> Self {
+        value.value
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Account {
+    pub id: UserId,
+    pub nickname: Nickname,
+}
@@ -0,0 +1,34 @@
+
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct User {
+    pub id: i64,
+    pub name: String,
+    pub score: f64,
+    #[serde(default = "default_user_age")]
+    pub age: i32,
+    #[serde(default = "default_user_tags")]
+    pub tags: Vec<String>,
+}
+
+#[inline(always)]
+fn default_user_age() -> i32 { 0 }
+
+#[inline(always)]
+fn default_user_tags() -> Vec<String> { vec![] }
+
+impl User {
+    /// Decodes an Avro datum written with `writer_schema`, resolving it against the schema
+    /// of `User`: fields are matched by name whatever their order, missing fields
+    /// take their default value and numbers are promoted.
+    pub fn decode_with_writer_schema(
+        bytes: &[u8],
+        writer_schema: &apache_avro::Schema,
+    ) -> Result<Self, apache_avro::Error> {
+        static SCHEMA: std::sync::OnceLock<apache_avro::Schema> = std::sync::OnceLock::new();
+        let schema = SCHEMA.get_or_init(|| {
+            apache_avro::Schema::parse_str("{\"fields\":[{\"name\":\"id\",\"type\":\"long\"},{\"name\":\"name\",\"type\":\"string\"},{\"name\":\"score\",\"type\":\"double\"},{\"default\":0,\"name\":\"age\",\"type\":\"int\"},{\"default\":[],\"name\":\"tags\",\"type\":{\"items\":\"string\",\"type\":\"array\"}}],\"name\":\"User\",\"namespace\":\"test\",\"type\":\"record\"}").expect("valid generated schema")
+        });
+        let value = apache_avro::from_avro_datum(writer_schema, &mut &bytes[..], Some(schema))?;
+        apache_avro::from_value(&value)
+    }
+}
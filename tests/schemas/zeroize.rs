@@ -0,0 +1,62 @@
+
+#[derive(PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Credentials {
+    pub user: String,
+    #[serde(with = "apache_avro::serde_avro_bytes")]
+    pub password: Vec<u8>,
+}
+
+impl zeroize::Zeroize for Credentials {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.user);
+        zeroize::Zeroize::zeroize(&mut self.password);
+    }
+}
+
+impl Drop for Credentials {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("user", &"****")
+            .field("password", &"****")
+            .finish()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Login {
+    pub host: String,
+    pub credentials: Credentials,
+    #[serde(default = "default_login_otp")]
+    pub otp: Option<String>,
+}
+
+#[inline(always)]
+fn default_login_otp() -> Option<String> { None }
+
+impl zeroize::Zeroize for Login {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.otp);
+    }
+}
+
+impl Drop for Login {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+impl std::fmt::Debug for Login {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Login")
+            .field("host", &self.host)
+            .field("credentials", &self.credentials)
+            .field("otp", &"****")
+            .finish()
+    }
+}
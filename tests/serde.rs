@@ -3,7 +3,25 @@ mod schemas;
 
 use std::collections::HashMap;
 
+use apache_avro::types::Value;
+use apache_avro::{AvroSchema, SpecificSingleObjectReader, SpecificSingleObjectWriter};
+
+use crate::schemas::avro_json;
+use crate::schemas::avro_message_impls::{AvroMessage, Shipment, ShipmentStatus};
+use crate::schemas::avro_schema_impls::{Reading, Unit};
+use crate::schemas::hex_debug::Blob;
+use crate::schemas::lenient::{Order, Priority, Status};
 use crate::schemas::multi_valued_union_with_avro_rs_unions::Contact;
+use crate::schemas::names_sanitize::{_3dPoint, Axis};
+use crate::schemas::nullable_array::{Tags, UnionStringLong};
+use crate::schemas::nullable_bytes::BytesData;
+use crate::schemas::protocol::{QuotaExceeded, Rejected, SendError};
+use crate::schemas::sensitive::Customer;
+use crate::schemas::string_enums::{Color, Light};
+use crate::schemas::symbol_aliases::Signal;
+use crate::schemas::try_from_bytes::{Origin, Ping};
+use crate::schemas::validators::{Address, Signup, ValidationErrors};
+use crate::schemas::writer_schema_decoders::User;
 
 #[test]
 fn multi_valued_union_serde() {
@@ -25,3 +43,885 @@ fn multi_valued_union_serde() {
     let value: Contact = apache_avro::from_value(&value).unwrap();
     assert_eq!(expected, value);
 }
+
+#[test]
+fn avro_message_impls() {
+    let shipment = Shipment {
+        id: "S1".to_string(),
+        status: ShipmentStatus::Sent,
+        note: Some("fragile".to_string()),
+    };
+    let messages: Vec<Box<dyn AvroMessage>> = vec![
+        Box::new(shipment.clone()),
+        Box::new(ShipmentStatus::Pending),
+    ];
+    assert_eq!(
+        vec!["test.Shipment", "test.ShipmentStatus"],
+        messages.iter().map(|m| m.fullname()).collect::<Vec<_>>()
+    );
+
+    let schema = apache_avro::Schema::parse_str(messages[0].schema_json()).unwrap();
+    assert_eq!(
+        std::fs::read_to_string("tests/schemas/avro_message_impls.avsc")
+            .map(|raw| apache_avro::Schema::parse_str(&raw).unwrap())
+            .unwrap(),
+        schema
+    );
+    let value = apache_avro::from_avro_datum(&schema, &mut &messages[0].encode()[..], None);
+    assert_eq!(shipment, apache_avro::from_value(&value.unwrap()).unwrap());
+    assert_eq!(vec![0], messages[1].encode());
+}
+
+#[test]
+fn string_enums() {
+    let raw = std::fs::read_to_string("tests/schemas/string_enums.avsc").unwrap();
+    let schema = apache_avro::Schema::parse_str(&raw).unwrap();
+    let light = Light {
+        color: Color::from(Color::FLASHING_AMBER),
+    };
+    let value = apache_avro::to_value(&light).unwrap();
+    let datum = apache_avro::to_avro_datum(&schema, value).unwrap();
+    let value = apache_avro::from_avro_datum(&schema, &mut &datum[..], None).unwrap();
+    assert_eq!(light, apache_avro::from_value::<Light>(&value).unwrap());
+    assert!(light.color.is_known() && light.color == Color::FLASHING_AMBER);
+    assert_eq!(Color::GREEN, Light::default().color.0);
+
+    // Symbols added by producers are kept, decoding with the writer schema
+    let writer =
+        apache_avro::Schema::parse_str(&raw.replace("\"red\",", "\"red\", \"blue\",")).unwrap();
+    let value = apache_avro::from_avro_datum(&writer, &mut &[2][..], None).unwrap();
+    let light = apache_avro::from_value::<Light>(&value).unwrap();
+    assert_eq!(Color::from("blue"), light.color);
+    assert!(!light.color.is_known());
+}
+
+#[test]
+fn avro_schema_impls() {
+    let schema = apache_avro::Schema::parse_str(
+        &std::fs::read_to_string("tests/schemas/avro_schema_impls.avsc").unwrap(),
+    )
+    .unwrap();
+    assert_eq!(schema, Reading::get_schema());
+    assert_eq!(
+        Some("test.Unit".to_string()),
+        Unit::get_schema().name().map(|n| n.fullname(None))
+    );
+
+    let reading = Reading {
+        sensor: "cellar".to_string(),
+        value: 12.5,
+        unit: Unit::Celsius,
+        previous: Some(Unit::Kelvin),
+    };
+    let mut buf = vec![];
+    SpecificSingleObjectWriter::<Reading>::with_capacity(64)
+        .unwrap()
+        .write_ref(&reading, &mut buf)
+        .unwrap();
+    let read = SpecificSingleObjectReader::<Reading>::new()
+        .unwrap()
+        .read(&mut &buf[..])
+        .unwrap();
+    assert_eq!(reading, read);
+}
+
+#[test]
+fn try_from_bytes() {
+    let ping = Ping {
+        seq: 42,
+        host: "localhost".to_string(),
+        origin: Origin::Remote,
+    };
+    let schema = apache_avro::Schema::parse_str(
+        &std::fs::read_to_string("tests/schemas/try_from_bytes.avsc").unwrap(),
+    )
+    .unwrap();
+    let mut datum =
+        apache_avro::to_avro_datum(&schema, apache_avro::to_value(&ping).unwrap()).unwrap();
+    assert_eq!(ping, Ping::try_from(&datum[..]).unwrap());
+
+    datum.push(0);
+    assert_eq!(
+        "Failed to deserialize Avro value into value: 1 bytes left after the datum of `Ping`",
+        Ping::try_from(&datum[..]).unwrap_err().to_string()
+    );
+    assert!(Ping::try_from(&datum[..1]).is_err());
+}
+
+#[test]
+fn decode_with_writer_schema() {
+    let writer_schema = apache_avro::Schema::parse_str(
+        r#"
+{
+  "type": "record",
+  "name": "User",
+  "namespace": "test",
+  "fields": [
+    {"name": "score", "type": "float"},
+    {"name": "name", "type": "string"},
+    {"name": "id", "type": "int"}
+  ]
+}
+"#,
+    )
+    .unwrap();
+    let mut record = apache_avro::types::Record::new(&writer_schema).unwrap();
+    record.put("score", 1.5f32);
+    record.put("name", "Jane");
+    record.put("id", 7);
+    let datum = apache_avro::to_avro_datum(&writer_schema, record).unwrap();
+
+    let user = User::decode_with_writer_schema(&datum, &writer_schema).unwrap();
+    assert_eq!(
+        User {
+            id: 7,
+            name: "Jane".to_string(),
+            score: 1.5,
+            age: 0,
+            tags: vec![],
+        },
+        user
+    );
+}
+
+#[test]
+fn sensitive_debug() {
+    let customer = Customer {
+        id: 1,
+        email: "jane@acme.com".to_string(),
+        r#type: "premium".to_string(),
+        ssn: Some("123-45-6789".to_string()),
+    };
+    assert_eq!(
+        r#"Customer { id: 1, email: "****", type: "premium", ssn: "****" }"#,
+        format!("{customer:?}")
+    );
+}
+
+#[test]
+fn hex_debug() {
+    let blob = Blob {
+        id: 1,
+        data: vec![0xde, 0xad, 0xbe, 0xef],
+        digest: [0xab; 16],
+        previous: None,
+        secret: vec![1, 2, 3],
+    };
+    assert_eq!(
+        "Blob { id: 1, data: 0xDEADBEEF (4 bytes), digest: 0xABABABABABABABAB… (16 bytes), \
+         previous: None, secret: \"****\" }",
+        format!("{blob:?}")
+    );
+    let blob = Blob {
+        data: vec![],
+        previous: Some([0; 16]),
+        ..blob
+    };
+    assert!(format!("{blob:?}")
+        .contains("data: 0x (0 bytes), digest: 0xABABABABABABABAB… (16 bytes), previous: Some(0x0000000000000000… (16 bytes))"));
+}
+
+#[test]
+fn avro_json() {
+    let schema = apache_avro::Schema::parse_str(
+        &std::fs::read_to_string("tests/schemas/hex_debug.avsc").unwrap(),
+    )
+    .unwrap();
+    let blob = Blob {
+        id: 1,
+        data: vec![0x00, 0x7f, 0xff],
+        digest: [0xab; 16],
+        previous: None,
+        secret: vec![],
+    };
+    let json = avro_json::to_json(&blob, &schema).unwrap();
+    assert_eq!(
+        serde_json::json!({
+            "id": 1,
+            "data": "\u{0}\u{7f}ÿ",
+            "digest": "«".repeat(16),
+            "previous": null,
+            "secret": "",
+        }),
+        json
+    );
+    let json = json.to_string();
+    assert_eq!(blob, avro_json::from_str(&json, &schema).unwrap());
+    assert!(avro_json::from_str::<Blob>(&json.replace("ÿ", "Ā"), &schema).is_err());
+
+    let schema = apache_avro::Schema::parse_str(include_str!(
+        "schemas/multi_valued_union_with_avro_rs_unions.avsc"
+    ))
+    .unwrap();
+    let contact = Contact {
+        extra: HashMap::from_iter([
+            ("bytes".into(), Some(vec![1].into())),
+            ("long".into(), Some(12.into())),
+            ("null".into(), None),
+        ]),
+    };
+    let json = avro_json::to_json(&contact, &schema).unwrap();
+    assert_eq!(
+        serde_json::json!({"extra": {"bytes": {"bytes": "\u{1}"}, "long": {"long": 12}, "null": null}}),
+        json
+    );
+    assert_eq!(contact, avro_json::from_json(&json, &schema).unwrap());
+    assert_eq!(
+        "Failed to deserialize Avro value into value: Invalid value 12 of type `union`",
+        avro_json::from_str::<Contact>(r#"{"extra":{"long":12}}"#, &schema)
+            .unwrap_err()
+            .to_string()
+    );
+}
+
+#[test]
+fn sanitized_names_serde() {
+    let point = _3dPoint {
+        _1st: 1.0,
+        r#self_: 2.0,
+        r#type: 3.0,
+        axis: Axis::r#Self_,
+    };
+    let schema =
+        apache_avro::Schema::parse_str(include_str!("schemas/names_sanitize.avsc")).unwrap();
+    let value = apache_avro::to_value(&point).unwrap();
+    assert!(value.validate(&schema));
+    let apache_avro::types::Value::Record(fields) = &value else {
+        panic!("Expected a record, found {value:?}");
+    };
+    let names = fields
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["_1st", "self", "type", "axis"], names);
+    assert_eq!(point, apache_avro::from_value(&value).unwrap());
+}
+
+#[test]
+fn nullable_optional_bytes_serde() {
+    let data = BytesData {
+        b: vec![1, 2],
+        nb: Some(vec![3, 4]),
+    };
+    let schema =
+        apache_avro::Schema::parse_str(include_str!("schemas/nullable_bytes.avsc")).unwrap();
+    let value = apache_avro::to_value(&data).unwrap();
+    assert_eq!(
+        Value::Record(vec![
+            ("b".into(), Value::Bytes(vec![1, 2])),
+            (
+                "nb".into(),
+                Value::Union(1, Box::new(Value::Bytes(vec![3, 4])))
+            ),
+        ]),
+        value
+    );
+    assert!(value.validate(&schema));
+}
+
+#[test]
+fn lenient_deserialization() {
+    let value = Value::Record(vec![
+        ("id".into(), Value::Long(7)),
+        ("status".into(), Value::Enum(3, "CANCELLED".into())),
+        ("priority".into(), Value::Enum(1, "HIGH".into())),
+    ]);
+    let expected = Order {
+        id: 7,
+        note: None,
+        items: vec![],
+        status: Status::Unknown,
+        priority: Priority::High,
+        quantity: 1,
+    };
+    assert_eq!(expected, apache_avro::from_value(&value).unwrap());
+
+    let value = Value::Record(vec![("priority".into(), Value::Enum(2, "URGENT".into()))]);
+    let order = apache_avro::from_value::<Order>(&value).unwrap();
+    assert_eq!((0, Priority::Low), (order.id, order.priority));
+}
+
+#[test]
+fn symbol_aliases() {
+    let signal = apache_avro::from_value::<Signal>(&Value::Enum(0, "RED".into())).unwrap();
+    assert_eq!(Signal::Stop, signal);
+
+    // Data written before the symbols were renamed
+    let writer_schema = apache_avro::Schema::parse_str(
+        r#"{"type": "enum", "name": "Signal", "symbols": ["RED", "CAUTION", "green"]}"#,
+    )
+    .unwrap();
+    let datum = apache_avro::to_avro_datum(&writer_schema, Value::Enum(2, "green".into())).unwrap();
+    let value = apache_avro::from_avro_datum(&writer_schema, &mut &datum[..], None).unwrap();
+    assert_eq!(
+        Signal::Go,
+        apache_avro::from_value::<Signal>(&value).unwrap()
+    );
+}
+
+#[test]
+fn missing_fields() {
+    let value = Value::Record(vec![("status".into(), Value::Enum(1, "SENT".into()))]);
+    let err = apache_avro::from_value::<Shipment>(&value).unwrap_err();
+    assert!(err.to_string().contains("missing field `id`"), "{err}");
+
+    let light = apache_avro::from_value::<Light>(&Value::Record(vec![])).unwrap();
+    assert_eq!(Light::default(), light);
+}
+
+#[test]
+fn nullable_array_items() {
+    let tags = Tags::default();
+    assert_eq!(vec![Some("a".to_owned()), None], tags.names);
+    assert_eq!(
+        vec![None, Some(UnionStringLong::String("b".to_owned()))],
+        tags.values
+    );
+
+    let schema =
+        apache_avro::Schema::parse_str(include_str!("schemas/nullable_array.avsc")).unwrap();
+    let tags = Tags {
+        names: vec![None, Some("c".to_owned())],
+        values: vec![Some(UnionStringLong::Long(3)), None],
+    };
+    let value = apache_avro::to_value(&tags)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    assert_eq!(tags, apache_avro::from_value(&value).unwrap());
+}
+
+#[test]
+fn strict_deserialization() {
+    use crate::schemas::strict::Order;
+
+    let value = Value::Record(vec![("id".into(), Value::Long(7))]);
+    let err = apache_avro::from_value::<Order>(&value).unwrap_err();
+    assert!(err.to_string().contains("missing field `note`"), "{err}");
+
+    let value = Value::Record(vec![
+        ("id".into(), Value::Long(7)),
+        ("note".into(), Value::Union(0, Box::new(Value::Null))),
+    ]);
+    let expected = Order {
+        id: 7,
+        note: None,
+        coupon: None,
+    };
+    assert_eq!(expected, apache_avro::from_value(&value).unwrap());
+}
+
+#[test]
+fn schema_constants() {
+    use crate::schemas::schema_constants::Order;
+
+    let schema = apache_avro::Schema::parse_str(Order::SCHEMA).unwrap();
+    assert_eq!(
+        schema,
+        apache_avro::Schema::parse_str(Order::SCHEMA_PRETTY).unwrap()
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(Order::SCHEMA_PRETTY).unwrap(),
+        Order::schema_json()
+    );
+    assert_eq!(
+        "An order, tagged \"#1\" when urgent",
+        Order::schema_json()["doc"]
+    );
+}
+
+#[test]
+fn decimal_structs() {
+    use crate::schemas::decimal_structs::{Decimal, Invoice};
+
+    let schema =
+        apache_avro::Schema::parse_str(include_str!("schemas/decimal_structs.avsc")).unwrap();
+    let invoice = Invoice {
+        total: Decimal {
+            mantissa: -12345,
+            scale: 2,
+        },
+        // Written with the scale of the schema
+        rate: Decimal {
+            mantissa: 7,
+            scale: 1,
+        },
+        discount: Some(Decimal {
+            mantissa: 50,
+            scale: 2,
+        }),
+    };
+    let value = apache_avro::to_value(&invoice)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    let datum = apache_avro::to_avro_datum(&schema, value).unwrap();
+    let value = apache_avro::from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+    let decoded = apache_avro::from_value::<Invoice>(&value).unwrap();
+    assert_eq!(invoice.total, decoded.total);
+    assert_eq!(
+        Decimal {
+            mantissa: 7000,
+            scale: 4
+        },
+        decoded.rate
+    );
+    assert_eq!(invoice.discount, decoded.discount);
+    assert_eq!(
+        ("-123.45", "0.7000", "0.50"),
+        (
+            decoded.total.to_string().as_str(),
+            decoded.rate.to_string().as_str(),
+            decoded.discount.unwrap().to_string().as_str()
+        )
+    );
+
+    let bytes = invoice.total.to_avro_bytes();
+    assert_eq!(Some(invoice.total), Decimal::from_avro_bytes(&bytes, 2));
+    assert_eq!(
+        Some(Decimal {
+            mantissa: -1,
+            scale: 0
+        }),
+        Decimal::from_avro_bytes(&[0xff; 20], 0)
+    );
+    assert_eq!(None, Decimal::from_avro_bytes(&[0x01; 17], 0));
+
+    let lossy = Invoice {
+        total: Decimal {
+            mantissa: 1,
+            scale: 3,
+        },
+        ..invoice
+    };
+    let err = apache_avro::to_value(&lossy).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("0.001 cannot be written with scale 2"),
+        "{err}"
+    );
+}
+
+#[test]
+fn checked_decimals() {
+    use crate::schemas::decimal_structs::{Decimal, DecimalError};
+
+    // The rate of the schema, a fixed of 4 bytes with a precision of 6 and a scale of 4
+    let rate = Decimal::try_new(-999_999, 4, 6, Some(4)).unwrap();
+    assert_eq!("-99.9999", rate.to_string());
+    assert_eq!(
+        Ok(Decimal {
+            mantissa: 0,
+            scale: 4
+        }),
+        Decimal::try_new(0, 4, 6, Some(4))
+    );
+    assert_eq!(
+        Err(DecimalError::Precision {
+            digits: 7,
+            precision: 6
+        }),
+        Decimal::try_new(1_000_000, 4, 6, Some(4))
+    );
+    assert_eq!(
+        Err(DecimalError::Scale {
+            scale: 7,
+            precision: 6
+        }),
+        Decimal::try_new(1, 7, 6, Some(4))
+    );
+
+    // Two's complement bounds of the fixed size
+    assert!(Decimal::try_new(32_767, 0, 10, Some(2)).is_ok());
+    assert!(Decimal::try_new(-32_768, 0, 10, Some(2)).is_ok());
+    let err = Decimal::try_new(32_768, 0, 10, Some(2)).unwrap_err();
+    assert_eq!(DecimalError::Size { len: 3, size: 2 }, err);
+    assert_eq!("3 bytes exceed the decimal fixed size 2", err.to_string());
+    assert!(Decimal::try_new(-32_769, 0, 10, Some(2)).is_err());
+    assert!(Decimal::try_new(i128::MAX, 0, 39, None).is_ok());
+}
+
+#[test]
+fn boxed() {
+    use crate::schemas::boxed::{Address, Customer as Buyer, Note, Order as Purchase};
+
+    let schema = apache_avro::Schema::parse_str(include_str!("schemas/boxed.avsc")).unwrap();
+    let buyer = Buyer {
+        name: "Ada".to_string(),
+        email: "ada@example.com".to_string(),
+        avatar: [7; 32],
+        address: Address {
+            street: "1 Main St".to_string(),
+            city: "Paris".to_string(),
+        },
+    };
+    let parent = Purchase {
+        id: 1,
+        customer: Box::new(buyer.clone()),
+        billing: None,
+        shipping: None,
+        note: Box::new(Note {
+            text: String::new(),
+        }),
+        parent: None,
+    };
+    let purchase = Purchase {
+        id: 2,
+        billing: Some(Box::new(buyer)),
+        parent: Some(Box::new(parent.clone())),
+        ..parent
+    };
+    let value = apache_avro::to_value(&purchase)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    let datum = apache_avro::to_avro_datum(&schema, value).unwrap();
+    let value = apache_avro::from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+    assert_eq!(
+        purchase,
+        apache_avro::from_value::<Purchase>(&value).unwrap()
+    );
+}
+
+#[test]
+fn unsigned() {
+    use crate::schemas::unsigned::Counters;
+
+    let schema = apache_avro::Schema::parse_str(include_str!("schemas/unsigned.avsc")).unwrap();
+    let counters = Counters {
+        id: i64::MAX as u64,
+        hits: 3,
+        parent: Some(7),
+        delta: -1,
+    };
+    let value = apache_avro::to_value(&counters)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    let datum = apache_avro::to_avro_datum(&schema, value).unwrap();
+    let value = apache_avro::from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+    assert_eq!(
+        counters,
+        apache_avro::from_value::<Counters>(&value).unwrap()
+    );
+
+    // Values out of the range of the Avro types fail instead of wrapping
+    let overflowing = Counters {
+        id: u64::MAX,
+        ..counters
+    };
+    let err = apache_avro::to_value(&overflowing).unwrap_err();
+    assert!(err.to_string().contains("overflows an Avro long"), "{err}");
+    let negative = Value::Record(vec![
+        ("id".to_string(), Value::Long(-1)),
+        ("hits".to_string(), Value::Int(3)),
+        ("parent".to_string(), Value::Union(0, Box::new(Value::Null))),
+        ("delta".to_string(), Value::Int(0)),
+    ]);
+    let err = apache_avro::from_value::<Counters>(&negative).unwrap_err();
+    assert!(err.to_string().contains("negative value -1"), "{err}");
+}
+
+#[test]
+fn union_collection_defaults() {
+    use crate::schemas::union_collection_defaults::{Preferences, UnionStringLong};
+
+    let preferences = Preferences::default();
+    assert_eq!(
+        HashMap::from_iter([("daily".to_string(), None), ("hourly".to_string(), Some(3))]),
+        preferences.limits
+    );
+    assert_eq!(vec![None, Some("beta".to_string())], preferences.tags);
+    assert_eq!(
+        vec![
+            None,
+            Some(UnionStringLong::String("a".to_string())),
+            Some(UnionStringLong::Long(1))
+        ],
+        preferences.values
+    );
+    assert_eq!(
+        HashMap::from_iter([("math".to_string(), vec![Some(1.5), None])]),
+        preferences.scores
+    );
+}
+
+#[test]
+fn logical_defaults() {
+    use crate::schemas::logical_defaults::{Schedule, UnionTimestampMicrosString, UnionUuidLong};
+
+    let schedule = Schedule::default();
+    let at = |millis| chrono::DateTime::from_timestamp_millis(millis).unwrap();
+    assert_eq!(at(1_700_000_000_000), schedule.starts_at);
+    assert_eq!(
+        "550e8400-e29b-41d4-a716-446655440000",
+        schedule.id.to_string()
+    );
+    assert_eq!(
+        UnionTimestampMicrosString::NaiveDateTime(at(1_700_000_000_000)),
+        schedule.deadline
+    );
+    assert_eq!(
+        UnionUuidLong::Uuid(apache_avro::Uuid::from_u128(1)),
+        schedule.owner
+    );
+    assert_eq!(vec![at(0), at(60_000)], schedule.reminders);
+    assert_eq!(None, schedule.cancelled_at);
+}
+
+#[test]
+fn optional_defaults() {
+    use crate::schemas::optional_defaults::{Limits, Settings};
+
+    let schema =
+        apache_avro::Schema::parse_str(include_str!("schemas/optional_defaults.avsc")).unwrap();
+    let decode = |settings: &Settings| {
+        let value = apache_avro::to_value(settings)
+            .unwrap()
+            .resolve(&schema)
+            .unwrap();
+        let datum = apache_avro::to_avro_datum(&schema, value).unwrap();
+        let value = apache_avro::from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+        apache_avro::from_value::<Settings>(&value).unwrap()
+    };
+
+    // Unset fields are written with their default value
+    let unset = Settings {
+        name: "app".to_string(),
+        retries: None,
+        token: None,
+        parent: None,
+        limits: None,
+    };
+    assert_eq!(
+        Settings {
+            name: "app".to_string(),
+            retries: Some(3),
+            token: Some(vec![]),
+            parent: None,
+            limits: Some(Limits { max: Some(20) }),
+        },
+        decode(&unset)
+    );
+    let set = Settings {
+        name: "app".to_string(),
+        retries: Some(5),
+        token: Some(b"secret".to_vec()),
+        parent: Some("root".to_string()),
+        limits: Some(Limits { max: None }),
+    };
+    assert_eq!(
+        Settings {
+            limits: Some(Limits { max: Some(10) }),
+            ..set.clone()
+        },
+        decode(&set)
+    );
+
+    assert_eq!(
+        r#"{"name":"app","parent":null}"#,
+        serde_json::to_string(&unset).unwrap()
+    );
+    assert_eq!(unset, serde_json::from_str(r#"{"name":"app"}"#).unwrap());
+}
+
+#[test]
+fn validators() {
+    let address = Address {
+        zip: "75001".to_string(),
+        floor: None,
+    };
+    let mut signup = Signup {
+        age: 30,
+        score: 1.0,
+        visits: 1000,
+        email: Some("jane@example.com".to_string()),
+        address: address.clone(),
+        billing: None,
+        previous: vec![address.clone()],
+        others: HashMap::new(),
+        ratio: 2.5,
+    };
+    assert_eq!(Ok(()), signup.validate());
+
+    signup.age = 12;
+    signup.visits = 1001;
+    signup.email = Some("jane".to_string());
+    signup.address.floor = Some(-6);
+    signup.previous.push(Address {
+        zip: "7500".to_string(),
+        floor: Some(-5),
+    });
+    let errors = signup.validate().unwrap_err();
+    assert_eq!(
+        ValidationErrors {
+            errors: vec![
+                ("age".to_string(), "must be at least 13".to_string()),
+                ("visits".to_string(), "must be at most 1000".to_string()),
+                (
+                    "email".to_string(),
+                    "must match the pattern `^[^@]+@[^@]+$`".to_string()
+                ),
+                (
+                    "address.floor".to_string(),
+                    "must be at least -5".to_string()
+                ),
+                (
+                    "previous.1.zip".to_string(),
+                    "must match the pattern `^[0-9]{5}$`".to_string()
+                ),
+            ]
+        },
+        errors
+    );
+    assert!(errors
+        .to_string()
+        .starts_with("`age` must be at least 13, `visits` must be at most 1000, "));
+}
+
+#[test]
+fn structural_validators() {
+    use crate::schemas::structural_validators::{Decimal, Invoice, Line, Stage, ValidationErrors};
+
+    let line = Line {
+        stage: Some(Stage::from(Stage::OPEN)),
+        quantity: 2,
+    };
+    let mut invoice = Invoice {
+        stage: Stage::from(Stage::SHIPPED),
+        history: vec![Stage::from(Stage::OPEN)],
+        price: Decimal {
+            mantissa: 999,
+            scale: 1,
+        },
+        discount: None,
+        lines: HashMap::from([("a".to_string(), line)]),
+        checksum: [0; 4],
+    };
+    assert_eq!(Ok(()), invoice.validate());
+
+    invoice.stage = Stage::from("lost");
+    invoice.history.push(Stage::from("returned"));
+    // 999.90 has more digits than the precision, and 1.05 more decimal places than the scale
+    invoice.price.mantissa = 9999;
+    invoice.discount = Some(Decimal {
+        mantissa: 105,
+        scale: 2,
+    });
+    invoice.lines.get_mut("a").unwrap().stage = Some(Stage::from("lost"));
+    assert_eq!(
+        ValidationErrors {
+            errors: vec![
+                (
+                    "stage".to_string(),
+                    "must be a symbol of `Stage`".to_string()
+                ),
+                (
+                    "history.1".to_string(),
+                    "must be a symbol of `Stage`".to_string()
+                ),
+                (
+                    "price".to_string(),
+                    "must fit the decimal precision 4 and scale 2".to_string()
+                ),
+                (
+                    "discount".to_string(),
+                    "must fit the decimal precision 3 and scale 1".to_string()
+                ),
+                (
+                    "lines.a.stage".to_string(),
+                    "must be a symbol of `Stage`".to_string()
+                ),
+            ]
+        },
+        invoice.validate().unwrap_err()
+    );
+}
+
+#[test]
+fn validate_derives() {
+    use crate::schemas::{validate_derive_serde_valid, validate_derive_validator};
+
+    let address = validate_derive_validator::Address {
+        zip: "75001".to_string(),
+        floor: None,
+    };
+    let mut signup = validate_derive_validator::Signup {
+        age: 30,
+        score: 1.0,
+        visits: 1000,
+        email: Some("jane@example.com".to_string()),
+        address: address.clone(),
+        billing: None,
+        previous: vec![address],
+        others: HashMap::new(),
+        ratio: 2.5,
+    };
+    assert!(validator::Validate::validate(&signup).is_ok());
+    signup.age = 12;
+    signup.previous[0].zip = "7500".to_string();
+    let errors = validator::Validate::validate(&signup).unwrap_err();
+    let mut invalid = errors.errors().keys().collect::<Vec<_>>();
+    invalid.sort();
+    assert_eq!(vec!["age", "previous"], invalid);
+
+    let address = validate_derive_serde_valid::Address {
+        zip: "75001".to_string(),
+        floor: None,
+    };
+    let mut signup = validate_derive_serde_valid::Signup {
+        age: 30,
+        score: 1.0,
+        visits: 1000,
+        email: Some("jane@example.com".to_string()),
+        address: address.clone(),
+        billing: None,
+        previous: vec![address],
+        others: HashMap::new(),
+        ratio: 2.5,
+    };
+    assert!(serde_valid::Validate::validate(&signup).is_ok());
+    signup.address.floor = Some(-6);
+    assert!(serde_valid::Validate::validate(&signup).is_err());
+}
+
+#[test]
+fn protocol_message_errors() {
+    fn send(quota: i32) -> Result<(), SendError> {
+        match quota {
+            0 => Err(SendError::QuotaExceeded(QuotaExceeded { limit: 100 })),
+            _ => Err(SendError::Transport("connection reset".into())),
+        }
+    }
+
+    let error = send(0).unwrap_err();
+    assert_eq!("QuotaExceeded { limit: 100 }", error.to_string());
+    assert!(std::error::Error::source(&error).is_none());
+    let error = send(1).unwrap_err();
+    assert_eq!("Transport error: connection reset", error.to_string());
+    assert_eq!(
+        "connection reset",
+        std::error::Error::source(&error).unwrap().to_string()
+    );
+
+    let rejected = Rejected {
+        reason: "spam".to_string(),
+    };
+    assert!(matches!(
+        SendError::Rejected(rejected),
+        SendError::Rejected(_)
+    ));
+}
+
+#[test]
+fn protocol_message_errors_without_debug() {
+    use crate::schemas::protocol_no_debug::{GetError, NotFound};
+
+    let error = GetError::NotFound(NotFound {
+        id: "42".to_string(),
+    });
+    assert_eq!("NotFound error", error.to_string());
+    assert_eq!("NotFound", format!("{error:?}"));
+    let error = GetError::String("timeout".to_string());
+    assert_eq!("timeout", error.to_string());
+}
@@ -0,0 +1,5 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Foo {
+    pub label: String,
+}
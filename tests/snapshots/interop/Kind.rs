@@ -0,0 +1,7 @@
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, serde::Deserialize, serde::Serialize)]
+pub enum Kind {
+    A,
+    B,
+    C,
+}
@@ -0,0 +1,2 @@
+
+pub type Md5 = [u8; 16];
@@ -0,0 +1,6 @@
+
+#[derive(Debug, PartialEq, Eq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Node {
+    pub label: String,
+    pub children: Vec<Node>,
+}
@@ -0,0 +1,49 @@
+#[rustfmt::skip]
+mod schemas;
+
+use apache_avro::types::Value;
+
+use crate::schemas::union_json::{
+    Attachment, Message, UnionStringAttachment, UnionStringLongAttachment,
+};
+
+#[test]
+fn union_json() {
+    let message = Message {
+        body: UnionStringLongAttachment::Long(12),
+        reply: Some(UnionStringAttachment::Attachment(Attachment { size: 1 })),
+    };
+    let json = r#"{"body":{"long":12},"reply":{"test.Attachment":{"size":1}}}"#;
+    assert_eq!(json, serde_json::to_string(&message).unwrap());
+    assert_eq!(message, serde_json::from_str(json).unwrap());
+
+    let message = Message {
+        body: UnionStringLongAttachment::String("hello".to_string()),
+        reply: None,
+    };
+    let json = r#"{"body":{"string":"hello"},"reply":null}"#;
+    assert_eq!(json, serde_json::to_string(&message).unwrap());
+    assert_eq!(message, serde_json::from_str(json).unwrap());
+    assert!(serde_json::from_str::<Message>(r#"{"body":12,"reply":null}"#).is_err());
+
+    // Process-wide, hence this separate test crate
+    apache_avro::set_serde_human_readable(false);
+    let schema = apache_avro::Schema::parse_str(include_str!("schemas/union_json.avsc")).unwrap();
+    let message = Message {
+        body: UnionStringLongAttachment::Long(7),
+        reply: Some(UnionStringAttachment::String("ok".to_string())),
+    };
+    let value = apache_avro::to_value(&message)
+        .unwrap()
+        .resolve(&schema)
+        .unwrap();
+    let expected = Value::Record(vec![
+        ("body".into(), Value::Union(1, Box::new(Value::Long(7)))),
+        (
+            "reply".into(),
+            Value::Union(1, Box::new(Value::String("ok".into()))),
+        ),
+    ]);
+    assert_eq!(expected, value);
+    assert_eq!(message, apache_avro::from_value(&value).unwrap());
+}